@@ -7,32 +7,110 @@ use anyhow::Context;
 /// Application name used for config directory and display.
 pub(crate) const APP_NAME: &str = "youtube-music-player";
 
-/// Maximum track duration in seconds (7 minutes). Tracks longer than this are filtered out
-/// in music-only mode (Shift+F). Raised from 5 min to accommodate longer rock tracks.
+/// Maximum track duration in seconds (7 minutes) for the built-in "Music" filter profile.
+/// Raised from 5 min to accommodate longer rock tracks.
 pub(crate) const MAX_TRACK_DURATION_SECS: u64 = 420;
 
 /// Maximum length for user search queries.
 pub(crate) const MAX_SEARCH_QUERY_LEN: usize = 500;
 
+/// Results fetched per search request; `+` loads the next page.
+pub(crate) const SEARCH_PAGE_SIZE: usize = 15;
+
 /// Maximum length for user-entered playlist URLs.
 pub(crate) const MAX_PLAYLIST_URL_LEN: usize = 2048;
 
+/// Maximum length for text entered in a prompt popup (playlist names, paths).
+pub(crate) const MAX_PROMPT_INPUT_LEN: usize = 1024;
+
 /// Maximum concurrent downloads (kept low to avoid resource exhaustion).
 pub(crate) const MAX_CONCURRENT_DOWNLOADS: usize = 5;
 
+/// Download attempts per track (first try included) before automatic
+/// retries stop until the failure record expires.
+pub(crate) const MAX_DOWNLOAD_ATTEMPTS: u32 = 4;
+
+/// Delay before the first automatic retry; doubles on each further failure.
+pub(crate) const DOWNLOAD_RETRY_BASE_SECS: u64 = 5;
+
+/// Upper bound on the retry delay.
+pub(crate) const DOWNLOAD_RETRY_MAX_SECS: u64 = 120;
+
+/// How long a failed download is remembered and refused before the track
+/// may be tried again from scratch.
+pub(crate) const FAILURE_RECORD_TTL_SECS: u64 = 30 * 60;
+
 /// Maximum age (in seconds) of temp audio files before cleanup sweeps remove them.
 pub(crate) const TEMP_FILE_MAX_AGE_SECS: u64 = 3600;
 
+/// Default size limit (MiB) of the offline audio cache, overridable via
+/// `cache_quota_mb` in `settings.json`.
+pub(crate) const DEFAULT_CACHE_QUOTA_MB: u64 = 2048;
+
+/// Default bitrate (kbps) when transcoding downloads, overridable via
+/// `audio_bitrate_kbps` in `settings.json`.
+pub(crate) const DEFAULT_AUDIO_BITRATE_KBPS: u32 = 192;
+
+/// Consecutive network failures (yt-dlp runs that couldn't reach YouTube)
+/// before the app switches to offline mode on its own.
+pub(crate) const OFFLINE_FAILURE_THRESHOLD: u32 = 3;
+
+/// How often (seconds) auto-detected offline mode probes for the network.
+pub(crate) const OFFLINE_PROBE_INTERVAL_SECS: u64 = 30;
+
+/// Downloads that "make available offline" jobs keep in flight at once.
+pub(crate) const OFFLINE_JOB_CONCURRENCY: usize = 2;
+
+/// Age (days) past which the diagnostics screen warns that yt-dlp is
+/// outdated. YouTube changes break old releases within weeks.
+pub(crate) const YTDLP_MAX_AGE_DAYS: i64 = 90;
+
 /// Number of tracks to pre-download ahead of the current position.
 pub(crate) const LOOKAHEAD_DOWNLOAD_COUNT: usize = 10;
 
+/// During album playback, the next track is appended to the player this
+/// many seconds before the current one ends.
+pub(crate) const GAPLESS_PRELOAD_SECS: f64 = 15.0;
+
 /// Number of tracks to pre-download on startup/queue restore.
 pub(crate) const STARTUP_DOWNLOAD_COUNT: usize = 5;
 
+/// A play stops counting as a skip once this many seconds (or half the
+/// track, whichever comes first) have been listened to.
+pub(crate) const SKIP_THRESHOLD_SECS: u64 = 240;
+
 /// How long (seconds) the on-disk feed cache is considered fresh.
 /// After this TTL the next `open_feed_browser` triggers a background re-fetch.
 pub(crate) const FEED_CACHE_TTL_SECS: u64 = 30 * 60; // 30 minutes
 
+/// How long a cached artist page is served before its channel is listed again.
+pub(crate) const ARTIST_CACHE_TTL_SECS: u64 = 6 * 60 * 60;
+
+/// How long a track with no lyrics or captions is remembered as having
+/// none before they are looked for again.
+pub(crate) const LYRICS_MISS_TTL_SECS: u64 = 24 * 60 * 60;
+
+/// Size limit of the on-disk lyrics cache; the lyrics shown least recently
+/// are dropped beyond it.
+pub(crate) const LYRICS_CACHE_MAX_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Covers are cropped square and kept at this many pixels a side.
+pub(crate) const COVER_SIZE_PX: u32 = 128;
+
+/// Size limit of the on-disk cover art cache; the least recently shown
+/// covers are dropped beyond it.
+pub(crate) const THUMBNAIL_CACHE_MAX_BYTES: u64 = 32 * 1024 * 1024;
+
+/// How long a cover that couldn't be loaded is left out before it's
+/// tried again. Coming back online retries them all.
+pub(crate) const COVER_RETRY_SECS: u64 = 10 * 60;
+
+/// Covers fetched at once; more wait until one finishes.
+pub(crate) const COVER_FETCHES_IN_FLIGHT: usize = 2;
+
+/// Entries listed per channel tab (videos, releases, playlists).
+pub(crate) const ARTIST_TAB_LIMIT: usize = 50;
+
 /// Returns the application config directory path (e.g., `~/.config/youtube-music-player`).
 pub(crate) fn config_dir() -> anyhow::Result<PathBuf> {
     let dir = dirs::config_dir()
@@ -41,6 +119,33 @@ pub(crate) fn config_dir() -> anyhow::Result<PathBuf> {
     Ok(dir)
 }
 
+/// Returns the offline audio cache directory (e.g., `~/.cache/youtube-music-player/audio`).
+pub(crate) fn audio_cache_dir() -> anyhow::Result<PathBuf> {
+    let dir = dirs::cache_dir()
+        .context("Could not find cache directory")?
+        .join(APP_NAME)
+        .join("audio");
+    Ok(dir)
+}
+
+/// Returns the cover art cache directory (e.g., `~/.cache/youtube-music-player/thumbnails`).
+pub(crate) fn thumbnail_cache_dir() -> anyhow::Result<PathBuf> {
+    let dir = dirs::cache_dir()
+        .context("Could not find cache directory")?
+        .join(APP_NAME)
+        .join("thumbnails");
+    Ok(dir)
+}
+
+/// Returns the lyrics cache directory (e.g., `~/.cache/youtube-music-player/lyrics`).
+pub(crate) fn lyrics_cache_dir() -> anyhow::Result<PathBuf> {
+    let dir = dirs::cache_dir()
+        .context("Could not find cache directory")?
+        .join(APP_NAME)
+        .join("lyrics");
+    Ok(dir)
+}
+
 /// Returns true if the URL is an allowed YouTube domain.
 #[must_use]
 pub(crate) fn is_allowed_youtube_url(url: &str) -> bool {
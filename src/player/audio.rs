@@ -11,7 +11,12 @@
 // Key Concept: Rodio is a pure Rust audio playback library
 // It provides a "Sink" abstraction for controlling audio playback
 
-use rodio::{Decoder, DeviceSinkBuilder, Player};
+use rodio::source::SeekError;
+use rodio::{Decoder, DeviceSinkBuilder, Player, Source};
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdout, Command, Stdio};
 use std::time::{Duration, Instant};
 
 // ==========================================
@@ -36,6 +41,142 @@ pub enum PlayerState {
     Loading, // Audio is being loaded/decoded (transitional state)
 }
 
+// ==========================================
+// DECODING
+// ==========================================
+// rodio decodes MP3, AAC (M4A), FLAC, Vorbis and WAV on its own. Opus,
+// which is what the `native` and `opus` audio formats usually store, isn't
+// among them, so files rodio can't open are decoded by ffmpeg (already
+// required by yt-dlp for audio extraction) as a stream: ffmpeg writes raw
+// 16-bit PCM to a pipe and the player reads it as it plays. Starting a
+// track costs no more than spawning ffmpeg, and nothing is written to disk.
+
+/// The format ffmpeg is asked to decode to.
+const FFMPEG_CHANNELS: u16 = 2;
+const FFMPEG_SAMPLE_RATE: u32 = 48_000;
+
+/// Open an audio file with rodio's own decoders. `Ok(None)` if the file is
+/// in a format rodio can't decode.
+pub(crate) fn open_native_decoder(path: &Path) -> Result<Option<Decoder<File>>, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open audio file: {}", e))?;
+    Ok(Decoder::new(file).ok())
+}
+
+/// Open an audio file for decoding, falling back to streaming through
+/// ffmpeg for formats rodio can't decode.
+pub(crate) fn open_decoder(path: &Path) -> Result<Box<dyn Source + Send>, String> {
+    match open_native_decoder(path)? {
+        Some(decoder) => Ok(Box::new(decoder)),
+        None => FfmpegStream::spawn(path, Duration::ZERO)
+            .map(|stream| Box::new(stream) as Box<dyn Source + Send>)
+            .map_err(|e| format!("unsupported format ({})", e)),
+    }
+}
+
+/// Check that ffmpeg can decode the first seconds of `path`, without
+/// decoding the rest.
+pub(crate) fn probe_with_ffmpeg(path: &Path) -> Result<(), String> {
+    let output = Command::new("ffmpeg")
+        .args(["-v", "error", "-nostdin", "-xerror", "-t", "2", "-i"])
+        .arg(path)
+        .args(["-f", "null", "-"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .output()
+        .map_err(|e| format!("ffmpeg not available: {}", e))?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Err(stderr
+        .lines()
+        .next()
+        .map_or_else(|| "ffmpeg could not decode it".to_string(), str::to_string))
+}
+
+/// Audio decoded by an ffmpeg child process and read from its stdout while
+/// it plays. ffmpeg only runs as far ahead as the pipe buffer lets it.
+/// Dropping the stream stops ffmpeg.
+pub(crate) struct FfmpegStream {
+    path: PathBuf,
+    child: Child,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl FfmpegStream {
+    /// Start decoding `path` from `start` into the track.
+    fn spawn(path: &Path, start: Duration) -> Result<Self, String> {
+        let mut child = Command::new("ffmpeg")
+            .args(["-v", "error", "-nostdin", "-ss"])
+            .arg(format!("{:.3}", start.as_secs_f64()))
+            .arg("-i")
+            .arg(path)
+            .args(["-f", "s16le", "-acodec", "pcm_s16le", "-ac"])
+            .arg(FFMPEG_CHANNELS.to_string())
+            .arg("-ar")
+            .arg(FFMPEG_SAMPLE_RATE.to_string())
+            .arg("-")
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("ffmpeg not available: {}", e))?;
+        let Some(stdout) = child.stdout.take() else {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err("ffmpeg has no output pipe".to_string());
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            child,
+            stdout: BufReader::new(stdout),
+        })
+    }
+}
+
+impl Iterator for FfmpegStream {
+    type Item = rodio::Sample;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut bytes = [0u8; 2];
+        self.stdout.read_exact(&mut bytes).ok()?;
+        Some(rodio::Sample::from(i16::from_le_bytes(bytes)) / 32_768.0)
+    }
+}
+
+impl Source for FfmpegStream {
+    fn current_span_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> rodio::ChannelCount {
+        rodio::ChannelCount::new(FFMPEG_CHANNELS).expect("channel count is non-zero")
+    }
+
+    fn sample_rate(&self) -> rodio::SampleRate {
+        rodio::SampleRate::new(FFMPEG_SAMPLE_RATE).expect("sample rate is non-zero")
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Restart ffmpeg at `pos` — it seeks in the file before decoding.
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        let restarted = Self::spawn(&self.path, pos)
+            .map_err(|e| SeekError::Other(std::sync::Arc::new(std::io::Error::other(e))))?;
+        *self = restarted;
+        Ok(())
+    }
+}
+
+impl Drop for FfmpegStream {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
 // ==========================================
 // AUDIO PLAYER STRUCT
 // ==========================================
@@ -88,6 +229,16 @@ pub struct AudioPlayer {
     // Seeking support (rodio workaround)
     current_file_path: Option<String>,
     seek_position: Option<f64>,
+    // Gapless playback: the track already appended behind the current one
+    queued_next: Option<QueuedTrack>,
+}
+
+/// A track appended to the player ahead of time by `queue_next()`.
+#[derive(Debug)]
+struct QueuedTrack {
+    file_path: String,
+    title: String,
+    duration: f64,
 }
 
 // Implement custom Drop to handle cleanup properly
@@ -166,6 +317,7 @@ impl AudioPlayer {
             total_paused_duration: Duration::from_secs(0),
             current_file_path: None,
             seek_position: None,
+            queued_next: None,
         }
     }
 
@@ -189,8 +341,9 @@ impl AudioPlayer {
             // This prevents auto-advance logic from thinking track finished
             self.state = PlayerState::Loading;
 
-            // First, stop any currently playing audio
+            // First, stop any currently playing audio (and a queued next track)
             player.stop();
+            self.queued_next = None;
 
             // Try to decode and play the audio file
             // Wrap the entire operation in a catch_unwind to prevent panics
@@ -246,13 +399,9 @@ impl AudioPlayer {
     // Returns the decoder and duration
     fn decode_from_file(
         file_path: &str,
-    ) -> Result<(Decoder<std::fs::File>, f64), Box<dyn std::error::Error>> {
-        // Open the file
-        let file = std::fs::File::open(file_path)
-            .map_err(|e| format!("Failed to open audio file: {}", e))?;
-
-        // Decode the audio format (MP3, M4A, WAV, etc.)
-        let decoder = Decoder::new(file).map_err(|e| {
+    ) -> Result<(Box<dyn Source + Send>, f64), Box<dyn std::error::Error>> {
+        // Open and decode the audio format (MP3, M4A, Opus, WAV, etc.)
+        let decoder = open_decoder(Path::new(file_path)).map_err(|e| {
             format!(
                 "Audio decode failed: {}. File may be corrupted or invalid format.",
                 e
@@ -265,6 +414,85 @@ impl AudioPlayer {
         Ok((decoder, duration))
     }
 
+    // ==========================================
+    // GAPLESS PLAYBACK: queue_next() / take_advance()
+    // ==========================================
+    // Normally the next track is only loaded after the current one has
+    // finished, which leaves a short silence. For albums that flow into
+    // each other, queue_next() decodes the next file while the current one
+    // is still playing and appends it to the same rodio Player, which moves
+    // on without a gap.
+    //
+    // rodio doesn't tell us when it moves on, so take_advance() is polled:
+    // once only one sound is left in the player, the queued track is the one
+    // playing and the title/duration/timing switch over to it.
+    //
+    // Returns false (and does nothing) unless a track is playing, nothing is
+    // queued yet, and the file decodes.
+    pub fn queue_next(&mut self, file_path: &str, title: &str, known_duration: f64) -> bool {
+        if self.state != PlayerState::Playing || self.queued_next.is_some() {
+            return false;
+        }
+        let Some(player) = &self.player else {
+            return false;
+        };
+        if player.len() != 1 {
+            return false;
+        }
+        let Ok((decoder, _)) = Self::decode_from_file(file_path) else {
+            return false;
+        };
+        player.append(decoder);
+        self.queued_next = Some(QueuedTrack {
+            file_path: file_path.to_string(),
+            title: title.to_string(),
+            duration: known_duration,
+        });
+        true
+    }
+
+    /// The files the player reads from or may reopen to seek: the current
+    /// track's and the one appended by `queue_next()`.
+    pub fn files_in_use(&self) -> Vec<&str> {
+        self.current_file_path
+            .iter()
+            .chain(self.queued_next.as_ref().map(|next| &next.file_path))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Whether a track has been appended by `queue_next()` and not reached yet.
+    pub fn has_queued_next(&self) -> bool {
+        self.queued_next.is_some()
+    }
+
+    // Returns true exactly once when the player has moved on to the track
+    // appended by queue_next().
+    pub fn take_advance(&mut self) -> bool {
+        let Some(player) = &self.player else {
+            return false;
+        };
+        if self.queued_next.is_none() || player.len() > 1 || self.state != PlayerState::Playing {
+            return false;
+        }
+        let Some(next) = self.queued_next.take() else {
+            return false;
+        };
+        self.current_title = next.title;
+        self.duration = next.duration;
+        self.current_file_path = Some(next.file_path);
+        self.start_time = Some(Instant::now());
+        self.pause_time = None;
+        self.total_paused_duration = Duration::from_secs(0);
+        self.seek_position = None;
+        true
+    }
+
+    /// Whether an audio output device was opened. Without one nothing plays.
+    pub fn has_output(&self) -> bool {
+        self.player.is_some()
+    }
+
     // ==========================================
     // PLAYBACK CONTROL: pause()
     // ==========================================
@@ -384,6 +612,7 @@ impl AudioPlayer {
         if let Some(player) = &self.player {
             player.stop();
         }
+        self.queued_next = None;
 
         // Reset timing information
         self.start_time = None;
@@ -704,9 +933,9 @@ impl AudioPlayer {
 //    - Rodio doesn't have built-in EQ
 //    - Would need audio processing library
 //
-// 3. Gapless playback
-//    - Preload next track while current plays
-//    - Seamless transition between tracks
+// 3. Gapless playback everywhere
+//    - queue_next() is only used for album playback so far
+//    - Could chain any two cached tracks in the queue
 //
 // 4. Audio visualization
 //    - FFT for frequency analysis
@@ -61,7 +61,7 @@ use std::collections::VecDeque;
 // - Debug: Can print track info for debugging (println!("{:?}", track))
 // - Clone: Can make copies of tracks (needed for queue operations)
 // - Serialize/Deserialize: For saving/loading history to JSON
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct Track {
     pub video_id: String,
     pub title: String,
@@ -276,6 +276,28 @@ impl Queue {
         None
     }
 
+    // ==========================================
+    // NAVIGATION: next_where()
+    // ==========================================
+    // Like next(), but jumps to the first queued track for which `pred`
+    // returns true. The tracks it jumps over stay in the queue, in order.
+    // Used in offline mode to skip tracks that aren't cached yet.
+    //
+    // Example flow (pred = "is cached", only C is cached):
+    // - Current: A, Queue: [B, C, D], History: []
+    // - Current: C, Queue: [B, D], History: [A]
+    //
+    // If no queued track matches, nothing changes and None is returned.
+    pub fn next_where(&mut self, pred: impl Fn(&Track) -> bool) -> Option<Track> {
+        let index = self.tracks.iter().position(pred)?;
+        let track = self.tracks.remove(index)?;
+        if let Some(current) = self.current_track.take() {
+            self.history.push(current);
+        }
+        self.current_track = Some(track.clone());
+        Some(track)
+    }
+
     // ==========================================
     // NAVIGATION: previous()
     // ==========================================
@@ -702,6 +724,31 @@ mod tests {
         assert!(queue.get_current().is_none());
     }
 
+    #[test]
+    fn test_next_where_skips_but_keeps_non_matching() {
+        let mut queue = Queue::new();
+        for id in ["a", "b", "c", "d"] {
+            queue.add(make_track(id));
+        }
+        queue.next(); // current = a
+
+        let track = queue.next_where(|t| t.video_id == "c");
+        assert_eq!(track.unwrap().video_id, "c");
+        assert_eq!(queue.get_current().unwrap().video_id, "c");
+        let ids: Vec<_> = queue
+            .get_queue_list()
+            .into_iter()
+            .map(|t| t.video_id)
+            .collect();
+        assert_eq!(ids, ["b", "d"]);
+        assert_eq!(queue.get_history()[0].video_id, "a");
+
+        // No match: nothing moves.
+        assert!(queue.next_where(|_| false).is_none());
+        assert_eq!(queue.get_current().unwrap().video_id, "c");
+        assert_eq!(queue.len(), 2);
+    }
+
     #[test]
     fn test_previous_goes_back() {
         let mut queue = Queue::new();
@@ -0,0 +1,236 @@
+//! Albums: YouTube Music's `OLAK5uy_*` album playlists.
+//!
+//! An album is listed like any other playlist, but with
+//! `--dump-single-json` so the playlist's own metadata (title, channel,
+//! description) comes along with the entries; the entry order is the track
+//! order. Which album a track is on is remembered in the library, so the
+//! history can group what was heard by album.
+
+use std::collections::HashMap;
+
+use crate::config::is_allowed_youtube_url;
+use crate::player::queue::Track;
+use crate::services::artist::display_name;
+use crate::services::playlist::track_from_flat_json;
+use crate::youtube::extractor::is_valid_list_id;
+use crate::youtube::ytdlp::{YtDlp, YtDlpArgs};
+
+/// An album with its tracks in release order.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Album {
+    /// The album playlist id (`OLAK5uy_…`).
+    pub id: String,
+    pub title: String,
+    pub artist: String,
+    pub year: Option<u16>,
+    pub url: String,
+    pub tracks: Vec<Track>,
+}
+
+impl Album {
+    /// Length of the whole album in seconds.
+    pub(crate) fn total_duration(&self) -> u64 {
+        self.tracks.iter().map(|t| t.duration).sum()
+    }
+}
+
+/// The album a track is on, as remembered by the library.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct AlbumRef {
+    pub album_id: String,
+    pub title: String,
+    pub artist: String,
+    pub year: Option<u16>,
+    /// 1-based position on the album.
+    pub track_no: usize,
+}
+
+impl AlbumRef {
+    /// "After Hours — The Weeknd (2020)"
+    pub(crate) fn heading(&self) -> String {
+        match self.year {
+            Some(year) => format!("{} — {} ({})", self.title, self.artist, year),
+            None => format!("{} — {}", self.title, self.artist),
+        }
+    }
+}
+
+/// YouTube Music album playlists all have this id prefix.
+pub(crate) fn is_album_id(id: &str) -> bool {
+    id.starts_with("OLAK5uy_")
+}
+
+/// Fetch an album's metadata and track list.
+pub(crate) fn fetch_album(
+    ytdlp: &dyn YtDlp,
+    url: &str,
+    cookie_config: Option<(bool, String)>,
+) -> Result<Album, String> {
+    if !is_allowed_youtube_url(url) {
+        return Err("Invalid URL: must be a YouTube or YouTube Music URL".to_string());
+    }
+    let args = YtDlpArgs::new()
+        .flag("--flat-playlist")
+        .flag("--dump-single-json")
+        .flag("--no-warnings")
+        .flag("--skip-download")
+        .cookies(cookie_config.as_ref())
+        .flag(url);
+    let output = ytdlp
+        .run(&args)
+        .map_err(|e| format!("{}. Is yt-dlp installed?", e))?;
+    if !output.success {
+        return Err(format!("yt-dlp failed: {}", output.error_snippet()));
+    }
+    parse_album(&output.stdout).ok_or_else(|| "No tracks found in album".to_string())
+}
+
+/// An album from a `--dump-single-json` playlist, or `None` if it has no
+/// valid id or no playable tracks.
+fn parse_album(stdout: &str) -> Option<Album> {
+    let json: serde_json::Value = serde_json::from_str(stdout.trim()).ok()?;
+    let id = json["id"].as_str().filter(|id| is_valid_list_id(id))?;
+    let tracks: Vec<Track> = json["entries"]
+        .as_array()?
+        .iter()
+        .filter_map(track_from_flat_json)
+        .collect();
+    let first = tracks.first()?;
+
+    let text = |key: &str| json[key].as_str().filter(|s| !s.is_empty());
+    let raw_title = text("title").unwrap_or("Unknown album");
+    let title = raw_title
+        .strip_prefix("Album - ")
+        .unwrap_or(raw_title)
+        .trim()
+        .to_string();
+    let artist = display_name(
+        text("channel")
+            .or_else(|| text("uploader"))
+            .unwrap_or(&first.uploader),
+    );
+    let year = json["release_year"]
+        .as_u64()
+        .and_then(|y| u16::try_from(y).ok())
+        .or_else(|| text("description").and_then(year_in));
+
+    Some(Album {
+        id: id.to_string(),
+        title,
+        artist,
+        year,
+        url: format!("https://music.youtube.com/playlist?list={}", id),
+        tracks,
+    })
+}
+
+/// The first plausible release year standing on its own in `text`.
+fn year_in(text: &str) -> Option<u16> {
+    text.split(|c: char| !c.is_ascii_digit())
+        .filter(|word| word.len() == 4)
+        .filter_map(|word| word.parse().ok())
+        .find(|year| (1900..=2100).contains(year))
+}
+
+/// Split `video_ids` into runs of consecutive tracks from the same album,
+/// as `(album, length)`. A track with no known album, or the only one heard
+/// from its album in a row, is a run of one with no album.
+pub(crate) fn album_runs<'a>(
+    video_ids: impl IntoIterator<Item = &'a str>,
+    albums: &'a HashMap<String, AlbumRef>,
+) -> Vec<(Option<&'a AlbumRef>, usize)> {
+    let mut runs: Vec<(Option<&AlbumRef>, usize)> = Vec::new();
+    for id in video_ids {
+        let album = albums.get(id);
+        match (runs.last_mut(), album) {
+            (Some((Some(last), len)), Some(album)) if last.album_id == album.album_id => *len += 1,
+            _ => runs.push((album, 1)),
+        }
+    }
+    for run in &mut runs {
+        if run.1 == 1 {
+            run.0 = None;
+        }
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::youtube::fake_ytdlp::{FakeYtDlp, Reply};
+
+    #[test]
+    fn albums_keep_track_order_and_metadata() {
+        let fake = FakeYtDlp::new().on("OLAK5uy_", Reply::fixture("album.json"));
+        let url =
+            "https://music.youtube.com/playlist?list=OLAK5uy_kLWIr9gv7XLlsbvfyCWgKyaHEsnhrCcWw";
+
+        let album = fetch_album(&fake, url, None).unwrap();
+        assert_eq!(album.title, "After Hours");
+        assert_eq!(album.artist, "The Weeknd");
+        assert_eq!(album.year, Some(2020));
+        // The private video has no id and is left out.
+        let titles: Vec<&str> = album.tracks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            [
+                "Alone Again",
+                "Too Late",
+                "Hardest To Love",
+                "Scared To Live",
+                "Blinding Lights"
+            ]
+        );
+        assert_eq!(album.total_duration(), 250 + 240 + 211 + 191 + 200);
+        assert!(fake.calls()[0]
+            .as_slice()
+            .iter()
+            .any(|a| a == "--dump-single-json"));
+
+        assert!(fetch_album(&fake, "https://example.com/playlist", None).is_err());
+    }
+
+    #[test]
+    fn album_years_prefer_release_year() {
+        let json = r#"{"id": "OLAK5uy_x", "title": "Dawn FM", "release_year": 2022,
+            "description": "Remastered 1999", "entries": [{"id": "ZRXA_rpB4lE", "title": "Gasoline"}]}"#;
+        let album = parse_album(json).unwrap();
+        assert_eq!((album.title.as_str(), album.year), ("Dawn FM", Some(2022)));
+        assert_eq!(album.artist, "Unknown");
+
+        assert_eq!(year_in("Released 12345 · 1987 remaster"), Some(1987));
+        assert_eq!(year_in("Vol. 3"), None);
+        assert!(parse_album(r#"{"id": "OLAK5uy_x", "entries": []}"#).is_none());
+    }
+
+    #[test]
+    fn history_runs_group_consecutive_album_tracks() {
+        let on = |album_id: &str, track_no| AlbumRef {
+            album_id: album_id.to_string(),
+            title: album_id.to_uppercase(),
+            artist: "Artist".to_string(),
+            year: None,
+            track_no,
+        };
+        let albums: HashMap<String, AlbumRef> = [
+            ("a1".to_string(), on("x", 1)),
+            ("a2".to_string(), on("x", 2)),
+            ("a3".to_string(), on("x", 3)),
+            ("b1".to_string(), on("y", 1)),
+        ]
+        .into_iter()
+        .collect();
+
+        let runs = album_runs(["a1", "a2", "loose", "a3", "b1", "b1"], &albums);
+        let summary: Vec<(Option<&str>, usize)> = runs
+            .iter()
+            .map(|(album, len)| (album.map(|a| a.album_id.as_str()), *len))
+            .collect();
+        assert_eq!(
+            summary,
+            [(Some("x"), 2), (None, 1), (None, 1), (Some("y"), 2)]
+        );
+        assert_eq!(on("x", 1).heading(), "X — Artist");
+    }
+}
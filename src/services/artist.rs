@@ -0,0 +1,512 @@
+//! Artist and channel pages.
+//!
+//! A track only knows its uploader's display name, so the channel behind
+//! it is resolved by asking yt-dlp for the video's metadata (`channel_id`,
+//! or the `@handle` in `uploader_id`). The channel's Videos, Releases and
+//! Playlists tabs are then listed with `--flat-playlist` and cached per
+//! channel in `artists/<channel id>.json` through [`CacheStore`].
+
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{is_allowed_youtube_url, ARTIST_CACHE_TTL_SECS, ARTIST_TAB_LIMIT};
+use crate::player::queue::Track;
+use crate::services::cache_store::CacheStore;
+use crate::services::network::is_network_error;
+use crate::services::playlist::{dump_flat_playlist, parse_flat_tracks};
+use crate::youtube::extractor::{is_valid_list_id, is_valid_video_id};
+use crate::youtube::ytdlp::{YtDlp, YtDlpArgs};
+
+/// A YouTube channel, resolved from a track or picked from search.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Channel {
+    /// `UC…` channel id, or `@handle` when yt-dlp only reported that.
+    pub id: String,
+    pub name: String,
+    pub url: String,
+}
+
+impl Channel {
+    /// The channel at `url` with id `id`, if the id is a channel id or
+    /// handle and the URL is YouTube's.
+    pub(crate) fn new(id: &str, name: &str, url: &str) -> Option<Self> {
+        (is_channel_id(id) && is_allowed_youtube_url(url)).then(|| Channel {
+            id: id.to_string(),
+            name: display_name(name),
+            url: url.trim_end_matches('/').to_string(),
+        })
+    }
+}
+
+/// An album, single or playlist listed on a channel.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Release {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    pub track_count: Option<u64>,
+}
+
+/// Everything the artist browser shows for one channel.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ArtistPage {
+    pub channel: Channel,
+    pub uploads: Vec<Track>,
+    pub albums: Vec<Release>,
+    pub singles: Vec<Release>,
+    pub playlists: Vec<Release>,
+    /// Why the Videos, Releases or Playlists tab couldn't be listed, when
+    /// the channel has it but yt-dlp failed on it.
+    pub uploads_error: Option<String>,
+    pub releases_error: Option<String>,
+    pub playlists_error: Option<String>,
+}
+
+impl ArtistPage {
+    /// Bump when `ArtistPage` changes shape; older cache files are then
+    /// treated as misses.
+    pub(crate) const CACHE_SCHEMA_VERSION: u32 = 2;
+
+    /// The tabs that failed, as "Tab: error".
+    pub(crate) fn tab_errors(&self) -> Vec<String> {
+        [
+            ("Videos", &self.uploads_error),
+            ("Releases", &self.releases_error),
+            ("Playlists", &self.playlists_error),
+        ]
+        .into_iter()
+        .filter_map(|(tab, err)| err.as_ref().map(|e| format!("{tab}: {e}")))
+        .collect()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.uploads.is_empty()
+            && self.albums.is_empty()
+            && self.singles.is_empty()
+            && self.playlists.is_empty()
+    }
+}
+
+/// `UC` + 22 id characters, or an `@handle`.
+fn is_channel_id(id: &str) -> bool {
+    let safe = |s: &str| {
+        s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    };
+    match id.strip_prefix('@') {
+        Some(handle) => {
+            !handle.is_empty()
+                && handle.len() <= 100
+                && handle
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c))
+                && !handle.starts_with('.')
+        }
+        None => id.len() == 24 && id.starts_with("UC") && safe(id),
+    }
+}
+
+/// `name` without the " - Topic" YouTube Music adds to auto-generated
+/// artist channels, and without control characters.
+pub(crate) fn display_name(name: &str) -> String {
+    let name: String = name.chars().filter(|c| !c.is_control()).collect();
+    let name = name.trim();
+    name.strip_suffix(" - Topic")
+        .unwrap_or(name)
+        .trim()
+        .to_string()
+}
+
+/// The channel in a video's `--dump-json` metadata: `channel_id` when
+/// present, else the `@handle` in `uploader_id`.
+pub(crate) fn channel_from_json(json: &serde_json::Value, fallback_name: &str) -> Option<Channel> {
+    let text = |key: &str| json[key].as_str().filter(|s| !s.is_empty());
+    let name = text("channel")
+        .or_else(|| text("uploader"))
+        .unwrap_or(fallback_name);
+
+    if let Some(id) = text("channel_id").filter(|id| is_channel_id(id)) {
+        let url = text("channel_url")
+            .filter(|url| is_allowed_youtube_url(url))
+            .map_or_else(
+                || format!("https://www.youtube.com/channel/{}", id),
+                str::to_string,
+            );
+        return Channel::new(id, name, &url);
+    }
+    let handle = text("uploader_id").filter(|id| id.starts_with('@'))?;
+    Channel::new(handle, name, &format!("https://www.youtube.com/{}", handle))
+}
+
+/// Find the channel that uploaded `track`.
+pub(crate) fn resolve_channel(
+    ytdlp: &dyn YtDlp,
+    track: &Track,
+    cookie_config: Option<(bool, String)>,
+) -> Result<Channel, String> {
+    if !is_valid_video_id(&track.video_id) {
+        return Err(format!("Can't look up the channel of '{}'", track.title));
+    }
+    let args = YtDlpArgs::new()
+        .flag("--dump-json")
+        .flag("--skip-download")
+        .flag("--no-playlist")
+        .flag("--no-warnings")
+        .cookies(cookie_config.as_ref())
+        .flag(&format!(
+            "https://www.youtube.com/watch?v={}",
+            track.video_id
+        ));
+    let output = ytdlp
+        .run(&args)
+        .map_err(|e| format!("{}. Is yt-dlp installed?", e))?;
+    if !output.success {
+        return Err(format!("yt-dlp failed: {}", output.error_snippet()));
+    }
+
+    output
+        .stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .find_map(|json| channel_from_json(&json, &track.uploader))
+        .ok_or_else(|| format!("No channel found for '{}'", display_name(&track.uploader)))
+}
+
+/// The albums, singles or playlists of a channel tab listing.
+fn parse_releases(stdout: &str) -> Vec<Release> {
+    stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter_map(|json| {
+            let id = json["id"].as_str().filter(|id| is_valid_list_id(id))?;
+            let url = json["url"]
+                .as_str()
+                .filter(|url| is_allowed_youtube_url(url))
+                .map_or_else(
+                    || format!("https://www.youtube.com/playlist?list={}", id),
+                    str::to_string,
+                );
+            let title = json["title"]
+                .as_str()
+                .map(display_name)
+                .filter(|t| !t.is_empty());
+            Some(Release {
+                id: id.to_string(),
+                title: title.unwrap_or_else(|| id.to_string()),
+                url,
+                track_count: json["playlist_count"].as_u64(),
+            })
+        })
+        .collect()
+}
+
+/// Singles share the Releases tab with albums and aren't labelled in the
+/// flat listing; YouTube counts a release of up to three tracks as one.
+fn is_single(release: &Release) -> bool {
+    release.track_count.is_some_and(|n| n <= 3)
+        || release.title.to_lowercase().ends_with(" - single")
+}
+
+/// What listing one channel tab gave: its flat listing, or why the tab
+/// failed. `None` for both when the channel doesn't have the tab.
+#[derive(Debug, Default)]
+struct TabListing {
+    stdout: Option<String>,
+    error: Option<String>,
+}
+
+/// List one channel tab. A channel without the tab makes yt-dlp fail;
+/// that's an empty tab, not an error. Other failures are kept for the
+/// page to show — unless the network is at fault, which fails the page.
+fn fetch_tab(
+    ytdlp: &dyn YtDlp,
+    channel: &Channel,
+    tab: &str,
+    cookie_config: Option<(bool, String)>,
+) -> Result<TabListing, String> {
+    let url = format!("{}/{}", channel.url, tab);
+    let args = YtDlpArgs::new()
+        .flag("--flat-playlist")
+        .flag("--dump-json")
+        .flag("--no-warnings")
+        .flag("--skip-download")
+        .opt("--playlist-end", ARTIST_TAB_LIMIT.to_string())
+        .cookies(cookie_config.as_ref())
+        .flag(&url);
+    let output = ytdlp
+        .run(&args)
+        .map_err(|e| format!("{}. Is yt-dlp installed?", e))?;
+    if output.success {
+        return Ok(TabListing {
+            stdout: Some(output.stdout),
+            error: None,
+        });
+    }
+    let err = format!("yt-dlp failed: {}", output.error_snippet());
+    if is_network_error(&err) {
+        return Err(err);
+    }
+    if output.stderr.contains("does not have a") {
+        return Ok(TabListing::default());
+    }
+    Ok(TabListing {
+        stdout: None,
+        error: Some(err),
+    })
+}
+
+/// Fetch the Videos, Releases and Playlists tabs of `channel` in parallel.
+pub(crate) fn fetch_artist_page(
+    ytdlp: &dyn YtDlp,
+    channel: &Channel,
+    cookie_config: Option<(bool, String)>,
+) -> Result<ArtistPage, String> {
+    let (videos, releases, playlists) = std::thread::scope(|s| {
+        let tab = |name: &'static str| {
+            let cookie_config = cookie_config.clone();
+            s.spawn(move || fetch_tab(ytdlp, channel, name, cookie_config))
+        };
+        let handles = [tab("videos"), tab("releases"), tab("playlists")];
+        let [videos, releases, playlists] = handles.map(|h| {
+            h.join()
+                .unwrap_or_else(|_| Err("yt-dlp thread panicked".to_string()))
+        });
+        (videos, releases, playlists)
+    });
+    let (videos, releases, playlists) = (videos?, releases?, playlists?);
+    if videos.stdout.is_none() && releases.stdout.is_none() && playlists.stdout.is_none() {
+        let why = [&videos, &releases, &playlists]
+            .into_iter()
+            .find_map(|tab| tab.error.as_ref());
+        return Err(match why {
+            Some(err) => format!("Couldn't list the channel of {}: {}", channel.name, err),
+            None => format!("Couldn't list the channel of {}", channel.name),
+        });
+    }
+
+    let mut uploads = videos
+        .stdout
+        .as_deref()
+        .map(parse_flat_tracks)
+        .unwrap_or_default();
+    // Channel tabs leave the uploader out.
+    for track in &mut uploads {
+        if track.uploader == "Unknown" {
+            track.uploader = channel.name.clone();
+        }
+    }
+    let (singles, albums) = releases
+        .stdout
+        .as_deref()
+        .map(parse_releases)
+        .unwrap_or_default()
+        .into_iter()
+        .partition(is_single);
+
+    Ok(ArtistPage {
+        channel: channel.clone(),
+        uploads,
+        albums,
+        singles,
+        playlists: playlists
+            .stdout
+            .as_deref()
+            .map(parse_releases)
+            .unwrap_or_default(),
+        uploads_error: videos.error,
+        releases_error: releases.error,
+        playlists_error: playlists.error,
+    })
+}
+
+/// The tracks of an album, single or playlist.
+pub(crate) fn fetch_release_tracks(
+    ytdlp: &dyn YtDlp,
+    release: &Release,
+    cookie_config: Option<(bool, String)>,
+) -> Result<Vec<Track>, String> {
+    if !is_allowed_youtube_url(&release.url) {
+        return Err("Invalid URL: must be a YouTube or YouTube Music URL".to_string());
+    }
+    let stdout = dump_flat_playlist(ytdlp, &release.url, cookie_config, "release")?;
+    Ok(parse_flat_tracks(&stdout))
+}
+
+/// The cache of `channel_id`'s page under `config_dir`.
+pub(crate) fn artist_cache_store(config_dir: &Path, channel_id: &str) -> CacheStore<ArtistPage> {
+    CacheStore::new(
+        config_dir
+            .join("artists")
+            .join(format!("{}.json", channel_id)),
+        Duration::from_secs(ARTIST_CACHE_TTL_SECS),
+        ArtistPage::CACHE_SCHEMA_VERSION,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::youtube::fake_ytdlp::{FakeYtDlp, Reply};
+
+    const WEEKND: &str = "UC0WP5P-ufpRfjbNrmOWwLBQ";
+
+    fn weeknd() -> Channel {
+        Channel::new(
+            WEEKND,
+            "The Weeknd - Topic",
+            &format!("https://www.youtube.com/channel/{}/", WEEKND),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn channels_resolve_from_video_metadata() {
+        let fake = FakeYtDlp::new()
+            .on("watch?v=4NRXx6U8ABQ", Reply::fixture("video_info.json"))
+            .on("watch?v=handleOnly1", Reply::Output(
+                r#"{"id": "handleOnly1", "uploader": "Someone", "uploader_id": "@someone.music"}"#.to_string(),
+            ))
+            .on("watch?v=nothing1234", Reply::Output(r#"{"id": "nothing1234", "uploader_id": "../x"}"#.to_string()));
+        let track = |id: &str| {
+            Track::new(
+                id.to_string(),
+                "Blinding Lights".to_string(),
+                200,
+                "The Weeknd".to_string(),
+                String::new(),
+            )
+        };
+
+        let channel = resolve_channel(&fake, &track("4NRXx6U8ABQ"), None).unwrap();
+        assert_eq!(channel, weeknd());
+        assert_eq!(
+            channel.url,
+            format!("https://www.youtube.com/channel/{}", WEEKND)
+        );
+
+        let channel = resolve_channel(&fake, &track("handleOnly1"), None).unwrap();
+        assert_eq!(channel.id, "@someone.music");
+        assert_eq!(channel.url, "https://www.youtube.com/@someone.music");
+
+        let err = resolve_channel(&fake, &track("nothing1234"), None).unwrap_err();
+        assert_eq!(err, "No channel found for 'The Weeknd'");
+        assert!(resolve_channel(&fake, &track("../../etc"), None).is_err());
+        assert_eq!(fake.calls().len(), 3);
+    }
+
+    #[test]
+    fn artist_page_lists_every_tab() {
+        let fake = FakeYtDlp::new()
+            .on("/videos", Reply::fixture("channel_videos.jsonl"))
+            .on("/releases", Reply::fixture("channel_releases.jsonl"))
+            .on(
+                "/playlists",
+                Reply::Fail(
+                    "ERROR: [youtube:tab] This channel does not have a playlists tab\n".to_string(),
+                ),
+            );
+        let page = fetch_artist_page(&fake, &weeknd(), None).unwrap();
+
+        let ids: Vec<_> = page.uploads.iter().map(|t| t.video_id.as_str()).collect();
+        assert_eq!(ids, ["4NRXx6U8ABQ", "XXYlFuWEuKI"]);
+        assert_eq!(page.uploads[0].duration, 263);
+        assert_eq!(page.uploads[0].uploader, "The Weeknd");
+
+        let titles =
+            |releases: &[Release]| releases.iter().map(|r| r.title.clone()).collect::<Vec<_>>();
+        assert_eq!(titles(&page.albums), ["After Hours", "Dawn FM"]);
+        assert_eq!(
+            titles(&page.singles),
+            ["Dancing In The Flames", "Timeless - Single"]
+        );
+        assert!(page.playlists.is_empty());
+        assert!(page.tab_errors().is_empty());
+        assert_eq!(
+            page.albums[0].url,
+            "https://www.youtube.com/playlist?list=OLAK5uy_kLWIr9gv7XLlsbvfyCWgKyaHEsnhrCcWw"
+        );
+
+        let args = fake.calls();
+        assert!(args
+            .iter()
+            .all(|a| a.value_of("--playlist-end") == Some("50")));
+    }
+
+    #[test]
+    fn artist_page_fails_when_nothing_lists_or_offline() {
+        let gone = FakeYtDlp::new().on(
+            "youtube.com",
+            Reply::Fail("ERROR: This channel does not exist.\n".to_string()),
+        );
+        assert!(fetch_artist_page(&gone, &weeknd(), None)
+            .unwrap_err()
+            .starts_with("Couldn't list"));
+
+        let offline = FakeYtDlp::new().on(
+            "/releases",
+            Reply::Fail("ERROR: Unable to download API page: <urlopen error [Errno -3] Temporary failure in name resolution>\n".to_string()),
+        )
+        .on("youtube.com", Reply::fixture("channel_videos.jsonl"));
+        let err = fetch_artist_page(&offline, &weeknd(), None).unwrap_err();
+        assert!(is_network_error(&err), "{}", err);
+    }
+
+    #[test]
+    fn failed_tabs_are_reported_on_the_page() {
+        let fake = FakeYtDlp::new()
+            .on("/videos", Reply::fixture("channel_videos.jsonl"))
+            .on(
+                "/releases",
+                Reply::Fail("ERROR: [youtube:tab] Sign in to confirm your age\n".to_string()),
+            )
+            .on(
+                "/playlists",
+                Reply::Fail(
+                    "ERROR: [youtube:tab] This channel does not have a playlists tab\n".to_string(),
+                ),
+            );
+        let page = fetch_artist_page(&fake, &weeknd(), None).unwrap();
+        assert_eq!(page.uploads.len(), 2);
+        assert!(page.albums.is_empty());
+        assert!(page.playlists_error.is_none());
+        let errors = page.tab_errors();
+        assert_eq!(errors.len(), 1);
+        assert!(
+            errors[0].starts_with("Releases: yt-dlp failed:"),
+            "{}",
+            errors[0]
+        );
+        assert!(
+            errors[0].contains("Sign in to confirm your age"),
+            "{}",
+            errors[0]
+        );
+    }
+
+    #[test]
+    fn pages_round_trip_through_the_cache() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let store = artist_cache_store(tmp.path(), WEEKND);
+        assert!(store.load().unwrap().is_none());
+        let page = ArtistPage {
+            channel: weeknd(),
+            uploads: Vec::new(),
+            albums: Vec::new(),
+            singles: Vec::new(),
+            playlists: Vec::new(),
+            uploads_error: None,
+            releases_error: None,
+            playlists_error: Some("yt-dlp failed: HTTP Error 500".to_string()),
+        };
+        assert!(page.is_empty());
+        store.save(&page).unwrap();
+        assert_eq!(store.load().unwrap(), Some(page));
+        assert!(tmp
+            .path()
+            .join("artists")
+            .join(format!("{}.json", WEEKND))
+            .is_file());
+    }
+}
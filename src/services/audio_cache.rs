@@ -0,0 +1,569 @@
+//! Permanent offline audio cache.
+//!
+//! Audio lives in the XDG cache directory (`~/.cache/youtube-music-player/audio`
+//! on Linux) as `<video_id>.<tag>.<ext>`, so a track's file can be found
+//! from its id and the configured audio format alone and survives reboots.
+//! The tag keys files by format (see [`AudioProfile::cache_tag`]). Files of
+//! another format found at startup stay playable, and each is replaced by a
+//! download in the current format the next time it plays (see
+//! [`outdated_formats`]). The cache is kept under a size quota by evicting
+//! the least-recently-played files; pinned tracks are never evicted.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result};
+
+use crate::player::audio::{open_native_decoder, probe_with_ffmpeg};
+use crate::services::settings::AudioProfile;
+use crate::youtube::extractor::is_valid_video_id;
+
+/// Files smaller than this are treated as failed or truncated downloads.
+pub(crate) const MIN_AUDIO_FILE_BYTES: u64 = 10_000;
+
+/// Lowest plausible bitrate (bytes per second, 24 kbps — below the lowest
+/// `audio_bitrate_kbps`) for a complete file. A file smaller than
+/// `duration × this` was cut off mid-download.
+const MIN_BYTES_PER_SEC: u64 = 3_000;
+
+/// Samples decoded when verifying a file — about a second of stereo audio.
+const VERIFY_DECODE_SAMPLES: usize = 88_200;
+
+/// Extensions of playable audio files that may sit in the cache.
+pub(crate) const AUDIO_EXTENSIONS: &[&str] = &["mp3", "m4a", "opus", "ogg", "flac", "wav"];
+
+/// Cover art yt-dlp downloads next to the audio before embedding it.
+const THUMBNAIL_EXTENSIONS: &[&str] = &[".webp", ".jpg", ".png"];
+
+/// Containers yt-dlp downloads into before extracting the audio.
+const INTERMEDIATE_EXTENSIONS: &[&str] = &["webm", "mp4"];
+
+/// Handle to the audio cache directory, for files of one audio profile.
+#[derive(Debug, Clone)]
+pub(crate) struct AudioCache {
+    dir: PathBuf,
+    /// Format new downloads are fetched in.
+    profile: AudioProfile,
+    /// `profile`'s filename tag; empty for plain `<video_id>.<ext>`.
+    tag: String,
+}
+
+impl AudioCache {
+    pub(crate) fn new(dir: PathBuf, profile: AudioProfile) -> Self {
+        Self {
+            dir,
+            profile,
+            tag: profile.cache_tag(),
+        }
+    }
+
+    /// Create the directory if needed, readable only by the owner.
+    pub(crate) fn ensure_dir(&self) -> Result<()> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create audio cache {}", self.dir.display()))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let _ = fs::set_permissions(&self.dir, fs::Permissions::from_mode(0o700));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub(crate) fn profile(&self) -> &AudioProfile {
+        &self.profile
+    }
+
+    /// Filename without extension of `video_id`'s file in the current format.
+    fn stem(&self, video_id: &str) -> String {
+        if self.tag.is_empty() {
+            video_id.to_string()
+        } else {
+            format!("{}.{}", video_id, self.tag)
+        }
+    }
+
+    /// yt-dlp output template for `video_id`, or `None` if the id isn't
+    /// safe to use as a filename.
+    pub(crate) fn output_template(&self, video_id: &str) -> Option<PathBuf> {
+        is_valid_video_id(video_id)
+            .then(|| self.dir.join(format!("{}.%(ext)s", self.stem(video_id))))
+    }
+
+    /// The cached audio file for `video_id` in the current format, if one
+    /// exists.
+    pub(crate) fn find(&self, video_id: &str) -> Option<PathBuf> {
+        if !is_valid_video_id(video_id) {
+            return None;
+        }
+        let stem = self.stem(video_id);
+        AUDIO_EXTENSIONS
+            .iter()
+            .map(|ext| self.dir.join(format!("{}.{}", stem, ext)))
+            .find(|path| path.is_file())
+    }
+
+    /// `true` if `path` is `video_id`'s file in the current format.
+    pub(crate) fn is_current_format(&self, video_id: &str, path: &Path) -> bool {
+        let ext_ok = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| AUDIO_EXTENSIONS.contains(&e));
+        ext_ok && path.file_stem().and_then(|s| s.to_str()) == Some(self.stem(video_id).as_str())
+    }
+
+    /// `true` if `path` is inside the cache directory.
+    pub(crate) fn contains(&self, path: &Path) -> bool {
+        match (path.canonicalize(), self.dir.canonicalize()) {
+            (Ok(path), Ok(dir)) => path.starts_with(dir),
+            _ => false,
+        }
+    }
+
+    /// Move `src` into the cache as `<video_id>.<ext>`, copying across
+    /// filesystems (e.g. out of a tmpfs `/tmp`). Returns the new path.
+    pub(crate) fn adopt(&self, video_id: &str, src: &Path) -> Result<PathBuf> {
+        anyhow::ensure!(
+            is_valid_video_id(video_id),
+            "invalid video id '{}'",
+            video_id
+        );
+        let ext = src
+            .extension()
+            .and_then(|e| e.to_str())
+            .filter(|e| AUDIO_EXTENSIONS.contains(e))
+            .unwrap_or("mp3");
+        let dest = self.dir.join(format!("{}.{}", video_id, ext));
+        if fs::rename(src, &dest).is_err() {
+            fs::copy(src, &dest)
+                .with_context(|| format!("Failed to copy {} into the cache", src.display()))?;
+            let _ = fs::remove_file(src);
+        }
+        Ok(dest)
+    }
+
+    /// Remove half-finished downloads (`.part`, `.ytdl`, intermediate files,
+    /// and cover art yt-dlp didn't get to embed and clean up) older than
+    /// `max_age`.
+    pub(crate) fn sweep_partials(&self, max_age: Duration) {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return;
+        };
+        let now = SystemTime::now();
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let partial = is_partial(&name);
+            let old = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .is_some_and(|age| age > max_age);
+            if partial && old {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+
+    /// Remove what a cancelled download of `video_id` left behind: partial
+    /// and intermediate files, and output yt-dlp didn't finish. Files of
+    /// other formats are left alone.
+    pub(crate) fn remove_leftovers(&self, video_id: &str) {
+        if !is_valid_video_id(video_id) {
+            return;
+        }
+        let prefix = format!("{}.", self.stem(video_id));
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Some(rest) = name.strip_prefix(&prefix) else {
+                continue;
+            };
+            // Audio with no complete download behind it is an intermediate
+            // or a half-written output file.
+            let audio = AUDIO_EXTENSIONS.contains(&rest) || INTERMEDIATE_EXTENSIONS.contains(&rest);
+            if audio || is_partial(&name) {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+}
+
+/// `.part`/`.ytdl` downloads, ffmpeg's `.temp.` files, and cover art yt-dlp
+/// didn't get to embed and clean up.
+fn is_partial(name: &str) -> bool {
+    name.ends_with(".part")
+        || name.ends_with(".ytdl")
+        || name.contains(".temp.")
+        || THUMBNAIL_EXTENSIONS.iter().any(|ext| name.ends_with(ext))
+}
+
+/// Outcome of verifying one cached file.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum FileHealth {
+    Ok,
+    Missing,
+    /// Smaller than a complete file could be (size in bytes).
+    Truncated(u64),
+    /// The container couldn't be opened or probed.
+    Unreadable(String),
+    /// The container opened but yielded (almost) no audio.
+    Undecodable,
+}
+
+impl FileHealth {
+    pub(crate) fn is_ok(&self) -> bool {
+        *self == FileHealth::Ok
+    }
+}
+
+impl std::fmt::Display for FileHealth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileHealth::Ok => write!(f, "ok"),
+            FileHealth::Missing => write!(f, "file missing"),
+            FileHealth::Truncated(size) => write!(f, "truncated ({})", format_bytes(*size)),
+            FileHealth::Unreadable(e) => write!(f, "unreadable: {}", e),
+            FileHealth::Undecodable => write!(f, "no decodable audio"),
+        }
+    }
+}
+
+/// Check a cached file: its size against the track's duration (when
+/// known), that the container probes, and that the first second decodes.
+pub(crate) fn verify_file(path: &Path, duration_secs: Option<u64>) -> FileHealth {
+    let size = match fs::metadata(path) {
+        Ok(metadata) if metadata.is_file() => metadata.len(),
+        _ => return FileHealth::Missing,
+    };
+    let min_size = duration_secs
+        .map(|secs| secs.saturating_mul(MIN_BYTES_PER_SEC))
+        .unwrap_or(0)
+        .max(MIN_AUDIO_FILE_BYTES);
+    if size < min_size {
+        return FileHealth::Truncated(size);
+    }
+
+    let decoder = match open_native_decoder(path) {
+        Ok(Some(decoder)) => decoder,
+        // Formats only ffmpeg reads (Opus) get a short ffmpeg probe.
+        Ok(None) => {
+            return match probe_with_ffmpeg(path) {
+                Ok(()) => FileHealth::Ok,
+                Err(e) => FileHealth::Unreadable(e),
+            }
+        }
+        Err(e) => return FileHealth::Unreadable(e),
+    };
+    // Short tracks may hold less than a second; a tenth of it is enough.
+    if decoder.take(VERIFY_DECODE_SAMPLES).count() < VERIFY_DECODE_SAMPLES / 10 {
+        return FileHealth::Undecodable;
+    }
+    FileHealth::Ok
+}
+
+/// Move legacy cache entries (temp-dir files from before the permanent
+/// cache) into `cache`. Entries already in the cache are kept as they are;
+/// entries that can't be moved are dropped. Returns the updated map and how
+/// many files were moved.
+pub(crate) fn migrate_legacy(
+    cache: &AudioCache,
+    entries: HashMap<String, String>,
+) -> (HashMap<String, String>, usize) {
+    let mut migrated = 0;
+    let mut out = HashMap::with_capacity(entries.len());
+    for (video_id, path) in entries {
+        let path = PathBuf::from(path);
+        if !path.is_file() {
+            continue;
+        }
+        if cache.contains(&path) {
+            out.insert(video_id, path.to_string_lossy().to_string());
+            continue;
+        }
+        if let Ok(dest) = cache.adopt(&video_id, &path) {
+            out.insert(video_id, dest.to_string_lossy().to_string());
+            migrated += 1;
+        }
+    }
+    (out, migrated)
+}
+
+/// Ids of cache entries downloaded under another `audio_format` /
+/// `audio_bitrate_kbps` setting than the current one. Their files stay
+/// playable until a download in the current format replaces them.
+pub(crate) fn outdated_formats(
+    cache: &AudioCache,
+    entries: &HashMap<String, String>,
+) -> HashSet<String> {
+    entries
+        .iter()
+        .filter(|(video_id, path)| !cache.is_current_format(video_id, Path::new(path)))
+        .map(|(video_id, _)| video_id.clone())
+        .collect()
+}
+
+/// One cached file as seen by the eviction planner.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CacheEntry {
+    pub video_id: String,
+    pub size_bytes: u64,
+    /// Unix seconds of the last play, or of caching if never played.
+    pub last_used: i64,
+}
+
+/// Pick files to evict so the cache fits in `quota_bytes`: least recently
+/// used first, never anything in `keep` (pinned, playing, pending). Returns
+/// the video ids to evict — possibly not enough to reach the quota if most
+/// of the cache is kept.
+pub(crate) fn plan_eviction(
+    entries: &[CacheEntry],
+    quota_bytes: u64,
+    keep: &HashSet<String>,
+) -> Vec<String> {
+    let mut total: u64 = entries.iter().map(|e| e.size_bytes).sum();
+    if total <= quota_bytes {
+        return Vec::new();
+    }
+    let mut candidates: Vec<&CacheEntry> = entries
+        .iter()
+        .filter(|e| !keep.contains(&e.video_id))
+        .collect();
+    candidates.sort_by(|a, b| {
+        a.last_used
+            .cmp(&b.last_used)
+            .then_with(|| a.video_id.cmp(&b.video_id))
+    });
+
+    let mut evict = Vec::new();
+    for entry in candidates {
+        if total <= quota_bytes {
+            break;
+        }
+        total = total.saturating_sub(entry.size_bytes);
+        evict.push(entry.video_id.clone());
+    }
+    evict
+}
+
+/// `1.4 GB`, `820 MB`, `96 KB` — sizes for the status bar and cache views.
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    const KB: f64 = 1024.0;
+    let b = bytes as f64;
+    if b >= KB * KB * KB {
+        format!("{:.1} GB", b / (KB * KB * KB))
+    } else if b >= KB * KB {
+        format!("{:.0} MB", b / (KB * KB))
+    } else {
+        format!("{:.0} KB", b / KB)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::settings::{AudioFormat, Settings};
+    use tempfile::TempDir;
+
+    fn entry(id: &str, size: u64, last_used: i64) -> CacheEntry {
+        CacheEntry {
+            video_id: id.to_string(),
+            size_bytes: size,
+            last_used,
+        }
+    }
+
+    #[test]
+    fn eviction_drops_least_recently_used_until_under_quota() {
+        let entries = [
+            entry("old", 40, 1),
+            entry("mid", 40, 2),
+            entry("new", 40, 3),
+        ];
+        assert!(plan_eviction(&entries, 120, &HashSet::new()).is_empty());
+        assert_eq!(plan_eviction(&entries, 80, &HashSet::new()), ["old"]);
+        assert_eq!(plan_eviction(&entries, 50, &HashSet::new()), ["old", "mid"]);
+    }
+
+    #[test]
+    fn eviction_never_touches_kept_tracks() {
+        let entries = [entry("pinned", 100, 1), entry("a", 10, 2)];
+        let keep: HashSet<String> = ["pinned".to_string()].into();
+        assert_eq!(plan_eviction(&entries, 50, &keep), ["a"]);
+    }
+
+    /// A cache for the default format (plain `<video_id>.<ext>` names).
+    fn cache_at(dir: &Path) -> AudioCache {
+        AudioCache::new(dir.to_path_buf(), Settings::default().audio_profile())
+    }
+
+    #[test]
+    fn find_and_template_use_video_id() {
+        let tmp = TempDir::new().unwrap();
+        let cache = cache_at(tmp.path());
+        assert!(cache.find("abc").is_none());
+        fs::write(tmp.path().join("abc.mp3"), b"x").unwrap();
+        assert_eq!(cache.find("abc"), Some(tmp.path().join("abc.mp3")));
+        assert_eq!(
+            cache.output_template("abc"),
+            Some(tmp.path().join("abc.%(ext)s"))
+        );
+        assert!(cache.output_template("../etc").is_none());
+        assert!(cache.find("../abc").is_none());
+    }
+
+    #[test]
+    fn format_tag_keys_files() {
+        let tmp = TempDir::new().unwrap();
+        let opus = Settings {
+            audio_format: AudioFormat::Opus,
+            audio_bitrate_kbps: 160,
+            ..Settings::default()
+        };
+        let cache = AudioCache::new(tmp.path().to_path_buf(), opus.audio_profile());
+        fs::write(tmp.path().join("abc.mp3"), b"x").unwrap();
+        assert!(cache.find("abc").is_none());
+        assert_eq!(
+            cache.output_template("abc"),
+            Some(tmp.path().join("abc.opus160k.%(ext)s"))
+        );
+        let current = tmp.path().join("abc.opus160k.opus");
+        fs::write(&current, b"x").unwrap();
+        assert_eq!(cache.find("abc"), Some(current.clone()));
+
+        // Switching formats leaves the MP3 outdated, but it isn't deleted.
+        let old = tmp.path().join("abc.mp3");
+        fs::write(&old, b"x").unwrap();
+        let path = |p: &Path| p.to_string_lossy().to_string();
+        let entries = HashMap::from([
+            ("abc".to_string(), path(&old)),
+            (
+                "def".to_string(),
+                path(&tmp.path().join("def.opus160k.opus")),
+            ),
+        ]);
+        assert_eq!(
+            outdated_formats(&cache, &entries),
+            HashSet::from(["abc".to_string()])
+        );
+        assert!(old.exists());
+    }
+
+    #[test]
+    fn remove_leftovers_keeps_other_formats_and_lyrics() {
+        let tmp = TempDir::new().unwrap();
+        let cache = cache_at(tmp.path());
+        for name in [
+            "abc.webm.part",
+            "abc.webm",
+            "abc.mp3",
+            "abc.temp.mp3",
+            "abc.webp",
+        ] {
+            fs::write(tmp.path().join(name), b"x").unwrap();
+        }
+        for name in ["abc.lrc", "abc.opus160k.opus", "abcd.mp3", "def.webm.part"] {
+            fs::write(tmp.path().join(name), b"x").unwrap();
+        }
+        cache.remove_leftovers("abc");
+        let mut left: Vec<String> = fs::read_dir(tmp.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        left.sort();
+        assert_eq!(
+            left,
+            ["abc.lrc", "abc.opus160k.opus", "abcd.mp3", "def.webm.part"]
+        );
+    }
+
+    #[test]
+    fn migrate_legacy_moves_files_and_drops_missing() {
+        let legacy = TempDir::new().unwrap();
+        let cache_dir = TempDir::new().unwrap();
+        let cache = cache_at(cache_dir.path());
+        let old = legacy.path().join("yt-music-audio-1-2.mp3");
+        fs::write(&old, b"audio").unwrap();
+        let kept = cache_dir.path().join("kept.mp3");
+        fs::write(&kept, b"audio").unwrap();
+
+        let entries: HashMap<String, String> = [
+            ("moved".to_string(), old.to_string_lossy().to_string()),
+            ("kept".to_string(), kept.to_string_lossy().to_string()),
+            ("gone".to_string(), "/nonexistent/x.mp3".to_string()),
+        ]
+        .into();
+        let (out, migrated) = migrate_legacy(&cache, entries);
+
+        assert_eq!(migrated, 1);
+        assert_eq!(out.len(), 2);
+        assert!(!old.exists());
+        assert_eq!(
+            out["moved"],
+            cache_dir.path().join("moved.mp3").to_string_lossy()
+        );
+        assert_eq!(
+            fs::read(cache_dir.path().join("moved.mp3")).unwrap(),
+            b"audio"
+        );
+        assert_eq!(out["kept"], kept.to_string_lossy());
+    }
+
+    /// A mono 16-bit PCM WAV of `samples` silent samples.
+    fn wav_bytes(samples: u32) -> Vec<u8> {
+        let data_len = samples * 2;
+        let mut out = Vec::new();
+        out.extend_from_slice(b"RIFF");
+        out.extend_from_slice(&(36 + data_len).to_le_bytes());
+        out.extend_from_slice(b"WAVEfmt ");
+        out.extend_from_slice(&16u32.to_le_bytes());
+        out.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        out.extend_from_slice(&1u16.to_le_bytes()); // mono
+        out.extend_from_slice(&44_100u32.to_le_bytes());
+        out.extend_from_slice(&88_200u32.to_le_bytes());
+        out.extend_from_slice(&2u16.to_le_bytes());
+        out.extend_from_slice(&16u16.to_le_bytes());
+        out.extend_from_slice(b"data");
+        out.extend_from_slice(&data_len.to_le_bytes());
+        out.resize(44 + data_len as usize, 0);
+        out
+    }
+
+    #[test]
+    fn verify_file_classifies_damage() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("a.wav");
+        assert_eq!(verify_file(&path, None), FileHealth::Missing);
+
+        fs::write(&path, wav_bytes(88_200)).unwrap();
+        assert_eq!(verify_file(&path, Some(2)), FileHealth::Ok);
+        // A 3-minute track can't fit in ~176 KB.
+        assert!(matches!(
+            verify_file(&path, Some(180)),
+            FileHealth::Truncated(_)
+        ));
+
+        fs::write(&path, vec![0x42; 20_000]).unwrap();
+        assert!(matches!(
+            verify_file(&path, None),
+            FileHealth::Unreadable(_)
+        ));
+
+        fs::write(&path, vec![0x42; 100]).unwrap();
+        assert_eq!(verify_file(&path, None), FileHealth::Truncated(100));
+    }
+
+    #[test]
+    fn format_bytes_picks_unit() {
+        assert_eq!(format_bytes(512 * 1024), "512 KB");
+        assert_eq!(format_bytes(300 * 1024 * 1024), "300 MB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024 / 2), "1.5 GB");
+    }
+}
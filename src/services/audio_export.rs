@@ -0,0 +1,224 @@
+//! Copy cached audio out of Crusty under human-readable names.
+//!
+//! Cache files are named by video id (`dQw4w9WgXcQ.mp3`); exported copies
+//! are named `Artist - Title.mp3` so they make sense in a music folder or
+//! on a phone. The tags yt-dlp embedded at download time travel with them.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::player::queue::Track;
+
+/// Longest exported file stem, in characters; some filesystems cap names
+/// at 255 bytes.
+const MAX_STEM_CHARS: usize = 120;
+
+/// What an export did.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct AudioExportReport {
+    pub copied: usize,
+    /// Already in the folder with the same size, from an earlier export.
+    pub unchanged: usize,
+    /// Tracks without a cached file.
+    pub not_cached: usize,
+}
+
+impl AudioExportReport {
+    /// "12 copied, 3 already there, 2 not cached".
+    pub(crate) fn summary(&self) -> String {
+        let mut parts = vec![format!("{} copied", self.copied)];
+        if self.unchanged > 0 {
+            parts.push(format!("{} already there", self.unchanged));
+        }
+        if self.not_cached > 0 {
+            parts.push(format!("{} not cached", self.not_cached));
+        }
+        parts.join(", ")
+    }
+}
+
+/// `Artist - Title` for `track`, safe as a file name. YouTube Music's
+/// auto-generated "Artist - Topic" channels lose the suffix, and titles
+/// that already lead with the artist aren't prefixed twice.
+pub(crate) fn export_stem(track: &Track) -> String {
+    let artist = track.uploader.trim();
+    let artist = artist.strip_suffix(" - Topic").unwrap_or(artist).trim();
+    let title = track.title.trim();
+    let name = if artist.is_empty() || title.to_lowercase().starts_with(&artist.to_lowercase()) {
+        title.to_string()
+    } else {
+        format!("{} - {}", artist, title)
+    };
+
+    let cleaned: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .take(MAX_STEM_CHARS)
+        .collect();
+    // Leading dots would hide the file; trailing dots and spaces upset Windows.
+    let cleaned = cleaned
+        .trim_start_matches('.')
+        .trim_end_matches(['.', ' '])
+        .trim();
+    if cleaned.is_empty() {
+        track.video_id.clone()
+    } else {
+        cleaned.to_string()
+    }
+}
+
+/// Copy the cached file of every track in `tracks` into `dir`, creating
+/// it. `cached` maps video ids to cache file paths. A name already taken by
+/// a different file gets a ` (2)` suffix; a same-sized file of the same
+/// name counts as exported before and is left alone.
+pub(crate) fn export_audio(
+    dir: &Path,
+    tracks: &[Track],
+    cached: &HashMap<String, String>,
+) -> Result<AudioExportReport> {
+    fs::create_dir_all(dir).with_context(|| format!("Can't create {}", dir.display()))?;
+
+    let mut report = AudioExportReport::default();
+    for track in tracks {
+        let Some(src) = cached
+            .get(&track.video_id)
+            .map(PathBuf::from)
+            .filter(|path| path.is_file())
+        else {
+            report.not_cached += 1;
+            continue;
+        };
+        let ext = src.extension().and_then(|e| e.to_str()).unwrap_or("mp3");
+        let size = fs::metadata(&src)?.len();
+
+        let stem = export_stem(track);
+        let mut dest = dir.join(format!("{}.{}", stem, ext));
+        let mut n = 2;
+        let unchanged = loop {
+            match fs::metadata(&dest) {
+                Ok(existing) if existing.len() == size => break true,
+                Ok(_) => {
+                    dest = dir.join(format!("{} ({}).{}", stem, n, ext));
+                    n += 1;
+                }
+                Err(_) => break false,
+            }
+        };
+        if unchanged {
+            report.unchanged += 1;
+            continue;
+        }
+        fs::copy(&src, &dest).with_context(|| format!("Failed to copy to {}", dest.display()))?;
+        report.copied += 1;
+    }
+    Ok(report)
+}
+
+/// Suggested export folder: `<Music dir>/<name>`.
+pub(crate) fn default_export_dir(name: &str) -> PathBuf {
+    let dir = dirs::audio_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(|| PathBuf::from("."));
+    let name: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || " -_".contains(c) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let name = if name.trim().is_empty() {
+        "Crusty"
+    } else {
+        name.trim()
+    };
+    dir.join(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(id: &str, title: &str, uploader: &str) -> Track {
+        Track::new(
+            id.to_string(),
+            title.to_string(),
+            200,
+            uploader.to_string(),
+            format!("https://www.youtube.com/watch?v={id}"),
+        )
+    }
+
+    #[test]
+    fn stems_are_readable_and_safe() {
+        assert_eq!(
+            export_stem(&track("a", "Song", "Band - Topic")),
+            "Band - Song"
+        );
+        assert_eq!(
+            export_stem(&track("a", "Band - Song (Official Video)", "Band")),
+            "Band - Song (Official Video)"
+        );
+        assert_eq!(export_stem(&track("a", "AC/DC: Live?", "")), "AC_DC_ Live_");
+        assert_eq!(export_stem(&track("a", "..hidden.", "")), "hidden");
+        assert_eq!(export_stem(&track("vid", "///", "")), "___");
+        assert_eq!(export_stem(&track("vid", " ", "")), "vid");
+        assert_eq!(
+            export_stem(&track("a", &"x".repeat(300), ""))
+                .chars()
+                .count(),
+            MAX_STEM_CHARS
+        );
+    }
+
+    #[test]
+    fn export_copies_renames_and_skips() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let cache = tmp.path().join("cache");
+        fs::create_dir_all(&cache).unwrap();
+        let a = cache.join("aaaaaaaaaaa.mp3");
+        let b = cache.join("bbbbbbbbbbb.opus");
+        fs::write(&a, b"aaaa").unwrap();
+        fs::write(&b, b"bbbbbb").unwrap();
+        let cached = HashMap::from([
+            ("aaaaaaaaaaa".to_string(), a.to_string_lossy().to_string()),
+            ("bbbbbbbbbbb".to_string(), b.to_string_lossy().to_string()),
+        ]);
+        let tracks = vec![
+            track("aaaaaaaaaaa", "Song", "Band"),
+            track("bbbbbbbbbbb", "Other", "Band"),
+            track("ccccccccccc", "Missing", "Band"),
+        ];
+        let out = tmp.path().join("out");
+        // A different file already has the first track's name.
+        fs::create_dir_all(&out).unwrap();
+        fs::write(out.join("Band - Song.mp3"), b"something else").unwrap();
+
+        let report = export_audio(&out, &tracks, &cached).unwrap();
+        assert_eq!(
+            report,
+            AudioExportReport {
+                copied: 2,
+                unchanged: 0,
+                not_cached: 1
+            }
+        );
+        assert_eq!(fs::read(out.join("Band - Song (2).mp3")).unwrap(), b"aaaa");
+        assert_eq!(fs::read(out.join("Band - Other.opus")).unwrap(), b"bbbbbb");
+
+        // Exporting again leaves the earlier copies alone.
+        let again = export_audio(&out, &tracks, &cached).unwrap();
+        assert_eq!((again.copied, again.unchanged), (0, 2));
+        assert_eq!(again.summary(), "0 copied, 2 already there, 1 not cached");
+    }
+}
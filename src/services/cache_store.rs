@@ -24,7 +24,8 @@
 //! `schema_version`. Old cache files will be treated as a miss on next load
 //! and silently replaced — no migration code required.
 
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime};
 
 use anyhow::Result;
@@ -84,6 +85,16 @@ impl<T: Serialize + DeserializeOwned> CacheStore<T> {
     ///   corrupt JSON). The caller should fetch fresh data and call [`save`].
     /// - `Err(_)` — I/O error other than "file not found".
     pub(crate) fn load(&self) -> Result<Option<T>> {
+        self.read(Some(self.ttl))
+    }
+
+    /// Like [`load`], but ignores the TTL — for when an expired cache beats
+    /// nothing at all (e.g. in offline mode).
+    pub(crate) fn load_stale(&self) -> Result<Option<T>> {
+        self.read(None)
+    }
+
+    fn read(&self, max_age: Option<Duration>) -> Result<Option<T>> {
         let bytes = match std::fs::read(&self.path) {
             Ok(b) => b,
             Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
@@ -110,7 +121,7 @@ impl<T: Serialize + DeserializeOwned> CacheStore<T> {
             .and_then(|saved| SystemTime::now().duration_since(saved).ok())
             .unwrap_or(Duration::MAX);
 
-        if age > self.ttl {
+        if max_age.is_some_and(|ttl| age > ttl) {
             return Ok(None);
         }
 
@@ -139,13 +150,49 @@ impl<T: Serialize + DeserializeOwned> CacheStore<T> {
         let _ = std::fs::remove_file(&self.path);
     }
 
-    /// Returns the path this store writes to (useful for logging/debugging).
-    #[allow(dead_code)]
+    /// Returns the path this store writes to.
     pub(crate) fn path(&self) -> &std::path::Path {
         &self.path
     }
 }
 
+// ---------------------------------------------------------------------------
+// Size-capped cache directories
+// ---------------------------------------------------------------------------
+
+/// Mark a cached file as just used, so `trim_dir` keeps it longest.
+pub(crate) fn mark_used(path: &Path) {
+    let _ = fs::File::options()
+        .append(true)
+        .open(path)
+        .and_then(|f| f.set_modified(SystemTime::now()));
+}
+
+/// Delete the least recently used files until `dir` fits in `max_bytes`.
+pub(crate) fn trim_dir(dir: &Path, max_bytes: u64) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut files: Vec<(SystemTime, u64, PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok()?;
+            meta.is_file().then_some(())?;
+            Some((meta.modified().ok()?, meta.len(), entry.path()))
+        })
+        .collect();
+    let mut total: u64 = files.iter().map(|(_, len, _)| len).sum();
+    files.sort();
+    for (_, len, path) in files {
+        if total <= max_bytes {
+            break;
+        }
+        if fs::remove_file(&path).is_ok() {
+            total -= len;
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -212,6 +259,8 @@ mod tests {
 
         // Even immediately after saving, TTL=0 means expired
         assert_eq!(store.load().unwrap(), None);
+        // ...but a stale load still returns it.
+        assert_eq!(store.load_stale().unwrap(), Some(payload));
     }
 
     #[test]
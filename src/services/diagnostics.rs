@@ -0,0 +1,370 @@
+//! Startup dependency diagnostics.
+//!
+//! Checks what playback and downloads depend on — yt-dlp and its age,
+//! ffmpeg, writable config and cache directories, an audio output device,
+//! and the selected browser's cookie store — and pairs every problem with
+//! a fix the user can act on. Runs once at startup (off the UI thread)
+//! and again from the Diagnostics screen.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::config::YTDLP_MAX_AGE_DAYS;
+use crate::youtube::ytdlp::{YtDlp, YtDlpArgs, YtDlpError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum CheckStatus {
+    Ok,
+    /// Works, but something will likely go wrong.
+    Warn,
+    /// Broken: some feature can't work until it's fixed.
+    Fail,
+}
+
+/// The outcome of one check.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Check {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// What to do about a warning or failure.
+    pub fix: Option<String>,
+}
+
+impl Check {
+    fn ok(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Ok,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn warn(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Warn,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+/// The selected browser account, for the cookie store check.
+#[derive(Debug, Clone)]
+pub(crate) struct CookieAccount {
+    pub display_name: String,
+    /// The profile's cookie database, if one was found.
+    pub store: Option<PathBuf>,
+}
+
+/// Everything the checks need from the app, gathered on the UI thread.
+#[derive(Debug, Clone)]
+pub(crate) struct DiagnosticsInput {
+    pub config_dir: PathBuf,
+    pub cache_dir: PathBuf,
+    pub audio_output: bool,
+    pub account: Option<CookieAccount>,
+    /// Unix time now, for the yt-dlp age check.
+    pub now: i64,
+}
+
+/// Run every check. Blocking: spawns yt-dlp and ffmpeg.
+pub(crate) fn run_checks(ytdlp: &dyn YtDlp, input: &DiagnosticsInput) -> Vec<Check> {
+    vec![
+        check_ytdlp(ytdlp, find_in_path("yt-dlp"), input.now),
+        check_ffmpeg(find_in_path("ffmpeg")),
+        check_writable("Config directory", &input.config_dir),
+        check_writable("Audio cache", &input.cache_dir),
+        check_audio_output(input.audio_output),
+        check_cookies(input.account.as_ref()),
+    ]
+}
+
+/// The worst status among `checks`.
+pub(crate) fn worst(checks: &[Check]) -> CheckStatus {
+    checks
+        .iter()
+        .map(|c| c.status)
+        .max()
+        .unwrap_or(CheckStatus::Ok)
+}
+
+/// Where `program` would be found on `PATH`.
+pub(crate) fn find_in_path(program: &str) -> Option<PathBuf> {
+    let exe = format!("{}{}", program, std::env::consts::EXE_SUFFIX);
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .map(|dir| dir.join(&exe))
+        .find(|path| path.is_file())
+}
+
+fn located(path: Option<&Path>) -> String {
+    path.map_or_else(String::new, |p| format!(" at {}", p.display()))
+}
+
+fn check_ytdlp(ytdlp: &dyn YtDlp, path: Option<PathBuf>, now: i64) -> Check {
+    const NAME: &str = "yt-dlp";
+    const UPDATE: &str =
+        "Update it with `yt-dlp -U`, `pip install -U yt-dlp`, or your package manager.";
+
+    let output = match ytdlp.run(&YtDlpArgs::new().flag("--version")) {
+        Ok(output) => output,
+        Err(YtDlpError::Missing) => {
+            return Check::fail(
+                NAME,
+                "Not found on PATH — nothing can be searched or downloaded",
+                "Install yt-dlp (`pip install yt-dlp` or your package manager) and restart.",
+            )
+        }
+        Err(e) => {
+            return Check::fail(
+                NAME,
+                e.to_string(),
+                "Reinstall yt-dlp. ".to_string() + UPDATE,
+            )
+        }
+    };
+    if !output.success {
+        return Check::fail(
+            NAME,
+            format!(
+                "Installed{} but broken: {}",
+                located(path.as_deref()),
+                output.error_snippet()
+            ),
+            "Reinstall yt-dlp. ".to_string() + UPDATE,
+        );
+    }
+
+    let version = output.stdout.trim();
+    let detail = format!("{}{}", version, located(path.as_deref()));
+    match version_age_days(version, now) {
+        Some(age) if age > YTDLP_MAX_AGE_DAYS => Check::warn(
+            NAME,
+            format!(
+                "{} — {} days old; YouTube changes often break old releases",
+                detail, age
+            ),
+            UPDATE,
+        ),
+        _ => Check::ok(NAME, detail),
+    }
+}
+
+/// Days between a `YYYY.MM.DD[.N]` yt-dlp version and `now`.
+fn version_age_days(version: &str, now: i64) -> Option<i64> {
+    let mut parts = version.split('.').map(|p| p.parse::<i64>().ok());
+    let (year, month, day) = (parts.next()??, parts.next()??, parts.next()??);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    Some(now.div_euclid(86_400) - days_from_civil(year, month, day))
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn check_ffmpeg(path: Option<PathBuf>) -> Check {
+    const NAME: &str = "ffmpeg";
+    const INSTALL: &str = "Install ffmpeg with your package manager (e.g. `apt install ffmpeg`, `brew install ffmpeg`).";
+
+    let output = Command::new("ffmpeg")
+        .arg("-version")
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output();
+    match output {
+        Ok(output) if output.status.success() => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            Check::ok(
+                NAME,
+                format!("{}{}", ffmpeg_version(&stdout), located(path.as_deref())),
+            )
+        }
+        Ok(_) => Check::fail(
+            NAME,
+            format!(
+                "Installed{} but `ffmpeg -version` failed",
+                located(path.as_deref())
+            ),
+            INSTALL,
+        ),
+        Err(_) => Check::fail(
+            NAME,
+            "Not found — yt-dlp needs it to extract audio (-x), and Opus files need it to play",
+            INSTALL,
+        ),
+    }
+}
+
+/// `ffmpeg 6.1.1` from the first line of `ffmpeg -version`.
+fn ffmpeg_version(stdout: &str) -> String {
+    stdout
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix("ffmpeg version "))
+        .and_then(|rest| rest.split_whitespace().next())
+        .map_or_else(|| "ffmpeg".to_string(), |v| format!("ffmpeg {}", v))
+}
+
+fn check_writable(name: &'static str, dir: &Path) -> Check {
+    let written = std::fs::create_dir_all(dir).and_then(|()| tempfile::tempfile_in(dir));
+    match written {
+        Ok(_) => Check::ok(name, dir.display().to_string()),
+        Err(e) => Check::fail(
+            name,
+            format!("{} isn't writable: {}", dir.display(), e),
+            format!(
+                "Fix the permissions (`chmod u+rwx {}`) or free up disk space.",
+                dir.display()
+            ),
+        ),
+    }
+}
+
+fn check_audio_output(available: bool) -> Check {
+    const NAME: &str = "Audio output";
+    if available {
+        Check::ok(NAME, "Default output device opened")
+    } else {
+        Check::fail(
+            NAME,
+            "No audio output device — tracks can't be played",
+            "Make sure a sound server (PipeWire/PulseAudio) is running and a device is connected, then restart.",
+        )
+    }
+}
+
+fn check_cookies(account: Option<&CookieAccount>) -> Check {
+    const NAME: &str = "Browser cookies";
+    let Some(account) = account else {
+        return Check::warn(
+            NAME,
+            "No browser account selected — the feed, My Mix, and private playlists need one",
+            "Press o to pick the browser you're logged into YouTube with.",
+        );
+    };
+    let Some(store) = &account.store else {
+        return Check::fail(
+            NAME,
+            format!("{}: no cookie database found", account.display_name),
+            "Log into YouTube in that browser once, or press o to pick another account.",
+        );
+    };
+    match std::fs::File::open(store) {
+        Ok(_) => Check::ok(NAME, format!("{} ({})", account.display_name, store.display())),
+        Err(e) => Check::fail(
+            NAME,
+            format!("{}: can't read {}: {}", account.display_name, store.display(), e),
+            "Check the file's permissions; if the browser locks it, close the browser and retry (r).",
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::youtube::fake_ytdlp::{FakeYtDlp, Reply};
+
+    /// 2026-10-18 00:00 UTC.
+    const NOW: i64 = 1_792_281_600;
+
+    #[test]
+    fn version_age_from_date_versions() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(version_age_days("2026.10.18", NOW), Some(0));
+        assert_eq!(version_age_days("2026.07.01.232908", NOW), Some(109));
+        assert_eq!(version_age_days("2025.10.18", NOW), Some(365));
+        assert_eq!(version_age_days("nightly", NOW), None);
+        assert_eq!(version_age_days("2026.13.01", NOW), None);
+    }
+
+    #[test]
+    fn ytdlp_check_statuses() {
+        let check = |reply: Reply| check_ytdlp(&FakeYtDlp::new().on("--version", reply), None, NOW);
+        let fresh = check(Reply::Output("2026.09.30\n".to_string()));
+        assert_eq!(
+            (fresh.status, fresh.detail.as_str()),
+            (CheckStatus::Ok, "2026.09.30")
+        );
+
+        let old = check(Reply::Output("2025.01.15\n".to_string()));
+        assert_eq!(old.status, CheckStatus::Warn);
+        assert!(old.detail.contains("days old") && old.fix.is_some());
+
+        assert_eq!(check(Reply::Missing).status, CheckStatus::Fail);
+        let broken = check(Reply::Fail(
+            "ImportError: No module named yt_dlp\n".to_string(),
+        ));
+        assert_eq!(broken.status, CheckStatus::Fail);
+        assert!(broken
+            .detail
+            .ends_with("broken: ImportError: No module named yt_dlp"));
+    }
+
+    #[test]
+    fn ffmpeg_version_from_banner() {
+        assert_eq!(
+            ffmpeg_version("ffmpeg version 6.1.1-3ubuntu5 Copyright (c) 2000-2023\nbuilt with gcc"),
+            "ffmpeg 6.1.1-3ubuntu5"
+        );
+        assert_eq!(ffmpeg_version(""), "ffmpeg");
+    }
+
+    #[test]
+    fn writable_and_cookie_checks() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        assert_eq!(
+            check_writable("Cache", &tmp.path().join("new")).status,
+            CheckStatus::Ok
+        );
+        let file = tmp.path().join("file");
+        std::fs::write(&file, b"x").unwrap();
+        assert_eq!(check_writable("Cache", &file).status, CheckStatus::Fail);
+
+        assert_eq!(check_cookies(None).status, CheckStatus::Warn);
+        let account = |store: Option<PathBuf>| CookieAccount {
+            display_name: "Firefox - default".to_string(),
+            store,
+        };
+        assert_eq!(
+            check_cookies(Some(&account(Some(file)))).status,
+            CheckStatus::Ok
+        );
+        assert_eq!(
+            check_cookies(Some(&account(None))).status,
+            CheckStatus::Fail
+        );
+        let missing = account(Some(tmp.path().join("cookies.sqlite")));
+        assert_eq!(check_cookies(Some(&missing)).status, CheckStatus::Fail);
+    }
+
+    #[test]
+    fn worst_status_wins() {
+        let checks = [
+            Check::ok("a", ""),
+            Check::warn("b", "", ""),
+            Check::ok("c", ""),
+        ];
+        assert_eq!(worst(&checks), CheckStatus::Warn);
+        assert_eq!(worst(&[]), CheckStatus::Ok);
+    }
+}
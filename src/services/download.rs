@@ -1,70 +1,359 @@
 //! Download manager for background audio file fetching and caching.
+//!
+//! Up to [`MAX_CONCURRENT_DOWNLOADS`] run at once. Further requests wait in
+//! a [`DownloadQueue`] by priority (current track > next tracks > hover >
+//! background); a request that outranks a running download preempts it,
+//! killing its yt-dlp process and queueing it again. Downloads can also be
+//! cancelled one by one.
+//!
+//! Failed downloads are classified as transient (network, throttling) or
+//! permanent (private, removed, geo-blocked). Transient failures are retried
+//! automatically with exponential backoff and jitter; every failure record
+//! expires after [`FAILURE_RECORD_TTL_SECS`] so the track can be tried again.
 
 use std::collections::{HashMap, HashSet};
+use std::path::Path;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use tokio::sync::mpsc;
 
-use crate::config::{is_allowed_youtube_url, MAX_CONCURRENT_DOWNLOADS, TEMP_FILE_MAX_AGE_SECS};
+use crate::config::{
+    is_allowed_youtube_url, DOWNLOAD_RETRY_BASE_SECS, DOWNLOAD_RETRY_MAX_SECS,
+    FAILURE_RECORD_TTL_SECS, MAX_CONCURRENT_DOWNLOADS, MAX_DOWNLOAD_ATTEMPTS,
+    TEMP_FILE_MAX_AGE_SECS,
+};
 use crate::player::queue::Track;
+use crate::services::audio_cache::{AudioCache, MIN_AUDIO_FILE_BYTES};
+use crate::services::download_queue::{preemption_victim, DownloadPriority, DownloadQueue};
+use crate::youtube::ytdlp::{CancelHandle, YtDlp, YtDlpArgs, YtDlpError};
 
 /// Result of a completed download: (video_id, Ok(file_path) | Err(error_message)).
 pub(crate) type DownloadResult = (String, Result<String, String>);
 
-/// Unified state for all download tracking, guarded by a single mutex.
-struct DownloadState {
-    downloaded_files: HashMap<String, String>,
-    failed_downloads: HashMap<String, String>,
-    active_count: usize,
-    downloading_videos: HashSet<String>,
+/// Message from a download task to the manager.
+#[derive(Debug)]
+pub(crate) enum DownloadEvent {
+    /// yt-dlp reported progress for a running download.
+    Progress(String, DownloadProgress),
+    Finished(DownloadResult),
+    /// The download was cancelled or preempted before it finished.
+    Cancelled(String),
 }
 
-/// Manages background audio downloads with rate limiting and caching.
-pub(crate) struct DownloadManager {
-    state: Arc<Mutex<DownloadState>>,
-    background_tasks: Arc<Mutex<Vec<tokio::task::JoinHandle<()>>>>,
-    download_tx: mpsc::UnboundedSender<DownloadResult>,
-    download_rx: mpsc::UnboundedReceiver<DownloadResult>,
+/// Progress of one running download, parsed from yt-dlp's output.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) struct DownloadProgress {
+    /// 0–100.
+    pub percent: f64,
+    /// Bytes per second.
+    pub speed: Option<f64>,
+    pub eta_secs: Option<u64>,
 }
 
-impl Default for DownloadManager {
-    fn default() -> Self {
-        Self::new()
+impl DownloadProgress {
+    /// The download finished and yt-dlp is extracting/converting the audio.
+    pub(crate) fn is_converting(&self) -> bool {
+        self.percent >= 100.0
+    }
+
+    /// A `width`-cell bar like `██████░░░░`.
+    pub(crate) fn bar(&self, width: usize) -> String {
+        let filled = ((self.percent.clamp(0.0, 100.0) / 100.0) * width as f64).round() as usize;
+        format!("{}{}", "█".repeat(filled), "░".repeat(width - filled))
     }
 }
 
-impl DownloadManager {
-    pub fn new() -> Self {
-        let (download_tx, download_rx) = mpsc::unbounded_channel();
+/// Marks our progress lines among yt-dlp's other stdout output.
+const PROGRESS_PREFIX: &str = "[crusty-progress]";
+
+/// `--progress-template` producing `[crusty-progress] <done> <total> <estimate> <speed> <eta>`,
+/// with `NA` for unknown fields.
+const PROGRESS_TEMPLATE: &str = "download:[crusty-progress] %(progress.downloaded_bytes)s \
+     %(progress.total_bytes)s %(progress.total_bytes_estimate)s %(progress.speed)s %(progress.eta)s";
+
+/// Parse one line of yt-dlp output printed with [`PROGRESS_TEMPLATE`].
+pub(crate) fn parse_progress_line(line: &str) -> Option<DownloadProgress> {
+    let fields: Vec<Option<f64>> = line
+        .trim()
+        .strip_prefix(PROGRESS_PREFIX)?
+        .split_whitespace()
+        .map(|field| {
+            field
+                .parse::<f64>()
+                .ok()
+                .filter(|v| v.is_finite() && *v >= 0.0)
+        })
+        .collect();
+    let [done, total, estimate, speed, eta] = fields[..] else {
+        return None;
+    };
+    let total = total.or(estimate).filter(|t| *t > 0.0)?;
+    Some(DownloadProgress {
+        percent: (done.unwrap_or(0.0) / total * 100.0).min(100.0),
+        speed,
+        eta_secs: eta.map(|e| e as u64),
+    })
+}
+
+/// Whether a failed download is worth retrying.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FailureKind {
+    /// Network errors, timeouts, throttling — retried with backoff.
+    Transient,
+    /// Private, removed, geo-blocked or age-gated — not retried until the
+    /// failure record expires.
+    Permanent,
+}
+
+/// Lower-case fragments of yt-dlp errors that retrying won't fix.
+const PERMANENT_MARKERS: &[&str] = &[
+    "private video",
+    "video unavailable",
+    "has been removed",
+    "no longer available",
+    "available in your country",
+    "blocked it in your country",
+    "geo restrict",
+    "copyright",
+    "account associated with this video has been terminated",
+    "sign in to confirm your age",
+    "members-only",
+    "join this channel",
+    "invalid url",
+    "invalid video id",
+];
+
+/// Classify a download error message.
+pub(crate) fn classify_failure(error: &str) -> FailureKind {
+    let error = error.to_lowercase();
+    if PERMANENT_MARKERS
+        .iter()
+        .any(|marker| error.contains(marker))
+    {
+        FailureKind::Permanent
+    } else {
+        FailureKind::Transient
+    }
+}
+
+/// Why `fetch_audio_blocking` failed, and whether retrying can help.
+#[derive(Debug, Clone, PartialEq)]
+struct FetchError {
+    message: String,
+    kind: FailureKind,
+}
+
+impl From<String> for FetchError {
+    /// A failure known only by its message, classified by it.
+    fn from(message: String) -> Self {
+        let kind = classify_failure(&message);
+        Self { message, kind }
+    }
+}
+
+impl From<YtDlpError> for FetchError {
+    /// yt-dlp couldn't be run. Only a missing yt-dlp is for good; failing
+    /// to spawn it or read its pipes (EAGAIN, EMFILE, …) may pass.
+    fn from(e: YtDlpError) -> Self {
+        let kind = match e {
+            YtDlpError::Missing => FailureKind::Permanent,
+            YtDlpError::Spawn(_) | YtDlpError::InvalidUtf8(_) => FailureKind::Transient,
+        };
         Self {
-            state: Arc::new(Mutex::new(DownloadState {
-                downloaded_files: HashMap::new(),
-                failed_downloads: HashMap::new(),
-                active_count: 0,
-                downloading_videos: HashSet::new(),
-            })),
-            background_tasks: Arc::new(Mutex::new(Vec::new())),
-            download_tx,
-            download_rx,
+            message: format!("{}. Is yt-dlp installed?", e),
+            kind,
         }
     }
+}
+
+/// Delay before retry number `attempt` (1 = first retry): the base delay
+/// doubled per attempt, capped, then scaled by ±25% using `jitter` in `[0, 1)`.
+pub(crate) fn backoff_delay(attempt: u32, jitter: f64) -> Duration {
+    let exp = DOWNLOAD_RETRY_BASE_SECS.saturating_mul(1u64 << attempt.saturating_sub(1).min(16));
+    let secs = exp.min(DOWNLOAD_RETRY_MAX_SECS) as f64 * (0.75 + 0.5 * jitter.clamp(0.0, 1.0));
+    Duration::from_secs_f64(secs)
+}
+
+/// A uniformly distributed value in `[0, 1)`, from the std hasher's random keys.
+fn jitter() -> f64 {
+    use std::hash::{BuildHasher, Hasher};
+    let bits = std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Why and when a track's download failed, and what happens next.
+#[derive(Debug, Clone)]
+pub(crate) struct FailureRecord {
+    pub track: Track,
+    pub reason: String,
+    pub kind: FailureKind,
+    /// Failed attempts so far.
+    pub attempts: u32,
+    /// When the next automatic retry is due; `None` for permanent failures
+    /// and once the attempts are used up.
+    pub retry_at: Option<Instant>,
+    /// When the record is dropped and the track may be downloaded again.
+    pub expires_at: Instant,
+}
+
+impl FailureRecord {
+    fn new(track: Track, reason: String, kind: FailureKind, attempts: u32, now: Instant) -> Self {
+        let retry_at = (kind == FailureKind::Transient && attempts < MAX_DOWNLOAD_ATTEMPTS)
+            .then(|| now + backoff_delay(attempts, jitter()));
+        Self {
+            track,
+            reason,
+            kind,
+            attempts,
+            retry_at,
+            expires_at: now + Duration::from_secs(FAILURE_RECORD_TTL_SECS),
+        }
+    }
+
+    fn is_expired(&self, now: Instant) -> bool {
+        now >= self.expires_at
+    }
+
+    fn is_retry_due(&self, now: Instant) -> bool {
+        self.retry_at.is_some_and(|at| now >= at)
+    }
+
+    /// The useful part of the error: yt-dlp's `ERROR:` line without the
+    /// extractor prefix, shortened for display next to a track.
+    pub(crate) fn short_reason(&self) -> String {
+        let reason = self.reason.trim();
+        let reason = reason
+            .find("ERROR:")
+            .map(|i| reason[i + "ERROR:".len()..].trim())
+            .unwrap_or(reason);
+        // "[youtube] abc123: Private video" → "Private video"
+        let reason = match (reason.starts_with('['), reason.find(": ")) {
+            (true, Some(i)) => &reason[i + 2..],
+            _ => reason,
+        };
+        let line = reason.lines().next().unwrap_or_default();
+        if line.chars().count() > 60 {
+            format!("{}…", line.chars().take(59).collect::<String>())
+        } else {
+            line.to_string()
+        }
+    }
+
+    /// `⟳ retry in 12s`, `✗ Private video`, or `✗ gave up: <reason>`.
+    pub(crate) fn label(&self, now: Instant) -> String {
+        match (self.kind, self.retry_at) {
+            (FailureKind::Transient, Some(at)) => {
+                format!(
+                    "⟳ retry in {}s",
+                    at.saturating_duration_since(now).as_secs()
+                )
+            }
+            (FailureKind::Transient, None) => format!("✗ gave up: {}", self.short_reason()),
+            (FailureKind::Permanent, _) => format!("✗ {}", self.short_reason()),
+        }
+    }
+}
+
+/// A download in progress.
+struct RunningDownload {
+    track: Track,
+    priority: DownloadPriority,
+    cookie_config: Option<(bool, String)>,
+    /// Start order; tells a restarted download apart from the preempted
+    /// one still shutting down, and picks preemption victims.
+    job: u64,
+    cancel: CancelHandle,
+}
+
+/// Unified state for all download tracking, guarded by a single mutex.
+struct DownloadState {
+    downloaded_files: HashMap<String, String>,
+    failed_downloads: HashMap<String, FailureRecord>,
+    running: HashMap<String, RunningDownload>,
+    queue: DownloadQueue,
+    next_job: u64,
+    /// In offline mode requests are only queued, never started.
+    offline: bool,
+    /// Cached tracks whose file is in an older audio format. They play
+    /// from it, but may be downloaded again in the current one.
+    outdated: HashSet<String>,
+    /// Tracks whose cancelled download is still being killed and cleaned
+    /// up. They aren't started again until it's done, so the two runs
+    /// never write the same files.
+    stopping: HashSet<String>,
+    /// Files in an older format that a new download replaced. They're
+    /// deleted once the player is done with them (see `remove_superseded`).
+    superseded: Vec<String>,
+}
 
+impl DownloadState {
+    /// Whether `video_id` may be downloaded now: not cached in the current
+    /// format, and no failure record or its retry is due. Drops an expired
+    /// record.
+    fn may_download(&mut self, video_id: &str, now: Instant) -> bool {
+        if self.downloaded_files.contains_key(video_id) && !self.outdated.contains(video_id) {
+            return false;
+        }
+        match self.failed_downloads.get(video_id) {
+            Some(record) if record.is_expired(now) => {
+                self.failed_downloads.remove(video_id);
+                true
+            }
+            Some(record) => record.is_retry_due(now),
+            None => true,
+        }
+    }
+}
+
+/// Manages background audio downloads with prioritized scheduling and caching.
+pub(crate) struct DownloadManager {
+    state: Arc<Mutex<DownloadState>>,
+    background_tasks: Arc<Mutex<Vec<tokio::task::JoinHandle<()>>>>,
+    download_tx: mpsc::UnboundedSender<DownloadEvent>,
+    download_rx: mpsc::UnboundedReceiver<DownloadEvent>,
+    /// Latest progress of each running download, updated while polling.
+    progress: HashMap<String, DownloadProgress>,
+    /// Where finished downloads are written, as `<video_id>.<ext>`.
+    audio_cache: AudioCache,
+    ytdlp: Arc<dyn YtDlp>,
+}
+
+impl DownloadManager {
     /// Create a new DownloadManager with a pre-warmed cache of previously downloaded files.
-    pub fn with_cache(cache: HashMap<String, String>) -> Self {
+    pub fn with_cache(
+        cache: HashMap<String, String>,
+        audio_cache: AudioCache,
+        ytdlp: Arc<dyn YtDlp>,
+    ) -> Self {
         let (download_tx, download_rx) = mpsc::unbounded_channel();
         Self {
             state: Arc::new(Mutex::new(DownloadState {
                 downloaded_files: cache,
                 failed_downloads: HashMap::new(),
-                active_count: 0,
-                downloading_videos: HashSet::new(),
+                running: HashMap::new(),
+                queue: DownloadQueue::default(),
+                next_job: 0,
+                offline: false,
+                outdated: HashSet::new(),
+                stopping: HashSet::new(),
+                superseded: Vec::new(),
             })),
             background_tasks: Arc::new(Mutex::new(Vec::new())),
             download_tx,
             download_rx,
+            progress: HashMap::new(),
+            audio_cache,
+            ytdlp,
         }
     }
 
+    pub fn audio_cache(&self) -> &AudioCache {
+        &self.audio_cache
+    }
+
     /// Returns a snapshot of the current download cache for persistence.
     pub fn get_cache_snapshot(&self) -> HashMap<String, String> {
         self.state
@@ -74,8 +363,10 @@ impl DownloadManager {
             .clone()
     }
 
-    /// Poll for a completed download without blocking.
-    /// Also prunes finished background tasks to prevent unbounded growth.
+    /// Poll for a completed download without blocking, recording any
+    /// progress events queued ahead of it, and start queued downloads in
+    /// free slots. Also prunes finished background tasks to prevent
+    /// unbounded growth.
     pub fn poll_completion(&mut self) -> Option<DownloadResult> {
         // Prune finished tasks on each poll cycle
         {
@@ -85,7 +376,46 @@ impl DownloadManager {
                 .unwrap_or_else(|e| e.into_inner());
             tasks.retain(|h| !h.is_finished());
         }
-        self.download_rx.try_recv().ok()
+        self.start_queued();
+        while let Ok(event) = self.download_rx.try_recv() {
+            match event {
+                DownloadEvent::Progress(video_id, progress) => {
+                    self.progress.insert(video_id, progress);
+                }
+                DownloadEvent::Cancelled(video_id) => {
+                    self.progress.remove(&video_id);
+                }
+                DownloadEvent::Finished(result) => {
+                    self.progress.remove(&result.0);
+                    return Some(result);
+                }
+            }
+        }
+        None
+    }
+
+    /// Latest progress of a running download; `None` before yt-dlp reports
+    /// any or once it's finished.
+    pub fn progress_for(&self, video_id: &str) -> Option<DownloadProgress> {
+        self.progress.get(video_id).copied()
+    }
+
+    /// Whether `video_id` is downloading right now.
+    pub fn is_downloading(&self, video_id: &str) -> bool {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .running
+            .contains_key(video_id)
+    }
+
+    /// Whether `video_id` is waiting for a download slot.
+    pub fn is_queued(&self, video_id: &str) -> bool {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .queue
+            .contains(video_id)
     }
 
     /// Returns the number of currently active downloads.
@@ -93,7 +423,17 @@ impl DownloadManager {
         self.state
             .lock()
             .unwrap_or_else(|e| e.into_inner())
-            .active_count
+            .running
+            .len()
+    }
+
+    /// Returns the number of downloads waiting for a slot.
+    pub fn queued_count(&self) -> usize {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .queue
+            .len()
     }
 
     /// Returns the number of cached (downloaded) files.
@@ -126,92 +466,338 @@ impl DownloadManager {
 
     /// Removes a video from the download cache (e.g., when file was deleted).
     pub fn remove_from_cache(&self, video_id: &str) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.downloaded_files.remove(video_id);
+        state.outdated.remove(video_id);
+    }
+
+    /// Mark cached tracks as being in an older audio format (see
+    /// [`crate::services::audio_cache::outdated_formats`]).
+    pub fn set_outdated(&self, video_ids: HashSet<String>) {
         self.state
             .lock()
             .unwrap_or_else(|e| e.into_inner())
-            .downloaded_files
-            .remove(video_id);
+            .outdated = video_ids;
     }
 
-    /// Spawn a background download for a track, respecting rate limits.
-    /// `cookie_config` is optional browser cookie info: (use_from_browser, cookie_arg).
-    /// Returns true if a download was actually spawned.
-    pub fn spawn_download(&self, track: &Track, cookie_config: Option<(bool, String)>) -> bool {
-        // Single lock acquisition for all precondition checks + slot claim
-        {
-            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+    /// Delete the older-format files new downloads replaced, except those in
+    /// `in_use` (the player's current and queued files), which are kept
+    /// for a later call.
+    pub fn remove_superseded(&self, in_use: &[&str]) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.superseded.is_empty() {
+            return;
+        }
+        state.superseded.retain(|path| {
+            if in_use.contains(&path.as_str()) {
+                return true;
+            }
+            let _ = std::fs::remove_file(path);
+            false
+        });
+    }
+
+    /// Whether `video_id`'s cached file is in an older audio format.
+    pub fn is_outdated(&self, video_id: &str) -> bool {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .outdated
+            .contains(video_id)
+    }
+
+    /// Request a background download of `track`. It starts right away if a
+    /// slot is free or it outranks a running download (which is preempted
+    /// and queued again); otherwise it waits in the queue. A track already
+    /// downloading or queued keeps its place, at the higher of the two
+    /// priorities. `cookie_config` is optional browser cookie info:
+    /// (use_from_browser, cookie_arg).
+    /// Returns true if the track is downloading or queued afterwards; false
+    /// if it's cached in the current format or its failed download isn't
+    /// due for a retry.
+    /// In offline mode the track is only queued.
+    pub fn request_download(
+        &self,
+        track: &Track,
+        priority: DownloadPriority,
+        cookie_config: Option<(bool, String)>,
+    ) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if !state.may_download(&track.video_id, Instant::now()) {
+            return false;
+        }
 
-            if state.active_count >= MAX_CONCURRENT_DOWNLOADS {
-                return false;
+        // Only one track is the one being waited for.
+        if priority == DownloadPriority::Current {
+            for running in state.running.values_mut() {
+                if running.priority == DownloadPriority::Current {
+                    running.priority = DownloadPriority::Lookahead;
+                }
             }
-            if state.downloaded_files.contains_key(&track.video_id) {
-                return false;
+        }
+        if let Some(running) = state.running.get_mut(&track.video_id) {
+            running.priority = running.priority.max(priority);
+            return true;
+        }
+        if state.offline || state.stopping.contains(&track.video_id) {
+            state.queue.push(track, priority, cookie_config);
+            return true;
+        }
+
+        if state.running.len() >= MAX_CONCURRENT_DOWNLOADS {
+            let ranks = state
+                .running
+                .iter()
+                .map(|(video_id, r)| (video_id.clone(), (r.priority, r.job)))
+                .collect();
+            let Some(victim) = preemption_victim(&ranks, priority) else {
+                state.queue.push(track, priority, cookie_config);
+                return true;
+            };
+            if let Some(preempted) = state.running.remove(&victim) {
+                preempted.cancel.cancel();
+                state.stopping.insert(victim);
+                state.queue.push(
+                    &preempted.track,
+                    preempted.priority,
+                    preempted.cookie_config,
+                );
             }
-            if state.failed_downloads.contains_key(&track.video_id) {
-                return false;
+        }
+
+        state.queue.remove(&track.video_id);
+        self.start(&mut state, track.clone(), priority, cookie_config);
+        true
+    }
+
+    /// Start queued downloads while slots are free. Entries that got cached
+    /// or failed in the meantime are dropped.
+    fn start_queued(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.offline {
+            return;
+        }
+        let now = Instant::now();
+        let mut waiting = Vec::new();
+        while state.running.len() < MAX_CONCURRENT_DOWNLOADS {
+            let Some(next) = state.queue.pop() else {
+                break;
+            };
+            if state.running.contains_key(&next.track.video_id)
+                || !state.may_download(&next.track.video_id, now)
+            {
+                continue;
             }
-            if state.downloading_videos.contains(&track.video_id) {
-                return false;
+            if state.stopping.contains(&next.track.video_id) {
+                waiting.push(next);
+                continue;
             }
-
-            // Atomically claim the download slot
-            state.downloading_videos.insert(track.video_id.clone());
-            state.active_count += 1;
+            self.start(&mut state, next.track, next.priority, next.cookie_config);
         }
+        for entry in waiting {
+            state
+                .queue
+                .push(&entry.track, entry.priority, entry.cookie_config);
+        }
+    }
+
+    /// Spawn the download task for `track` and record it as running.
+    fn start(
+        &self,
+        state: &mut DownloadState,
+        track: Track,
+        priority: DownloadPriority,
+        cookie_config: Option<(bool, String)>,
+    ) {
+        let job = state.next_job;
+        state.next_job += 1;
+        let cancel = CancelHandle::default();
+        state.running.insert(
+            track.video_id.clone(),
+            RunningDownload {
+                track: track.clone(),
+                priority,
+                cookie_config: cookie_config.clone(),
+                job,
+                cancel: cancel.clone(),
+            },
+        );
 
         let video_id = track.video_id.clone();
         let youtube_url = track.url.clone();
         let state = self.state.clone();
         let download_tx = self.download_tx.clone();
+        let audio_cache = self.audio_cache.clone();
+        let fetch_cache = self.audio_cache.clone();
+        let ytdlp = self.ytdlp.clone();
+        let fetch_id = video_id.clone();
+        let fetch_cancel = cancel.clone();
+
+        let progress_tx = self.download_tx.clone();
 
         let handle = tokio::spawn(async move {
             let result = tokio::task::spawn_blocking(move || {
-                fetch_audio_url_blocking(&youtube_url, cookie_config)
+                let on_progress = |progress| {
+                    let _ = progress_tx.send(DownloadEvent::Progress(fetch_id.clone(), progress));
+                };
+                fetch_audio_blocking(
+                    &*ytdlp,
+                    &youtube_url,
+                    &fetch_id,
+                    &fetch_cache,
+                    cookie_config,
+                    &fetch_cancel,
+                    on_progress,
+                )
             })
             .await;
+            let cancelled = cancel.is_cancelled();
 
             // Single lock for all post-download bookkeeping
             {
                 let mut st = state.lock().unwrap_or_else(|e| e.into_inner());
-                match &result {
+                // A cancelled or preempted job was removed already, and the
+                // track may be downloading again as a newer job.
+                if st.running.get(&video_id).is_some_and(|r| r.job == job) {
+                    st.running.remove(&video_id);
+                }
+                if cancelled {
+                    st.stopping.remove(&video_id);
+                }
+                let error = match &result {
                     Ok(Ok(file_path)) => {
-                        st.downloaded_files
+                        // The file in the current format replaces one in
+                        // an older format, which may still be playing; it's
+                        // deleted once it isn't.
+                        let old = st
+                            .downloaded_files
                             .insert(video_id.clone(), file_path.clone());
+                        if let Some(old) = old.filter(|old| old != file_path) {
+                            if audio_cache.contains(Path::new(&old)) {
+                                st.superseded.push(old);
+                            }
+                        }
+                        st.outdated.remove(&video_id);
+                        st.failed_downloads.remove(&video_id);
+                        st.queue.remove(&video_id);
+                        None
                     }
-                    Ok(Err(e)) => {
-                        st.failed_downloads.insert(video_id.clone(), e.clone());
-                    }
-                    Err(_) => {
-                        st.failed_downloads.insert(
-                            video_id.clone(),
-                            "Download task failed unexpectedly".to_string(),
-                        );
-                    }
+                    _ if cancelled => None,
+                    Ok(Err(e)) => Some((e.message.clone(), e.kind)),
+                    Err(_) => Some((
+                        "Download task failed unexpectedly".to_string(),
+                        FailureKind::Transient,
+                    )),
+                };
+                if let Some((error, kind)) = error {
+                    let attempts = st
+                        .failed_downloads
+                        .get(&video_id)
+                        .map_or(0, |record| record.attempts)
+                        + 1;
+                    let record = FailureRecord::new(track, error, kind, attempts, Instant::now());
+                    st.failed_downloads.insert(video_id.clone(), record);
                 }
-                st.active_count = st.active_count.saturating_sub(1);
-                st.downloading_videos.remove(&video_id);
             }
 
             // Send result outside the lock
-            let send_result = match result {
-                Ok(Ok(file_path)) => Ok(file_path),
-                Ok(Err(e)) => Err(e),
-                Err(_) => Err("Download task failed unexpectedly".to_string()),
+            let event = match result {
+                Ok(Ok(file_path)) => DownloadEvent::Finished((video_id, Ok(file_path))),
+                _ if cancelled => DownloadEvent::Cancelled(video_id),
+                Ok(Err(e)) => DownloadEvent::Finished((video_id, Err(e.message))),
+                Err(_) => DownloadEvent::Finished((
+                    video_id,
+                    Err("Download task failed unexpectedly".to_string()),
+                )),
             };
-            let _ = download_tx.send((video_id, send_result));
+            let _ = download_tx.send(event);
         });
 
         // Track the background task, pruning finished ones
-        {
-            let mut tasks = self
-                .background_tasks
-                .lock()
-                .unwrap_or_else(|e| e.into_inner());
-            tasks.retain(|h| !h.is_finished());
-            tasks.push(handle);
+        let mut tasks = self
+            .background_tasks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        tasks.retain(|h| !h.is_finished());
+        tasks.push(handle);
+    }
+
+    /// Cancel `video_id`'s download, killing yt-dlp if it's running, or
+    /// take it out of the queue. Returns false if it wasn't downloading.
+    pub fn cancel_download(&self, video_id: &str) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let queued = state.queue.remove(video_id).is_some();
+        match state.running.remove(video_id) {
+            Some(running) => {
+                running.cancel.cancel();
+                state.stopping.insert(video_id.to_string());
+                true
+            }
+            None => queued,
         }
+    }
 
-        true
+    /// Enter or leave offline mode. Going offline stops running downloads
+    /// and queues them again, so they resume once back online.
+    pub fn set_offline(&self, offline: bool) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.offline = offline;
+        if !offline {
+            return;
+        }
+        let running: Vec<RunningDownload> = state.running.drain().map(|(_, r)| r).collect();
+        for stopped in running {
+            stopped.cancel.cancel();
+            state.stopping.insert(stopped.track.video_id.clone());
+            state
+                .queue
+                .push(&stopped.track, stopped.priority, stopped.cookie_config);
+        }
+    }
+
+    /// The failure record for a track, if its last download failed and the
+    /// record hasn't expired.
+    pub fn failure_for(&self, video_id: &str) -> Option<FailureRecord> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state
+            .failed_downloads
+            .get(video_id)
+            .filter(|record| !record.is_expired(Instant::now()))
+            .cloned()
+    }
+
+    /// Tracks whose automatic retry is due and that aren't downloading.
+    /// Expired records are dropped along the way. Nothing is due offline.
+    pub fn due_retries(&self) -> Vec<Track> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.offline {
+            return Vec::new();
+        }
+        let now = Instant::now();
+        state
+            .failed_downloads
+            .retain(|_, record| !record.is_expired(now));
+        state
+            .failed_downloads
+            .values()
+            .filter(|record| record.is_retry_due(now))
+            .filter(|record| {
+                let video_id = &record.track.video_id;
+                !state.running.contains_key(video_id) && !state.queue.contains(video_id)
+            })
+            .map(|record| record.track.clone())
+            .collect()
+    }
+
+    /// Forget every failure (resetting attempt counts) and return the
+    /// tracks, so they can be downloaded again right away.
+    pub fn take_failed(&self) -> Vec<Track> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state
+            .failed_downloads
+            .drain()
+            .map(|(_, record)| record.track)
+            .collect()
     }
 
     /// Download tracks near the current queue position for instant playback.
@@ -221,7 +807,7 @@ impl DownloadManager {
         cookie_config: Option<(bool, String)>,
     ) {
         for track in queue_slice {
-            self.spawn_download(track, cookie_config.clone());
+            self.request_download(track, DownloadPriority::Lookahead, cookie_config.clone());
         }
     }
 
@@ -232,12 +818,20 @@ impl DownloadManager {
         cookie_config: Option<(bool, String)>,
     ) {
         if let Some(&track) = queue_slice.first() {
-            self.spawn_download(track, cookie_config);
+            self.request_download(track, DownloadPriority::Hover, cookie_config);
         }
     }
 
-    /// Abort all background download tasks.
+    /// Drop queued downloads, kill running yt-dlp processes, and abort all
+    /// background download tasks.
     pub fn abort_all(&self) {
+        {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            state.queue.clear();
+            for (_, running) in state.running.drain() {
+                running.cancel.cancel();
+            }
+        }
         let mut tasks = self
             .background_tasks
             .lock()
@@ -247,16 +841,20 @@ impl DownloadManager {
         }
     }
 
-    /// Clean up old pre-downloaded files from the temp directory.
-    pub fn cleanup_old_downloads() {
+    /// Clean up stale files on startup: partial downloads left in the
+    /// audio cache, and temp-dir downloads from versions before the
+    /// permanent cache that weren't migrated into it.
+    pub fn cleanup_old_downloads(&self) {
         use std::env;
         use std::time::{Duration, SystemTime};
 
+        let max_age = Duration::from_secs(TEMP_FILE_MAX_AGE_SECS);
+        self.audio_cache.sweep_partials(max_age);
+
         let temp_dir = env::temp_dir();
 
         if let Ok(entries) = std::fs::read_dir(&temp_dir) {
             let now = SystemTime::now();
-            let max_age = Duration::from_secs(TEMP_FILE_MAX_AGE_SECS);
 
             for entry in entries.flatten() {
                 if let Ok(file_name) = entry.file_name().into_string() {
@@ -277,108 +875,106 @@ impl DownloadManager {
     }
 }
 
-/// Download audio to a temp file using yt-dlp.
-fn fetch_audio_url_blocking(
+/// Download audio into the cache as `<video_id>.<tag>.<ext>` in the
+/// configured format using yt-dlp, reporting progress through
+/// `on_progress` as yt-dlp prints it. A file already in the cache (e.g.
+/// downloaded by a session that exited before recording it) is reused
+/// without downloading.
+fn fetch_audio_blocking(
+    ytdlp: &dyn YtDlp,
     youtube_url: &str,
+    video_id: &str,
+    audio_cache: &AudioCache,
     cookie_config: Option<(bool, String)>,
-) -> Result<String, String> {
-    use std::env;
-    use std::process::Command;
-    use std::time::{SystemTime, UNIX_EPOCH};
-
+    cancel: &CancelHandle,
+    mut on_progress: impl FnMut(DownloadProgress),
+) -> Result<String, FetchError> {
     if !is_allowed_youtube_url(youtube_url) {
-        return Err("Invalid URL: must be a YouTube or YouTube Music URL".to_string());
-    }
-
-    let temp_dir = env::temp_dir();
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_micros();
-    let temp_file = temp_dir.join(format!(
-        "yt-music-audio-{}-{}.%(ext)s",
-        std::process::id(),
-        timestamp
-    ));
-
-    let mut cmd = Command::new("yt-dlp");
-    cmd.arg("-f")
-        .arg("bestaudio/best")
-        .arg("-x")
-        .arg("--audio-format")
-        .arg("mp3")
-        .arg("--audio-quality")
-        .arg("192K")
-        .arg("-o")
-        .arg(&temp_file)
-        .arg("--no-playlist")
-        .arg("--no-mtime")
-        .arg("--socket-timeout")
-        .arg("30")
-        .arg("--retries")
-        .arg("2");
-
-    if let Some((use_from_browser, cookie_arg)) = cookie_config {
-        if use_from_browser {
-            cmd.arg("--cookies-from-browser").arg(cookie_arg);
-        }
-    }
-
-    cmd.arg(youtube_url);
-
-    let output = cmd
-        .output()
-        .map_err(|e| format!("Failed to run yt-dlp: {}. Is yt-dlp installed?", e))?;
-
-    if !output.status.success() {
-        let error = String::from_utf8_lossy(&output.stderr);
-        let snippet: String = error.chars().take(200).collect();
-        return Err(format!("yt-dlp download failed: {}", snippet));
-    }
-
-    // Find the downloaded file (yt-dlp replaces %(ext)s with actual extension)
-    let temp_dir_path = env::temp_dir();
-    let search_pattern = format!("yt-music-audio-{}-{}", std::process::id(), timestamp);
-
-    let files: Vec<_> = std::fs::read_dir(&temp_dir_path)
-        .map_err(|e| format!("Failed to read temp dir: {}", e))?
-        .filter_map(|entry| entry.ok())
-        .filter(|entry| {
-            entry
-                .file_name()
-                .to_string_lossy()
-                .starts_with(&search_pattern)
+        return Err("Invalid URL: must be a YouTube or YouTube Music URL"
+            .to_string()
+            .into());
+    }
+    let output_template = audio_cache
+        .output_template(video_id)
+        .ok_or_else(|| format!("Invalid video id '{}'", video_id))?;
+    audio_cache.ensure_dir().map_err(|e| format!("{:#}", e))?;
+
+    if let Some(existing) = audio_cache.find(video_id) {
+        if let Ok(path) = verify_downloaded_file(&existing, audio_cache) {
+            return Ok(path);
+        }
+    }
+
+    let args = YtDlpArgs::new()
+        .args(audio_cache.profile().ytdlp_args())
+        .args(audio_cache.profile().embed_args())
+        .opt("-o", output_template.to_string_lossy())
+        .flag("--no-playlist")
+        .flag("--no-mtime")
+        .flag("--newline")
+        .opt("--progress-template", PROGRESS_TEMPLATE)
+        .cookies(cookie_config.as_ref())
+        .flag(youtube_url);
+
+    if cancel.is_cancelled() {
+        return Err("Download cancelled".to_string().into());
+    }
+    let mut last_percent = -1.0;
+    let output = ytdlp
+        .stream(&args, cancel, &mut |line| {
+            if let Some(progress) = parse_progress_line(line) {
+                // yt-dlp prints many lines per second; skip tiny steps.
+                if progress.percent - last_percent >= 0.5 || progress.is_converting() {
+                    last_percent = progress.percent;
+                    on_progress(progress);
+                }
+            }
         })
-        .collect();
+        .map_err(FetchError::from)?;
 
-    if files.is_empty() {
-        return Err(format!(
-            "yt-dlp completed but no audio file found (searched for {}.*)",
-            search_pattern
-        ));
+    if cancel.is_cancelled() {
+        // yt-dlp and its ffmpeg are dead by now; don't leave their files
+        // for the next run to trip over.
+        audio_cache.remove_leftovers(video_id);
+        return Err("Download cancelled".to_string().into());
+    }
+
+    if !output.success {
+        return Err(format!("yt-dlp download failed: {}", output.error_snippet()).into());
     }
 
-    let downloaded_file = files[0].path();
+    // yt-dlp replaces %(ext)s with the actual extension
+    let downloaded_file = audio_cache.find(video_id).ok_or_else(|| {
+        format!(
+            "yt-dlp completed but no audio file found (searched for {})",
+            output_template.display()
+        )
+    })?;
+    Ok(verify_downloaded_file(&downloaded_file, audio_cache)?)
+}
 
-    // Canonicalize and verify the file is within the temp directory
-    let canonical = downloaded_file
+/// Check that a downloaded file is inside the cache and plausibly complete,
+/// deleting it if it's truncated so the next attempt starts fresh.
+fn verify_downloaded_file(
+    path: &std::path::Path,
+    audio_cache: &AudioCache,
+) -> Result<String, String> {
+    // Canonicalize and verify the file is within the cache directory
+    let canonical = path
         .canonicalize()
         .map_err(|e| format!("Failed to canonicalize downloaded file path: {}", e))?;
-    let temp_canonical = temp_dir_path
-        .canonicalize()
-        .map_err(|e| format!("Failed to canonicalize temp dir: {}", e))?;
-    if !canonical.starts_with(&temp_canonical) {
-        return Err("Downloaded file path is outside temp directory".to_string());
+    if !audio_cache.contains(&canonical) {
+        return Err("Downloaded file path is outside the audio cache".to_string());
     }
 
     let metadata = std::fs::metadata(&canonical)
         .map_err(|e| format!("Failed to check downloaded file: {}", e))?;
 
-    if metadata.len() == 0 {
-        return Err("Downloaded file is empty".to_string());
-    }
-
-    if metadata.len() < 10000 {
+    if metadata.len() < MIN_AUDIO_FILE_BYTES {
+        let _ = std::fs::remove_file(&canonical);
+        if metadata.len() == 0 {
+            return Err("Downloaded file is empty".to_string());
+        }
         return Err(format!(
             "Downloaded file is too small ({} bytes), likely incomplete",
             metadata.len()
@@ -387,3 +983,344 @@ fn fetch_audio_url_blocking(
 
     Ok(canonical.to_string_lossy().to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(reason: &str, attempts: u32, now: Instant) -> FailureRecord {
+        let track = Track::new(
+            "abc".to_string(),
+            "Song".to_string(),
+            180,
+            String::new(),
+            "https://www.youtube.com/watch?v=abc".to_string(),
+        );
+        FailureRecord::new(
+            track,
+            reason.to_string(),
+            classify_failure(reason),
+            attempts,
+            now,
+        )
+    }
+
+    #[test]
+    fn classifies_permanent_and_transient_errors() {
+        assert_eq!(
+            classify_failure("yt-dlp download failed: ERROR: [youtube] abc: Private video"),
+            FailureKind::Permanent
+        );
+        assert_eq!(
+            classify_failure(
+                "ERROR: The uploader has not made this video available in your country"
+            ),
+            FailureKind::Permanent
+        );
+        assert_eq!(
+            classify_failure(
+                "ERROR: unable to download video data: HTTP Error 429: Too Many Requests"
+            ),
+            FailureKind::Transient
+        );
+        assert_eq!(classify_failure("Read timed out"), FailureKind::Transient);
+    }
+
+    #[test]
+    fn only_a_missing_ytdlp_is_a_permanent_run_failure() {
+        let missing = FetchError::from(YtDlpError::Missing);
+        assert_eq!(missing.kind, FailureKind::Permanent);
+        let spawn = FetchError::from(YtDlpError::Spawn(
+            "Resource temporarily unavailable (os error 11)".to_string(),
+        ));
+        assert_eq!(spawn.kind, FailureKind::Transient);
+        assert!(
+            spawn.message.ends_with("Is yt-dlp installed?"),
+            "{}",
+            spawn.message
+        );
+
+        let tmp = TempDir::new().unwrap();
+        let fake = FakeYtDlp::new().on(VIDEO_ID, Reply::Missing);
+        let err = fetch_audio_blocking(
+            &fake,
+            &song().url,
+            VIDEO_ID,
+            &cache_in(&tmp),
+            None,
+            &CancelHandle::default(),
+            |_| {},
+        )
+        .unwrap_err();
+        assert_eq!(err.kind, FailureKind::Permanent);
+    }
+
+    #[test]
+    fn backoff_doubles_caps_and_jitters() {
+        assert_eq!(
+            backoff_delay(1, 0.5),
+            Duration::from_secs(DOWNLOAD_RETRY_BASE_SECS)
+        );
+        assert_eq!(
+            backoff_delay(2, 0.5),
+            Duration::from_secs(DOWNLOAD_RETRY_BASE_SECS * 2)
+        );
+        assert_eq!(
+            backoff_delay(30, 0.5),
+            Duration::from_secs(DOWNLOAD_RETRY_MAX_SECS)
+        );
+        let low = backoff_delay(1, 0.0).as_secs_f64();
+        let high = backoff_delay(1, 0.999).as_secs_f64();
+        assert!(low >= DOWNLOAD_RETRY_BASE_SECS as f64 * 0.75 - 1e-9);
+        assert!(high < DOWNLOAD_RETRY_BASE_SECS as f64 * 1.25);
+        for _ in 0..100 {
+            assert!((0.0..1.0).contains(&jitter()));
+        }
+    }
+
+    #[test]
+    fn transient_failures_retry_until_attempts_run_out() {
+        let now = Instant::now();
+        let first = record("HTTP Error 503", 1, now);
+        assert!(first.retry_at.is_some());
+        assert!(!first.is_retry_due(now));
+        assert!(first.is_retry_due(now + Duration::from_secs(DOWNLOAD_RETRY_MAX_SECS * 2)));
+
+        let last = record("HTTP Error 503", MAX_DOWNLOAD_ATTEMPTS, now);
+        assert!(last.retry_at.is_none());
+        assert!(last.label(now).starts_with("✗ gave up"));
+
+        let private = record(
+            "ERROR: [youtube] abc: Private video. Sign in if you've been granted access",
+            1,
+            now,
+        );
+        assert!(private.retry_at.is_none());
+        assert!(!private.is_expired(now));
+        assert!(private.is_expired(now + Duration::from_secs(FAILURE_RECORD_TTL_SECS)));
+    }
+
+    #[test]
+    fn parses_progress_template_lines() {
+        let p = parse_progress_line("[crusty-progress] 2500000 5000000 NA 1048576.5 3").unwrap();
+        assert_eq!(p.percent, 50.0);
+        assert_eq!(p.speed, Some(1048576.5));
+        assert_eq!(p.eta_secs, Some(3));
+        assert_eq!(p.bar(10), "█████░░░░░");
+
+        // Falls back to the size estimate; unknown speed/ETA are NA.
+        let p = parse_progress_line("[crusty-progress] 100 NA 400.0 NA NA").unwrap();
+        assert_eq!(p.percent, 25.0);
+        assert_eq!((p.speed, p.eta_secs), (None, None));
+
+        assert!(parse_progress_line("[crusty-progress] 100 NA NA NA NA").is_none());
+        assert!(parse_progress_line("[download] Destination: x.webm").is_none());
+        assert!(parse_progress_line("[crusty-progress] 1 2").is_none());
+    }
+
+    #[test]
+    fn short_reason_strips_prefixes() {
+        let now = Instant::now();
+        let r = record(
+            "yt-dlp download failed: ERROR: [youtube] abc: Video unavailable\nmore",
+            1,
+            now,
+        );
+        assert_eq!(r.short_reason(), "Video unavailable");
+        assert_eq!(r.label(now), "✗ Video unavailable");
+        assert_eq!(
+            record("Read timed out", 1, now).short_reason(),
+            "Read timed out"
+        );
+    }
+
+    // -- End to end against a fake yt-dlp --
+
+    use crate::services::settings::Settings;
+    use crate::youtube::fake_ytdlp::{fixture, FakeYtDlp, Reply};
+    use tempfile::TempDir;
+
+    const VIDEO_ID: &str = "4NRXx6U8ABQ";
+
+    fn cache_in(dir: &TempDir) -> AudioCache {
+        AudioCache::new(
+            dir.path().to_path_buf(),
+            Settings::default().audio_profile(),
+        )
+    }
+
+    fn song() -> Track {
+        Track::new(
+            VIDEO_ID.to_string(),
+            "Blinding Lights".to_string(),
+            201,
+            "The Weeknd".to_string(),
+            format!("https://www.youtube.com/watch?v={}", VIDEO_ID),
+        )
+    }
+
+    fn full_download() -> Reply {
+        Reply::Download {
+            stdout: fixture("download.txt"),
+            bytes: MIN_AUDIO_FILE_BYTES as usize,
+        }
+    }
+
+    #[test]
+    fn fetch_reports_progress_and_verifies_the_file() {
+        let tmp = TempDir::new().unwrap();
+        let cache = cache_in(&tmp);
+        let fake = FakeYtDlp::new().on(VIDEO_ID, full_download());
+
+        let mut percents = Vec::new();
+        let path = fetch_audio_blocking(
+            &fake,
+            &song().url,
+            VIDEO_ID,
+            &cache,
+            Some((true, "firefox".to_string())),
+            &CancelHandle::default(),
+            |p| percents.push(p.percent),
+        )
+        .unwrap();
+
+        assert_eq!(percents.len(), 4);
+        assert_eq!(percents[1..], [25.0, 50.0, 100.0]);
+        assert_eq!(
+            Some(std::path::PathBuf::from(&path)),
+            cache.find(VIDEO_ID).map(|p| p.canonicalize().unwrap())
+        );
+        let args = &fake.calls()[0];
+        assert_eq!(args.value_of("--cookies-from-browser"), Some("firefox"));
+        assert_eq!(
+            args.value_of("--progress-template"),
+            Some(PROGRESS_TEMPLATE)
+        );
+
+        // Already cached: no second run.
+        let again = fetch_audio_blocking(
+            &fake,
+            &song().url,
+            VIDEO_ID,
+            &cache,
+            None,
+            &CancelHandle::default(),
+            |_| {},
+        );
+        assert_eq!(again.map_err(|e| e.message), Ok(path));
+        assert_eq!(fake.calls().len(), 1);
+    }
+
+    #[test]
+    fn fetch_rejects_truncated_and_cancelled_downloads() {
+        let tmp = TempDir::new().unwrap();
+        let cache = cache_in(&tmp);
+        let fake = FakeYtDlp::new().on(
+            VIDEO_ID,
+            Reply::Download {
+                stdout: String::new(),
+                bytes: 10,
+            },
+        );
+        let err = fetch_audio_blocking(
+            &fake,
+            &song().url,
+            VIDEO_ID,
+            &cache,
+            None,
+            &CancelHandle::default(),
+            |_| {},
+        )
+        .unwrap_err();
+        assert!(err.message.contains("too small"), "{}", err.message);
+        assert!(cache.find(VIDEO_ID).is_none());
+
+        let cancel = CancelHandle::default();
+        cancel.cancel();
+        let fake = FakeYtDlp::new().on(VIDEO_ID, full_download());
+        let err = fetch_audio_blocking(&fake, &song().url, VIDEO_ID, &cache, None, &cancel, |_| {});
+        assert_eq!(
+            err.map_err(|e| e.message),
+            Err("Download cancelled".to_string())
+        );
+        assert!(fake.calls().is_empty());
+    }
+
+    async fn wait_for_result(manager: &mut DownloadManager) -> DownloadResult {
+        for _ in 0..200 {
+            if let Some(result) = manager.poll_completion() {
+                return result;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("download never finished");
+    }
+
+    #[tokio::test]
+    async fn outdated_files_play_until_a_new_download_replaces_them() {
+        let tmp = TempDir::new().unwrap();
+        let old = tmp.path().join(format!("{}.opus160k.opus", VIDEO_ID));
+        std::fs::write(&old, b"old format").unwrap();
+        let old = old.canonicalize().unwrap().to_string_lossy().to_string();
+        let fake = Arc::new(FakeYtDlp::new().on(VIDEO_ID, full_download()));
+        let mut manager = DownloadManager::with_cache(
+            HashMap::from([(VIDEO_ID.to_string(), old.clone())]),
+            cache_in(&tmp),
+            fake,
+        );
+
+        // Still cached and playable; only an outdated file may download again.
+        assert!(!manager.request_download(&song(), DownloadPriority::Background, None));
+        manager.set_outdated(HashSet::from([VIDEO_ID.to_string()]));
+        assert_eq!(manager.get_cached_file(VIDEO_ID), Some(old.clone()));
+        assert!(manager.request_download(&song(), DownloadPriority::Background, None));
+
+        let (_, result) = wait_for_result(&mut manager).await;
+        let new = result.unwrap();
+        assert_ne!(new, old);
+        assert_eq!(manager.get_cached_file(VIDEO_ID), Some(new));
+        assert!(!manager.is_outdated(VIDEO_ID));
+
+        // Kept while the player still has it, deleted once it doesn't.
+        manager.remove_superseded(&[old.as_str()]);
+        assert!(Path::new(&old).exists());
+        manager.remove_superseded(&[]);
+        assert!(!Path::new(&old).exists());
+    }
+
+    #[tokio::test]
+    async fn manager_downloads_then_records_permanent_failures() {
+        let tmp = TempDir::new().unwrap();
+        let fake = Arc::new(FakeYtDlp::new().on(VIDEO_ID, full_download()).on(
+            "watch?v=privateVid1",
+            Reply::Fail("ERROR: [youtube] privateVid1: Private video\n".to_string()),
+        ));
+        let mut manager = DownloadManager::with_cache(HashMap::new(), cache_in(&tmp), fake.clone());
+
+        assert!(manager.request_download(&song(), DownloadPriority::Current, None));
+        let (id, result) = wait_for_result(&mut manager).await;
+        assert_eq!(id, VIDEO_ID);
+        assert!(result.is_ok());
+        assert!(manager.is_cached(VIDEO_ID));
+        assert!(!manager.request_download(&song(), DownloadPriority::Current, None));
+
+        let private = Track::new(
+            "privateVid1".to_string(),
+            "Secret".to_string(),
+            0,
+            String::new(),
+            "https://www.youtube.com/watch?v=privateVid1".to_string(),
+        );
+        assert!(manager.request_download(&private, DownloadPriority::Background, None));
+        let (_, result) = wait_for_result(&mut manager).await;
+        assert_eq!(
+            result,
+            Err("yt-dlp download failed: ERROR: [youtube] privateVid1: Private video".to_string())
+        );
+        let record = manager.failure_for("privateVid1").unwrap();
+        assert_eq!(record.kind, FailureKind::Permanent);
+        assert!(record.retry_at.is_none());
+        assert!(!manager.request_download(&private, DownloadPriority::Current, None));
+        assert_eq!(fake.calls().len(), 2);
+    }
+}
@@ -0,0 +1,226 @@
+//! Priority queue of downloads waiting for a free slot.
+//!
+//! Requests that can't start right away are kept here instead of being
+//! dropped, and started highest priority first (oldest first within a
+//! priority) as slots free up. This module holds the pure parts: the
+//! queue itself and picking which running download to preempt.
+
+use std::collections::HashMap;
+
+use crate::player::queue::Track;
+
+/// Queued downloads beyond this are dropped, lowest priority first.
+pub(crate) const MAX_QUEUED_DOWNLOADS: usize = 50;
+
+/// How urgently a track is needed. Ordered lowest to highest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) enum DownloadPriority {
+    /// Part of a "make available offline" playlist job.
+    Bulk,
+    /// Added to the queue, pinned, or re-downloaded from the cache view.
+    Background,
+    /// Far ahead of the queue cursor while scrolling.
+    Hover,
+    /// The next few tracks after the current one.
+    Lookahead,
+    /// The track the user is waiting to hear.
+    Current,
+}
+
+/// A download waiting for a slot.
+#[derive(Debug, Clone)]
+pub(crate) struct QueuedDownload {
+    pub track: Track,
+    pub priority: DownloadPriority,
+    /// Browser cookie info passed on to yt-dlp: (use_from_browser, cookie_arg).
+    pub cookie_config: Option<(bool, String)>,
+    seq: u64,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct DownloadQueue {
+    entries: Vec<QueuedDownload>,
+    next_seq: u64,
+}
+
+impl DownloadQueue {
+    /// Queue `track`, or raise its priority if it's already queued. Only
+    /// one track can be `Current`: an earlier one drops to `Lookahead`.
+    pub(crate) fn push(
+        &mut self,
+        track: &Track,
+        priority: DownloadPriority,
+        cookie_config: Option<(bool, String)>,
+    ) {
+        if priority == DownloadPriority::Current {
+            for entry in &mut self.entries {
+                if entry.priority == DownloadPriority::Current {
+                    entry.priority = DownloadPriority::Lookahead;
+                }
+            }
+        }
+        if let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|e| e.track.video_id == track.video_id)
+        {
+            entry.priority = entry.priority.max(priority);
+            return;
+        }
+        self.entries.push(QueuedDownload {
+            track: track.clone(),
+            priority,
+            cookie_config,
+            seq: self.next_seq,
+        });
+        self.next_seq += 1;
+
+        if self.entries.len() > MAX_QUEUED_DOWNLOADS {
+            if let Some(i) = self.lowest() {
+                self.entries.remove(i);
+            }
+        }
+    }
+
+    /// Take the highest-priority, oldest entry.
+    pub(crate) fn pop(&mut self) -> Option<QueuedDownload> {
+        let i = self
+            .entries
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, e)| (e.priority, std::cmp::Reverse(e.seq)))
+            .map(|(i, _)| i)?;
+        Some(self.entries.remove(i))
+    }
+
+    pub(crate) fn remove(&mut self, video_id: &str) -> Option<QueuedDownload> {
+        let i = self
+            .entries
+            .iter()
+            .position(|e| e.track.video_id == video_id)?;
+        Some(self.entries.remove(i))
+    }
+
+    pub(crate) fn contains(&self, video_id: &str) -> bool {
+        self.entries.iter().any(|e| e.track.video_id == video_id)
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Index of the lowest-priority, newest entry — the first to drop.
+    fn lowest(&self) -> Option<usize> {
+        self.entries
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, e)| (e.priority, std::cmp::Reverse(e.seq)))
+            .map(|(i, _)| i)
+    }
+}
+
+/// The running download to cancel so a `priority` request can start:
+/// the lowest-priority one strictly below it, the most recently started
+/// among equals (it has the least progress to lose). `running` maps video
+/// ids to (priority, start sequence).
+pub(crate) fn preemption_victim(
+    running: &HashMap<String, (DownloadPriority, u64)>,
+    priority: DownloadPriority,
+) -> Option<String> {
+    running
+        .iter()
+        .filter(|(_, (p, _))| *p < priority)
+        .min_by_key(|(_, (p, started))| (*p, std::cmp::Reverse(*started)))
+        .map(|(video_id, _)| video_id.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use DownloadPriority::*;
+
+    fn track(id: &str) -> Track {
+        Track::new(
+            id.to_string(),
+            id.to_string(),
+            0,
+            String::new(),
+            format!("https://www.youtube.com/watch?v={}", id),
+        )
+    }
+
+    fn pop_id(queue: &mut DownloadQueue) -> Option<String> {
+        queue.pop().map(|e| e.track.video_id)
+    }
+
+    #[test]
+    fn pops_by_priority_then_age() {
+        let mut queue = DownloadQueue::default();
+        queue.push(&track("bulk"), Bulk, None);
+        queue.push(&track("bg"), Background, None);
+        queue.push(&track("next1"), Lookahead, None);
+        queue.push(&track("hover"), Hover, None);
+        queue.push(&track("next2"), Lookahead, None);
+        queue.push(&track("now"), Current, None);
+
+        let order: Vec<_> = std::iter::from_fn(|| pop_id(&mut queue)).collect();
+        assert_eq!(order, ["now", "next1", "next2", "hover", "bg", "bulk"]);
+    }
+
+    #[test]
+    fn push_dedupes_raises_and_keeps_one_current() {
+        let mut queue = DownloadQueue::default();
+        queue.push(&track("a"), Current, None);
+        queue.push(&track("b"), Background, None);
+        queue.push(&track("b"), Hover, None);
+        queue.push(&track("b"), Background, None);
+        assert_eq!(queue.len(), 2);
+
+        // A new current track demotes the old one.
+        queue.push(&track("c"), Current, None);
+        assert_eq!(pop_id(&mut queue).as_deref(), Some("c"));
+        let demoted = queue.pop().unwrap();
+        assert_eq!(
+            (demoted.track.video_id.as_str(), demoted.priority),
+            ("a", Lookahead)
+        );
+        assert_eq!(queue.pop().map(|e| e.priority), Some(Hover));
+    }
+
+    #[test]
+    fn overflow_drops_lowest_priority() {
+        let mut queue = DownloadQueue::default();
+        queue.push(&track("keep"), Lookahead, None);
+        for i in 0..MAX_QUEUED_DOWNLOADS {
+            queue.push(&track(&format!("bg{}", i)), Background, None);
+        }
+        assert_eq!(queue.len(), MAX_QUEUED_DOWNLOADS);
+        assert!(queue.contains("keep"));
+        assert!(!queue.contains(&format!("bg{}", MAX_QUEUED_DOWNLOADS - 1)));
+        assert!(queue.remove("keep").is_some());
+        assert!(!queue.contains("keep"));
+    }
+
+    #[test]
+    fn preempts_lowest_newest_below_priority() {
+        let running: HashMap<String, (DownloadPriority, u64)> = [
+            ("next".to_string(), (Lookahead, 1)),
+            ("old_bg".to_string(), (Background, 2)),
+            ("new_bg".to_string(), (Background, 3)),
+        ]
+        .into();
+        assert_eq!(
+            preemption_victim(&running, Current).as_deref(),
+            Some("new_bg")
+        );
+        assert_eq!(
+            preemption_victim(&running, Hover).as_deref(),
+            Some("new_bg")
+        );
+        assert_eq!(preemption_victim(&running, Background), None);
+    }
+}
@@ -13,9 +13,8 @@
 //!
 //! All functions return [`FeedSection`]s containing [`FeedPlaylist`] entries.
 
-use std::process::Command;
-
 use crate::ui::state::{FeedPlaylist, FeedSection, PlaylistType};
+use crate::youtube::ytdlp::{YtDlp, YtDlpArgs, YtDlpError};
 
 // ---------------------------------------------------------------------------
 // Error type
@@ -63,10 +62,12 @@ impl FeedError {
 ///
 /// Returns a `(FeedSection, Option<channel_id>)` tuple.
 pub(crate) fn fetch_liked(
+    ytdlp: &dyn YtDlp,
     cookie_config: Option<(bool, String)>,
 ) -> Result<(FeedSection, Option<String>), FeedError> {
     let cookie_config = cookie_config.ok_or(FeedError::NoCookies)?;
     let stdout = run_yt_dlp(
+        ytdlp,
         &["https://music.youtube.com/playlist?list=LM"],
         &cookie_config,
     )?;
@@ -131,10 +132,12 @@ pub(crate) fn fetch_liked(
 /// Returns two sections: "Saved Mixes" (RDCLAK/RDAMPL) and "My Playlists"
 /// (everything else), omitting whichever is empty.
 pub(crate) fn fetch_library_playlists(
+    ytdlp: &dyn YtDlp,
     cookie_config: Option<(bool, String)>,
 ) -> Result<Vec<FeedSection>, FeedError> {
     let cookie_config = cookie_config.ok_or(FeedError::NoCookies)?;
     let stdout = run_yt_dlp(
+        ytdlp,
         &["https://www.youtube.com/feed/playlists"],
         &cookie_config,
     )?;
@@ -174,11 +177,14 @@ pub(crate) fn fetch_library_playlists(
             PlaylistType::Mix => mixes.push(entry),
             // LibraryLiked should never appear here (LM is in SKIP_IDS above),
             // but if it does, treat it as a regular playlist rather than silently
-            // dropping it. Exhaustive match ensures new variants force a decision.
+            // dropping it. Favorites is local-only and never classified from a
+            // feed entry. Exhaustive match ensures new variants force a decision.
             PlaylistType::LibrarySaved
             | PlaylistType::Recommended
+            | PlaylistType::Album
             | PlaylistType::ListenAgain
             | PlaylistType::LibraryLiked
+            | PlaylistType::Favorites
             | PlaylistType::Unknown => playlists.push(entry),
         }
     }
@@ -220,17 +226,18 @@ pub(crate) fn fetch_library_playlists(
 /// yt-dlp subprocesses). The caller in `actions.rs` wraps this in
 /// `spawn_blocking` so it does not block the async runtime.
 pub(crate) fn fetch_all_parallel(
+    ytdlp: &dyn YtDlp,
     cookie_config: Option<(bool, String)>,
 ) -> Result<Vec<FeedSection>, FeedError> {
     let cookie_config = cookie_config.ok_or(FeedError::NoCookies)?;
 
     // Step 1: Liked Music — mandatory (auth check).
-    let (liked_section, _channel_id) = fetch_liked(Some(cookie_config.clone()))?;
+    let (liked_section, _channel_id) = fetch_liked(ytdlp, Some(cookie_config.clone()))?;
 
     // Step 2: Full library feed — optional, failure is non-fatal.
     // Auth failures are already caught by fetch_liked above; this handles
     // transient errors (network, yt-dlp version drift, etc.).
-    let library_sections = match fetch_library_playlists(Some(cookie_config)) {
+    let library_sections = match fetch_library_playlists(ytdlp, Some(cookie_config)) {
         Ok(s) => s,
         Err(e) => {
             // Non-fatal: surface in logs so the user can diagnose if needed.
@@ -267,6 +274,7 @@ pub(crate) fn fetch_all_parallel(
 ///
 /// This is used when the user expands a playlist to cherry-pick tracks.
 pub(crate) fn fetch_tracks_for_playlist(
+    ytdlp: &dyn YtDlp,
     cookie_config: Option<(bool, String)>,
     url: &str,
 ) -> Result<Vec<crate::ui::state::FeedTrack>, FeedError> {
@@ -277,7 +285,7 @@ pub(crate) fn fetch_tracks_for_playlist(
         )));
     }
     let cookie_config = cookie_config.ok_or(FeedError::NoCookies)?;
-    let stdout = run_yt_dlp(&[url], &cookie_config)?;
+    let stdout = run_yt_dlp(ytdlp, &[url], &cookie_config)?;
 
     if stdout.trim().is_empty() {
         return Err(FeedError::AuthExpired);
@@ -336,55 +344,40 @@ pub(crate) fn fetch_tracks_for_playlist(
 
 /// Run yt-dlp with `--flat-playlist --dump-json` against `urls`, injecting
 /// the browser cookie argument. Returns stdout as a `String`.
-///
-/// Extracted as a separate function so tests can verify argument construction
-/// without invoking the real binary.
-fn run_yt_dlp(urls: &[&str], cookie_config: &(bool, String)) -> Result<String, FeedError> {
-    let (_use_from_browser, cookie_arg) = cookie_config;
-
-    let mut cmd = Command::new("yt-dlp");
-    cmd.arg("--flat-playlist")
-        .arg("--dump-json")
-        .arg("--no-warnings")
-        .arg("--skip-download")
-        .arg("--socket-timeout")
-        .arg("30")
-        .arg("--retries")
-        .arg("2")
-        .arg("--cookies-from-browser")
-        .arg(cookie_arg);
-
-    for url in urls {
-        cmd.arg(url);
-    }
-
-    let output = cmd.output().map_err(|e| {
-        if e.kind() == std::io::ErrorKind::NotFound {
-            FeedError::YtDlpMissing
-        } else {
-            FeedError::YtDlpFailed(e.to_string())
-        }
+fn run_yt_dlp(
+    ytdlp: &dyn YtDlp,
+    urls: &[&str],
+    cookie_config: &(bool, String),
+) -> Result<String, FeedError> {
+    let args = urls.iter().fold(
+        YtDlpArgs::new()
+            .flag("--flat-playlist")
+            .flag("--dump-json")
+            .flag("--no-warnings")
+            .flag("--skip-download")
+            .cookies(Some(cookie_config)),
+        |args, url| args.flag(url),
+    );
+
+    let output = ytdlp.run(&args).map_err(|e| match e {
+        YtDlpError::Missing => FeedError::YtDlpMissing,
+        YtDlpError::InvalidUtf8(e) => FeedError::InvalidUtf8(e),
+        YtDlpError::Spawn(e) => FeedError::YtDlpFailed(e),
     })?;
 
     // Check stderr for auth-related failures before checking exit status,
     // because yt-dlp sometimes exits 0 even when auth fails.
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    if is_auth_error(&stderr) {
+    if is_auth_error(&output.stderr) {
         return Err(FeedError::AuthExpired);
     }
 
-    if !output.status.success() {
-        // Take only the first non-empty line, cap at 200 chars, and strip
-        // control characters so the message is safe to display in the TUI.
-        let snippet = stderr
-            .lines()
-            .find(|l| !l.trim().is_empty())
-            .unwrap_or("unknown error");
-        let snippet = sanitize_text(&snippet.chars().take(200).collect::<String>());
-        return Err(FeedError::YtDlpFailed(snippet));
+    if !output.success {
+        // The error line, capped and stripped of control characters so it's
+        // safe to display in the TUI.
+        return Err(FeedError::YtDlpFailed(output.error_snippet()));
     }
 
-    String::from_utf8(output.stdout).map_err(|e| FeedError::InvalidUtf8(e.to_string()))
+    Ok(output.stdout)
 }
 
 /// Strip control characters from a string sourced from yt-dlp output.
@@ -520,8 +513,8 @@ pub(crate) fn classify(id: &str, title: &str) -> PlaylistType {
         PlaylistType::Mix
     } else if id == "LM" {
         PlaylistType::LibraryLiked
-    } else if id.starts_with("OLAK5uy_") {
-        PlaylistType::Recommended
+    } else if crate::services::album::is_album_id(id) {
+        PlaylistType::Album
     } else if id.starts_with("PL") || id.starts_with("VL") {
         // ID-based library check before title check so "Listen Again" in the
         // title of a PL* playlist doesn't override the library classification.
@@ -544,7 +537,7 @@ fn group_into_sections(entries: Vec<FeedPlaylist>) -> Vec<FeedSection> {
     for entry in entries {
         match entry.playlist_type {
             PlaylistType::Mix => mixes.push(entry),
-            PlaylistType::Recommended => recommended.push(entry),
+            PlaylistType::Recommended | PlaylistType::Album => recommended.push(entry),
             PlaylistType::ListenAgain => listen_again.push(entry),
             _ => other.push(entry),
         }
@@ -610,10 +603,10 @@ mod tests {
     }
 
     #[test]
-    fn classify_olak5uy_is_recommended() {
+    fn classify_olak5uy_is_album() {
         assert_eq!(
             classify("OLAK5uy_abc", "Album Playlist"),
-            PlaylistType::Recommended
+            PlaylistType::Album
         );
     }
 
@@ -688,12 +681,12 @@ mod tests {
     }
 
     #[test]
-    fn parse_entries_parses_recommended_playlist() {
+    fn parse_entries_parses_album_playlist() {
         let input = r#"{"_type":"playlist","id":"OLAK5uy_abc","title":"Chill Vibes","n_entries":30}"#;
         let entries = parse_entries(input);
 
         assert_eq!(entries.len(), 1);
-        assert_eq!(entries[0].playlist_type, PlaylistType::Recommended);
+        assert_eq!(entries[0].playlist_type, PlaylistType::Album);
         assert_eq!(entries[0].track_count_estimate, 30);
     }
 
@@ -727,7 +720,7 @@ mod tests {
         let entries = parse_entries(input);
         assert_eq!(entries.len(), 3);
         assert_eq!(entries[0].playlist_type, PlaylistType::Mix);
-        assert_eq!(entries[1].playlist_type, PlaylistType::Recommended);
+        assert_eq!(entries[1].playlist_type, PlaylistType::Album);
         assert_eq!(entries[2].playlist_type, PlaylistType::LibrarySaved);
     }
 
@@ -1061,4 +1054,91 @@ mod tests {
         );
         assert!(store_v3.load().unwrap().is_none());
     }
+
+    // -- End to end against a fake yt-dlp --
+
+    use crate::youtube::fake_ytdlp::{FakeYtDlp, Reply};
+
+    fn cookies() -> Option<(bool, String)> {
+        Some((true, "chrome".to_string()))
+    }
+
+    fn feed_fake() -> FakeYtDlp {
+        FakeYtDlp::new()
+            .on("list=LM", Reply::fixture("liked.jsonl"))
+            .on("feed/playlists", Reply::fixture("library_playlists.jsonl"))
+    }
+
+    #[test]
+    fn fetch_all_merges_library_and_liked_sections() {
+        let fake = feed_fake();
+        let sections = fetch_all_parallel(&fake, cookies()).unwrap();
+        let titles: Vec<_> = sections.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, ["Saved Mixes", "My Playlists", "Liked Music"]);
+        assert_eq!(sections[1].items[0].title, "Road trip");
+        // Feed page counts are meaningless and dropped.
+        assert_eq!(sections[1].items[0].track_count_estimate, 0);
+        assert_eq!(sections[2].items[0].track_count_estimate, 3);
+        assert!(fake
+            .calls()
+            .iter()
+            .all(|args| args.value_of("--cookies-from-browser") == Some("chrome")));
+
+        assert!(matches!(
+            fetch_all_parallel(&fake, None),
+            Err(FeedError::NoCookies)
+        ));
+    }
+
+    #[test]
+    fn fetch_all_survives_library_failure_but_not_auth_failure() {
+        let fake = FakeYtDlp::new()
+            .on("list=LM", Reply::fixture("liked.jsonl"))
+            .on(
+                "feed/playlists",
+                Reply::Fail("ERROR: HTTP Error 500\n".to_string()),
+            );
+        let sections = fetch_all_parallel(&fake, cookies()).unwrap();
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].kind, PlaylistType::LibraryLiked);
+
+        let fake = FakeYtDlp::new().on(
+            "list=LM",
+            Reply::Fail(
+                "ERROR: [youtube:tab] LM: Sign in to confirm you\u{2019}re not a bot\n".to_string(),
+            ),
+        );
+        assert!(matches!(
+            fetch_all_parallel(&fake, cookies()),
+            Err(FeedError::AuthExpired)
+        ));
+
+        let fake = FakeYtDlp::new().on("list=LM", Reply::Missing);
+        assert!(matches!(
+            fetch_all_parallel(&fake, cookies()),
+            Err(FeedError::YtDlpMissing)
+        ));
+    }
+
+    #[test]
+    fn fetch_tracks_for_playlist_from_fixture() {
+        let fake = feed_fake();
+        let tracks = fetch_tracks_for_playlist(
+            &fake,
+            cookies(),
+            "https://music.youtube.com/playlist?list=LM",
+        )
+        .unwrap();
+        assert_eq!(tracks.len(), 3);
+        assert_eq!(tracks[1].title, "Save Your Tears");
+        assert_eq!(tracks[1].uploader, "The Weeknd");
+        assert_eq!(
+            tracks[1].url,
+            "https://music.youtube.com/watch?v=XXYlFuWEuKI"
+        );
+
+        let err = fetch_tracks_for_playlist(&fake, cookies(), "https://evil.example/list=LM");
+        assert!(matches!(err, Err(FeedError::YtDlpFailed(_))));
+        assert_eq!(fake.calls().len(), 1);
+    }
 }
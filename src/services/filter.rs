@@ -0,0 +1,353 @@
+//! Rule-based content filter applied whenever tracks are added to the queue.
+//!
+//! A [`FilterProfile`] is a named set of rules (duration bounds, title
+//! keywords, uploader allow/block lists, unknown-duration policy). The user
+//! cycles between profiles with Shift+F; profiles live in `filters.json` in the
+//! config directory so they can be edited by hand.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::MAX_TRACK_DURATION_SECS;
+
+/// What to do with tracks whose duration is unknown (reported as `0`).
+///
+/// Flat playlist extraction frequently omits durations, so blocking them
+/// would drop most of a freshly loaded playlist.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum UnknownDurationPolicy {
+    #[default]
+    Allow,
+    Block,
+}
+
+/// Why a track was rejected by a profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum FilterReason {
+    BlockedUploader,
+    UnknownDuration,
+    TooShort { secs: u64 },
+    TooLong { secs: u64 },
+    ExcludedKeyword(String),
+    MissingKeyword,
+}
+
+impl std::fmt::Display for FilterReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FilterReason::BlockedUploader => write!(f, "uploader is blocked"),
+            FilterReason::UnknownDuration => write!(f, "unknown duration"),
+            FilterReason::TooShort { secs } => write!(f, "too short ({}s)", secs),
+            FilterReason::TooLong { secs } => write!(f, "too long ({}min)", secs / 60),
+            FilterReason::ExcludedKeyword(k) => write!(f, "title contains '{}'", k),
+            FilterReason::MissingKeyword => write!(f, "title matches no include keyword"),
+        }
+    }
+}
+
+/// A named set of filter rules.
+///
+/// Evaluation order: blocked uploaders are always rejected, allowed uploaders
+/// are always accepted (so a long track from a trusted artist gets through),
+/// then the duration rules and title keywords are checked.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct FilterProfile {
+    pub name: String,
+    pub min_duration_secs: Option<u64>,
+    pub max_duration_secs: Option<u64>,
+    /// If non-empty, the title must contain at least one of these keywords.
+    pub title_include: Vec<String>,
+    /// Tracks whose title contains any of these keywords are rejected.
+    pub title_exclude: Vec<String>,
+    /// Uploaders that bypass every other rule (case-insensitive exact match).
+    pub uploader_allow: Vec<String>,
+    /// Uploaders that are always rejected (case-insensitive exact match).
+    pub uploader_block: Vec<String>,
+    pub unknown_duration: UnknownDurationPolicy,
+}
+
+impl FilterProfile {
+    /// A profile with no rules — everything passes.
+    pub(crate) fn allow_all(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Check a track against this profile. `duration == 0` means unknown.
+    pub(crate) fn check(
+        &self,
+        title: &str,
+        uploader: &str,
+        duration: u64,
+    ) -> Result<(), FilterReason> {
+        let uploader = uploader.trim();
+        if contains_uploader(&self.uploader_block, uploader) {
+            return Err(FilterReason::BlockedUploader);
+        }
+        if contains_uploader(&self.uploader_allow, uploader) {
+            return Ok(());
+        }
+
+        if duration == 0 {
+            if self.unknown_duration == UnknownDurationPolicy::Block {
+                return Err(FilterReason::UnknownDuration);
+            }
+        } else {
+            if let Some(min) = self.min_duration_secs {
+                if duration < min {
+                    return Err(FilterReason::TooShort { secs: duration });
+                }
+            }
+            if let Some(max) = self.max_duration_secs {
+                if duration > max {
+                    return Err(FilterReason::TooLong { secs: duration });
+                }
+            }
+        }
+
+        if let Some(keyword) = self
+            .title_exclude
+            .iter()
+            .find(|k| contains_keyword(title, k))
+        {
+            return Err(FilterReason::ExcludedKeyword(keyword.clone()));
+        }
+        if !self.title_include.is_empty()
+            && !self
+                .title_include
+                .iter()
+                .any(|k| contains_keyword(title, k))
+        {
+            return Err(FilterReason::MissingKeyword);
+        }
+
+        Ok(())
+    }
+}
+
+fn contains_uploader(list: &[String], uploader: &str) -> bool {
+    !uploader.is_empty() && list.iter().any(|u| u.trim().eq_ignore_ascii_case(uploader))
+}
+
+/// Case-insensitive keyword match on word boundaries, so "live" matches
+/// "Song (Live at Wembley)" but not "Alive" or "Deliver".
+fn contains_keyword(title: &str, keyword: &str) -> bool {
+    let keyword = keyword.trim().to_lowercase();
+    if keyword.is_empty() {
+        return false;
+    }
+    let title = title.to_lowercase();
+    let mut start = 0;
+    while let Some(pos) = title[start..].find(&keyword) {
+        let begin = start + pos;
+        let end = begin + keyword.len();
+        let before_ok = title[..begin]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !c.is_alphanumeric());
+        let after_ok = title[end..]
+            .chars()
+            .next()
+            .is_none_or(|c| !c.is_alphanumeric());
+        if before_ok && after_ok {
+            return true;
+        }
+        // Advance by one character (not byte) to stay on a char boundary.
+        start = begin + title[begin..].chars().next().map_or(1, char::len_utf8);
+    }
+    false
+}
+
+// ---------------------------------------------------------------------------
+// FilterSet — the persisted list of profiles plus the active one
+// ---------------------------------------------------------------------------
+
+/// All configured profiles plus the name of the active one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct FilterSet {
+    /// Name of the active profile. Falls back to the first profile if unknown.
+    pub active: String,
+    pub profiles: Vec<FilterProfile>,
+}
+
+impl Default for FilterSet {
+    /// Built-in profiles. "Music" reproduces the old music-only mode.
+    fn default() -> Self {
+        let music = FilterProfile {
+            name: "Music".to_string(),
+            max_duration_secs: Some(MAX_TRACK_DURATION_SECS),
+            ..Default::default()
+        };
+        let studio = FilterProfile {
+            name: "Studio".to_string(),
+            max_duration_secs: Some(MAX_TRACK_DURATION_SECS),
+            title_exclude: ["live", "reaction", "cover", "karaoke", "remix"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            ..Default::default()
+        };
+        Self {
+            active: music.name.clone(),
+            profiles: vec![music, studio, FilterProfile::allow_all("All")],
+        }
+    }
+}
+
+impl FilterSet {
+    /// The active profile. An empty set behaves like "All".
+    pub(crate) fn active(&self) -> std::borrow::Cow<'_, FilterProfile> {
+        match self
+            .profiles
+            .iter()
+            .find(|p| p.name == self.active)
+            .or_else(|| self.profiles.first())
+        {
+            Some(p) => std::borrow::Cow::Borrowed(p),
+            None => std::borrow::Cow::Owned(FilterProfile::allow_all("All")),
+        }
+    }
+
+    /// Switch to the next profile (wrapping) and return its name.
+    pub(crate) fn cycle(&mut self) -> String {
+        if self.profiles.is_empty() {
+            return self.active().name.clone();
+        }
+        let idx = self
+            .profiles
+            .iter()
+            .position(|p| p.name == self.active)
+            .map_or(0, |i| (i + 1) % self.profiles.len());
+        self.active = self.profiles[idx].name.clone();
+        self.active.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile() -> FilterProfile {
+        FilterProfile {
+            name: "Test".to_string(),
+            min_duration_secs: Some(60),
+            max_duration_secs: Some(420),
+            title_exclude: vec!["live".to_string(), "reaction".to_string()],
+            uploader_allow: vec!["Trusted Artist".to_string()],
+            uploader_block: vec!["Spam Channel".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn accepts_track_within_bounds() {
+        assert_eq!(profile().check("Song", "Artist", 200), Ok(()));
+    }
+
+    #[test]
+    fn rejects_too_long_and_too_short() {
+        assert_eq!(
+            profile().check("Song", "Artist", 600),
+            Err(FilterReason::TooLong { secs: 600 })
+        );
+        assert_eq!(
+            profile().check("Song", "Artist", 30),
+            Err(FilterReason::TooShort { secs: 30 })
+        );
+    }
+
+    #[test]
+    fn excluded_keyword_matches_on_word_boundaries() {
+        let p = profile();
+        assert!(matches!(
+            p.check("Song (Live at Wembley)", "Artist", 200),
+            Err(FilterReason::ExcludedKeyword(_))
+        ));
+        assert_eq!(p.check("Stayin' Alive", "Artist", 200), Ok(()));
+        assert_eq!(p.check("Deliverance", "Artist", 200), Ok(()));
+    }
+
+    #[test]
+    fn include_keywords_require_a_match() {
+        let p = FilterProfile {
+            title_include: vec!["lofi".to_string()],
+            ..FilterProfile::allow_all("Lofi")
+        };
+        assert_eq!(p.check("Chill LOFI beats", "", 100), Ok(()));
+        assert_eq!(p.check("Metal", "", 100), Err(FilterReason::MissingKeyword));
+    }
+
+    #[test]
+    fn allowed_uploader_bypasses_other_rules() {
+        assert_eq!(
+            profile().check("Epic (Live)", "trusted artist", 1800),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn blocked_uploader_always_rejected() {
+        assert_eq!(
+            profile().check("Song", "Spam Channel", 200),
+            Err(FilterReason::BlockedUploader)
+        );
+    }
+
+    #[test]
+    fn unknown_duration_policy() {
+        let mut p = profile();
+        assert_eq!(p.check("Song", "Artist", 0), Ok(()));
+        p.unknown_duration = UnknownDurationPolicy::Block;
+        assert_eq!(
+            p.check("Song", "Artist", 0),
+            Err(FilterReason::UnknownDuration)
+        );
+    }
+
+    #[test]
+    fn keyword_match_handles_multibyte_titles() {
+        assert!(contains_keyword("Café — live édition", "live"));
+        assert!(!contains_keyword("ライブ", "live"));
+    }
+
+    #[test]
+    fn default_set_reproduces_music_only_mode() {
+        let set = FilterSet::default();
+        let music = set.active();
+        assert_eq!(music.name, "Music");
+        assert!(music.check("Song", "", MAX_TRACK_DURATION_SECS).is_ok());
+        assert!(music
+            .check("Song", "", MAX_TRACK_DURATION_SECS + 1)
+            .is_err());
+    }
+
+    #[test]
+    fn cycle_wraps_around() {
+        let mut set = FilterSet::default();
+        assert_eq!(set.cycle(), "Studio");
+        assert_eq!(set.cycle(), "All");
+        assert_eq!(set.cycle(), "Music");
+    }
+
+    #[test]
+    fn unknown_active_name_falls_back_to_first() {
+        let set = FilterSet {
+            active: "Missing".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(set.active().name, "Music");
+    }
+
+    #[test]
+    fn empty_set_allows_everything() {
+        let mut set = FilterSet {
+            active: String::new(),
+            profiles: Vec::new(),
+        };
+        assert!(set.active().check("x", "y", 99_999).is_ok());
+        assert_eq!(set.cycle(), "All");
+    }
+}
@@ -0,0 +1,1653 @@
+//! Embedded SQLite library database (`library.db` in the config directory).
+//!
+//! Long-term store for everything the capped JSON files can't hold: every
+//! track we've seen, every play with its timestamp, the files in the download
+//! cache, and playlists. The JSON files remain the source of truth for
+//! session state (queue, playback position); this database accumulates.
+//!
+//! Schema changes go through [`MIGRATIONS`]: append a new entry, never edit
+//! an existing one. The applied version is tracked in `PRAGMA user_version`.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::player::queue::Track;
+use crate::services::album::{Album, AlbumRef};
+use crate::services::audio_cache::CacheEntry;
+use crate::services::local_playlists::LocalPlaylist;
+use crate::services::offline_jobs::OfflineJob;
+use crate::services::tags::TagQuery;
+
+// ---------------------------------------------------------------------------
+// Migrations
+// ---------------------------------------------------------------------------
+
+/// A single forward-only schema migration.
+struct Migration {
+    /// Target `user_version` once applied. Must be strictly increasing.
+    version: u32,
+    description: &'static str,
+    sql: &'static str,
+}
+
+/// Every schema migration, in order. Append only.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "initial schema: tracks, plays, cached files, playlists",
+        sql: "
+            CREATE TABLE tracks (
+                video_id      TEXT PRIMARY KEY,
+                title         TEXT NOT NULL,
+                uploader      TEXT NOT NULL DEFAULT '',
+                duration      INTEGER NOT NULL DEFAULT 0,
+                url           TEXT NOT NULL,
+                first_seen_at INTEGER NOT NULL,
+                updated_at    INTEGER NOT NULL
+            );
+
+            CREATE TABLE plays (
+                id        INTEGER PRIMARY KEY AUTOINCREMENT,
+                video_id  TEXT NOT NULL REFERENCES tracks(video_id) ON DELETE CASCADE,
+                played_at INTEGER NOT NULL
+            );
+            CREATE INDEX plays_by_video ON plays(video_id);
+            CREATE INDEX plays_by_time ON plays(played_at);
+
+            CREATE TABLE cached_files (
+                video_id       TEXT PRIMARY KEY,
+                path           TEXT NOT NULL,
+                size_bytes     INTEGER NOT NULL DEFAULT 0,
+                cached_at      INTEGER NOT NULL,
+                last_played_at INTEGER
+            );
+
+            CREATE TABLE playlists (
+                id         INTEGER PRIMARY KEY AUTOINCREMENT,
+                name       TEXT NOT NULL UNIQUE,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            );
+
+            CREATE TABLE playlist_tracks (
+                playlist_id INTEGER NOT NULL REFERENCES playlists(id) ON DELETE CASCADE,
+                position    INTEGER NOT NULL,
+                video_id    TEXT NOT NULL REFERENCES tracks(video_id),
+                PRIMARY KEY (playlist_id, position)
+            );
+
+            CREATE TABLE meta (
+                key   TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            );
+        ",
+    },
+    Migration {
+        version: 2,
+        description: "listened duration and skip flag on plays",
+        // Plays recorded before this migration have no listening data;
+        // assume they were heard in full rather than counting them as 0s.
+        sql: "
+            ALTER TABLE plays ADD COLUMN listened_secs INTEGER NOT NULL DEFAULT 0;
+            ALTER TABLE plays ADD COLUMN skipped INTEGER NOT NULL DEFAULT 0;
+            UPDATE plays SET listened_secs =
+                (SELECT duration FROM tracks WHERE tracks.video_id = plays.video_id);
+        ",
+    },
+    Migration {
+        version: 3,
+        description: "local favorites and star ratings",
+        // A row exists only while a track is a favorite or has a rating.
+        sql: "
+            CREATE TABLE track_ratings (
+                video_id   TEXT PRIMARY KEY REFERENCES tracks(video_id) ON DELETE CASCADE,
+                favorite   INTEGER NOT NULL DEFAULT 0,
+                stars      INTEGER CHECK (stars BETWEEN 1 AND 5),
+                updated_at INTEGER NOT NULL
+            );
+        ",
+    },
+    Migration {
+        version: 4,
+        description: "user tags on tracks",
+        sql: "
+            CREATE TABLE track_tags (
+                video_id TEXT NOT NULL REFERENCES tracks(video_id) ON DELETE CASCADE,
+                tag      TEXT NOT NULL,
+                added_at INTEGER NOT NULL,
+                PRIMARY KEY (video_id, tag)
+            );
+            CREATE INDEX track_tags_by_tag ON track_tags(tag);
+        ",
+    },
+    Migration {
+        version: 5,
+        description: "pinned tracks exempt from cache eviction",
+        sql: "
+            CREATE TABLE pinned_tracks (
+                video_id  TEXT PRIMARY KEY REFERENCES tracks(video_id) ON DELETE CASCADE,
+                pinned_at INTEGER NOT NULL
+            );
+        ",
+    },
+    Migration {
+        version: 6,
+        description: "make-available-offline playlist jobs",
+        sql: "
+            CREATE TABLE offline_jobs (
+                id           TEXT PRIMARY KEY,
+                title        TEXT NOT NULL,
+                created_at   INTEGER NOT NULL,
+                completed_at INTEGER
+            );
+
+            CREATE TABLE offline_job_tracks (
+                job_id   TEXT NOT NULL REFERENCES offline_jobs(id) ON DELETE CASCADE,
+                position INTEGER NOT NULL,
+                video_id TEXT NOT NULL REFERENCES tracks(video_id) ON DELETE CASCADE,
+                PRIMARY KEY (job_id, position)
+            );
+        ",
+    },
+    Migration {
+        version: 7,
+        description: "submitted search queries",
+        // One row per query regardless of case; the latest spelling wins.
+        sql: "
+            CREATE TABLE search_history (
+                query       TEXT PRIMARY KEY COLLATE NOCASE,
+                searched_at INTEGER NOT NULL,
+                times       INTEGER NOT NULL DEFAULT 1
+            );
+            CREATE INDEX search_history_by_time ON search_history(searched_at);
+        ",
+    },
+    Migration {
+        version: 8,
+        description: "albums and the tracks on them",
+        sql: "
+            CREATE TABLE albums (
+                id         TEXT PRIMARY KEY,
+                title      TEXT NOT NULL,
+                artist     TEXT NOT NULL DEFAULT '',
+                year       INTEGER,
+                updated_at INTEGER NOT NULL
+            );
+
+            CREATE TABLE album_tracks (
+                album_id TEXT NOT NULL REFERENCES albums(id) ON DELETE CASCADE,
+                position INTEGER NOT NULL,
+                video_id TEXT NOT NULL REFERENCES tracks(video_id) ON DELETE CASCADE,
+                PRIMARY KEY (album_id, position)
+            );
+            CREATE INDEX album_tracks_by_video ON album_tracks(video_id);
+        ",
+    },
+];
+
+// ---------------------------------------------------------------------------
+// Listening statistics
+// ---------------------------------------------------------------------------
+
+/// Aggregated listening data for the Stats view.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct ListeningStats {
+    pub plays: u64,
+    pub skips: u64,
+    pub total_listened_secs: u64,
+    pub top_tracks: Vec<TrackStat>,
+    pub top_uploaders: Vec<UploaderStat>,
+    /// Listening seconds per day, oldest first.
+    pub daily_secs: Vec<u64>,
+}
+
+impl ListeningStats {
+    /// Fraction of plays that were skipped, in `0.0..=1.0`.
+    pub(crate) fn skip_rate(&self) -> f64 {
+        if self.plays == 0 {
+            0.0
+        } else {
+            self.skips as f64 / self.plays as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TrackStat {
+    pub title: String,
+    pub uploader: String,
+    pub plays: u64,
+    pub listened_secs: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct UploaderStat {
+    pub uploader: String,
+    pub plays: u64,
+    pub listened_secs: u64,
+}
+
+// ---------------------------------------------------------------------------
+// Favorites and ratings
+// ---------------------------------------------------------------------------
+
+/// Highest star rating a track can have.
+pub(crate) const MAX_STARS: u8 = 5;
+
+/// The user's own verdict on a track — independent of YouTube likes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct TrackRating {
+    pub favorite: bool,
+    /// `1..=MAX_STARS`, or `None` if unrated.
+    pub stars: Option<u8>,
+}
+
+impl TrackRating {
+    /// Neither a favorite nor rated — nothing to store.
+    pub(crate) fn is_empty(&self) -> bool {
+        !self.favorite && self.stars.is_none()
+    }
+
+    /// `★★★☆☆` for a 3-star track, empty when unrated.
+    pub(crate) fn stars_label(&self) -> String {
+        self.stars.map_or_else(String::new, |n| {
+            let n = n.min(MAX_STARS) as usize;
+            format!("{}{}", "★".repeat(n), "☆".repeat(MAX_STARS as usize - n))
+        })
+    }
+}
+
+/// Which rated tracks to select when building a queue from ratings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RatedFilter {
+    Favorites,
+    /// Tracks rated at least this many stars.
+    MinStars(u8),
+}
+
+impl RatedFilter {
+    pub(crate) fn matches(&self, rating: &TrackRating) -> bool {
+        match self {
+            RatedFilter::Favorites => rating.favorite,
+            RatedFilter::MinStars(min) => rating.stars.is_some_and(|s| s >= *min),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Search history
+// ---------------------------------------------------------------------------
+
+/// A completion offered while typing a search.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SearchSuggestion {
+    pub text: String,
+    pub source: SuggestionSource,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SuggestionSource {
+    /// A query searched before, last at this Unix time.
+    Recent { searched_at: i64 },
+    /// The title of a track that has been played.
+    Track,
+    /// An uploader or artist of a known track.
+    Uploader,
+}
+
+/// `query` with runs of whitespace collapsed, as stored in the history.
+fn normalize_query(query: &str) -> String {
+    query.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// A `LIKE` pattern matching strings that start with `prefix`.
+fn like_prefix(prefix: &str) -> String {
+    let escaped = prefix
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("{}%", escaped)
+}
+
+/// Current Unix time in seconds.
+pub(crate) fn now_unix() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+// ---------------------------------------------------------------------------
+// Library
+// ---------------------------------------------------------------------------
+
+/// Handle to the library database.
+pub(crate) struct Library {
+    conn: Connection,
+}
+
+impl Library {
+    /// Open (or create) the database at `path` and bring the schema up to date.
+    pub(crate) fn open(path: &Path) -> Result<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).context("Failed to create config directory")?;
+        }
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open library database {}", path.display()))?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let _ = std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600));
+        }
+
+        Self::from_connection(conn)
+    }
+
+    /// In-memory database for tests.
+    #[cfg(test)]
+    pub(crate) fn open_in_memory() -> Result<Self> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    fn from_connection(mut conn: Connection) -> Result<Self> {
+        conn.pragma_update(None, "foreign_keys", true)
+            .context("Failed to enable foreign keys")?;
+        // WAL keeps readers unblocked while a play is being recorded.
+        // In-memory databases silently stay in "memory" mode, which is fine.
+        let _: String = conn
+            .pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get(0))
+            .context("Failed to set journal mode")?;
+        migrate(&mut conn)?;
+        Ok(Self { conn })
+    }
+
+    /// The schema version currently applied.
+    #[cfg(test)]
+    pub(crate) fn schema_version(&self) -> Result<u32> {
+        Ok(self
+            .conn
+            .pragma_query_value(None, "user_version", |row| row.get(0))?)
+    }
+
+    // -- Meta ---------------------------------------------------------------
+
+    pub(crate) fn get_meta(&self, key: &str) -> Result<Option<String>> {
+        Ok(self
+            .conn
+            .query_row("SELECT value FROM meta WHERE key = ?1", [key], |row| {
+                row.get(0)
+            })
+            .optional()?)
+    }
+
+    pub(crate) fn set_meta(&self, key: &str, value: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO meta (key, value) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![key, value],
+        )?;
+        Ok(())
+    }
+
+    // -- Tracks -------------------------------------------------------------
+
+    /// Insert a track or refresh its metadata if already known.
+    pub(crate) fn upsert_track(&self, track: &Track) -> Result<()> {
+        let now = now_unix();
+        self.conn.execute(
+            "INSERT INTO tracks (video_id, title, uploader, duration, url, first_seen_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)
+             ON CONFLICT(video_id) DO UPDATE SET
+                 title = excluded.title,
+                 uploader = excluded.uploader,
+                 -- Flat playlist extraction reports 0 for unknown durations;
+                 -- don't let that clobber a known value.
+                 duration = CASE WHEN excluded.duration > 0
+                                 THEN excluded.duration ELSE tracks.duration END,
+                 url = excluded.url,
+                 updated_at = excluded.updated_at",
+            params![
+                track.video_id,
+                track.title,
+                track.uploader,
+                track.duration as i64,
+                track.url,
+                now
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub(crate) fn get_track(&self, video_id: &str) -> Result<Option<Track>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT video_id, title, duration, uploader, url FROM tracks WHERE video_id = ?1",
+                [video_id],
+                |row| {
+                    Ok(Track::new(
+                        row.get(0)?,
+                        row.get(1)?,
+                        row.get::<_, i64>(2)?.max(0) as u64,
+                        row.get(3)?,
+                        row.get(4)?,
+                    ))
+                },
+            )
+            .optional()?)
+    }
+
+    // -- Plays --------------------------------------------------------------
+
+    /// Record that `track` started playing at `played_at` (Unix seconds).
+    /// Returns the new play's row id.
+    pub(crate) fn record_play(&self, track: &Track, played_at: i64) -> Result<i64> {
+        self.upsert_track(track)?;
+        self.conn.execute(
+            "INSERT INTO plays (video_id, played_at) VALUES (?1, ?2)",
+            params![track.video_id, played_at],
+        )?;
+        let id = self.conn.last_insert_rowid();
+        self.conn.execute(
+            "UPDATE cached_files SET last_played_at = ?2 WHERE video_id = ?1",
+            params![track.video_id, played_at],
+        )?;
+        Ok(id)
+    }
+
+    /// Store how long a play was actually listened to and whether it was
+    /// skipped. Safe to call repeatedly — later calls overwrite earlier ones.
+    pub(crate) fn finish_play(
+        &self,
+        play_id: i64,
+        listened_secs: u64,
+        skipped: bool,
+    ) -> Result<()> {
+        self.conn.execute(
+            "UPDATE plays SET listened_secs = ?2, skipped = ?3 WHERE id = ?1",
+            params![play_id, listened_secs as i64, skipped],
+        )?;
+        Ok(())
+    }
+
+    /// Aggregate listening statistics for plays at or after `since` (Unix
+    /// seconds; `None` = all time). `daily_buckets` days of per-day listening
+    /// time ending at `now` are returned for the sparkline.
+    pub(crate) fn listening_stats(
+        &self,
+        since: Option<i64>,
+        now: i64,
+        daily_buckets: usize,
+    ) -> Result<ListeningStats> {
+        const TOP_N: i64 = 10;
+        let since = since.unwrap_or(i64::MIN);
+
+        let (plays, skips, total): (i64, i64, i64) = self.conn.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(skipped), 0), COALESCE(SUM(listened_secs), 0)
+             FROM plays WHERE played_at >= ?1",
+            [since],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+
+        let mut stmt = self.conn.prepare(
+            "SELECT t.title, t.uploader, COUNT(*) AS n, SUM(p.listened_secs) AS secs
+             FROM plays p JOIN tracks t ON t.video_id = p.video_id
+             WHERE p.played_at >= ?1
+             GROUP BY p.video_id
+             ORDER BY n DESC, secs DESC, t.title
+             LIMIT ?2",
+        )?;
+        let top_tracks = stmt
+            .query_map(params![since, TOP_N], |row| {
+                Ok(TrackStat {
+                    title: row.get(0)?,
+                    uploader: row.get(1)?,
+                    plays: row.get::<_, i64>(2)? as u64,
+                    listened_secs: row.get::<_, i64>(3)?.max(0) as u64,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut stmt = self.conn.prepare(
+            "SELECT t.uploader, COUNT(*) AS n, SUM(p.listened_secs) AS secs
+             FROM plays p JOIN tracks t ON t.video_id = p.video_id
+             WHERE p.played_at >= ?1 AND t.uploader != ''
+             GROUP BY t.uploader
+             ORDER BY n DESC, secs DESC, t.uploader
+             LIMIT ?2",
+        )?;
+        let top_uploaders = stmt
+            .query_map(params![since, TOP_N], |row| {
+                Ok(UploaderStat {
+                    uploader: row.get(0)?,
+                    plays: row.get::<_, i64>(1)? as u64,
+                    listened_secs: row.get::<_, i64>(2)?.max(0) as u64,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        // Buckets are 24h slices counted back from `now` (index 0 = oldest),
+        // which sidesteps local-timezone day boundaries.
+        let mut daily_secs = vec![0u64; daily_buckets];
+        if daily_buckets > 0 {
+            let oldest = now - daily_buckets as i64 * 86_400;
+            let mut stmt = self.conn.prepare(
+                "SELECT (?1 - played_at) / 86400 AS days_ago, SUM(listened_secs)
+                 FROM plays WHERE played_at > ?2 AND played_at <= ?1
+                 GROUP BY days_ago",
+            )?;
+            let rows = stmt.query_map(params![now, oldest.max(since)], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?))
+            })?;
+            for row in rows {
+                let (days_ago, secs) = row?;
+                if let Some(slot) = usize::try_from(days_ago)
+                    .ok()
+                    .and_then(|d| daily_buckets.checked_sub(d + 1))
+                {
+                    daily_secs[slot] = secs.max(0) as u64;
+                }
+            }
+        }
+
+        Ok(ListeningStats {
+            plays: plays as u64,
+            skips: skips as u64,
+            total_listened_secs: total.max(0) as u64,
+            top_tracks,
+            top_uploaders,
+            daily_secs,
+        })
+    }
+
+    #[cfg(test)]
+    pub(crate) fn play_count(&self, video_id: &str) -> Result<u64> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM plays WHERE video_id = ?1",
+            [video_id],
+            |row| row.get(0),
+        )?;
+        Ok(count as u64)
+    }
+
+    // -- Favorites and ratings ---------------------------------------------
+
+    /// Store `rating` for `track`, replacing any previous one. An empty
+    /// rating removes the row.
+    pub(crate) fn set_track_rating(&self, track: &Track, rating: TrackRating) -> Result<()> {
+        if rating.is_empty() {
+            self.conn.execute(
+                "DELETE FROM track_ratings WHERE video_id = ?1",
+                [&track.video_id],
+            )?;
+            return Ok(());
+        }
+        self.upsert_track(track)?;
+        self.conn.execute(
+            "INSERT INTO track_ratings (video_id, favorite, stars, updated_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(video_id) DO UPDATE SET
+                 favorite = excluded.favorite,
+                 stars = excluded.stars,
+                 updated_at = excluded.updated_at",
+            params![track.video_id, rating.favorite, rating.stars, now_unix()],
+        )?;
+        Ok(())
+    }
+
+    /// Every stored rating as `video_id → rating`.
+    pub(crate) fn track_ratings(&self) -> Result<HashMap<String, TrackRating>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT video_id, favorite, stars FROM track_ratings")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                TrackRating {
+                    favorite: row.get(1)?,
+                    stars: row.get(2)?,
+                },
+            ))
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Tracks matching `filter`, best rated first, then most recently rated.
+    pub(crate) fn rated_tracks(&self, filter: RatedFilter) -> Result<Vec<Track>> {
+        // 0 selects favorites; anything else is a minimum star count.
+        let min_stars = match filter {
+            RatedFilter::Favorites => 0,
+            RatedFilter::MinStars(min) => min.max(1),
+        };
+        let mut stmt = self.conn.prepare(
+            "SELECT t.video_id, t.title, t.duration, t.uploader, t.url
+             FROM track_ratings r JOIN tracks t ON t.video_id = r.video_id
+             WHERE (?1 = 0 AND r.favorite = 1) OR (?1 > 0 AND r.stars >= ?1)
+             ORDER BY COALESCE(r.stars, 0) DESC, r.updated_at DESC, t.title",
+        )?;
+        let rows = stmt.query_map([i64::from(min_stars)], |row| {
+            Ok(Track::new(
+                row.get(0)?,
+                row.get(1)?,
+                row.get::<_, i64>(2)?.max(0) as u64,
+                row.get(3)?,
+                row.get(4)?,
+            ))
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    // -- Tags ---------------------------------------------------------------
+
+    /// Replace the tags on `track` with `tags` (already normalized).
+    pub(crate) fn set_track_tags(&mut self, track: &Track, tags: &[String]) -> Result<()> {
+        self.transaction(|lib| {
+            lib.upsert_track(track)?;
+            lib.conn
+                .execute("DELETE FROM track_tags WHERE video_id = ?1", [&track.video_id])?;
+            let now = now_unix();
+            for tag in tags {
+                lib.conn.execute(
+                    "INSERT OR IGNORE INTO track_tags (video_id, tag, added_at) VALUES (?1, ?2, ?3)",
+                    params![track.video_id, tag, now],
+                )?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Tags on `video_id`, in the order they were added.
+    pub(crate) fn track_tags(&self, video_id: &str) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT tag FROM track_tags WHERE video_id = ?1 ORDER BY added_at, rowid")?;
+        let rows = stmt.query_map([video_id], |row| row.get(0))?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Every tag with the number of tracks carrying it, most used first.
+    pub(crate) fn tag_counts(&self) -> Result<Vec<(String, u64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tag, COUNT(*) AS n FROM track_tags GROUP BY tag ORDER BY n DESC, tag",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get(0)?, row.get::<_, i64>(1)?.max(0) as u64))
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Tracks matching `query`, ordered by title.
+    pub(crate) fn tracks_with_tags(&self, query: &TagQuery) -> Result<Vec<Track>> {
+        let tags = query.tags();
+        if tags.is_empty() {
+            return Ok(Vec::new());
+        }
+        // All = the track carries every tag; Any = at least one.
+        let required = match query {
+            TagQuery::All(_) => tags.len(),
+            TagQuery::Any(_) => 1,
+        };
+        let placeholders = vec!["?"; tags.len()].join(", ");
+        let sql = format!(
+            "SELECT t.video_id, t.title, t.duration, t.uploader, t.url
+             FROM tracks t
+             JOIN (SELECT video_id FROM track_tags WHERE tag IN ({})
+                   GROUP BY video_id HAVING COUNT(*) >= {}) m
+               ON m.video_id = t.video_id
+             ORDER BY t.title COLLATE NOCASE",
+            placeholders, required
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(rusqlite::params_from_iter(tags), |row| {
+            Ok(Track::new(
+                row.get(0)?,
+                row.get(1)?,
+                row.get::<_, i64>(2)?.max(0) as u64,
+                row.get(3)?,
+                row.get(4)?,
+            ))
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    // -- Cached files -------------------------------------------------------
+
+    /// Record a file in the download cache. `size_bytes` is read from disk.
+    pub(crate) fn record_cached_file(
+        &self,
+        video_id: &str,
+        path: &str,
+        cached_at: i64,
+    ) -> Result<()> {
+        let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        self.conn.execute(
+            "INSERT INTO cached_files (video_id, path, size_bytes, cached_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(video_id) DO UPDATE SET
+                 path = excluded.path,
+                 size_bytes = excluded.size_bytes,
+                 cached_at = excluded.cached_at",
+            params![video_id, path, size as i64, cached_at],
+        )?;
+        Ok(())
+    }
+
+    pub(crate) fn remove_cached_file(&self, video_id: &str) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM cached_files WHERE video_id = ?1", [video_id])?;
+        Ok(())
+    }
+
+    /// All cached files as `video_id → path`.
+    pub(crate) fn cached_files(&self) -> Result<HashMap<String, String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT video_id, path FROM cached_files")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Cached files with size and last use (last play, else caching time),
+    /// for quota eviction.
+    pub(crate) fn cache_entries(&self) -> Result<Vec<CacheEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT video_id, size_bytes, COALESCE(last_played_at, cached_at)
+             FROM cached_files",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(CacheEntry {
+                video_id: row.get(0)?,
+                size_bytes: row.get::<_, i64>(1)?.max(0) as u64,
+                last_used: row.get(2)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Cached tracks whose title or uploader contains every word of `query`
+    /// (case-insensitive), most recently used first. Used to search while
+    /// offline.
+    pub(crate) fn search_cached_tracks(&self, query: &str, limit: usize) -> Result<Vec<Track>> {
+        let words: Vec<String> = query
+            .split_whitespace()
+            .map(|w| {
+                let escaped = w
+                    .replace('\\', "\\\\")
+                    .replace('%', "\\%")
+                    .replace('_', "\\_");
+                format!("%{}%", escaped)
+            })
+            .collect();
+        if words.is_empty() {
+            return Ok(Vec::new());
+        }
+        let conditions =
+            vec!["(t.title LIKE ? ESCAPE '\\' OR t.uploader LIKE ? ESCAPE '\\')"; words.len()]
+                .join(" AND ");
+        let sql = format!(
+            "SELECT t.video_id, t.title, t.duration, t.uploader, t.url
+             FROM cached_files c JOIN tracks t ON t.video_id = c.video_id
+             WHERE {}
+             ORDER BY COALESCE(c.last_played_at, c.cached_at) DESC
+             LIMIT {}",
+            conditions, limit
+        );
+        let params = words.iter().flat_map(|w| [w, w]);
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(rusqlite::params_from_iter(params), |row| {
+            Ok(Track::new(
+                row.get(0)?,
+                row.get(1)?,
+                row.get::<_, i64>(2)?.max(0) as u64,
+                row.get(3)?,
+                row.get(4)?,
+            ))
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Pin or unpin a track. Pinned tracks are never evicted from the cache.
+    pub(crate) fn set_pinned(&self, track: &Track, pinned: bool) -> Result<()> {
+        if pinned {
+            self.upsert_track(track)?;
+            self.conn.execute(
+                "INSERT OR IGNORE INTO pinned_tracks (video_id, pinned_at) VALUES (?1, ?2)",
+                params![track.video_id, now_unix()],
+            )?;
+        } else {
+            self.conn.execute(
+                "DELETE FROM pinned_tracks WHERE video_id = ?1",
+                [&track.video_id],
+            )?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn pinned_ids(&self) -> Result<HashSet<String>> {
+        let mut stmt = self.conn.prepare("SELECT video_id FROM pinned_tracks")?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    // -- Search history -----------------------------------------------------
+
+    /// Remember a submitted search. Repeating a query (in any case) moves
+    /// it to the top instead of adding a second entry.
+    pub(crate) fn record_search(&self, query: &str, searched_at: i64) -> Result<()> {
+        let query = normalize_query(query);
+        if query.is_empty() {
+            return Ok(());
+        }
+        self.conn.execute(
+            "INSERT INTO search_history (query, searched_at) VALUES (?1, ?2)
+             ON CONFLICT(query) DO UPDATE SET
+                 query = excluded.query,
+                 searched_at = excluded.searched_at,
+                 times = search_history.times + 1",
+            params![query, searched_at],
+        )?;
+        Ok(())
+    }
+
+    pub(crate) fn forget_search(&self, query: &str) -> Result<()> {
+        self.conn.execute(
+            "DELETE FROM search_history WHERE query = ?1",
+            [normalize_query(query)],
+        )?;
+        Ok(())
+    }
+
+    /// The most recent searches, newest first.
+    pub(crate) fn recent_searches(&self, limit: usize) -> Result<Vec<SearchSuggestion>> {
+        self.history_matching("", limit)
+    }
+
+    fn history_matching(&self, prefix: &str, limit: usize) -> Result<Vec<SearchSuggestion>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT query, searched_at FROM search_history
+             WHERE query LIKE ?1 ESCAPE '\\'
+             ORDER BY searched_at DESC, rowid DESC LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(params![like_prefix(prefix), limit as i64], |row| {
+            Ok(SearchSuggestion {
+                text: row.get(0)?,
+                source: SuggestionSource::Recent {
+                    searched_at: row.get(1)?,
+                },
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Completions for a search starting with `prefix` (case-insensitive):
+    /// past queries newest first, then titles of played tracks and known
+    /// uploaders, most played first. Nothing is suggested for an empty or
+    /// already complete prefix.
+    pub(crate) fn search_suggestions(
+        &self,
+        prefix: &str,
+        limit: usize,
+    ) -> Result<Vec<SearchSuggestion>> {
+        let prefix = prefix.trim_start();
+        if prefix.is_empty() {
+            return Ok(Vec::new());
+        }
+        let pattern = like_prefix(prefix);
+        let mut found = self.history_matching(prefix, limit)?;
+
+        let mut titles = self.conn.prepare(
+            "SELECT t.title FROM tracks t JOIN plays p ON p.video_id = t.video_id
+             WHERE t.title LIKE ?1 ESCAPE '\\'
+             GROUP BY t.video_id ORDER BY COUNT(*) DESC, MAX(p.played_at) DESC LIMIT ?2",
+        )?;
+        for title in titles.query_map(params![pattern, limit as i64], |row| {
+            row.get::<_, String>(0)
+        })? {
+            found.push(SearchSuggestion {
+                text: title?,
+                source: SuggestionSource::Track,
+            });
+        }
+
+        // YouTube Music's auto-generated channels are "Artist - Topic";
+        // match and suggest the artist name alone.
+        let mut uploaders = self.conn.prepare(
+            "SELECT t.uploader FROM tracks t LEFT JOIN plays p ON p.video_id = t.video_id
+             WHERE t.uploader LIKE ?1 ESCAPE '\\'
+             GROUP BY t.uploader ORDER BY COUNT(p.id) DESC, COUNT(*) DESC LIMIT ?2",
+        )?;
+        for uploader in uploaders.query_map(params![pattern, limit as i64], |row| {
+            row.get::<_, String>(0)
+        })? {
+            let uploader = uploader?;
+            found.push(SearchSuggestion {
+                text: uploader
+                    .strip_suffix(" - Topic")
+                    .unwrap_or(&uploader)
+                    .to_string(),
+                source: SuggestionSource::Uploader,
+            });
+        }
+
+        let typed = prefix.to_lowercase();
+        let mut seen = HashSet::new();
+        found.retain(|s| {
+            let key = s.text.to_lowercase();
+            key != typed && seen.insert(key)
+        });
+        found.truncate(limit);
+        Ok(found)
+    }
+
+    // -- Albums -------------------------------------------------------------
+
+    /// Store `album` and its track order, replacing what was stored for it.
+    pub(crate) fn save_album(&mut self, album: &Album) -> Result<()> {
+        self.transaction(|lib| {
+            lib.conn.execute(
+                "INSERT INTO albums (id, title, artist, year, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT(id) DO UPDATE SET
+                     title = excluded.title,
+                     artist = excluded.artist,
+                     year = excluded.year,
+                     updated_at = excluded.updated_at",
+                params![album.id, album.title, album.artist, album.year, now_unix()],
+            )?;
+            lib.conn
+                .execute("DELETE FROM album_tracks WHERE album_id = ?1", [&album.id])?;
+            for (position, track) in album.tracks.iter().enumerate() {
+                lib.upsert_track(track)?;
+                lib.conn.execute(
+                    "INSERT INTO album_tracks (album_id, position, video_id) VALUES (?1, ?2, ?3)",
+                    params![album.id, position as i64, track.video_id],
+                )?;
+            }
+            Ok(())
+        })
+    }
+
+    /// The album of every track on a stored album, by video id. A track on
+    /// several albums (a single and its album) maps to the latest stored.
+    pub(crate) fn album_refs(&self) -> Result<HashMap<String, AlbumRef>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.video_id, a.id, a.title, a.artist, a.year, t.position
+             FROM album_tracks t JOIN albums a ON a.id = t.album_id
+             ORDER BY a.updated_at, a.rowid",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                AlbumRef {
+                    album_id: row.get(1)?,
+                    title: row.get(2)?,
+                    artist: row.get(3)?,
+                    year: row.get(4)?,
+                    track_no: row.get::<_, i64>(5)?.max(0) as usize + 1,
+                },
+            ))
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    // -- Playlists ----------------------------------------------------------
+
+    /// Replace every stored playlist with `playlists`, keeping the library in
+    /// step with `playlists.json`. Tracks are upserted so they can be joined
+    /// against plays.
+    pub(crate) fn replace_playlists(&mut self, playlists: &[LocalPlaylist]) -> Result<()> {
+        self.transaction(|lib| {
+            lib.conn.execute("DELETE FROM playlists", [])?;
+            for playlist in playlists {
+                lib.conn.execute(
+                    "INSERT INTO playlists (name, created_at, updated_at) VALUES (?1, ?2, ?3)",
+                    params![playlist.name, playlist.created_at, playlist.updated_at],
+                )?;
+                let playlist_id = lib.conn.last_insert_rowid();
+                for (position, track) in playlist.tracks.iter().enumerate() {
+                    lib.upsert_track(track)?;
+                    lib.conn.execute(
+                        "INSERT INTO playlist_tracks (playlist_id, position, video_id)
+                         VALUES (?1, ?2, ?3)",
+                        params![playlist_id, position as i64, track.video_id],
+                    )?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Playlist names with their track ids in order.
+    #[cfg(test)]
+    pub(crate) fn playlists(&self) -> Result<Vec<(String, Vec<String>)>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, name FROM playlists ORDER BY id")?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        let mut tracks = self.conn.prepare(
+            "SELECT video_id FROM playlist_tracks WHERE playlist_id = ?1 ORDER BY position",
+        )?;
+        let mut out = Vec::new();
+        for (id, name) in rows {
+            let ids = tracks
+                .query_map([id], |row| row.get(0))?
+                .collect::<rusqlite::Result<Vec<String>>>()?;
+            out.push((name, ids));
+        }
+        Ok(out)
+    }
+
+    // -- Offline jobs -------------------------------------------------------
+
+    /// Store `job`, replacing a stored job with the same id.
+    pub(crate) fn save_offline_job(&mut self, job: &OfflineJob) -> Result<()> {
+        self.transaction(|lib| {
+            lib.conn.execute(
+                "INSERT INTO offline_jobs (id, title, created_at, completed_at)
+                 VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT(id) DO UPDATE SET
+                     title = excluded.title,
+                     completed_at = excluded.completed_at",
+                params![job.id, job.title, job.created_at, job.completed_at],
+            )?;
+            lib.conn.execute(
+                "DELETE FROM offline_job_tracks WHERE job_id = ?1",
+                [&job.id],
+            )?;
+            for (position, track) in job.tracks.iter().enumerate() {
+                lib.upsert_track(track)?;
+                lib.conn.execute(
+                    "INSERT INTO offline_job_tracks (job_id, position, video_id)
+                     VALUES (?1, ?2, ?3)",
+                    params![job.id, position as i64, track.video_id],
+                )?;
+            }
+            Ok(())
+        })
+    }
+
+    pub(crate) fn set_offline_job_completed(
+        &self,
+        id: &str,
+        completed_at: Option<i64>,
+    ) -> Result<()> {
+        self.conn.execute(
+            "UPDATE offline_jobs SET completed_at = ?2 WHERE id = ?1",
+            params![id, completed_at],
+        )?;
+        Ok(())
+    }
+
+    pub(crate) fn delete_offline_job(&self, id: &str) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM offline_jobs WHERE id = ?1", [id])?;
+        Ok(())
+    }
+
+    /// Every stored job with its tracks, oldest first.
+    pub(crate) fn offline_jobs(&self) -> Result<Vec<OfflineJob>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, title, created_at, completed_at FROM offline_jobs ORDER BY created_at, id",
+        )?;
+        let mut jobs = stmt
+            .query_map([], |row| {
+                Ok(OfflineJob {
+                    id: row.get(0)?,
+                    title: row.get(1)?,
+                    tracks: Vec::new(),
+                    created_at: row.get(2)?,
+                    completed_at: row.get(3)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        let mut tracks = self.conn.prepare(
+            "SELECT t.video_id, t.title, t.duration, t.uploader, t.url
+             FROM offline_job_tracks j JOIN tracks t ON t.video_id = j.video_id
+             WHERE j.job_id = ?1 ORDER BY j.position",
+        )?;
+        for job in &mut jobs {
+            job.tracks = tracks
+                .query_map([&job.id], |row| {
+                    Ok(Track::new(
+                        row.get(0)?,
+                        row.get(1)?,
+                        row.get::<_, i64>(2)?.max(0) as u64,
+                        row.get(3)?,
+                        row.get(4)?,
+                    ))
+                })?
+                .collect::<rusqlite::Result<_>>()?;
+        }
+        Ok(jobs)
+    }
+
+    /// Run `f` inside a transaction, committing only if it succeeds.
+    pub(crate) fn transaction<T>(&mut self, f: impl FnOnce(&Library) -> Result<T>) -> Result<T> {
+        self.conn.execute_batch("BEGIN")?;
+        match f(self) {
+            Ok(value) => {
+                self.conn.execute_batch("COMMIT")?;
+                Ok(value)
+            }
+            Err(e) => {
+                let _ = self.conn.execute_batch("ROLLBACK");
+                Err(e)
+            }
+        }
+    }
+}
+
+/// Apply every migration newer than the database's `user_version`, each in
+/// its own transaction. Refuses to open a database from a newer build.
+fn migrate(conn: &mut Connection) -> Result<()> {
+    let current: u32 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    let latest = MIGRATIONS.last().map_or(0, |m| m.version);
+    if current > latest {
+        anyhow::bail!(
+            "Library database schema v{} is newer than this build supports (v{})",
+            current,
+            latest
+        );
+    }
+
+    for migration in MIGRATIONS.iter().filter(|m| m.version > current) {
+        let tx = conn.transaction()?;
+        tx.execute_batch(migration.sql).with_context(|| {
+            format!(
+                "Library migration v{} ({}) failed",
+                migration.version, migration.description
+            )
+        })?;
+        tx.pragma_update(None, "user_version", migration.version)?;
+        tx.commit()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn make_track(id: &str, duration: u64) -> Track {
+        Track::new(
+            id.to_string(),
+            format!("Song {id}"),
+            duration,
+            "Artist".to_string(),
+            format!("https://www.youtube.com/watch?v={id}"),
+        )
+    }
+
+    #[test]
+    fn migrations_are_strictly_increasing() {
+        for pair in MIGRATIONS.windows(2) {
+            assert!(pair[0].version < pair[1].version);
+        }
+    }
+
+    #[test]
+    fn open_applies_all_migrations() {
+        let lib = Library::open_in_memory().unwrap();
+        assert_eq!(
+            lib.schema_version().unwrap(),
+            MIGRATIONS.last().unwrap().version
+        );
+    }
+
+    #[test]
+    fn reopening_is_idempotent() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("library.db");
+        {
+            let lib = Library::open(&path).unwrap();
+            lib.upsert_track(&make_track("a", 100)).unwrap();
+        }
+        let lib = Library::open(&path).unwrap();
+        assert!(lib.get_track("a").unwrap().is_some());
+    }
+
+    #[test]
+    fn refuses_newer_schema() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("library.db");
+        {
+            let conn = Connection::open(&path).unwrap();
+            conn.pragma_update(None, "user_version", 9999).unwrap();
+        }
+        let err = Library::open(&path).err().unwrap();
+        assert!(err.to_string().contains("newer"));
+    }
+
+    #[test]
+    fn upsert_keeps_known_duration() {
+        let lib = Library::open_in_memory().unwrap();
+        lib.upsert_track(&make_track("a", 215)).unwrap();
+        lib.upsert_track(&make_track("a", 0)).unwrap();
+        assert_eq!(lib.get_track("a").unwrap().unwrap().duration, 215);
+    }
+
+    #[test]
+    fn record_play_counts_and_touches_cache() {
+        let lib = Library::open_in_memory().unwrap();
+        lib.record_cached_file("a", "/nonexistent/a.mp3", 10)
+            .unwrap();
+        lib.record_play(&make_track("a", 100), 50).unwrap();
+        lib.record_play(&make_track("a", 100), 60).unwrap();
+
+        assert_eq!(lib.play_count("a").unwrap(), 2);
+        let last: Option<i64> = lib
+            .conn
+            .query_row(
+                "SELECT last_played_at FROM cached_files WHERE video_id = 'a'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(last, Some(60));
+    }
+
+    fn make_track_by(id: &str, uploader: &str, duration: u64) -> Track {
+        Track::new(
+            id.to_string(),
+            format!("Song {id}"),
+            duration,
+            uploader.to_string(),
+            format!("https://www.youtube.com/watch?v={id}"),
+        )
+    }
+
+    #[test]
+    fn listening_stats_aggregates_window() {
+        let lib = Library::open_in_memory().unwrap();
+        let now = 100 * 86_400;
+        let a = make_track_by("a", "Alpha", 200);
+        let b = make_track_by("b", "Beta", 300);
+
+        for offset in [10, 20, 86_400 + 5] {
+            let id = lib.record_play(&a, now - offset).unwrap();
+            lib.finish_play(id, 200, false).unwrap();
+        }
+        let id = lib.record_play(&b, now - 30).unwrap();
+        lib.finish_play(id, 15, true).unwrap();
+        // Outside a 7-day window.
+        let id = lib.record_play(&b, now - 10 * 86_400).unwrap();
+        lib.finish_play(id, 300, false).unwrap();
+
+        let week = lib.listening_stats(Some(now - 7 * 86_400), now, 7).unwrap();
+        assert_eq!(week.plays, 4);
+        assert_eq!(week.skips, 1);
+        assert_eq!(week.total_listened_secs, 615);
+        assert_eq!(week.top_tracks[0].title, "Song a");
+        assert_eq!(week.top_tracks[0].plays, 3);
+        assert_eq!(week.top_uploaders[0].uploader, "Alpha");
+        assert_eq!(week.daily_secs, vec![0, 0, 0, 0, 0, 200, 415]);
+        assert!((week.skip_rate() - 0.25).abs() < f64::EPSILON);
+
+        let all = lib.listening_stats(None, now, 30).unwrap();
+        assert_eq!(all.plays, 5);
+        assert_eq!(all.top_uploaders.len(), 2);
+    }
+
+    #[test]
+    fn listening_stats_empty_library() {
+        let lib = Library::open_in_memory().unwrap();
+        let stats = lib.listening_stats(None, 1_000_000, 7).unwrap();
+        assert_eq!(stats.plays, 0);
+        assert_eq!(stats.skip_rate(), 0.0);
+        assert_eq!(stats.daily_secs, vec![0; 7]);
+    }
+
+    #[test]
+    fn v2_migration_backfills_listened_time() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        let tx = conn.transaction().unwrap();
+        tx.execute_batch(MIGRATIONS[0].sql).unwrap();
+        tx.pragma_update(None, "user_version", 1).unwrap();
+        tx.execute_batch(
+            "INSERT INTO tracks VALUES ('a', 'Song', 'X', 180, 'u', 0, 0);
+             INSERT INTO plays (video_id, played_at) VALUES ('a', 5);",
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        let lib = Library::from_connection(conn).unwrap();
+        let stats = lib.listening_stats(None, 10, 0).unwrap();
+        assert_eq!(stats.total_listened_secs, 180);
+    }
+
+    #[test]
+    fn cached_files_round_trip() {
+        let lib = Library::open_in_memory().unwrap();
+        lib.record_cached_file("a", "/x/a.mp3", 1).unwrap();
+        lib.record_cached_file("b", "/x/b.mp3", 1).unwrap();
+        lib.remove_cached_file("a").unwrap();
+
+        let files = lib.cached_files().unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files["b"], "/x/b.mp3");
+    }
+
+    #[test]
+    fn search_cached_tracks_matches_every_word() {
+        let lib = Library::open_in_memory().unwrap();
+        for (id, uploader, cached_at) in [
+            ("a", "Daft Punk", 1),
+            ("b", "Punk 100%", 2),
+            ("c", "Daft Punk", 3),
+        ] {
+            lib.upsert_track(&make_track_by(id, uploader, 100)).unwrap();
+            if id != "c" {
+                lib.record_cached_file(id, "/nonexistent/x.mp3", cached_at)
+                    .unwrap();
+            }
+        }
+
+        let ids = |q: &str| -> Vec<String> {
+            lib.search_cached_tracks(q, 10)
+                .unwrap()
+                .into_iter()
+                .map(|t| t.video_id)
+                .collect()
+        };
+        // Uncached "c" never shows up; most recently cached first.
+        assert_eq!(ids("punk"), ["b", "a"]);
+        assert_eq!(ids("daft SONG"), ["a"]);
+        assert_eq!(ids("100%"), ["b"]);
+        assert!(ids("k%").is_empty());
+        assert!(ids("  ").is_empty());
+    }
+
+    #[test]
+    fn offline_jobs_round_trip_and_replace() {
+        let mut lib = Library::open_in_memory().unwrap();
+        let tracks = vec![make_track("a", 100), make_track("b", 200)];
+        let mut job = OfflineJob::new("PL1".to_string(), "Mix".to_string(), tracks, 10);
+        lib.save_offline_job(&job).unwrap();
+        assert_eq!(lib.offline_jobs().unwrap(), vec![job.clone()]);
+
+        // Saving again replaces the track list but keeps the creation time.
+        job.tracks.reverse();
+        job.tracks.pop();
+        job.created_at = 99;
+        lib.save_offline_job(&job).unwrap();
+        lib.set_offline_job_completed("PL1", Some(50)).unwrap();
+        let stored = lib.offline_jobs().unwrap();
+        assert_eq!(stored[0].created_at, 10);
+        assert_eq!(stored[0].completed_at, Some(50));
+        let ids: Vec<_> = stored[0]
+            .tracks
+            .iter()
+            .map(|t| t.video_id.as_str())
+            .collect();
+        assert_eq!(ids, ["b"]);
+
+        lib.delete_offline_job("PL1").unwrap();
+        assert!(lib.offline_jobs().unwrap().is_empty());
+    }
+
+    #[test]
+    fn transaction_rolls_back_on_error() {
+        let mut lib = Library::open_in_memory().unwrap();
+        let result: Result<()> = lib.transaction(|lib| {
+            lib.upsert_track(&make_track("a", 100))?;
+            anyhow::bail!("boom")
+        });
+        assert!(result.is_err());
+        assert!(lib.get_track("a").unwrap().is_none());
+    }
+
+    #[test]
+    fn search_history_dedupes_and_suggests() {
+        let lib = Library::open_in_memory().unwrap();
+        lib.record_search("daft  punk", 100).unwrap();
+        lib.record_search("Aphex Twin", 200).unwrap();
+        lib.record_search("Daft Punk", 300).unwrap();
+        lib.record_search("   ", 400).unwrap();
+
+        let recent = lib.recent_searches(10).unwrap();
+        let texts: Vec<_> = recent.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, ["Daft Punk", "Aphex Twin"]);
+        assert_eq!(
+            recent[0].source,
+            SuggestionSource::Recent { searched_at: 300 }
+        );
+
+        lib.record_play(&make_track_by("a", "Daft Punk - Topic", 200), 10)
+            .unwrap();
+        let mut track = make_track_by("b", "Dave", 200);
+        track.title = "Da Funk".to_string();
+        lib.record_play(&track, 20).unwrap();
+        // Known but never played: its uploader counts, its title doesn't.
+        let mut unplayed = make_track_by("c", "Daniel", 200);
+        unplayed.title = "Dance".to_string();
+        lib.upsert_track(&unplayed).unwrap();
+
+        let suggestions = lib.search_suggestions("da", 10).unwrap();
+        let got: Vec<_> = suggestions
+            .iter()
+            .map(|s| (s.text.as_str(), s.source))
+            .collect();
+        assert_eq!(
+            got,
+            [
+                ("Daft Punk", SuggestionSource::Recent { searched_at: 300 }),
+                ("Da Funk", SuggestionSource::Track),
+                ("Dave", SuggestionSource::Uploader),
+                ("Daniel", SuggestionSource::Uploader),
+            ]
+        );
+        // The typed text itself and wildcard characters suggest nothing new.
+        assert!(lib.search_suggestions("daft punk", 10).unwrap().is_empty());
+        assert!(lib.search_suggestions("%", 10).unwrap().is_empty());
+        assert!(lib.search_suggestions("", 10).unwrap().is_empty());
+
+        lib.forget_search("daft punk").unwrap();
+        assert_eq!(lib.recent_searches(10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn albums_map_tracks_to_their_latest_album() {
+        let mut lib = Library::open_in_memory().unwrap();
+        let mut album = Album {
+            id: "OLAK5uy_single".to_string(),
+            title: "Blinding Lights".to_string(),
+            artist: "The Weeknd".to_string(),
+            year: None,
+            url: String::new(),
+            tracks: vec![make_track("b", 200)],
+        };
+        lib.save_album(&album).unwrap();
+
+        album.id = "OLAK5uy_album".to_string();
+        album.title = "After Hours".to_string();
+        album.year = Some(2020);
+        album.tracks = vec![make_track("a", 250), make_track("b", 200)];
+        lib.save_album(&album).unwrap();
+        album.tracks.pop();
+        lib.save_album(&album).unwrap();
+
+        let refs = lib.album_refs().unwrap();
+        assert_eq!(refs["a"].track_no, 1);
+        assert_eq!(refs["a"].year, Some(2020));
+        // Dropped from the album again, so only the single claims it.
+        assert_eq!(refs["b"].album_id, "OLAK5uy_single");
+        assert!(lib.get_track("b").unwrap().is_some());
+    }
+
+    #[test]
+    fn meta_round_trip() {
+        let lib = Library::open_in_memory().unwrap();
+        assert_eq!(lib.get_meta("k").unwrap(), None);
+        lib.set_meta("k", "v1").unwrap();
+        lib.set_meta("k", "v2").unwrap();
+        assert_eq!(lib.get_meta("k").unwrap().as_deref(), Some("v2"));
+    }
+
+    #[test]
+    fn replace_playlists_mirrors_order_and_drops_removed() {
+        use crate::services::local_playlists::LocalPlaylists;
+
+        let mut lib = Library::open_in_memory().unwrap();
+        let mut store = LocalPlaylists::default();
+        store.create("A").unwrap();
+        store.create("B").unwrap();
+        store.add_track(0, make_track("x", 100)).unwrap();
+        store.add_track(0, make_track("y", 100)).unwrap();
+        lib.replace_playlists(&store.playlists).unwrap();
+        assert_eq!(
+            lib.playlists().unwrap(),
+            vec![
+                ("A".to_string(), vec!["x".to_string(), "y".to_string()]),
+                ("B".to_string(), vec![]),
+            ]
+        );
+
+        store.move_track(0, 0, true).unwrap();
+        store.delete(1);
+        lib.replace_playlists(&store.playlists).unwrap();
+        assert_eq!(
+            lib.playlists().unwrap(),
+            vec![("A".to_string(), vec!["y".to_string(), "x".to_string()])]
+        );
+    }
+
+    #[test]
+    fn ratings_round_trip_and_clear() {
+        let lib = Library::open_in_memory().unwrap();
+        let track = make_track("a", 100);
+        let rating = TrackRating {
+            favorite: true,
+            stars: Some(4),
+        };
+        lib.set_track_rating(&track, rating).unwrap();
+        assert_eq!(lib.track_ratings().unwrap().get("a"), Some(&rating));
+        assert!(lib.get_track("a").unwrap().is_some());
+
+        lib.set_track_rating(&track, TrackRating::default())
+            .unwrap();
+        assert!(lib.track_ratings().unwrap().is_empty());
+    }
+
+    #[test]
+    fn rated_tracks_filters_and_orders_by_stars() {
+        let lib = Library::open_in_memory().unwrap();
+        let rate = |id: &str, favorite: bool, stars: Option<u8>| {
+            lib.set_track_rating(&make_track(id, 60), TrackRating { favorite, stars })
+                .unwrap();
+        };
+        rate("three", false, Some(3));
+        rate("fav", true, None);
+        rate("five", true, Some(5));
+
+        let ids = |filter| -> Vec<String> {
+            lib.rated_tracks(filter)
+                .unwrap()
+                .into_iter()
+                .map(|t| t.video_id)
+                .collect()
+        };
+        assert_eq!(ids(RatedFilter::Favorites), ["five", "fav"]);
+        assert_eq!(ids(RatedFilter::MinStars(3)), ["five", "three"]);
+        assert_eq!(ids(RatedFilter::MinStars(5)), ["five"]);
+    }
+
+    #[test]
+    fn stars_label_pads_to_five() {
+        let rating = TrackRating {
+            favorite: false,
+            stars: Some(2),
+        };
+        assert_eq!(rating.stars_label(), "★★☆☆☆");
+        assert_eq!(TrackRating::default().stars_label(), "");
+    }
+
+    #[test]
+    fn tags_replace_and_count() {
+        let mut lib = Library::open_in_memory().unwrap();
+        let tag = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        lib.set_track_tags(&make_track("a", 60), &tag(&["coding", "chill"]))
+            .unwrap();
+        lib.set_track_tags(&make_track("b", 60), &tag(&["coding"]))
+            .unwrap();
+        assert_eq!(lib.track_tags("a").unwrap(), ["coding", "chill"]);
+        assert_eq!(
+            lib.tag_counts().unwrap(),
+            vec![("coding".to_string(), 2), ("chill".to_string(), 1)]
+        );
+
+        lib.set_track_tags(&make_track("a", 60), &tag(&["gym"]))
+            .unwrap();
+        assert_eq!(lib.track_tags("a").unwrap(), ["gym"]);
+        lib.set_track_tags(&make_track("b", 60), &[]).unwrap();
+        assert_eq!(lib.tag_counts().unwrap(), vec![("gym".to_string(), 1)]);
+    }
+
+    #[test]
+    fn tracks_with_tags_and_or() {
+        let mut lib = Library::open_in_memory().unwrap();
+        let tag = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        lib.set_track_tags(&make_track("a", 60), &tag(&["coding", "chill"]))
+            .unwrap();
+        lib.set_track_tags(&make_track("b", 60), &tag(&["coding"]))
+            .unwrap();
+        lib.set_track_tags(&make_track("c", 60), &tag(&["gym"]))
+            .unwrap();
+
+        let ids = |query: TagQuery| -> Vec<String> {
+            lib.tracks_with_tags(&query)
+                .unwrap()
+                .into_iter()
+                .map(|t| t.video_id)
+                .collect()
+        };
+        assert_eq!(ids(TagQuery::parse("coding & chill").unwrap()), ["a"]);
+        assert_eq!(ids(TagQuery::parse("chill | gym").unwrap()), ["a", "c"]);
+        assert_eq!(ids(TagQuery::parse("coding").unwrap()), ["a", "b"]);
+        assert!(ids(TagQuery::parse("nope").unwrap()).is_empty());
+    }
+
+    #[test]
+    fn tags_survive_cache_removal() {
+        let mut lib = Library::open_in_memory().unwrap();
+        lib.set_track_tags(&make_track("a", 60), &["gym".to_string()])
+            .unwrap();
+        lib.record_cached_file("a", "/nonexistent/a.mp3", 1)
+            .unwrap();
+        lib.remove_cached_file("a").unwrap();
+        assert_eq!(lib.track_tags("a").unwrap(), ["gym"]);
+    }
+
+    #[test]
+    fn pins_round_trip_and_cache_entries_use_last_play() {
+        let lib = Library::open_in_memory().unwrap();
+        let a = make_track("a", 100);
+        lib.set_pinned(&a, true).unwrap();
+        lib.set_pinned(&a, true).unwrap();
+        assert_eq!(lib.pinned_ids().unwrap(), HashSet::from(["a".to_string()]));
+        lib.set_pinned(&a, false).unwrap();
+        assert!(lib.pinned_ids().unwrap().is_empty());
+
+        lib.record_cached_file("a", "/nonexistent/a.mp3", 10)
+            .unwrap();
+        lib.record_cached_file("b", "/nonexistent/b.mp3", 20)
+            .unwrap();
+        lib.record_play(&a, 30).unwrap();
+        let mut entries = lib.cache_entries().unwrap();
+        entries.sort_by(|x, y| x.video_id.cmp(&y.video_id));
+        let used: Vec<(&str, i64)> = entries
+            .iter()
+            .map(|e| (e.video_id.as_str(), e.last_used))
+            .collect();
+        assert_eq!(used, [("a", 30), ("b", 20)]);
+    }
+}
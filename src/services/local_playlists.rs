@@ -0,0 +1,349 @@
+//! User-created playlists stored locally in `playlists.json`.
+//!
+//! Unlike remote playlists (loaded by URL or from the feed) these are owned
+//! by the user: they can be created, renamed, deleted, and edited track by
+//! track. The store is plain data — persistence lives in
+//! [`PersistenceService`](super::persistence::PersistenceService).
+
+use serde::{Deserialize, Serialize};
+
+use crate::player::queue::Track;
+use crate::services::library::now_unix;
+
+/// Maximum length of a playlist name, in characters.
+pub(crate) const MAX_PLAYLIST_NAME_LEN: usize = 100;
+
+/// Why a playlist operation was refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PlaylistError {
+    EmptyName,
+    NameTooLong,
+    DuplicateName(String),
+    NotFound,
+}
+
+impl std::fmt::Display for PlaylistError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlaylistError::EmptyName => write!(f, "name cannot be empty"),
+            PlaylistError::NameTooLong => {
+                write!(
+                    f,
+                    "name is longer than {} characters",
+                    MAX_PLAYLIST_NAME_LEN
+                )
+            }
+            PlaylistError::DuplicateName(name) => {
+                write!(f, "a playlist named '{}' already exists", name)
+            }
+            PlaylistError::NotFound => write!(f, "no such playlist"),
+        }
+    }
+}
+
+/// A named, ordered list of tracks. A track appears at most once.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct LocalPlaylist {
+    pub name: String,
+    #[serde(default)]
+    pub tracks: Vec<Track>,
+    /// Unix seconds.
+    #[serde(default)]
+    pub created_at: i64,
+    /// Unix seconds; bumped on every edit.
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+impl LocalPlaylist {
+    pub(crate) fn contains(&self, video_id: &str) -> bool {
+        self.tracks.iter().any(|t| t.video_id == video_id)
+    }
+
+    /// Total duration of tracks with a known length, in seconds.
+    pub(crate) fn total_duration(&self) -> u64 {
+        self.tracks.iter().map(|t| t.duration).sum()
+    }
+
+    fn touch(&mut self) {
+        self.updated_at = now_unix();
+    }
+}
+
+/// All local playlists, in display order.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct LocalPlaylists {
+    #[serde(default)]
+    pub playlists: Vec<LocalPlaylist>,
+}
+
+impl LocalPlaylists {
+    pub(crate) fn len(&self) -> usize {
+        self.playlists.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.playlists.is_empty()
+    }
+
+    pub(crate) fn get(&self, index: usize) -> Option<&LocalPlaylist> {
+        self.playlists.get(index)
+    }
+
+    /// Trim and validate a name. Names are unique case-insensitively;
+    /// `except` is the index being renamed, which may keep its own name.
+    fn validate_name(&self, name: &str, except: Option<usize>) -> Result<String, PlaylistError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(PlaylistError::EmptyName);
+        }
+        if name.chars().count() > MAX_PLAYLIST_NAME_LEN {
+            return Err(PlaylistError::NameTooLong);
+        }
+        let taken = self
+            .playlists
+            .iter()
+            .enumerate()
+            .any(|(i, p)| Some(i) != except && p.name.eq_ignore_ascii_case(name));
+        if taken {
+            return Err(PlaylistError::DuplicateName(name.to_string()));
+        }
+        Ok(name.to_string())
+    }
+
+    /// `base`, or `base (2)`, `base (3)`, … — the first name not yet taken.
+    pub(crate) fn unique_name(&self, base: &str) -> String {
+        let base: String = base
+            .trim()
+            .chars()
+            .take(MAX_PLAYLIST_NAME_LEN - 6)
+            .collect();
+        let base = if base.is_empty() {
+            "Imported".to_string()
+        } else {
+            base
+        };
+        let taken = |name: &str| {
+            self.playlists
+                .iter()
+                .any(|p| p.name.eq_ignore_ascii_case(name))
+        };
+        if !taken(&base) {
+            return base;
+        }
+        (2..)
+            .map(|n| format!("{} ({})", base, n))
+            .find(|name| !taken(name))
+            .unwrap_or(base)
+    }
+
+    /// Create an empty playlist at the end of the list. Returns its index.
+    pub(crate) fn create(&mut self, name: &str) -> Result<usize, PlaylistError> {
+        let name = self.validate_name(name, None)?;
+        let now = now_unix();
+        self.playlists.push(LocalPlaylist {
+            name,
+            tracks: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        });
+        Ok(self.playlists.len() - 1)
+    }
+
+    pub(crate) fn rename(&mut self, index: usize, name: &str) -> Result<(), PlaylistError> {
+        if index >= self.playlists.len() {
+            return Err(PlaylistError::NotFound);
+        }
+        let name = self.validate_name(name, Some(index))?;
+        let playlist = &mut self.playlists[index];
+        playlist.name = name;
+        playlist.touch();
+        Ok(())
+    }
+
+    pub(crate) fn delete(&mut self, index: usize) -> Option<LocalPlaylist> {
+        (index < self.playlists.len()).then(|| self.playlists.remove(index))
+    }
+
+    /// Append `track` unless it's already in the playlist. Returns `true` if added.
+    pub(crate) fn add_track(&mut self, index: usize, track: Track) -> Result<bool, PlaylistError> {
+        let playlist = self
+            .playlists
+            .get_mut(index)
+            .ok_or(PlaylistError::NotFound)?;
+        if playlist.contains(&track.video_id) {
+            return Ok(false);
+        }
+        playlist.tracks.push(Track {
+            local_file: None,
+            ..track
+        });
+        playlist.touch();
+        Ok(true)
+    }
+
+    pub(crate) fn remove_track(&mut self, index: usize, position: usize) -> Option<Track> {
+        let playlist = self.playlists.get_mut(index)?;
+        if position >= playlist.tracks.len() {
+            return None;
+        }
+        let track = playlist.tracks.remove(position);
+        playlist.touch();
+        Some(track)
+    }
+
+    /// Remove the track with `video_id`, if present.
+    pub(crate) fn remove_video(&mut self, index: usize, video_id: &str) -> Option<Track> {
+        let position = self
+            .get(index)?
+            .tracks
+            .iter()
+            .position(|t| t.video_id == video_id)?;
+        self.remove_track(index, position)
+    }
+
+    /// Swap the track at `position` with its neighbour (`down` = towards the
+    /// end). Returns the track's new position, or `None` at the boundary.
+    pub(crate) fn move_track(
+        &mut self,
+        index: usize,
+        position: usize,
+        down: bool,
+    ) -> Option<usize> {
+        let playlist = self.playlists.get_mut(index)?;
+        let target = if down {
+            position
+                .checked_add(1)
+                .filter(|&t| t < playlist.tracks.len())?
+        } else {
+            position.checked_sub(1)?
+        };
+        if position >= playlist.tracks.len() {
+            return None;
+        }
+        playlist.tracks.swap(position, target);
+        playlist.touch();
+        Some(target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_track(id: &str) -> Track {
+        Track::new(
+            id.to_string(),
+            format!("Track {id}"),
+            120,
+            "Uploader".to_string(),
+            format!("https://www.youtube.com/watch?v={id}"),
+        )
+    }
+
+    fn store_with_tracks(ids: &[&str]) -> LocalPlaylists {
+        let mut store = LocalPlaylists::default();
+        store.create("Mix").unwrap();
+        for id in ids {
+            store.add_track(0, make_track(id)).unwrap();
+        }
+        store
+    }
+
+    fn ids(store: &LocalPlaylists) -> Vec<&str> {
+        store.playlists[0]
+            .tracks
+            .iter()
+            .map(|t| t.video_id.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn create_trims_and_rejects_bad_names() {
+        let mut store = LocalPlaylists::default();
+        assert_eq!(store.create("  Road trip "), Ok(0));
+        assert_eq!(store.playlists[0].name, "Road trip");
+        assert_eq!(store.create("   "), Err(PlaylistError::EmptyName));
+        assert_eq!(
+            store.create("road TRIP"),
+            Err(PlaylistError::DuplicateName("road TRIP".to_string()))
+        );
+        let long = "x".repeat(MAX_PLAYLIST_NAME_LEN + 1);
+        assert_eq!(store.create(&long), Err(PlaylistError::NameTooLong));
+    }
+
+    #[test]
+    fn rename_allows_keeping_own_name() {
+        let mut store = LocalPlaylists::default();
+        store.create("A").unwrap();
+        store.create("B").unwrap();
+        assert_eq!(store.rename(0, "a"), Ok(()));
+        assert_eq!(store.playlists[0].name, "a");
+        assert!(matches!(
+            store.rename(0, "B"),
+            Err(PlaylistError::DuplicateName(_))
+        ));
+        assert_eq!(store.rename(5, "C"), Err(PlaylistError::NotFound));
+    }
+
+    #[test]
+    fn delete_removes_playlist() {
+        let mut store = store_with_tracks(&["a"]);
+        assert_eq!(store.delete(0).map(|p| p.name), Some("Mix".to_string()));
+        assert!(store.is_empty());
+        assert!(store.delete(0).is_none());
+    }
+
+    #[test]
+    fn add_track_skips_duplicates_and_strips_local_file() {
+        let mut store = store_with_tracks(&[]);
+        let mut track = make_track("a");
+        track.local_file = Some("/tmp/a.mp3".to_string());
+        assert_eq!(store.add_track(0, track.clone()), Ok(true));
+        assert_eq!(store.add_track(0, track), Ok(false));
+        assert_eq!(store.playlists[0].tracks.len(), 1);
+        assert!(store.playlists[0].tracks[0].local_file.is_none());
+        assert_eq!(
+            store.add_track(3, make_track("b")),
+            Err(PlaylistError::NotFound)
+        );
+    }
+
+    #[test]
+    fn remove_track_by_position_and_video_id() {
+        let mut store = store_with_tracks(&["a", "b", "c"]);
+        assert_eq!(
+            store.remove_track(0, 1).map(|t| t.video_id),
+            Some("b".to_string())
+        );
+        assert_eq!(
+            store.remove_video(0, "c").map(|t| t.video_id),
+            Some("c".to_string())
+        );
+        assert!(store.remove_video(0, "zzz").is_none());
+        assert!(store.remove_track(0, 9).is_none());
+        assert_eq!(ids(&store), ["a"]);
+    }
+
+    #[test]
+    fn move_track_swaps_neighbours_and_stops_at_edges() {
+        let mut store = store_with_tracks(&["a", "b", "c"]);
+        assert_eq!(store.move_track(0, 0, true), Some(1));
+        assert_eq!(ids(&store), ["b", "a", "c"]);
+        assert_eq!(store.move_track(0, 2, false), Some(1));
+        assert_eq!(ids(&store), ["b", "c", "a"]);
+        assert_eq!(store.move_track(0, 0, false), None);
+        assert_eq!(store.move_track(0, 2, true), None);
+        assert_eq!(ids(&store), ["b", "c", "a"]);
+    }
+
+    #[test]
+    fn unique_name_appends_counter() {
+        let mut store = LocalPlaylists::default();
+        assert_eq!(store.unique_name("  Mix "), "Mix");
+        store.create("Mix").unwrap();
+        store.create("Mix (2)").unwrap();
+        assert_eq!(store.unique_name("mix"), "mix (3)");
+        assert_eq!(store.unique_name(""), "Imported");
+    }
+}
@@ -0,0 +1,541 @@
+//! Lyrics for the playing track.
+//!
+//! An `.lrc` file dropped next to a track's cached audio (`<video_id>.lrc`,
+//! or the audio file's name with `.lrc`) wins; otherwise the video's
+//! YouTube captions are used — uploaded subtitles first, auto-generated
+//! ones if there are none. Timed lines follow playback; plain text is
+//! scrolled by hand. Captions are cached per video id so they are there
+//! offline too, and so is their absence, for a while. The cache directory
+//! is trimmed to a size limit by dropping the lyrics shown least recently.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{LYRICS_CACHE_MAX_BYTES, LYRICS_MISS_TTL_SECS};
+use crate::services::audio_cache::AudioCache;
+use crate::services::cache_store::{mark_used, trim_dir, CacheStore};
+use crate::youtube::extractor::is_valid_video_id;
+use crate::youtube::ytdlp::{YtDlp, YtDlpArgs};
+
+/// One line of lyrics.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct LyricLine {
+    /// Seconds into the track; 0 for unsynced lyrics.
+    pub start: f64,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Lyrics {
+    pub lines: Vec<LyricLine>,
+    /// `true` if the lines are timed and can follow playback.
+    pub synced: bool,
+    /// Where the lyrics came from ("LRC file", "YouTube captions").
+    pub source: String,
+}
+
+impl Lyrics {
+    pub(crate) const CACHE_SCHEMA_VERSION: u32 = 1;
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Index of the line being sung `pos` seconds in, or `None` before the
+    /// first line (and always for unsynced lyrics).
+    pub(crate) fn line_at(&self, pos: f64) -> Option<usize> {
+        if !self.synced {
+            return None;
+        }
+        self.lines
+            .partition_point(|line| line.start <= pos)
+            .checked_sub(1)
+    }
+}
+
+/// Lyrics for `video_id`: a local `.lrc` file, the cache in `cache_dir`,
+/// or — unless `offline` — the video's captions, which are then cached.
+pub(crate) fn load_lyrics(
+    ytdlp: &dyn YtDlp,
+    video_id: &str,
+    audio_cache: &AudioCache,
+    cache_dir: &Path,
+    cookie_config: Option<(bool, String)>,
+    offline: bool,
+) -> Result<Lyrics, String> {
+    if let Some(path) = find_lrc(audio_cache, video_id) {
+        let text = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        return Ok(parse_lrc(&text));
+    }
+
+    // Found lyrics never go stale; a miss is retried once it expires.
+    if !is_valid_video_id(video_id) {
+        return Err(format!("Invalid video id '{}'", video_id));
+    }
+    let store = lyrics_cache_store(cache_dir, video_id);
+    if let Ok(Some(lyrics)) = store.load_stale() {
+        if !lyrics.is_empty() {
+            // Mark them recently shown, so trimming keeps them.
+            mark_used(store.path());
+            return Ok(lyrics);
+        }
+    }
+    if let Ok(Some(miss)) = store.load() {
+        return Ok(miss);
+    }
+    if offline {
+        return Err("Lyrics not downloaded yet — unavailable offline".to_string());
+    }
+
+    let lyrics = fetch_captions(ytdlp, video_id, cookie_config)?;
+    if store.save(&lyrics).is_ok() {
+        trim_dir(cache_dir, LYRICS_CACHE_MAX_BYTES);
+    }
+    Ok(lyrics)
+}
+
+/// An `.lrc` file for `video_id` in the audio cache directory.
+fn find_lrc(audio_cache: &AudioCache, video_id: &str) -> Option<PathBuf> {
+    if !is_valid_video_id(video_id) {
+        return None;
+    }
+    audio_cache
+        .find(video_id)
+        .map(|audio| audio.with_extension("lrc"))
+        .into_iter()
+        .chain([audio_cache.dir().join(format!("{}.lrc", video_id))])
+        .find(|path| path.is_file())
+}
+
+/// Fetch the video's English captions as lyrics. A video without captions
+/// gives empty lyrics, not an error.
+pub(crate) fn fetch_captions(
+    ytdlp: &dyn YtDlp,
+    video_id: &str,
+    cookie_config: Option<(bool, String)>,
+) -> Result<Lyrics, String> {
+    if !is_valid_video_id(video_id) {
+        return Err(format!("Invalid video id '{}'", video_id));
+    }
+    let dir = tempfile::tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let template = dir.path().join(format!("{}.%(ext)s", video_id));
+    let args = YtDlpArgs::new()
+        .flag("--skip-download")
+        .flag("--write-subs")
+        .flag("--write-auto-subs")
+        .opt("--sub-langs", "en.*,en,-live_chat")
+        .opt("--sub-format", "vtt")
+        .flag("--no-warnings")
+        .flag("--no-playlist")
+        .opt("-o", template.to_string_lossy().into_owned())
+        .cookies(cookie_config.as_ref())
+        .flag(&format!("https://www.youtube.com/watch?v={}", video_id));
+    let output = ytdlp
+        .run(&args)
+        .map_err(|e| format!("{}. Is yt-dlp installed?", e))?;
+    if !output.success {
+        return Err(format!("yt-dlp failed: {}", output.error_snippet()));
+    }
+
+    let source = "YouTube captions".to_string();
+    let Some(path) = subtitle_file(dir.path(), video_id) else {
+        return Ok(Lyrics {
+            source,
+            ..Lyrics::default()
+        });
+    };
+    let text =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read captions: {}", e))?;
+    let lines = parse_vtt(&text);
+    Ok(Lyrics {
+        synced: !lines.is_empty(),
+        lines,
+        source,
+    })
+}
+
+/// The subtitle file yt-dlp wrote into `dir`: plain English if there is
+/// one, else the first regional variant.
+fn subtitle_file(dir: &Path, video_id: &str) -> Option<PathBuf> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(video_id) && n.ends_with(".vtt"))
+        })
+        .collect();
+    files.sort();
+    let plain = dir.join(format!("{}.en.vtt", video_id));
+    files
+        .iter()
+        .find(|path| **path == plain)
+        .or(files.first())
+        .cloned()
+}
+
+/// Parse LRC lyrics: `[mm:ss.xx]` tags (several per line for repeated
+/// lines), an `[offset:±ms]` tag, and `<mm:ss.xx>` word timings, which are
+/// dropped. Without any timestamps the text is kept as unsynced lyrics.
+pub(crate) fn parse_lrc(text: &str) -> Lyrics {
+    let mut offset = 0.0;
+    let mut timed = Vec::new();
+    let mut plain = Vec::new();
+    for row in text.lines() {
+        let mut rest = row.trim();
+        let mut tagged = false;
+        let mut stamps = Vec::new();
+        while let Some((tag, after)) = rest.strip_prefix('[').and_then(|r| r.split_once(']')) {
+            tagged = true;
+            if let Some(ms) = tag.strip_prefix("offset:") {
+                offset = ms.trim().parse::<f64>().unwrap_or(0.0) / 1000.0;
+            } else if let Some(start) = lrc_timestamp(tag) {
+                stamps.push(start);
+            }
+            rest = after.trim_start();
+        }
+        let line = strip_tags(rest).trim().to_string();
+        if !stamps.is_empty() {
+            timed.extend(stamps.into_iter().map(|start| LyricLine {
+                start,
+                text: line.clone(),
+            }));
+        } else if !tagged {
+            plain.push(LyricLine {
+                start: 0.0,
+                text: line,
+            });
+        }
+    }
+
+    let source = "LRC file".to_string();
+    if timed.is_empty() {
+        // Blank lines separate verses, but not at the ends.
+        let first = plain
+            .iter()
+            .position(|l| !l.text.is_empty())
+            .unwrap_or(plain.len());
+        let last = plain
+            .iter()
+            .rposition(|l| !l.text.is_empty())
+            .map_or(first, |i| i + 1);
+        return Lyrics {
+            lines: plain[first..last].to_vec(),
+            synced: false,
+            source,
+        };
+    }
+    // A positive offset shows the lyrics earlier.
+    for line in &mut timed {
+        line.start = (line.start - offset).max(0.0);
+    }
+    timed.sort_by(|a, b| a.start.total_cmp(&b.start));
+    Lyrics {
+        lines: timed,
+        synced: true,
+        source,
+    }
+}
+
+/// An LRC timestamp; LRC also writes hundredths after a colon (`mm:ss:xx`).
+fn lrc_timestamp(tag: &str) -> Option<f64> {
+    match tag.matches(':').count() {
+        2 if !tag.contains('.') => {
+            let (clock, hundredths) = tag.rsplit_once(':')?;
+            parse_timestamp(&format!("{}.{}", clock, hundredths))
+        }
+        _ => parse_timestamp(tag),
+    }
+}
+
+/// Parse WebVTT captions into timed lines. YouTube's auto-generated
+/// captions roll: each cue repeats the previous cue's line above the new
+/// one, so a line already shown in the cue before is skipped.
+pub(crate) fn parse_vtt(text: &str) -> Vec<LyricLine> {
+    let text = text.replace("\r\n", "\n");
+    let mut lines = Vec::new();
+    let mut previous: Vec<String> = Vec::new();
+    for block in text.split("\n\n") {
+        let mut rows = block.lines();
+        let Some(start) = rows.by_ref().find_map(|row| {
+            row.split_once("-->")
+                .and_then(|(start, _)| parse_timestamp(start))
+        }) else {
+            continue;
+        };
+        let cue: Vec<String> = rows.map(clean_caption).filter(|t| !t.is_empty()).collect();
+        if cue.is_empty() {
+            continue;
+        }
+        for text in &cue {
+            if !previous.contains(text) {
+                lines.push(LyricLine {
+                    start,
+                    text: text.clone(),
+                });
+            }
+        }
+        previous = cue;
+    }
+    lines
+}
+
+/// A caption row as plain text: word timings and styling removed, HTML
+/// entities decoded, and `[Music]` shown as a note.
+fn clean_caption(row: &str) -> String {
+    let text = strip_tags(row)
+        .replace("&nbsp;", " ")
+        .replace("&#39;", "'")
+        .replace("&quot;", "\"")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&");
+    let text = text.trim();
+    if text.eq_ignore_ascii_case("[music]") {
+        "♪".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+/// `text` without `<…>` tags.
+fn strip_tags(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Seconds from `[hh:]mm:ss[.fff]`. `None` for a clock too large to count.
+fn parse_timestamp(s: &str) -> Option<f64> {
+    let fields: Vec<&str> = s.trim().split(':').collect();
+    if !(2..=3).contains(&fields.len()) {
+        return None;
+    }
+    let (seconds, clock) = fields.split_last()?;
+    let seconds = seconds
+        .parse::<f64>()
+        .ok()
+        .filter(|s| (0.0..60.0).contains(s))?;
+    let minutes = clock.iter().try_fold(0u32, |acc, field| {
+        acc.checked_mul(60)?.checked_add(field.parse::<u32>().ok()?)
+    })?;
+    Some(f64::from(minutes) * 60.0 + seconds)
+}
+
+/// The cache of `video_id`'s lyrics in `cache_dir`.
+pub(crate) fn lyrics_cache_store(cache_dir: &Path, video_id: &str) -> CacheStore<Lyrics> {
+    CacheStore::new(
+        cache_dir.join(format!("{}.json", video_id)),
+        Duration::from_secs(LYRICS_MISS_TTL_SECS),
+        Lyrics::CACHE_SCHEMA_VERSION,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::SystemTime;
+
+    use crate::services::settings::Settings;
+    use crate::youtube::fake_ytdlp::{fixture, FakeYtDlp, Reply};
+
+    const BLINDING_LIGHTS: &str = "4NRXx6U8ABQ";
+
+    fn texts(lyrics: &Lyrics) -> Vec<&str> {
+        lyrics.lines.iter().map(|l| l.text.as_str()).collect()
+    }
+
+    #[test]
+    fn lrc_lines_are_timed_sorted_and_offset() {
+        let lrc = "[ar:The Weeknd]\n[ti:Blinding Lights]\n[offset:+500]\n\
+            [00:04.75]I said, <00:05.12>ooh, I'm blinded by the lights\n\
+            [00:08.50][01:00:50]No, I can't sleep until I feel your touch\n\
+            [00:13.50]\n";
+        let lyrics = parse_lrc(lrc);
+        assert!(lyrics.synced);
+        assert_eq!(
+            texts(&lyrics),
+            [
+                "I said, ooh, I'm blinded by the lights",
+                "No, I can't sleep until I feel your touch",
+                "",
+                "No, I can't sleep until I feel your touch"
+            ]
+        );
+        let starts: Vec<f64> = lyrics.lines.iter().map(|l| l.start).collect();
+        assert_eq!(starts, [4.25, 8.0, 13.0, 60.0]);
+
+        assert_eq!(lyrics.line_at(2.0), None);
+        assert_eq!(lyrics.line_at(4.25), Some(0));
+        assert_eq!(lyrics.line_at(30.0), Some(2));
+        assert_eq!(lyrics.line_at(600.0), Some(3));
+
+        // Clocks that don't fit are no timestamps, not a panic.
+        assert_eq!(parse_timestamp("1:4294967295:00.0"), None);
+        assert_eq!(parse_timestamp("4294967295:00:00.000"), None);
+        assert_eq!(parse_timestamp("01:02:03.5"), Some(3723.5));
+        assert!(!parse_lrc("[1:4294967295:00.0]Overflow\n").synced);
+    }
+
+    #[test]
+    fn lrc_without_timestamps_is_unsynced() {
+        let lyrics = parse_lrc("[ar:Someone]\n\nFirst verse\n\nSecond verse\n\n");
+        assert!(!lyrics.synced);
+        assert_eq!(texts(&lyrics), ["First verse", "", "Second verse"]);
+        assert_eq!(lyrics.line_at(10.0), None);
+        assert!(parse_lrc("[ti:Nothing]\n").is_empty());
+    }
+
+    #[test]
+    fn rolling_captions_become_one_line_each() {
+        let fake = FakeYtDlp::new().on("watch?v=", Reply::Subtitles(fixture("captions.en.vtt")));
+        let lyrics = fetch_captions(&fake, BLINDING_LIGHTS, None).unwrap();
+        assert!(lyrics.synced);
+        assert_eq!(
+            texts(&lyrics),
+            [
+                "♪",
+                "I said, ooh, I'm blinded by the lights",
+                "No, I can't sleep until I feel your touch"
+            ]
+        );
+        let starts: Vec<f64> = lyrics.lines.iter().map(|l| l.start).collect();
+        assert_eq!(starts, [0.0, 4.12, 8.44]);
+        let args = fake.calls()[0].clone();
+        assert!(args.as_slice().iter().any(|a| a == "--write-auto-subs"));
+        assert_eq!(args.value_of("--sub-format"), Some("vtt"));
+
+        // No captions at all: empty lyrics, not an error.
+        let fake = FakeYtDlp::new().on("watch?v=", Reply::Output(String::new()));
+        assert!(fetch_captions(&fake, BLINDING_LIGHTS, None)
+            .unwrap()
+            .is_empty());
+        assert!(fetch_captions(&fake, "../etc", None).is_err());
+    }
+
+    #[test]
+    fn lyrics_prefer_lrc_files_and_are_cached_for_offline() {
+        let lyrics_dir = tempfile::tempdir().unwrap();
+        let audio = tempfile::tempdir().unwrap();
+        let cache = AudioCache::new(
+            audio.path().to_path_buf(),
+            Settings::default().audio_profile(),
+        );
+        let fake = FakeYtDlp::new().on("watch?v=", Reply::Subtitles(fixture("captions.en.vtt")));
+
+        let fetched = load_lyrics(
+            &fake,
+            BLINDING_LIGHTS,
+            &cache,
+            lyrics_dir.path(),
+            None,
+            false,
+        )
+        .unwrap();
+        assert_eq!(fetched.source, "YouTube captions");
+        // Cached: offline, and with no yt-dlp to ask, it's still there.
+        let offline = FakeYtDlp::new();
+        let cached = load_lyrics(
+            &offline,
+            BLINDING_LIGHTS,
+            &cache,
+            lyrics_dir.path(),
+            None,
+            true,
+        )
+        .unwrap();
+        assert_eq!(cached, fetched);
+        assert_eq!(fake.calls().len(), 1);
+        assert!(lyrics_dir
+            .path()
+            .join(format!("{}.json", BLINDING_LIGHTS))
+            .is_file());
+        assert!(load_lyrics(
+            &offline,
+            "ZRXA_rpB4lE",
+            &cache,
+            lyrics_dir.path(),
+            None,
+            true
+        )
+        .is_err());
+
+        std::fs::write(
+            audio.path().join(format!("{}.lrc", BLINDING_LIGHTS)),
+            "[00:01.00]Local",
+        )
+        .unwrap();
+        let local = load_lyrics(
+            &offline,
+            BLINDING_LIGHTS,
+            &cache,
+            lyrics_dir.path(),
+            None,
+            true,
+        )
+        .unwrap();
+        assert_eq!(
+            (local.source.as_str(), texts(&local)),
+            ("LRC file", vec!["Local"])
+        );
+    }
+
+    #[test]
+    fn lyrics_cache_is_trimmed_to_its_limit() {
+        let lyrics_dir = tempfile::tempdir().unwrap();
+        let audio = tempfile::tempdir().unwrap();
+        let cache = AudioCache::new(
+            audio.path().to_path_buf(),
+            Settings::default().audio_profile(),
+        );
+        let now = SystemTime::now();
+        for (i, id) in ["oldLyrics01", "newLyrics01"].iter().enumerate() {
+            let path = lyrics_dir.path().join(format!("{}.json", id));
+            std::fs::write(&path, vec![b' '; (LYRICS_CACHE_MAX_BYTES / 2) as usize]).unwrap();
+            let age = Duration::from_secs(100 * (2 - i as u64));
+            std::fs::File::options()
+                .append(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(now - age)
+                .unwrap();
+        }
+        let fake = FakeYtDlp::new().on("watch?v=", Reply::Subtitles(fixture("captions.en.vtt")));
+        load_lyrics(
+            &fake,
+            BLINDING_LIGHTS,
+            &cache,
+            lyrics_dir.path(),
+            None,
+            false,
+        )
+        .unwrap();
+
+        let mut left: Vec<String> = std::fs::read_dir(lyrics_dir.path())
+            .unwrap()
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .collect();
+        left.sort();
+        assert_eq!(
+            left,
+            [
+                format!("{}.json", BLINDING_LIGHTS),
+                "newLyrics01.json".to_string()
+            ]
+        );
+    }
+}
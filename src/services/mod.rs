@@ -1,5 +1,21 @@
+pub(crate) mod album;
+pub(crate) mod artist;
+pub(crate) mod audio_cache;
+pub(crate) mod audio_export;
 pub(crate) mod cache_store;
+pub(crate) mod diagnostics;
 pub(crate) mod download;
+pub(crate) mod download_queue;
 pub(crate) mod feed;
+pub(crate) mod filter;
+pub(crate) mod library;
+pub(crate) mod local_playlists;
+pub(crate) mod lyrics;
+pub(crate) mod network;
+pub(crate) mod offline_jobs;
 pub(crate) mod persistence;
 pub(crate) mod playlist;
+pub(crate) mod playlist_io;
+pub(crate) mod settings;
+pub(crate) mod tags;
+pub(crate) mod thumbnail;
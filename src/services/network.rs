@@ -0,0 +1,217 @@
+//! Network status tracking for offline mode.
+//!
+//! Offline mode is either switched on by hand or detected: yt-dlp errors
+//! that look like connectivity problems are counted, and after
+//! `OFFLINE_FAILURE_THRESHOLD` in a row the app goes offline. While
+//! auto-detected, a cheap TCP probe runs every `OFFLINE_PROBE_INTERVAL_SECS`
+//! and brings the app back online once YouTube is reachable again.
+
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+use crate::config::{OFFLINE_FAILURE_THRESHOLD, OFFLINE_PROBE_INTERVAL_SECS};
+use crate::services::feed::sanitize_text;
+
+/// Substrings (lowercase) of yt-dlp / OS errors that mean the network is
+/// unreachable, as opposed to a video being unavailable.
+const NETWORK_MARKERS: &[&str] = &[
+    "urlopen error",
+    "temporary failure in name resolution",
+    "name or service not known",
+    "failed to resolve",
+    "nodename nor servname",
+    "getaddrinfo failed",
+    "network is unreachable",
+    "no route to host",
+    "connection refused",
+    "connection reset",
+    "timed out",
+];
+
+/// The line of yt-dlp's stderr worth showing: the first `ERROR:` line, else
+/// the first non-empty one, capped and stripped of control characters. It
+/// carries the cause [`is_network_error`] looks for.
+pub(crate) fn error_snippet(stderr: &str) -> String {
+    let line = stderr
+        .lines()
+        .find(|l| l.starts_with("ERROR"))
+        .or_else(|| stderr.lines().find(|l| !l.trim().is_empty()))
+        .unwrap_or("unknown error");
+    sanitize_text(&line.trim().chars().take(200).collect::<String>())
+}
+
+/// Whether `error` looks like a connectivity failure.
+pub(crate) fn is_network_error(error: &str) -> bool {
+    let lower = error.to_lowercase();
+    NETWORK_MARKERS.iter().any(|m| lower.contains(m))
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct NetworkStatus {
+    /// Offline mode switched on by the user; only they can switch it off.
+    manual: bool,
+    /// Offline mode detected from repeated network failures.
+    auto: bool,
+    /// Network failures since the last success.
+    failures: u32,
+    last_probe: Option<Instant>,
+}
+
+impl NetworkStatus {
+    pub(crate) fn is_offline(&self) -> bool {
+        self.manual || self.auto
+    }
+
+    /// Whether offline mode was detected rather than switched on by hand.
+    pub(crate) fn is_auto(&self) -> bool {
+        self.auto && !self.manual
+    }
+
+    /// Flip manual offline mode. Switching it off also clears a detected
+    /// offline state so the user can retry right away. Returns the new
+    /// offline state.
+    pub(crate) fn toggle_manual(&mut self) -> bool {
+        if self.is_offline() {
+            self.manual = false;
+            self.auto = false;
+            self.failures = 0;
+        } else {
+            self.manual = true;
+        }
+        self.is_offline()
+    }
+
+    /// Count a failed network operation. Returns true if this failure
+    /// switched the app offline.
+    pub(crate) fn record_failure(&mut self, error: &str) -> bool {
+        if !is_network_error(error) {
+            return false;
+        }
+        self.failures = self.failures.saturating_add(1);
+        if self.failures >= OFFLINE_FAILURE_THRESHOLD && !self.is_offline() {
+            self.auto = true;
+            self.last_probe = Some(Instant::now());
+            return true;
+        }
+        false
+    }
+
+    /// Any successful network operation resets the failure count.
+    pub(crate) fn record_success(&mut self) {
+        self.failures = 0;
+    }
+
+    /// Whether it's time to probe for the network again (auto mode only).
+    pub(crate) fn probe_due(&self, now: Instant) -> bool {
+        self.is_auto()
+            && self.last_probe.is_none_or(|last| {
+                now.duration_since(last) >= Duration::from_secs(OFFLINE_PROBE_INTERVAL_SECS)
+            })
+    }
+
+    pub(crate) fn mark_probe_started(&mut self, now: Instant) {
+        self.last_probe = Some(now);
+    }
+
+    /// Apply a probe result. Returns true if it brought the app back online.
+    pub(crate) fn record_probe(&mut self, reachable: bool) -> bool {
+        if reachable && self.is_auto() {
+            self.auto = false;
+            self.failures = 0;
+            return true;
+        }
+        false
+    }
+}
+
+/// Try a TCP connection to YouTube. Blocking — run it off the UI thread.
+pub(crate) fn probe_youtube() -> bool {
+    let Ok(addrs) = ("www.youtube.com", 443).to_socket_addrs() else {
+        return false;
+    };
+    addrs
+        .into_iter()
+        .any(|addr| TcpStream::connect_timeout(&addr, Duration::from_secs(3)).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DNS_ERROR: &str =
+        "ERROR: [youtube] abc: Unable to download webpage: <urlopen error [Errno -3] Temporary failure in name resolution>";
+
+    #[test]
+    fn classifies_network_errors() {
+        assert!(is_network_error(DNS_ERROR));
+        assert!(is_network_error("Connection reset by peer"));
+        assert!(!is_network_error("ERROR: [youtube] abc: Video unavailable"));
+        assert!(!is_network_error("Sign in to confirm your age"));
+    }
+
+    #[test]
+    fn snippet_prefers_error_line() {
+        let stderr = format!("WARNING: [youtube] falling back\n{}\n", DNS_ERROR);
+        assert_eq!(
+            error_snippet(&stderr),
+            DNS_ERROR.chars().take(200).collect::<String>()
+        );
+        assert_eq!(error_snippet("\n  oops \n"), "oops");
+        assert_eq!(error_snippet(""), "unknown error");
+    }
+
+    #[test]
+    fn goes_offline_after_repeated_network_failures() {
+        let mut status = NetworkStatus::default();
+        for _ in 1..OFFLINE_FAILURE_THRESHOLD {
+            assert!(!status.record_failure(DNS_ERROR));
+        }
+        // Unrelated errors neither count nor reset.
+        assert!(!status.record_failure("Video unavailable"));
+        assert!(status.record_failure(DNS_ERROR));
+        assert!(status.is_offline() && status.is_auto());
+        // Already offline: no second transition.
+        assert!(!status.record_failure(DNS_ERROR));
+
+        status.record_success();
+        assert!(status.record_probe(true));
+        assert!(!status.is_offline());
+    }
+
+    #[test]
+    fn success_resets_failure_count() {
+        let mut status = NetworkStatus::default();
+        for _ in 1..OFFLINE_FAILURE_THRESHOLD {
+            status.record_failure(DNS_ERROR);
+        }
+        status.record_success();
+        assert!(!status.record_failure(DNS_ERROR));
+        assert!(!status.is_offline());
+    }
+
+    #[test]
+    fn manual_mode_ignores_probes() {
+        let mut status = NetworkStatus::default();
+        assert!(status.toggle_manual());
+        assert!(!status.probe_due(Instant::now()));
+        assert!(!status.record_probe(true));
+        assert!(status.is_offline());
+        assert!(!status.toggle_manual());
+    }
+
+    #[test]
+    fn probes_are_spaced_out() {
+        let mut status = NetworkStatus::default();
+        for _ in 0..OFFLINE_FAILURE_THRESHOLD {
+            status.record_failure(DNS_ERROR);
+        }
+        let now = Instant::now();
+        assert!(!status.probe_due(now));
+        let later = now + Duration::from_secs(OFFLINE_PROBE_INTERVAL_SECS + 1);
+        assert!(status.probe_due(later));
+        status.mark_probe_started(later);
+        assert!(!status.probe_due(later));
+        assert!(!status.record_probe(false));
+        assert!(status.is_offline());
+    }
+}
@@ -0,0 +1,202 @@
+//! "Make available offline" jobs: download every track of a playlist.
+//!
+//! A job is a playlist snapshot stored in the library, so it resumes after
+//! a restart. Its progress isn't stored: it's worked out from the download
+//! cache each time. The app feeds job tracks to the `DownloadManager` a few
+//! at a time at the lowest priority, so they never hold up playback.
+
+use std::collections::HashSet;
+
+use crate::player::queue::Track;
+
+/// Where a job's track stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TrackStatus {
+    Cached,
+    /// Downloading, queued, or waiting to retry.
+    Downloading,
+    /// Failed and won't be retried automatically.
+    Failed,
+    /// Not requested yet.
+    Pending,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct OfflineJob {
+    /// The playlist's id (feed playlists) or URL (loaded playlists).
+    pub id: String,
+    pub title: String,
+    pub tracks: Vec<Track>,
+    pub created_at: i64,
+    /// When every track was last seen cached; `None` while incomplete.
+    pub completed_at: Option<i64>,
+}
+
+impl OfflineJob {
+    /// A new job for `tracks`, dropping repeated video ids.
+    pub(crate) fn new(id: String, title: String, tracks: Vec<Track>, created_at: i64) -> Self {
+        let mut seen = HashSet::new();
+        let tracks = tracks
+            .into_iter()
+            .filter(|t| seen.insert(t.video_id.clone()))
+            .collect();
+        Self {
+            id,
+            title,
+            tracks,
+            created_at,
+            completed_at: None,
+        }
+    }
+
+    /// Count the job's tracks by status. `size` gives a cached file's size.
+    pub(crate) fn progress(
+        &self,
+        status: impl Fn(&Track) -> TrackStatus,
+        size: impl Fn(&str) -> u64,
+    ) -> JobProgress {
+        let mut progress = JobProgress {
+            total: self.tracks.len(),
+            ..JobProgress::default()
+        };
+        for track in &self.tracks {
+            match status(track) {
+                TrackStatus::Cached => {
+                    progress.cached += 1;
+                    progress.bytes += size(&track.video_id);
+                }
+                TrackStatus::Downloading => progress.downloading += 1,
+                TrackStatus::Failed => progress.failed += 1,
+                TrackStatus::Pending => {}
+            }
+        }
+        progress
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct JobProgress {
+    pub total: usize,
+    pub cached: usize,
+    pub downloading: usize,
+    pub failed: usize,
+    /// Size of the cached tracks.
+    pub bytes: u64,
+}
+
+impl JobProgress {
+    /// Every track is cached: the playlist is fully available offline.
+    pub(crate) fn is_complete(&self) -> bool {
+        self.total > 0 && self.cached == self.total
+    }
+
+    /// Nothing left to do: every track is cached or failed for good.
+    pub(crate) fn is_settled(&self) -> bool {
+        self.cached + self.failed == self.total
+    }
+
+    pub(crate) fn ratio(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.cached as f64 / self.total as f64
+    }
+}
+
+/// The tracks to request next so that at most `limit` job tracks are in
+/// flight, oldest job first. A track shared by several jobs counts once.
+pub(crate) fn next_requests(
+    jobs: &[OfflineJob],
+    status: impl Fn(&Track) -> TrackStatus,
+    limit: usize,
+) -> Vec<&Track> {
+    let mut seen = HashSet::new();
+    let mut in_flight = 0;
+    let mut pending = Vec::new();
+    for track in jobs.iter().flat_map(|job| &job.tracks) {
+        if !seen.insert(track.video_id.as_str()) {
+            continue;
+        }
+        match status(track) {
+            TrackStatus::Downloading => in_flight += 1,
+            TrackStatus::Pending => pending.push(track),
+            TrackStatus::Cached | TrackStatus::Failed => {}
+        }
+    }
+    pending.truncate(limit.saturating_sub(in_flight));
+    pending
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(id: &str) -> Track {
+        Track::new(
+            id.to_string(),
+            id.to_string(),
+            0,
+            String::new(),
+            format!("https://www.youtube.com/watch?v={}", id),
+        )
+    }
+
+    fn job(id: &str, ids: &[&str]) -> OfflineJob {
+        OfflineJob::new(
+            id.to_string(),
+            id.to_string(),
+            ids.iter().map(|i| track(i)).collect(),
+            0,
+        )
+    }
+
+    fn status_of(t: &Track) -> TrackStatus {
+        match t.video_id.chars().next() {
+            Some('c') => TrackStatus::Cached,
+            Some('d') => TrackStatus::Downloading,
+            Some('f') => TrackStatus::Failed,
+            _ => TrackStatus::Pending,
+        }
+    }
+
+    fn job_progress_of(ids: &[&str]) -> JobProgress {
+        job("j", ids).progress(status_of, |_| 0)
+    }
+
+    #[test]
+    fn progress_counts_and_sums_sizes() {
+        let job = job("j", &["c1", "c2", "d1", "f1", "p1", "c1"]);
+        assert_eq!(job.tracks.len(), 5);
+        let progress = job.progress(status_of, |_| 10);
+        assert_eq!(
+            progress,
+            JobProgress {
+                total: 5,
+                cached: 2,
+                downloading: 1,
+                failed: 1,
+                bytes: 20
+            }
+        );
+        assert!(!progress.is_complete() && !progress.is_settled());
+
+        let done = job_progress_of(&["c1", "f1"]);
+        assert!(done.is_settled() && !done.is_complete());
+        assert!(job_progress_of(&["c1"]).is_complete());
+        assert!(!job_progress_of(&[]).is_complete());
+    }
+
+    #[test]
+    fn requests_fill_free_slots_across_jobs() {
+        let jobs = [job("a", &["d1", "p1", "c1"]), job("b", &["p1", "p2", "p3"])];
+        let ids = |limit| -> Vec<&str> {
+            next_requests(&jobs, status_of, limit)
+                .into_iter()
+                .map(|t| t.video_id.as_str())
+                .collect()
+        };
+        assert_eq!(ids(3), ["p1", "p2"]);
+        assert_eq!(ids(4), ["p1", "p2", "p3"]);
+        assert!(ids(1).is_empty());
+    }
+}
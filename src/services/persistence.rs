@@ -9,17 +9,27 @@ use anyhow::{Context, Result};
 
 use crate::config;
 use crate::player::queue::Track;
+use crate::services::album::Album;
+use crate::services::filter::FilterSet;
+use crate::services::library::{now_unix, Library};
+use crate::services::local_playlists::LocalPlaylists;
+use crate::services::offline_jobs::OfflineJob;
+use crate::services::settings::Settings;
 use crate::ui::state::QueueState;
 
 /// Maximum file size in bytes (10 MB).
 pub(crate) const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
 
 /// Maximum number of entries allowed in a persisted collection.
-const MAX_ENTRY_COUNT: usize = 10_000;
+pub(crate) const MAX_ENTRY_COUNT: usize = 10_000;
 
 /// Maximum number of history entries to persist.
 pub(crate) const MAX_HISTORY_SIZE: usize = 100;
 
+/// `meta` key recording when the legacy JSON files were imported into the
+/// library database. Its presence means the import never runs again.
+const LEGACY_IMPORT_KEY: &str = "legacy_json_imported_at";
+
 // ---------------------------------------------------------------------------
 // Atomic write helper
 // ---------------------------------------------------------------------------
@@ -60,25 +70,110 @@ pub(crate) fn write_atomic(path: &Path, bytes: &[u8]) -> Result<()> {
 // PersistenceService
 // ---------------------------------------------------------------------------
 
-/// Handles reading and writing history/queue state to disk.
+/// Handles reading and writing history/queue state to disk, and owns the
+/// SQLite library database once [`open_library`](Self::open_library) is called.
 pub(crate) struct PersistenceService {
     config_dir: PathBuf,
+    library: Option<Library>,
+}
+
+/// Counts from the one-time import of the legacy JSON files.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct LegacyImport {
+    pub plays: usize,
+    pub tracks: usize,
+    pub cached_files: usize,
 }
 
 impl PersistenceService {
     pub(crate) fn new() -> Result<Self> {
         let config_dir = config::config_dir()?;
-        Ok(Self { config_dir })
+        Ok(Self::from_dir(config_dir))
     }
 
+    /// A service rooted at `config_dir` without the library database — cheap
+    /// enough to construct inside `spawn_blocking` for one-off loads.
     pub(crate) fn from_dir(config_dir: PathBuf) -> Self {
-        Self { config_dir }
+        Self {
+            config_dir,
+            library: None,
+        }
     }
 
     pub(crate) fn config_dir(&self) -> &Path {
         &self.config_dir
     }
 
+    // -- Library database -------------------------------------------------
+
+    /// Open `library.db`, run pending migrations, and import the legacy JSON
+    /// files the first time. Returns the import counts if an import ran.
+    pub(crate) fn open_library(&mut self) -> Result<Option<LegacyImport>> {
+        let mut library = Library::open(&self.config_dir.join("library.db"))?;
+        let imported = if library.get_meta(LEGACY_IMPORT_KEY)?.is_none() {
+            Some(self.import_legacy_json(&mut library)?)
+        } else {
+            None
+        };
+        self.library = Some(library);
+        Ok(imported)
+    }
+
+    /// The library database, if it was opened successfully.
+    pub(crate) fn library(&self) -> Option<&Library> {
+        self.library.as_ref()
+    }
+
+    /// Copy `history.json`, `queue.json`, and `download_cache.json` into the
+    /// library in a single transaction. Unreadable files are skipped rather
+    /// than blocking startup — they're still used by the JSON code paths.
+    ///
+    /// History has no timestamps, so plays are spaced one second apart
+    /// ending at the history file's modification time, preserving order.
+    fn import_legacy_json(&self, library: &mut Library) -> Result<LegacyImport> {
+        let history = self.load_history().unwrap_or_default();
+        let queue = self.load_queue().ok();
+        let cache = self.load_download_cache();
+
+        let history_mtime = fs::metadata(self.config_dir.join("history.json"))
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map_or_else(now_unix, |d| d.as_secs() as i64);
+
+        library.transaction(|lib| {
+            let mut summary = LegacyImport::default();
+
+            // Cache first so the history import below fills in last_played_at.
+            for (video_id, path) in &cache {
+                let cached_at = fs::metadata(path)
+                    .and_then(|m| m.modified())
+                    .ok()
+                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map_or(history_mtime, |d| d.as_secs() as i64);
+                lib.record_cached_file(video_id, path, cached_at)?;
+                summary.cached_files += 1;
+            }
+
+            let n = history.len() as i64;
+            for (i, track) in history.iter().enumerate() {
+                let play_id = lib.record_play(track, history_mtime - (n - 1 - i as i64))?;
+                lib.finish_play(play_id, track.duration, false)?;
+                summary.plays += 1;
+            }
+
+            if let Some(queue) = &queue {
+                for track in queue.tracks.iter().chain(queue.current_track.iter()) {
+                    lib.upsert_track(track)?;
+                    summary.tracks += 1;
+                }
+            }
+
+            lib.set_meta(LEGACY_IMPORT_KEY, &now_unix().to_string())?;
+            Ok(summary)
+        })
+    }
+
     // -- History --------------------------------------------------------
 
     pub(crate) fn load_history(&self) -> Result<Vec<Track>> {
@@ -219,6 +314,151 @@ impl PersistenceService {
         write_atomic(&path, json.as_bytes()).context("Failed to write download cache")
     }
 
+    // -- Filter profiles ------------------------------------------------
+
+    /// Load the content filter profiles. A missing file yields the built-in
+    /// defaults so a fresh install behaves like the old music-only mode.
+    pub(crate) fn load_filters(&self) -> Result<FilterSet> {
+        let path = self.config_dir.join("filters.json");
+
+        let mut file = match fs::File::open(&path) {
+            Ok(f) => f,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(FilterSet::default()),
+            Err(e) => return Err(e).context("Failed to open filters file"),
+        };
+
+        let metadata = file.metadata().context("Failed to stat filters file")?;
+        if metadata.len() > MAX_FILE_SIZE {
+            anyhow::bail!("Filters file too large ({} bytes)", metadata.len());
+        }
+
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)
+            .context("Failed to read filters file")?;
+        serde_json::from_str(&contents).context("Failed to parse filters file")
+    }
+
+    /// Save the filter profiles (including the active profile name).
+    pub(crate) fn save_filters(&self, filters: &FilterSet) -> Result<()> {
+        let path = self.config_dir.join("filters.json");
+        let json = serde_json::to_string_pretty(filters).context("Failed to serialize filters")?;
+        write_atomic(&path, json.as_bytes()).context("Failed to write filters file")
+    }
+
+    // -- Settings -------------------------------------------------------
+
+    /// Load `settings.json`. A missing file yields the defaults; missing
+    /// fields take their default values.
+    pub(crate) fn load_settings(&self) -> Result<Settings> {
+        let path = self.config_dir.join("settings.json");
+
+        let mut file = match fs::File::open(&path) {
+            Ok(f) => f,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Settings::default()),
+            Err(e) => return Err(e).context("Failed to open settings file"),
+        };
+
+        let metadata = file.metadata().context("Failed to stat settings file")?;
+        if metadata.len() > MAX_FILE_SIZE {
+            anyhow::bail!("Settings file too large ({} bytes)", metadata.len());
+        }
+
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)
+            .context("Failed to read settings file")?;
+        serde_json::from_str(&contents).context("Failed to parse settings file")
+    }
+
+    // -- Local playlists ------------------------------------------------
+
+    /// Load the user's local playlists. Tracks with a non-YouTube URL are
+    /// dropped, matching how the queue is restored.
+    pub(crate) fn load_local_playlists(&self) -> Result<LocalPlaylists> {
+        let path = self.config_dir.join("playlists.json");
+
+        let mut file = match fs::File::open(&path) {
+            Ok(f) => f,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(LocalPlaylists::default())
+            }
+            Err(e) => return Err(e).context("Failed to open playlists file"),
+        };
+
+        let metadata = file.metadata().context("Failed to stat playlists file")?;
+        if metadata.len() > MAX_FILE_SIZE {
+            anyhow::bail!("Playlists file too large ({} bytes)", metadata.len());
+        }
+
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)
+            .context("Failed to read playlists file")?;
+        let mut store: LocalPlaylists =
+            serde_json::from_str(&contents).context("Failed to parse playlists file")?;
+
+        let total: usize = store.playlists.iter().map(|p| p.tracks.len()).sum();
+        if store.len() > MAX_ENTRY_COUNT || total > MAX_ENTRY_COUNT {
+            anyhow::bail!("Playlists file contains too many entries ({})", total);
+        }
+
+        for playlist in &mut store.playlists {
+            playlist
+                .tracks
+                .retain(|t| config::is_allowed_youtube_url(&t.url));
+            for track in &mut playlist.tracks {
+                track.local_file = None;
+            }
+        }
+
+        Ok(store)
+    }
+
+    /// Save the local playlists and mirror them into the library database.
+    /// The JSON file is authoritative; a library error is returned only
+    /// after the file has been written.
+    pub(crate) fn save_local_playlists(&mut self, store: &LocalPlaylists) -> Result<()> {
+        let path = self.config_dir.join("playlists.json");
+        let json = serde_json::to_string_pretty(store).context("Failed to serialize playlists")?;
+        write_atomic(&path, json.as_bytes()).context("Failed to write playlists file")?;
+
+        if let Some(library) = self.library.as_mut() {
+            library
+                .replace_playlists(&store.playlists)
+                .context("Failed to update playlists in library")?;
+        }
+        Ok(())
+    }
+
+    // -- Tags ---------------------------------------------------------------
+
+    /// Replace a track's tags. Tags live in the library database keyed by
+    /// video id, so they outlast the track's cached audio file.
+    pub(crate) fn save_track_tags(&mut self, track: &Track, tags: &[String]) -> Result<()> {
+        self.library
+            .as_mut()
+            .context("Library database unavailable")?
+            .set_track_tags(track, tags)
+    }
+
+    // -- Albums -------------------------------------------------------------
+
+    /// Remember which tracks are on `album`, in order.
+    pub(crate) fn save_album(&mut self, album: &Album) -> Result<()> {
+        self.library
+            .as_mut()
+            .context("Library database unavailable")?
+            .save_album(album)
+    }
+
+    // -- Offline jobs -------------------------------------------------------
+
+    /// Store a "make available offline" job so it resumes after a restart.
+    pub(crate) fn save_offline_job(&mut self, job: &OfflineJob) -> Result<()> {
+        self.library
+            .as_mut()
+            .context("Library database unavailable")?
+            .save_offline_job(job)
+    }
+
     // -- Playback state (resume position) -----------------------------------
 
     /// Save the current playback position so it can be resumed on restart.
@@ -487,6 +727,203 @@ mod tests {
         assert!(loaded.tracks[0].local_file.is_none());
     }
 
+    // -- Library import tests --
+
+    #[test]
+    fn open_library_imports_legacy_json_once() {
+        let tmp = TempDir::new().unwrap();
+        let mut svc = service_in(tmp.path());
+
+        let audio = tmp.path().join("a.mp3");
+        fs::write(&audio, b"audio").unwrap();
+        svc.save_history(&[
+            make_track("a", "Song A", "X"),
+            make_track("b", "Song B", "Y"),
+        ])
+        .unwrap();
+        svc.save_queue(&QueueState {
+            tracks: vec![make_track("c", "Song C", "Z")],
+            current_track: Some(make_track("d", "Song D", "Z")),
+        })
+        .unwrap();
+        let mut cache = HashMap::new();
+        cache.insert("a".to_string(), audio.to_string_lossy().to_string());
+        cache.insert("gone".to_string(), "/nonexistent/gone.mp3".to_string());
+        svc.save_download_cache(&cache).unwrap();
+
+        let imported = svc.open_library().unwrap();
+        assert_eq!(
+            imported,
+            Some(LegacyImport {
+                plays: 2,
+                tracks: 2,
+                cached_files: 1,
+            })
+        );
+        let lib = svc.library().unwrap();
+        assert_eq!(lib.play_count("a").unwrap(), 1);
+        assert!(lib.get_track("d").unwrap().is_some());
+        assert_eq!(lib.cached_files().unwrap().len(), 1);
+
+        // Second open must not import again.
+        let mut svc = service_in(tmp.path());
+        assert_eq!(svc.open_library().unwrap(), None);
+        assert_eq!(svc.library().unwrap().play_count("a").unwrap(), 1);
+    }
+
+    #[test]
+    fn open_library_with_no_legacy_files() {
+        let tmp = TempDir::new().unwrap();
+        let mut svc = service_in(tmp.path());
+
+        assert_eq!(svc.open_library().unwrap(), Some(LegacyImport::default()));
+        assert!(svc.library().is_some());
+    }
+
+    #[test]
+    fn open_library_skips_corrupt_history() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("history.json"), "not json").unwrap();
+        let mut svc = service_in(tmp.path());
+
+        assert_eq!(svc.open_library().unwrap().unwrap().plays, 0);
+    }
+
+    // -- Filter profile tests --
+
+    #[test]
+    fn load_filters_defaults_when_no_file() {
+        let tmp = TempDir::new().unwrap();
+        let svc = service_in(tmp.path());
+
+        assert_eq!(svc.load_filters().unwrap(), FilterSet::default());
+    }
+
+    #[test]
+    fn save_and_load_filters_round_trip() {
+        let tmp = TempDir::new().unwrap();
+        let svc = service_in(tmp.path());
+
+        let mut filters = FilterSet::default();
+        filters.cycle();
+        filters.profiles[0]
+            .uploader_allow
+            .push("Trusted".to_string());
+
+        svc.save_filters(&filters).unwrap();
+        assert_eq!(svc.load_filters().unwrap(), filters);
+    }
+
+    #[test]
+    fn load_filters_fills_missing_profile_fields() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(
+            tmp.path().join("filters.json"),
+            r#"{"active":"Short","profiles":[{"name":"Short","max_duration_secs":180}]}"#,
+        )
+        .unwrap();
+
+        let filters = service_in(tmp.path()).load_filters().unwrap();
+        let active = filters.active();
+        assert_eq!(active.max_duration_secs, Some(180));
+        assert!(active.title_exclude.is_empty());
+    }
+
+    // -- Local playlist tests --
+
+    #[test]
+    fn load_local_playlists_empty_when_no_file() {
+        let tmp = TempDir::new().unwrap();
+        let store = service_in(tmp.path()).load_local_playlists().unwrap();
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn save_and_load_local_playlists_round_trip() {
+        let tmp = TempDir::new().unwrap();
+        let mut svc = service_in(tmp.path());
+        let mut store = LocalPlaylists::default();
+        store.create("Road trip").unwrap();
+        store
+            .add_track(0, make_track("a", "Song A", "Artist"))
+            .unwrap();
+        store
+            .add_track(0, make_track("b", "Song B", "Artist"))
+            .unwrap();
+
+        svc.save_local_playlists(&store).unwrap();
+        assert_eq!(svc.load_local_playlists().unwrap(), store);
+    }
+
+    #[test]
+    fn load_local_playlists_drops_disallowed_urls() {
+        let tmp = TempDir::new().unwrap();
+        let mut svc = service_in(tmp.path());
+        let mut store = LocalPlaylists::default();
+        store.create("Mixed").unwrap();
+        store
+            .add_track(0, make_track("a", "Song A", "Artist"))
+            .unwrap();
+        let mut bad = make_track("b", "Song B", "Artist");
+        bad.url = "https://evil.example.com/b".to_string();
+        store.add_track(0, bad).unwrap();
+
+        svc.save_local_playlists(&store).unwrap();
+        let loaded = svc.load_local_playlists().unwrap();
+        assert_eq!(loaded.playlists[0].tracks.len(), 1);
+        assert_eq!(loaded.playlists[0].tracks[0].video_id, "a");
+    }
+
+    #[test]
+    fn save_local_playlists_mirrors_into_library() {
+        let tmp = TempDir::new().unwrap();
+        let mut svc = service_in(tmp.path());
+        svc.open_library().unwrap();
+        let mut store = LocalPlaylists::default();
+        store.create("Focus").unwrap();
+        store
+            .add_track(0, make_track("a", "Song A", "Artist"))
+            .unwrap();
+
+        svc.save_local_playlists(&store).unwrap();
+        assert_eq!(
+            svc.library().unwrap().playlists().unwrap(),
+            vec![("Focus".to_string(), vec!["a".to_string()])]
+        );
+    }
+
+    #[test]
+    fn save_track_tags_needs_library() {
+        let tmp = TempDir::new().unwrap();
+        let mut svc = service_in(tmp.path());
+        let track = make_track("a", "Song A", "Artist");
+        assert!(svc.save_track_tags(&track, &["gym".to_string()]).is_err());
+
+        svc.open_library().unwrap();
+        svc.save_track_tags(&track, &["gym".to_string()]).unwrap();
+        assert_eq!(svc.library().unwrap().track_tags("a").unwrap(), ["gym"]);
+    }
+
+    #[test]
+    fn load_settings_defaults_and_reads_file() {
+        let tmp = TempDir::new().unwrap();
+        let svc = service_in(tmp.path());
+        assert_eq!(svc.load_settings().unwrap(), Settings::default());
+
+        fs::write(
+            tmp.path().join("settings.json"),
+            r#"{"cache_quota_mb": 512}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            svc.load_settings().unwrap(),
+            Settings {
+                cache_quota_mb: 512,
+                ..Settings::default()
+            }
+        );
+    }
+
     // -- search_history tests --
 
     #[test]
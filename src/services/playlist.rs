@@ -1,14 +1,39 @@
 //! Playlist fetching service — yt-dlp based playlist and My Mix extraction.
 
-use std::process::Command;
-
 use crate::config::is_allowed_youtube_url;
 use crate::player::queue::Track;
 use crate::ui::state::MixPlaylist;
 use crate::youtube::extractor;
+use crate::youtube::ytdlp::{YtDlp, YtDlpArgs};
+
+/// Run a `--flat-playlist` dump of `url`, returning stdout.
+pub(super) fn dump_flat_playlist(
+    ytdlp: &dyn YtDlp,
+    url: &str,
+    cookie_config: Option<(bool, String)>,
+    what: &str,
+) -> Result<String, String> {
+    let args = YtDlpArgs::new()
+        .flag("--flat-playlist")
+        .flag("--dump-json")
+        .flag("--no-warnings")
+        .flag("--skip-download")
+        .cookies(cookie_config.as_ref())
+        .flag(url);
+    let output = ytdlp
+        .run(&args)
+        .map_err(|e| format!("{}. Is yt-dlp installed?", e))?;
+
+    if !output.success {
+        eprintln!("yt-dlp {} error: {}", what, output.stderr);
+        return Err(format!("yt-dlp failed: {}", output.error_snippet()));
+    }
+    Ok(output.stdout)
+}
 
 /// Fetch tracks from a playlist URL using yt-dlp.
 pub(crate) fn fetch_playlist_tracks(
+    ytdlp: &dyn YtDlp,
     playlist_url: &str,
     cookie_config: Option<(bool, String)>,
 ) -> Result<Vec<Track>, String> {
@@ -16,93 +41,47 @@ pub(crate) fn fetch_playlist_tracks(
         return Err("Invalid URL: must be a YouTube or YouTube Music URL".to_string());
     }
 
-    let mut cmd = Command::new("yt-dlp");
-    cmd.arg("--flat-playlist")
-        .arg("--dump-json")
-        .arg("--no-warnings")
-        .arg("--socket-timeout")
-        .arg("30")
-        .arg("--retries")
-        .arg("2");
-
-    if let Some((_use_from_browser, cookie_arg)) = cookie_config {
-        cmd.arg("--cookies-from-browser").arg(cookie_arg);
-    }
-
-    cmd.arg(playlist_url);
-
-    let output = cmd
-        .output()
-        .map_err(|e| format!("Failed to run yt-dlp: {}. Is yt-dlp installed?", e))?;
-
-    if !output.status.success() {
-        let error = String::from_utf8_lossy(&output.stderr);
-        eprintln!("yt-dlp playlist error: {}", error);
-        return Err("yt-dlp failed — check logs for details".to_string());
-    }
-
-    let stdout = String::from_utf8(output.stdout).map_err(|e| format!("Invalid UTF-8: {}", e))?;
-
-    let mut tracks = Vec::new();
-
-    for line in stdout.lines() {
-        if line.trim().is_empty() {
-            continue;
-        }
-
-        if let Ok(json) = serde_json::from_str::<serde_json::Value>(line) {
-            let video_id = json["id"].as_str().unwrap_or("").to_string();
-            if !extractor::is_valid_video_id(&video_id) {
-                continue;
-            }
+    let stdout = dump_flat_playlist(ytdlp, playlist_url, cookie_config, "playlist")?;
+    Ok(parse_flat_tracks(&stdout))
+}
 
-            let title = json["title"].as_str().unwrap_or("Unknown").to_string();
-            let duration = json["duration"].as_u64().unwrap_or(0);
-            let uploader = json["uploader"]
-                .as_str()
-                .or_else(|| json["channel"].as_str())
-                .unwrap_or("Unknown")
-                .to_string();
-            let url = format!("https://www.youtube.com/watch?v={}", video_id);
+/// The videos of a `--flat-playlist --dump-json` listing, skipping lines
+/// that aren't JSON or don't carry a valid video id.
+pub(super) fn parse_flat_tracks(stdout: &str) -> Vec<Track> {
+    stdout
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter_map(|json| track_from_flat_json(&json))
+        .collect()
+}
 
-            tracks.push(Track::new(video_id, title, duration, uploader, url));
-        }
+/// One flat playlist entry as a track, or `None` without a valid video id.
+pub(super) fn track_from_flat_json(json: &serde_json::Value) -> Option<Track> {
+    let video_id = json["id"].as_str().unwrap_or("").to_string();
+    if !extractor::is_valid_video_id(&video_id) {
+        return None;
     }
 
-    Ok(tracks)
+    let title = json["title"].as_str().unwrap_or("Unknown").to_string();
+    // Channel tabs report durations as floats.
+    let duration = json["duration"].as_f64().unwrap_or(0.0) as u64;
+    let uploader = json["uploader"]
+        .as_str()
+        .or_else(|| json["channel"].as_str())
+        .unwrap_or("Unknown")
+        .to_string();
+    let url = format!("https://www.youtube.com/watch?v={}", video_id);
+
+    Some(Track::new(video_id, title, duration, uploader, url))
 }
 
 /// Fetch My Mix playlists from YouTube Music home page.
 pub(crate) fn fetch_my_mix(
+    ytdlp: &dyn YtDlp,
     cookie_config: Option<(bool, String)>,
 ) -> Result<Vec<MixPlaylist>, String> {
-    let mut cmd = Command::new("yt-dlp");
-    cmd.arg("--flat-playlist")
-        .arg("--dump-json")
-        .arg("--no-warnings")
-        .arg("--skip-download")
-        .arg("--socket-timeout")
-        .arg("30")
-        .arg("--retries")
-        .arg("2");
-
-    if let Some((_use_from_browser, cookie_arg)) = cookie_config {
-        cmd.arg("--cookies-from-browser").arg(cookie_arg);
-    }
-
-    cmd.arg("https://music.youtube.com");
-
-    let output = cmd
-        .output()
-        .map_err(|e| format!("Failed to run yt-dlp: {}. Is yt-dlp installed?", e))?;
-
-    if !output.status.success() {
-        let error = String::from_utf8_lossy(&output.stderr);
-        eprintln!("yt-dlp my mix error: {}", error);
-        return Err("yt-dlp failed — check logs for details".to_string());
-    }
-
-    let stdout = String::from_utf8(output.stdout).map_err(|e| format!("Invalid UTF-8: {}", e))?;
+    let stdout = dump_flat_playlist(ytdlp, "https://music.youtube.com", cookie_config, "my mix")?;
 
     let mut playlists = Vec::new();
 
@@ -157,3 +136,65 @@ pub(crate) fn fetch_my_mix(
 
     Ok(playlists)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::youtube::fake_ytdlp::{FakeYtDlp, Reply};
+
+    fn cookies() -> Option<(bool, String)> {
+        Some((true, "firefox".to_string()))
+    }
+
+    #[test]
+    fn playlist_tracks_from_fixture() {
+        let fake = FakeYtDlp::new().on("list=PLrAX", Reply::fixture("playlist.jsonl"));
+        let url = "https://www.youtube.com/playlist?list=PLrAXtmErZgOeiKm4sgNOknGvNjby9efdf";
+        let tracks = fetch_playlist_tracks(&fake, url, cookies()).unwrap();
+
+        let ids: Vec<_> = tracks.iter().map(|t| t.video_id.as_str()).collect();
+        assert_eq!(ids, ["4NRXx6U8ABQ", "XXYlFuWEuKI", "fHI8X4OXluQ"]);
+        // `uploader` is null in flat dumps; the channel stands in.
+        assert_eq!(tracks[0].uploader, "The Weeknd");
+        assert_eq!(tracks[2].uploader, "Unknown");
+        assert_eq!(tracks[1].duration, 216);
+
+        let args = &fake.calls()[0];
+        assert_eq!(args.value_of("--cookies-from-browser"), Some("firefox"));
+        assert_eq!(args.target(), url);
+    }
+
+    #[test]
+    fn playlist_rejects_foreign_urls_and_reports_failures() {
+        let fake = FakeYtDlp::new().on(
+            "list=PLgone",
+            Reply::Fail("ERROR: [youtube:tab] PLgone: The playlist does not exist.\n".to_string()),
+        );
+        let err =
+            fetch_playlist_tracks(&fake, "https://example.com/list=PLgone", None).unwrap_err();
+        assert!(err.starts_with("Invalid URL"));
+        assert!(fake.calls().is_empty());
+
+        let err =
+            fetch_playlist_tracks(&fake, "https://www.youtube.com/playlist?list=PLgone", None)
+                .unwrap_err();
+        assert_eq!(
+            err,
+            "yt-dlp failed: ERROR: [youtube:tab] PLgone: The playlist does not exist."
+        );
+    }
+
+    #[test]
+    fn my_mix_keeps_only_mixes() {
+        let fake = FakeYtDlp::new().on("music.youtube.com", Reply::fixture("my_mix.jsonl"));
+        let mixes = fetch_my_mix(&fake, cookies()).unwrap();
+        let titles: Vec<_> = mixes.iter().map(|m| m.title.as_str()).collect();
+        assert_eq!(titles, ["Chill Hits", "My Supermix"]);
+        assert_eq!(mixes[0].track_count, 50);
+        assert_eq!(mixes[1].track_count, 100);
+        assert_eq!(
+            mixes[1].url,
+            "https://music.youtube.com/playlist?list=RDTMAK5uy_nilrsVWxrKskY0ZUpVZ3zpB0u4LwWTVJ4"
+        );
+    }
+}
@@ -0,0 +1,739 @@
+//! Export and import of track lists as M3U8, XSPF, or Crusty JSON.
+//!
+//! Export writes YouTube URLs; M3U8 can instead point at cached audio files
+//! so the playlist works in other players offline. Import accepts all three
+//! formats, validates every entry with [`is_allowed_youtube_url`] and
+//! [`is_valid_video_id`], and reports the entries it skipped rather than
+//! failing the whole file.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::config::is_allowed_youtube_url;
+use crate::player::queue::Track;
+use crate::services::library::now_unix;
+use crate::services::persistence::{write_atomic, MAX_ENTRY_COUNT, MAX_FILE_SIZE};
+use crate::youtube::extractor::is_valid_video_id;
+
+/// Identifies Crusty JSON exports.
+const CRUSTY_JSON_FORMAT: &str = "crusty-playlist";
+
+/// Current Crusty JSON version. Bump when the layout changes incompatibly;
+/// import refuses files from a newer version.
+pub(crate) const CRUSTY_JSON_VERSION: u32 = 1;
+
+/// Comment line carrying the YouTube URL of an M3U8 entry whose location is
+/// a local file, so a re-import can recover the track.
+const M3U_URL_TAG: &str = "#CRUSTY-URL:";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PlaylistFormat {
+    M3u8,
+    Xspf,
+    Json,
+}
+
+impl PlaylistFormat {
+    /// Pick a format from the file extension (`.m3u8`/`.m3u`, `.xspf`, `.json`).
+    pub(crate) fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "m3u8" | "m3u" => Some(PlaylistFormat::M3u8),
+            "xspf" => Some(PlaylistFormat::Xspf),
+            "json" => Some(PlaylistFormat::Json),
+            _ => None,
+        }
+    }
+
+    /// Guess the format from file contents, for files without a known extension.
+    fn sniff(contents: &str) -> Option<Self> {
+        let head = contents.trim_start_matches('\u{feff}').trim_start();
+        if head.starts_with('{') {
+            Some(PlaylistFormat::Json)
+        } else if head.starts_with('<') {
+            Some(PlaylistFormat::Xspf)
+        } else if head.starts_with("#EXTM3U") || head.starts_with("https://") {
+            Some(PlaylistFormat::M3u8)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            PlaylistFormat::M3u8 => "M3U8",
+            PlaylistFormat::Xspf => "XSPF",
+            PlaylistFormat::Json => "JSON",
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Export
+// ---------------------------------------------------------------------------
+
+/// Render `tracks` as a playlist file. When `local_files` (video_id → path)
+/// is given, M3U8 entries point at the cached file where one exists.
+pub(crate) fn export_playlist(
+    name: &str,
+    tracks: &[Track],
+    format: PlaylistFormat,
+    local_files: Option<&HashMap<String, String>>,
+) -> String {
+    match format {
+        PlaylistFormat::M3u8 => export_m3u8(name, tracks, local_files),
+        PlaylistFormat::Xspf => export_xspf(name, tracks),
+        PlaylistFormat::Json => export_json(name, tracks),
+    }
+}
+
+/// Write `tracks` to `path`, choosing the format from the extension.
+pub(crate) fn export_to_file(
+    path: &Path,
+    name: &str,
+    tracks: &[Track],
+    local_files: Option<&HashMap<String, String>>,
+) -> Result<PlaylistFormat> {
+    let format = PlaylistFormat::from_path(path)
+        .context("Unknown playlist extension — use .m3u8, .xspf, or .json")?;
+    let contents = export_playlist(name, tracks, format, local_files);
+    write_atomic(path, contents.as_bytes())
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(format)
+}
+
+fn export_m3u8(
+    name: &str,
+    tracks: &[Track],
+    local_files: Option<&HashMap<String, String>>,
+) -> String {
+    let mut out = String::from("#EXTM3U\n");
+    out.push_str(&format!("#PLAYLIST:{}\n", one_line(name)));
+    for track in tracks {
+        out.push_str(&format!(
+            "#EXTINF:{},{}\n",
+            track.duration,
+            one_line(&track.title)
+        ));
+        if !track.uploader.is_empty() {
+            out.push_str(&format!("#EXTART:{}\n", one_line(&track.uploader)));
+        }
+        let local = local_files
+            .and_then(|files| files.get(&track.video_id))
+            .filter(|path| Path::new(path).exists());
+        match local {
+            Some(path) => {
+                out.push_str(&format!("{}{}\n", M3U_URL_TAG, track.url));
+                out.push_str(&format!("{}\n", one_line(path)));
+            }
+            None => out.push_str(&format!("{}\n", track.url)),
+        }
+    }
+    out
+}
+
+fn export_xspf(name: &str, tracks: &[Track]) -> String {
+    let mut out = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<playlist version=\"1\" xmlns=\"http://xspf.org/ns/0/\">\n",
+    );
+    out.push_str(&format!(
+        "  <title>{}</title>\n  <trackList>\n",
+        xml_escape(name)
+    ));
+    for track in tracks {
+        out.push_str("    <track>\n");
+        out.push_str(&format!(
+            "      <location>{}</location>\n",
+            xml_escape(&track.url)
+        ));
+        out.push_str(&format!(
+            "      <title>{}</title>\n",
+            xml_escape(&track.title)
+        ));
+        if !track.uploader.is_empty() {
+            out.push_str(&format!(
+                "      <creator>{}</creator>\n",
+                xml_escape(&track.uploader)
+            ));
+        }
+        if track.duration > 0 {
+            // XSPF durations are in milliseconds.
+            out.push_str(&format!(
+                "      <duration>{}</duration>\n",
+                track.duration * 1000
+            ));
+        }
+        out.push_str("    </track>\n");
+    }
+    out.push_str("  </trackList>\n</playlist>\n");
+    out
+}
+
+/// Versioned Crusty JSON document.
+#[derive(Debug, Serialize, Deserialize)]
+struct CrustyPlaylist {
+    format: String,
+    version: u32,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    exported_at: i64,
+    #[serde(default)]
+    tracks: Vec<serde_json::Value>,
+}
+
+/// One track in a Crusty JSON export. Decoded per entry so a single bad
+/// track is skipped instead of rejecting the file.
+#[derive(Debug, Serialize, Deserialize)]
+struct CrustyTrack {
+    video_id: String,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    uploader: String,
+    #[serde(default)]
+    duration: u64,
+    url: String,
+}
+
+fn export_json(name: &str, tracks: &[Track]) -> String {
+    let doc = CrustyPlaylist {
+        format: CRUSTY_JSON_FORMAT.to_string(),
+        version: CRUSTY_JSON_VERSION,
+        name: name.to_string(),
+        exported_at: now_unix(),
+        tracks: tracks
+            .iter()
+            .map(|t| {
+                serde_json::to_value(CrustyTrack {
+                    video_id: t.video_id.clone(),
+                    title: t.title.clone(),
+                    uploader: t.uploader.clone(),
+                    duration: t.duration,
+                    url: t.url.clone(),
+                })
+                .unwrap_or_default()
+            })
+            .collect(),
+    };
+    // Serializing plain strings and integers cannot fail.
+    serde_json::to_string_pretty(&doc).unwrap_or_default()
+}
+
+/// Strip line breaks so a value can't start a new M3U8 line.
+fn one_line(s: &str) -> String {
+    s.replace(['\r', '\n'], " ")
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            // Control characters other than tab/newline are invalid in XML 1.0.
+            c if c.is_control() && c != '\t' && c != '\n' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+// ---------------------------------------------------------------------------
+// Import
+// ---------------------------------------------------------------------------
+
+/// An entry that was not imported, and why.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SkippedEntry {
+    /// Line number (M3U8, XSPF) or 1-based track index (JSON).
+    pub position: usize,
+    pub reason: String,
+}
+
+impl std::fmt::Display for SkippedEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{}: {}", self.position, self.reason)
+    }
+}
+
+/// Result of parsing a playlist file.
+#[derive(Debug, Default)]
+pub(crate) struct ImportReport {
+    /// Playlist title stored in the file, if any.
+    pub name: Option<String>,
+    pub tracks: Vec<Track>,
+    pub skipped: Vec<SkippedEntry>,
+}
+
+impl ImportReport {
+    /// Validate one entry and add it, or record why it was skipped.
+    fn push(
+        &mut self,
+        seen: &mut HashSet<String>,
+        position: usize,
+        url: &str,
+        title: Option<String>,
+        uploader: Option<String>,
+        duration: u64,
+    ) {
+        let url = url.trim();
+        let reason = if !is_allowed_youtube_url(url) {
+            Some(format!("not a YouTube URL: {}", truncate(url, 60)))
+        } else {
+            match video_id_from_url(url) {
+                None => Some("no video id in URL".to_string()),
+                Some(id) if !is_valid_video_id(&id) => Some(format!("invalid video id '{}'", id)),
+                Some(id) if !seen.insert(id.clone()) => Some("duplicate track".to_string()),
+                Some(id) => {
+                    if self.tracks.len() >= MAX_ENTRY_COUNT {
+                        Some("too many tracks".to_string())
+                    } else {
+                        let title = title
+                            .filter(|t| !t.trim().is_empty())
+                            .unwrap_or_else(|| id.clone());
+                        self.tracks.push(Track::new(
+                            id,
+                            title.trim().to_string(),
+                            duration,
+                            uploader.unwrap_or_default().trim().to_string(),
+                            url.to_string(),
+                        ));
+                        None
+                    }
+                }
+            }
+        };
+        if let Some(reason) = reason {
+            self.skipped.push(SkippedEntry { position, reason });
+        }
+    }
+}
+
+/// Parse a playlist from `contents`.
+pub(crate) fn import_playlist(contents: &str, format: PlaylistFormat) -> Result<ImportReport> {
+    let contents = contents.trim_start_matches('\u{feff}');
+    match format {
+        PlaylistFormat::M3u8 => Ok(import_m3u8(contents)),
+        PlaylistFormat::Xspf => import_xspf(contents),
+        PlaylistFormat::Json => import_json(contents),
+    }
+}
+
+/// Read and parse a playlist file. The format comes from the extension, or
+/// from the contents if the extension is unknown.
+pub(crate) fn import_from_file(path: &Path) -> Result<ImportReport> {
+    let metadata =
+        std::fs::metadata(path).with_context(|| format!("Cannot read {}", path.display()))?;
+    if metadata.len() > MAX_FILE_SIZE {
+        anyhow::bail!("Playlist file too large ({} bytes)", metadata.len());
+    }
+    let contents =
+        std::fs::read_to_string(path).with_context(|| format!("Cannot read {}", path.display()))?;
+    let format = PlaylistFormat::from_path(path)
+        .or_else(|| PlaylistFormat::sniff(&contents))
+        .context("Unrecognised playlist format — expected M3U8, XSPF, or JSON")?;
+    import_playlist(&contents, format)
+}
+
+fn import_m3u8(contents: &str) -> ImportReport {
+    let mut report = ImportReport::default();
+    let mut seen = HashSet::new();
+    let mut title: Option<String> = None;
+    let mut uploader: Option<String> = None;
+    let mut duration = 0;
+    let mut tagged_url: Option<String> = None;
+
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix("#PLAYLIST:") {
+            report.name = Some(name.trim().to_string());
+        } else if let Some(info) = line.strip_prefix("#EXTINF:") {
+            // "#EXTINF:<secs>[ attrs],<title>"
+            let (head, rest) = info.split_once(',').unwrap_or((info, ""));
+            let secs = head.split_whitespace().next().unwrap_or("");
+            duration = secs.parse::<i64>().ok().filter(|&d| d > 0).unwrap_or(0) as u64;
+            title = Some(rest.to_string());
+        } else if let Some(artist) = line.strip_prefix("#EXTART:") {
+            uploader = Some(artist.to_string());
+        } else if let Some(url) = line.strip_prefix(M3U_URL_TAG) {
+            tagged_url = Some(url.trim().to_string());
+        } else if line.starts_with('#') {
+            // Other directives and comments.
+        } else {
+            // A location line closes the entry. Local files are resolved via
+            // the URL tag written on export.
+            let url = if is_allowed_youtube_url(line) {
+                line.to_string()
+            } else {
+                tagged_url.take().unwrap_or_else(|| line.to_string())
+            };
+            report.push(
+                &mut seen,
+                i + 1,
+                &url,
+                title.take(),
+                uploader.take(),
+                duration,
+            );
+            duration = 0;
+            tagged_url = None;
+        }
+    }
+    report
+}
+
+fn import_xspf(contents: &str) -> Result<ImportReport> {
+    let doc = roxmltree::Document::parse(contents).context("Invalid XSPF (XML) file")?;
+    let root = doc.root_element();
+    if !root.has_tag_name("playlist") {
+        anyhow::bail!(
+            "Invalid XSPF file: root element is <{}>",
+            root.tag_name().name()
+        );
+    }
+
+    let child_text = |node: roxmltree::Node, name: &str| -> Option<String> {
+        node.children()
+            .find(|c| c.is_element() && c.tag_name().name() == name)
+            .and_then(|c| c.text())
+            .map(|t| t.trim().to_string())
+    };
+
+    let mut report = ImportReport {
+        name: child_text(root, "title"),
+        ..Default::default()
+    };
+    let mut seen = HashSet::new();
+    let tracks = root
+        .descendants()
+        .filter(|n| n.is_element() && n.tag_name().name() == "track");
+    for track in tracks {
+        let line = doc.text_pos_at(track.range().start).row as usize;
+        let Some(location) = child_text(track, "location") else {
+            report.skipped.push(SkippedEntry {
+                position: line,
+                reason: "track has no <location>".to_string(),
+            });
+            continue;
+        };
+        let duration = child_text(track, "duration")
+            .and_then(|ms| ms.parse::<u64>().ok())
+            .map_or(0, |ms| ms / 1000);
+        report.push(
+            &mut seen,
+            line,
+            &location,
+            child_text(track, "title"),
+            child_text(track, "creator"),
+            duration,
+        );
+    }
+    Ok(report)
+}
+
+fn import_json(contents: &str) -> Result<ImportReport> {
+    let doc: CrustyPlaylist =
+        serde_json::from_str(contents).context("Invalid Crusty JSON playlist")?;
+    if doc.format != CRUSTY_JSON_FORMAT {
+        anyhow::bail!("Not a Crusty playlist (format '{}')", doc.format);
+    }
+    if doc.version > CRUSTY_JSON_VERSION {
+        anyhow::bail!(
+            "Playlist version {} is newer than this build supports (v{})",
+            doc.version,
+            CRUSTY_JSON_VERSION
+        );
+    }
+
+    let mut report = ImportReport {
+        name: Some(doc.name).filter(|n| !n.trim().is_empty()),
+        ..Default::default()
+    };
+    let mut seen = HashSet::new();
+    for (i, value) in doc.tracks.into_iter().enumerate() {
+        match serde_json::from_value::<CrustyTrack>(value) {
+            Ok(t) if video_id_from_url(&t.url).as_deref() != Some(t.video_id.as_str()) => {
+                report.skipped.push(SkippedEntry {
+                    position: i + 1,
+                    reason: format!(
+                        "video id '{}' does not match its URL",
+                        truncate(&t.video_id, 20)
+                    ),
+                });
+            }
+            Ok(t) => report.push(
+                &mut seen,
+                i + 1,
+                &t.url,
+                Some(t.title),
+                Some(t.uploader),
+                t.duration,
+            ),
+            Err(e) => report.skipped.push(SkippedEntry {
+                position: i + 1,
+                reason: format!("malformed track: {}", e),
+            }),
+        }
+    }
+    Ok(report)
+}
+
+/// Extract the video id from a `watch?v=`, `youtu.be/`, or `shorts/` URL.
+fn video_id_from_url(url: &str) -> Option<String> {
+    let rest = url.split_once("://").map_or(url, |(_, r)| r);
+    let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+    let id = if host == "youtu.be" {
+        path.split(['?', '#', '/']).next()
+    } else if let Some(shorts) = path.strip_prefix("shorts/") {
+        shorts.split(['?', '#', '/']).next()
+    } else {
+        let query = path.split_once('?')?.1;
+        query
+            .split(['&', '#'])
+            .find_map(|pair| pair.strip_prefix("v="))
+    }?;
+    (!id.is_empty()).then(|| id.to_string())
+}
+
+fn truncate(s: &str, max: usize) -> String {
+    if s.chars().count() > max {
+        let t: String = s.chars().take(max.saturating_sub(1)).collect();
+        format!("{t}…")
+    } else {
+        s.to_string()
+    }
+}
+
+/// Expand a leading `~/` to the home directory.
+pub(crate) fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Suggested export path: `<Music dir>/<name>.m3u8`, with characters that
+/// are awkward in file names replaced.
+pub(crate) fn default_export_path(name: &str) -> PathBuf {
+    let dir = dirs::audio_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(|| PathBuf::from("."));
+    let stem: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || " -_".contains(c) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let stem = if stem.trim().is_empty() {
+        "playlist".to_string()
+    } else {
+        stem
+    };
+    dir.join(format!("{}.m3u8", stem.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_track(id: &str, title: &str) -> Track {
+        Track::new(
+            id.to_string(),
+            title.to_string(),
+            200,
+            "Artist".to_string(),
+            format!("https://www.youtube.com/watch?v={id}"),
+        )
+    }
+
+    fn ids(report: &ImportReport) -> Vec<&str> {
+        report.tracks.iter().map(|t| t.video_id.as_str()).collect()
+    }
+
+    #[test]
+    fn format_from_extension() {
+        assert_eq!(
+            PlaylistFormat::from_path(Path::new("a.M3U8")),
+            Some(PlaylistFormat::M3u8)
+        );
+        assert_eq!(
+            PlaylistFormat::from_path(Path::new("a.m3u")),
+            Some(PlaylistFormat::M3u8)
+        );
+        assert_eq!(
+            PlaylistFormat::from_path(Path::new("a.xspf")),
+            Some(PlaylistFormat::Xspf)
+        );
+        assert_eq!(
+            PlaylistFormat::from_path(Path::new("a.json")),
+            Some(PlaylistFormat::Json)
+        );
+        assert_eq!(PlaylistFormat::from_path(Path::new("a.txt")), None);
+    }
+
+    #[test]
+    fn round_trip_every_format() {
+        let tracks = vec![
+            make_track("aaaaaaaaaaa", "Tom & Jerry <Live>"),
+            make_track("bbbbbbbbbbb", "Second"),
+        ];
+        for format in [
+            PlaylistFormat::M3u8,
+            PlaylistFormat::Xspf,
+            PlaylistFormat::Json,
+        ] {
+            let text = export_playlist("My \"list\"", &tracks, format, None);
+            let report = import_playlist(&text, format).unwrap();
+            assert_eq!(report.name.as_deref(), Some("My \"list\""), "{format:?}");
+            assert_eq!(report.tracks, tracks, "{format:?}");
+            assert!(report.skipped.is_empty(), "{format:?}");
+        }
+    }
+
+    #[test]
+    fn m3u8_uses_cached_files_and_reimports_via_url_tag() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let path = file.path().to_string_lossy().to_string();
+        let tracks = vec![
+            make_track("aaaaaaaaaaa", "Cached"),
+            make_track("bbbbbbbbbbb", "Remote"),
+        ];
+        let local = HashMap::from([("aaaaaaaaaaa".to_string(), path.clone())]);
+
+        let text = export_playlist("x", &tracks, PlaylistFormat::M3u8, Some(&local));
+        assert!(text.lines().any(|l| l == path));
+        assert!(text.contains("https://www.youtube.com/watch?v=bbbbbbbbbbb"));
+
+        let report = import_playlist(&text, PlaylistFormat::M3u8).unwrap();
+        assert_eq!(ids(&report), ["aaaaaaaaaaa", "bbbbbbbbbbb"]);
+    }
+
+    #[test]
+    fn m3u8_import_reports_skipped_lines() {
+        let text = "#EXTM3U\n\
+                    #EXTINF:100,Good\n\
+                    https://youtu.be/aaaaaaaaaaa\n\
+                    https://evil.example.com/watch?v=bbbbbbbbbbb\n\
+                    /home/me/song.mp3\n\
+                    https://www.youtube.com/watch?v=bad$id\n\
+                    https://music.youtube.com/watch?v=aaaaaaaaaaa\n\
+                    https://www.youtube.com/playlist?list=PL123\n";
+        let report = import_playlist(text, PlaylistFormat::M3u8).unwrap();
+        assert_eq!(ids(&report), ["aaaaaaaaaaa"]);
+        assert_eq!(report.tracks[0].title, "Good");
+        assert_eq!(report.tracks[0].duration, 100);
+        let lines: Vec<usize> = report.skipped.iter().map(|s| s.position).collect();
+        assert_eq!(lines, [4, 5, 6, 7, 8]);
+        assert!(report.skipped[3].reason.contains("duplicate"));
+    }
+
+    #[test]
+    fn xspf_import_validates_entries() {
+        let text = r#"<?xml version="1.0"?>
+<playlist version="1" xmlns="http://xspf.org/ns/0/">
+  <trackList>
+    <track><location>https://www.youtube.com/watch?v=aaaaaaaaaaa&amp;t=5</location><duration>61000</duration></track>
+    <track><title>No location</title></track>
+    <track><location>file:///music/a.mp3</location></track>
+  </trackList>
+</playlist>"#;
+        let report = import_playlist(text, PlaylistFormat::Xspf).unwrap();
+        assert_eq!(ids(&report), ["aaaaaaaaaaa"]);
+        assert_eq!(report.tracks[0].duration, 61);
+        assert_eq!(report.tracks[0].title, "aaaaaaaaaaa");
+        assert_eq!(report.skipped.len(), 2);
+        assert_eq!(report.skipped[0].position, 5);
+    }
+
+    #[test]
+    fn xspf_rejects_malformed_xml() {
+        assert!(import_playlist("<playlist><trackList>", PlaylistFormat::Xspf).is_err());
+        assert!(import_playlist("<html/>", PlaylistFormat::Xspf).is_err());
+    }
+
+    #[test]
+    fn json_rejects_newer_version_and_foreign_format() {
+        let newer = format!(
+            r#"{{"format":"crusty-playlist","version":{}}}"#,
+            CRUSTY_JSON_VERSION + 1
+        );
+        assert!(import_playlist(&newer, PlaylistFormat::Json).is_err());
+        let foreign = r#"{"format":"other","version":1}"#;
+        assert!(import_playlist(foreign, PlaylistFormat::Json).is_err());
+    }
+
+    #[test]
+    fn json_skips_bad_tracks_individually() {
+        let text = r#"{"format":"crusty-playlist","version":1,"name":"n","tracks":[
+            {"video_id":"aaaaaaaaaaa","url":"https://www.youtube.com/watch?v=aaaaaaaaaaa"},
+            {"video_id":"ccccccccccc","url":"https://www.youtube.com/watch?v=bbbbbbbbbbb"},
+            {"title":"missing fields"}
+        ]}"#;
+        let report = import_playlist(text, PlaylistFormat::Json).unwrap();
+        assert_eq!(ids(&report), ["aaaaaaaaaaa"]);
+        let positions: Vec<usize> = report.skipped.iter().map(|s| s.position).collect();
+        assert_eq!(positions, [2, 3]);
+    }
+
+    #[test]
+    fn import_from_file_sniffs_unknown_extension() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("list.txt");
+        std::fs::write(&path, "#EXTM3U\nhttps://youtu.be/aaaaaaaaaaa\n").unwrap();
+        assert_eq!(ids(&import_from_file(&path).unwrap()), ["aaaaaaaaaaa"]);
+    }
+
+    #[test]
+    fn export_to_file_requires_known_extension() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let tracks = [make_track("aaaaaaaaaaa", "A")];
+        assert!(export_to_file(&dir.path().join("x.txt"), "x", &tracks, None).is_err());
+        let path = dir.path().join("x.xspf");
+        assert_eq!(
+            export_to_file(&path, "x", &tracks, None).unwrap(),
+            PlaylistFormat::Xspf
+        );
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn video_id_from_common_urls() {
+        assert_eq!(
+            video_id_from_url("https://youtu.be/abc?t=1").as_deref(),
+            Some("abc")
+        );
+        assert_eq!(
+            video_id_from_url("https://music.youtube.com/watch?list=x&v=abc").as_deref(),
+            Some("abc")
+        );
+        assert_eq!(
+            video_id_from_url("https://www.youtube.com/shorts/abc").as_deref(),
+            Some("abc")
+        );
+        assert_eq!(
+            video_id_from_url("https://www.youtube.com/playlist?list=PL1"),
+            None
+        );
+    }
+
+    #[test]
+    fn default_export_path_sanitises_name() {
+        let path = default_export_path("Road/trip: 2024");
+        assert_eq!(path.file_name().unwrap(), "Road_trip_ 2024.m3u8");
+    }
+}
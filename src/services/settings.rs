@@ -0,0 +1,206 @@
+//! User-editable settings in `settings.json` in the config directory.
+//!
+//! Every field has a default, so a missing file or a file written by an
+//! older version loads cleanly.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{DEFAULT_AUDIO_BITRATE_KBPS, DEFAULT_CACHE_QUOTA_MB};
+
+/// Bitrates outside this range (kbps) are clamped.
+const BITRATE_RANGE_KBPS: std::ops::RangeInclusive<u32> = 32..=320;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct Settings {
+    /// Size limit of the offline audio cache in MiB. Least-recently-played
+    /// tracks are evicted past it; pinned tracks never are. `0` = no limit.
+    pub cache_quota_mb: u64,
+    /// Format downloads are stored in.
+    pub audio_format: AudioFormat,
+    /// Target bitrate when transcoding to a lossy format.
+    pub audio_bitrate_kbps: u32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            cache_quota_mb: DEFAULT_CACHE_QUOTA_MB,
+            audio_format: AudioFormat::default(),
+            audio_bitrate_kbps: DEFAULT_AUDIO_BITRATE_KBPS,
+        }
+    }
+}
+
+impl Settings {
+    /// The cache quota in bytes, or `None` if unlimited.
+    pub(crate) fn cache_quota_bytes(&self) -> Option<u64> {
+        (self.cache_quota_mb > 0).then(|| self.cache_quota_mb.saturating_mul(1024 * 1024))
+    }
+
+    /// How downloads are fetched and stored.
+    pub(crate) fn audio_profile(&self) -> AudioProfile {
+        AudioProfile {
+            format: self.audio_format,
+            bitrate_kbps: self
+                .audio_bitrate_kbps
+                .clamp(*BITRATE_RANGE_KBPS.start(), *BITRATE_RANGE_KBPS.end()),
+        }
+    }
+}
+
+/// Audio format of downloaded files (`audio_format` in `settings.json`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum AudioFormat {
+    /// YouTube's own stream — usually Opus, sometimes AAC — extracted
+    /// without re-encoding.
+    Native,
+    /// Opus; Opus streams are copied as they are, others transcoded.
+    Opus,
+    /// AAC in M4A; AAC streams are copied as they are, others transcoded.
+    M4a,
+    #[default]
+    Mp3,
+    /// Lossless container for whatever was downloaded. Large files.
+    Flac,
+}
+
+/// A format plus bitrate: what yt-dlp is asked for and what the cache
+/// files are keyed by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct AudioProfile {
+    pub format: AudioFormat,
+    pub bitrate_kbps: u32,
+}
+
+impl AudioProfile {
+    /// Tag in cache filenames (`<video_id>.<tag>.<ext>`), so files from
+    /// another format or bitrate are never mistaken for current ones.
+    /// MP3 at the default bitrate was the only format before this became
+    /// configurable; it keeps the plain `<video_id>.mp3` name so existing
+    /// caches stay valid.
+    pub(crate) fn cache_tag(&self) -> String {
+        match self.format {
+            AudioFormat::Mp3 if self.bitrate_kbps == DEFAULT_AUDIO_BITRATE_KBPS => String::new(),
+            AudioFormat::Native => "native".to_string(),
+            AudioFormat::Flac => "flac".to_string(),
+            AudioFormat::Opus => format!("opus{}k", self.bitrate_kbps),
+            AudioFormat::M4a => format!("m4a{}k", self.bitrate_kbps),
+            AudioFormat::Mp3 => format!("mp3{}k", self.bitrate_kbps),
+        }
+    }
+
+    /// yt-dlp format selection and extraction arguments. yt-dlp copies
+    /// the stream instead of transcoding when its codec already matches,
+    /// so Opus and M4A prefer streams in that codec.
+    pub(crate) fn ytdlp_args(&self) -> Vec<String> {
+        let (selector, target) = match self.format {
+            AudioFormat::Native => ("bestaudio/best", "best"),
+            AudioFormat::Opus => ("bestaudio[acodec=opus]/bestaudio/best", "opus"),
+            AudioFormat::M4a => ("bestaudio[ext=m4a]/bestaudio/best", "m4a"),
+            AudioFormat::Mp3 => ("bestaudio/best", "mp3"),
+            AudioFormat::Flac => ("bestaudio/best", "flac"),
+        };
+        let mut args = vec![
+            "-f".to_string(),
+            selector.to_string(),
+            "-x".to_string(),
+            "--audio-format".to_string(),
+            target.to_string(),
+        ];
+        if matches!(
+            self.format,
+            AudioFormat::Opus | AudioFormat::M4a | AudioFormat::Mp3
+        ) {
+            args.push("--audio-quality".to_string());
+            args.push(format!("{}K", self.bitrate_kbps));
+        }
+        args
+    }
+
+    /// yt-dlp arguments that tag downloaded files, so they're usable
+    /// outside Crusty: title, artist (the uploader when YouTube has no
+    /// artist), album when known, and the video URL as a comment. Cover
+    /// art is embedded into MP3 and M4A, which ffmpeg can write; Opus and
+    /// FLAC would need yt-dlp's optional mutagen dependency, and a missing
+    /// one fails the whole download.
+    pub(crate) fn embed_args(&self) -> Vec<String> {
+        let mut args = vec!["--embed-metadata".to_string()];
+        if matches!(self.format, AudioFormat::Mp3 | AudioFormat::M4a) {
+            args.extend([
+                "--embed-thumbnail".to_string(),
+                "--convert-thumbnails".to_string(),
+                "jpg".to_string(),
+            ]);
+        }
+        args
+    }
+}
+
+impl std::fmt::Display for AudioProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.format {
+            AudioFormat::Native => write!(f, "native (no re-encoding)"),
+            AudioFormat::Opus => write!(f, "Opus {} kbps", self.bitrate_kbps),
+            AudioFormat::M4a => write!(f, "M4A {} kbps", self.bitrate_kbps),
+            AudioFormat::Mp3 => write!(f, "MP3 {} kbps", self.bitrate_kbps),
+            AudioFormat::Flac => write!(f, "FLAC"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_fields_take_defaults() {
+        let settings: Settings = serde_json::from_str("{}").unwrap();
+        assert_eq!(settings, Settings::default());
+    }
+
+    #[test]
+    fn zero_quota_is_unlimited() {
+        let settings = Settings {
+            cache_quota_mb: 0,
+            ..Settings::default()
+        };
+        assert_eq!(settings.cache_quota_bytes(), None);
+        let settings = Settings {
+            cache_quota_mb: 2,
+            ..Settings::default()
+        };
+        assert_eq!(settings.cache_quota_bytes(), Some(2 * 1024 * 1024));
+    }
+
+    #[test]
+    fn audio_profile_tags_and_args() {
+        let settings: Settings =
+            serde_json::from_str(r#"{"audio_format": "opus", "audio_bitrate_kbps": 9999}"#)
+                .unwrap();
+        let profile = settings.audio_profile();
+        assert_eq!(profile.bitrate_kbps, 320);
+        assert_eq!(profile.cache_tag(), "opus320k");
+        assert!(profile.ytdlp_args().ends_with(&[
+            "opus".to_string(),
+            "--audio-quality".to_string(),
+            "320K".to_string()
+        ]));
+
+        // The default keeps the pre-existing `<id>.mp3` cache names.
+        assert_eq!(Settings::default().audio_profile().cache_tag(), "");
+
+        let native = AudioProfile {
+            format: AudioFormat::Native,
+            bitrate_kbps: 192,
+        };
+        assert_eq!(native.cache_tag(), "native");
+        assert!(!native.ytdlp_args().contains(&"--audio-quality".to_string()));
+        assert_eq!(native.embed_args(), ["--embed-metadata"]);
+        assert!(Settings::default()
+            .audio_profile()
+            .embed_args()
+            .contains(&"--embed-thumbnail".to_string()));
+    }
+}
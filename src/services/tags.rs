@@ -0,0 +1,149 @@
+//! Free-form user tags ("coding", "gym", "chill") attached to video ids.
+//!
+//! Tags are stored in the library database keyed by `video_id`, so they are
+//! unaffected by the download cache. This module holds the pure parts:
+//! normalizing user input and parsing AND/OR tag queries.
+
+/// Maximum length of a single tag, in characters.
+pub(crate) const MAX_TAG_LEN: usize = 32;
+
+/// Maximum number of tags on one track.
+pub(crate) const MAX_TAGS_PER_TRACK: usize = 16;
+
+/// Lower-case `raw`, join inner whitespace with `-`, and drop anything but
+/// letters, digits, `-` and `_`. Returns `None` if nothing is left.
+pub(crate) fn normalize_tag(raw: &str) -> Option<String> {
+    let tag: String = raw
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+        .flat_map(char::to_lowercase)
+        .take(MAX_TAG_LEN)
+        .collect();
+    let tag = tag.trim_matches('-').to_string();
+    (!tag.is_empty()).then_some(tag)
+}
+
+/// Parse the tag editor's comma-separated input into normalized, unique
+/// tags in entry order.
+pub(crate) fn parse_tag_list(input: &str) -> Result<Vec<String>, String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in input.split(',').filter_map(normalize_tag) {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    if tags.len() > MAX_TAGS_PER_TRACK {
+        return Err(format!("at most {} tags per track", MAX_TAGS_PER_TRACK));
+    }
+    Ok(tags)
+}
+
+/// Which tracks a set of tags selects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TagQuery {
+    /// Tracks carrying every one of these tags.
+    All(Vec<String>),
+    /// Tracks carrying at least one of these tags.
+    Any(Vec<String>),
+}
+
+impl TagQuery {
+    pub(crate) fn tags(&self) -> &[String] {
+        match self {
+            TagQuery::All(tags) | TagQuery::Any(tags) => tags,
+        }
+    }
+
+    /// Parse `gym & coding` (AND) or `chill | coding` (OR). A single tag is
+    /// an OR of one. Mixing `&` and `|` is rejected rather than guessing at
+    /// precedence.
+    pub(crate) fn parse(input: &str) -> Result<TagQuery, String> {
+        let has_and = input.contains('&');
+        let has_or = input.contains('|');
+        if has_and && has_or {
+            return Err("use either & (all) or | (any), not both".to_string());
+        }
+        let separator = if has_and { '&' } else { '|' };
+        let mut tags: Vec<String> = Vec::new();
+        for tag in input.split(separator).filter_map(normalize_tag) {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        if tags.is_empty() {
+            return Err("enter at least one tag".to_string());
+        }
+        Ok(if has_and {
+            TagQuery::All(tags)
+        } else {
+            TagQuery::Any(tags)
+        })
+    }
+}
+
+impl std::fmt::Display for TagQuery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TagQuery::All(tags) => write!(f, "{}", tags.join(" & ")),
+            TagQuery::Any(tags) => write!(f, "{}", tags.join(" | ")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_lowercases_and_joins_words() {
+        assert_eq!(
+            normalize_tag("  Late Night "),
+            Some("late-night".to_string())
+        );
+        assert_eq!(normalize_tag("Lo-Fi!"), Some("lo-fi".to_string()));
+        assert_eq!(normalize_tag(" #! "), None);
+        assert_eq!(
+            normalize_tag(&"x".repeat(50)).map(|t| t.len()),
+            Some(MAX_TAG_LEN)
+        );
+    }
+
+    #[test]
+    fn parse_tag_list_dedupes_and_skips_blanks() {
+        assert_eq!(
+            parse_tag_list("coding, Gym,, coding ,chill").unwrap(),
+            ["coding", "gym", "chill"]
+        );
+        assert!(parse_tag_list("").unwrap().is_empty());
+        let many: Vec<String> = (0..=MAX_TAGS_PER_TRACK).map(|i| format!("t{i}")).collect();
+        assert!(parse_tag_list(&many.join(",")).is_err());
+    }
+
+    #[test]
+    fn query_parses_and_or_and_single() {
+        assert_eq!(
+            TagQuery::parse("gym & Coding").unwrap(),
+            TagQuery::All(vec!["gym".to_string(), "coding".to_string()])
+        );
+        assert_eq!(
+            TagQuery::parse("chill | coding").unwrap(),
+            TagQuery::Any(vec!["chill".to_string(), "coding".to_string()])
+        );
+        assert_eq!(
+            TagQuery::parse(" chill ").unwrap(),
+            TagQuery::Any(vec!["chill".to_string()])
+        );
+        assert!(TagQuery::parse("a & b | c").is_err());
+        assert!(TagQuery::parse(" & ").is_err());
+    }
+
+    #[test]
+    fn query_display_round_trips() {
+        let query = TagQuery::parse("gym&coding").unwrap();
+        assert_eq!(query.to_string(), "gym & coding");
+        assert_eq!(TagQuery::parse(&query.to_string()).unwrap(), query);
+    }
+}
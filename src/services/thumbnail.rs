@@ -0,0 +1,334 @@
+//! Cover art: video and playlist thumbnails, small enough for a terminal.
+//!
+//! Thumbnails are fetched by yt-dlp (`--write-thumbnail`), so they go
+//! through the same cookie-aware path as everything else, then decoded,
+//! cropped square and scaled down by ffmpeg. Only the small RGB image is
+//! kept, as a PPM file named after the video or playlist id in the cache
+//! directory, which is trimmed to a size limit by dropping the covers shown
+//! least recently. yt-dlp is given the video or playlist page rather than
+//! a thumbnail URL captured with a listing, so it picks the best size and
+//! private playlists get their cookies.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::config::{is_allowed_youtube_url, COVER_SIZE_PX, THUMBNAIL_CACHE_MAX_BYTES};
+use crate::services::cache_store::{mark_used, trim_dir};
+use crate::services::persistence::write_atomic;
+use crate::youtube::extractor::is_valid_list_id;
+use crate::youtube::ytdlp::{YtDlp, YtDlpArgs};
+
+/// A decoded cover: `width × height` pixels, three bytes (RGB) each.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Cover {
+    pub width: u32,
+    pub height: u32,
+    pub rgb: Vec<u8>,
+}
+
+impl Cover {
+    /// The cover scaled to `width × height`, each pixel the average of the
+    /// source pixels it covers.
+    pub(crate) fn resample(&self, width: u32, height: u32) -> Vec<[u8; 3]> {
+        let mut out = Vec::with_capacity((width * height) as usize);
+        for y in 0..height {
+            let (y0, y1) = span(y, height, self.height);
+            for x in 0..width {
+                let (x0, x1) = span(x, width, self.width);
+                let mut sum = [0u32; 3];
+                for sy in y0..y1 {
+                    for sx in x0..x1 {
+                        let i = ((sy * self.width + sx) * 3) as usize;
+                        for (c, total) in sum.iter_mut().enumerate() {
+                            *total += u32::from(self.rgb[i + c]);
+                        }
+                    }
+                }
+                let n = (y1 - y0) * (x1 - x0);
+                out.push(sum.map(|total| (total / n) as u8));
+            }
+        }
+        out
+    }
+
+    fn to_ppm(&self) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        bytes.extend_from_slice(&self.rgb);
+        bytes
+    }
+
+    fn from_ppm(bytes: &[u8]) -> Option<Self> {
+        // "P6\n<width> <height>\n255\n" then the pixels.
+        let mut fields = Vec::new();
+        let mut start = 0;
+        for (i, b) in bytes.iter().enumerate() {
+            if b.is_ascii_whitespace() {
+                if i > start {
+                    fields.push(std::str::from_utf8(&bytes[start..i]).ok()?);
+                }
+                start = i + 1;
+                if fields.len() == 4 {
+                    break;
+                }
+            }
+        }
+        let [magic, width, height, max] = fields[..] else {
+            return None;
+        };
+        let (width, height): (u32, u32) = (width.parse().ok()?, height.parse().ok()?);
+        if magic != "P6" || max != "255" || width == 0 || height == 0 {
+            return None;
+        }
+        let rgb = bytes.get(start..)?;
+        // A damaged header can claim any size; one that doesn't fit is no cover.
+        let len = width.checked_mul(height)?.checked_mul(3)?;
+        (usize::try_from(len).ok()? == rgb.len()).then(|| Cover {
+            width,
+            height,
+            rgb: rgb.to_vec(),
+        })
+    }
+}
+
+/// The source rows (or columns) `[from, to)` that output row `i` of `out`
+/// covers when scaling from `src`. Never empty.
+fn span(i: u32, out: u32, src: u32) -> (u32, u32) {
+    let from = i * src / out;
+    let to = ((i + 1) * src / out).max(from + 1).min(src);
+    (from.min(src - 1), to)
+}
+
+/// The cover of the video or playlist at `page_url`, cached as `key`: from
+/// the cache, or — unless `offline` — fetched, decoded and cached.
+pub(crate) fn load_cover(
+    ytdlp: &dyn YtDlp,
+    key: &str,
+    page_url: &str,
+    cookie_config: Option<(bool, String)>,
+    cache_dir: &Path,
+    offline: bool,
+) -> Result<Cover, String> {
+    if !is_valid_list_id(key) {
+        return Err(format!("Invalid cover id '{}'", key));
+    }
+    let cached = cache_dir.join(format!("{}.ppm", key));
+    if let Some(cover) = fs::read(&cached)
+        .ok()
+        .and_then(|bytes| Cover::from_ppm(&bytes))
+    {
+        // Mark it recently shown, so trimming keeps it.
+        mark_used(&cached);
+        return Ok(cover);
+    }
+    if offline {
+        return Err("Cover not cached — unavailable offline".to_string());
+    }
+
+    let tmp = tempfile::tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let thumbnail = fetch_thumbnail(ytdlp, page_url, key, cookie_config, tmp.path())?;
+    let cover = decode_thumbnail(&thumbnail, COVER_SIZE_PX)?;
+    fs::create_dir_all(cache_dir)
+        .map_err(|e| format!("Failed to create {}: {}", cache_dir.display(), e))?;
+    write_atomic(&cached, &cover.to_ppm()).map_err(|e| format!("Failed to cache cover: {}", e))?;
+    trim_dir(cache_dir, THUMBNAIL_CACHE_MAX_BYTES);
+    Ok(cover)
+}
+
+/// Have yt-dlp write the thumbnail of the video or playlist at `page_url`
+/// into `dir` as `<key>.<ext>`, and return its path.
+pub(crate) fn fetch_thumbnail(
+    ytdlp: &dyn YtDlp,
+    page_url: &str,
+    key: &str,
+    cookie_config: Option<(bool, String)>,
+    dir: &Path,
+) -> Result<PathBuf, String> {
+    if !is_allowed_youtube_url(page_url) {
+        return Err("Invalid URL: must be a YouTube or YouTube Music URL".to_string());
+    }
+    // A playlist's own thumbnail is written before its entries, which
+    // `--flat-playlist` keeps from being resolved.
+    let args = YtDlpArgs::new()
+        .flag("--skip-download")
+        .flag("--write-thumbnail")
+        .flag("--flat-playlist")
+        .opt("--playlist-end", "1")
+        .flag("--no-warnings")
+        .opt(
+            "-o",
+            dir.join(format!("{}.%(ext)s", key))
+                .to_string_lossy()
+                .into_owned(),
+        )
+        .cookies(cookie_config.as_ref())
+        .flag(page_url);
+    let output = ytdlp
+        .run(&args)
+        .map_err(|e| format!("{}. Is yt-dlp installed?", e))?;
+    if !output.success {
+        return Err(format!("yt-dlp failed: {}", output.error_snippet()));
+    }
+
+    let prefix = format!("{}.", key);
+    let mut written: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| e.to_string())?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(&prefix))
+        })
+        .collect();
+    written.sort();
+    written
+        .into_iter()
+        .next()
+        .ok_or_else(|| "No thumbnail available".to_string())
+}
+
+/// Decode an image with ffmpeg, cropped to a centred square and scaled to
+/// `size × size`.
+fn decode_thumbnail(path: &Path, size: u32) -> Result<Cover, String> {
+    let filter = format!("crop='min(iw,ih)':'min(iw,ih)',scale={0}:{0}", size);
+    let output = Command::new("ffmpeg")
+        .args(["-v", "error", "-i"])
+        .arg(path)
+        .args([
+            "-vf",
+            &filter,
+            "-frames:v",
+            "1",
+            "-f",
+            "rawvideo",
+            "-pix_fmt",
+            "rgb24",
+            "-",
+        ])
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| format!("ffmpeg not available: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!(
+            "ffmpeg couldn't decode the thumbnail: {}",
+            stderr.trim()
+        ));
+    }
+    if output.stdout.len() != (size * size * 3) as usize {
+        return Err("ffmpeg returned a truncated image".to_string());
+    }
+    Ok(Cover {
+        width: size,
+        height: size,
+        rgb: output.stdout,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+
+    use crate::youtube::fake_ytdlp::{FakeYtDlp, Reply};
+
+    fn checkerboard(size: u32) -> Cover {
+        let rgb = (0..size * size)
+            .flat_map(|i| {
+                if (i % size + i / size).is_multiple_of(2) {
+                    [255, 0, 0]
+                } else {
+                    [0, 0, 255]
+                }
+            })
+            .collect();
+        Cover {
+            width: size,
+            height: size,
+            rgb,
+        }
+    }
+
+    #[test]
+    fn covers_round_trip_through_ppm_and_average_when_scaled() {
+        let cover = checkerboard(4);
+        assert_eq!(Cover::from_ppm(&cover.to_ppm()), Some(cover.clone()));
+        assert_eq!(Cover::from_ppm(b"P6\n4 4\n255\n\x00"), None);
+        assert_eq!(Cover::from_ppm(b"P3\n1 1\n255\n\x00\x00\x00"), None);
+        assert_eq!(Cover::from_ppm(b"P6\n65536 65536\n255\n\x00\x00\x00"), None);
+        assert_eq!(
+            Cover::from_ppm(b"P6\n4294967295 2\n255\n\x00\x00\x00"),
+            None
+        );
+
+        // Every 2×2 block holds two red and two blue pixels.
+        assert_eq!(cover.resample(2, 2), vec![[127, 0, 127]; 4]);
+        let up = cover.resample(8, 8);
+        assert_eq!((up.len(), up[0], up[2]), (64, [255, 0, 0], [0, 0, 255]));
+    }
+
+    #[test]
+    fn thumbnails_are_fetched_with_cookies_into_the_given_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        let fake = FakeYtDlp::new().on(
+            "watch?v=4NRXx6U8ABQ",
+            Reply::Download {
+                stdout: String::new(),
+                bytes: 64,
+            },
+        );
+        let cookie = Some((true, "firefox".to_string()));
+        let url = "https://www.youtube.com/watch?v=4NRXx6U8ABQ";
+
+        let path = fetch_thumbnail(&fake, url, "4NRXx6U8ABQ", cookie, tmp.path()).unwrap();
+        assert!(path.starts_with(tmp.path()));
+        let args = fake.calls()[0].clone();
+        assert!(args.as_slice().iter().any(|a| a == "--write-thumbnail"));
+        assert!(args
+            .as_slice()
+            .iter()
+            .any(|a| a == "--cookies-from-browser"));
+
+        assert!(
+            fetch_thumbnail(&fake, "https://example.com/a.jpg", "x", None, tmp.path()).is_err()
+        );
+        let offline = load_cover(&fake, "ZRXA_rpB4lE", url, None, tmp.path(), true);
+        assert!(offline.is_err());
+        assert!(load_cover(&fake, "../up", url, None, tmp.path(), false).is_err());
+    }
+
+    #[test]
+    fn cached_covers_load_offline_and_the_cache_is_trimmed_oldest_first() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cover = checkerboard(4);
+        let now = SystemTime::now();
+        for (i, key) in ["old", "mid", "new"].iter().enumerate() {
+            let path = tmp.path().join(format!("{}.ppm", key));
+            fs::write(&path, cover.to_ppm()).unwrap();
+            let age = Duration::from_secs(100 * (3 - i as u64));
+            fs::File::options()
+                .append(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(now - age)
+                .unwrap();
+        }
+        // Loading "old" marks it recently shown.
+        let fake = FakeYtDlp::new();
+        let loaded =
+            load_cover(&fake, "old", "https://youtu.be/x", None, tmp.path(), true).unwrap();
+        assert_eq!(loaded, cover);
+        assert!(fake.calls().is_empty());
+
+        let size = cover.to_ppm().len() as u64;
+        trim_dir(tmp.path(), 2 * size);
+        let mut left: Vec<String> = fs::read_dir(tmp.path())
+            .unwrap()
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .collect();
+        left.sort();
+        assert_eq!(left, ["new.ppm", "old.ppm"]);
+    }
+}
@@ -5,11 +5,15 @@
 
 use std::time::Duration;
 
-use crate::config::{clean_title, is_allowed_youtube_url, FEED_CACHE_TTL_SECS, MAX_TRACK_DURATION_SECS};
+use crate::config::{clean_title, is_allowed_youtube_url, FEED_CACHE_TTL_SECS, SEARCH_PAGE_SIZE};
 use crate::player::queue::Track;
 use crate::services::cache_store::CacheStore;
-use crate::ui::state::{AppMode, FeedSection};
-use crate::youtube::extractor::YouTubeExtractor;
+use crate::services::download_queue::DownloadPriority;
+use crate::ui::state::{AppMode, FeedSection, OpenedList, PlaylistType, SearchEvent};
+use crate::youtube::extractor::{
+    ListInfo, SearchPage, SearchResult, SearchScope, VideoInfo, YouTubeExtractor,
+};
+use crate::youtube::ytdlp::CancelHandle;
 
 use super::app::MusicPlayerApp;
 
@@ -18,12 +22,8 @@ impl MusicPlayerApp {
     // Shared helpers
     // -----------------------------------------------------------------------
 
-    /// Add `tracks` to the queue, applying the music-only duration filter.
-    ///
-    /// Returns `(added, filtered)` counts. Does **not** trigger downloads —
-    /// callers are responsible for calling `trigger_smart_downloads()`.
     /// Returns the set of video IDs currently in the queue (for deduplication).
-    fn queued_video_ids(&self) -> std::collections::HashSet<String> {
+    pub(super) fn queued_video_ids(&self) -> std::collections::HashSet<String> {
         let mut ids: std::collections::HashSet<String> = self.queue
             .get_queue_list()
             .iter()
@@ -38,49 +38,235 @@ impl MusicPlayerApp {
 
     /// Add tracks to the queue, skipping:
     ///   - tracks already in the queue (deduplication by video_id)
-    ///   - tracks over the duration limit when music-only mode is on
+    ///   - tracks rejected by the active filter profile
     ///
-    /// Returns `(added, skipped_duration, skipped_duplicate)`.
-    fn add_filtered_tracks(&mut self, tracks: &[Track]) -> (usize, usize, usize) {
+    /// Returns `(added, skipped_filtered, skipped_duplicate)`. Does **not**
+    /// trigger downloads — callers are responsible for calling
+    /// `trigger_smart_downloads()`.
+    pub(super) fn add_filtered_tracks(&mut self, tracks: &[Track]) -> (usize, usize, usize) {
         let already_queued = self.queued_video_ids();
+        let profile = self.filters.active().into_owned();
         let mut added = 0;
-        let mut skipped_duration = 0;
+        let mut skipped_filtered = 0;
         let mut skipped_duplicate = 0;
         for track in tracks {
             if already_queued.contains(&track.video_id) {
                 skipped_duplicate += 1;
                 continue;
             }
-            if !self.ui.music_only_mode || track.duration <= MAX_TRACK_DURATION_SECS {
+            if profile
+                .check(&track.title, &track.uploader, track.duration)
+                .is_ok()
+            {
                 self.queue.add(track.clone());
                 added += 1;
             } else {
-                skipped_duration += 1;
+                skipped_filtered += 1;
             }
         }
-        (added, skipped_duration, skipped_duplicate)
+        (added, skipped_filtered, skipped_duplicate)
     }
 
     pub(super) async fn perform_search(&mut self, query: &str) {
-        // Mark as searching
-        self.search.is_searching = true;
+        if self.is_offline() {
+            self.search_offline(query);
+            return;
+        }
+
+        // A new query replaces whatever is still streaming in.
+        if let Some(cancel) = self.search.cancel.take() {
+            cancel.cancel();
+        }
+        self.search.results.clear();
+        self.search.offline = false;
+        self.search.opened = None;
+        self.search.last_query = query.to_string();
+        self.search.last_scope = self.search.scope;
+        self.search.pages = 0;
+        self.search.has_more = false;
+        self.ui.selected_result = 0;
+        self.fetch_search_page();
+    }
+
+    /// `+` in the search view: fetch the page after the last one.
+    pub(super) fn load_more_results(&mut self) {
+        if self.search.is_searching || self.search.offline || self.search.opened.is_some() {
+            return;
+        }
+        if !self.search.has_more {
+            self.status_message = "No more results".to_string();
+            return;
+        }
+        if let Some(msg) = self.offline_refusal("load more results") {
+            self.status_message = msg;
+            return;
+        }
+        self.status_message = "Loading more results…".to_string();
+        self.fetch_search_page();
+    }
 
-        // Spawn background task for search
-        let extractor = YouTubeExtractor::new();
-        let query = query.to_string();
+    /// Start fetching page `search.pages` of the current search in the
+    /// background; results arrive one by one on `search_tx`.
+    fn fetch_search_page(&mut self) {
+        self.search.generation += 1;
+        self.search.is_searching = true;
+        let cancel = CancelHandle::default();
+        self.search.cancel = Some(cancel.clone());
+
+        let extractor = YouTubeExtractor::new(self.ytdlp.clone());
+        let query = self.search.last_query.clone();
+        let scope = self.search.last_scope;
+        let page = SearchPage::nth(self.search.pages, SEARCH_PAGE_SIZE);
+        let generation = self.search.generation;
         let tx = self.search_tx.clone();
 
         tokio::spawn(async move {
-            match extractor.search(&query, 15).await {
-                Ok(results) => {
-                    let _ = tx.send(results);
+            let results_tx = tx.clone();
+            let outcome = extractor
+                .search(&query, scope, page, cancel, move |result| {
+                    let _ = results_tx.send(SearchEvent::Result { generation, result });
+                })
+                .await;
+            let _ = tx.send(SearchEvent::Done {
+                generation,
+                outcome,
+            });
+        });
+    }
+
+    /// Apply one event from the search in flight; stale ones are dropped.
+    pub(super) async fn handle_search_event(&mut self, event: SearchEvent) {
+        match event {
+            SearchEvent::Result { generation, result } if generation == self.search.generation => {
+                // Pages can overlap when YouTube reshuffles between requests.
+                let id = result.id();
+                if !self.search.results.iter().any(|r| r.id() == id) {
+                    self.search.results.push(result);
                 }
-                Err(_e) => {
-                    // Send empty results to unblock UI (shows "Found 0 results")
-                    let _ = tx.send(Vec::new());
+            }
+            SearchEvent::Done {
+                generation,
+                outcome,
+            } if generation == self.search.generation => {
+                self.search.is_searching = false;
+                self.search.cancel = None;
+                match outcome {
+                    Ok(entries) => {
+                        self.note_network_success();
+                        self.search.pages += 1;
+                        self.search.has_more = entries >= SEARCH_PAGE_SIZE;
+                        let more = if self.search.has_more {
+                            " (+ for more)"
+                        } else {
+                            ""
+                        };
+                        self.status_message =
+                            format!("Found {} results{}", self.search.results.len(), more);
+                    }
+                    Err(e) => {
+                        self.search.has_more = false;
+                        self.status_message = format!("Search failed: {}", e);
+                        self.note_network_failure(&e).await;
+                    }
                 }
             }
-        });
+            _ => {}
+        }
+    }
+
+    /// Enter in the search view: queue a song or video, open an album or
+    /// playlist, or search an artist's songs.
+    pub(super) async fn select_search_result(&mut self) {
+        let Some(result) = self.search.results.get(self.ui.selected_result).cloned() else {
+            return;
+        };
+        if result.video().is_some() {
+            self.add_selected_to_queue();
+        } else if let Some(list) = result.list() {
+            self.open_search_list(list.clone()).await;
+        } else if let SearchResult::Artist(artist) = result {
+            self.search.scope = SearchScope::Songs;
+            self.status_message = format!("Searching songs by {}…", artist.name);
+            self.perform_search(&artist.name).await;
+        }
+    }
+
+    /// Replace the search results with the tracks of `list`; Esc goes back.
+    async fn open_search_list(&mut self, list: ListInfo) {
+        if let Some(msg) = self.offline_refusal("open albums or playlists") {
+            self.status_message = msg;
+            return;
+        }
+        self.status_message = format!("Opening '{}'…", clean_title(&list.title));
+
+        let cookie_config = self.cookie_config();
+        let ytdlp = self.ytdlp.clone();
+        let url = list.url.clone();
+        let fetch_result = tokio::task::spawn_blocking(move || {
+            crate::services::playlist::fetch_playlist_tracks(&*ytdlp, &url, cookie_config)
+        })
+        .await;
+
+        let tracks = match fetch_result {
+            Ok(Ok(tracks)) if tracks.is_empty() => {
+                self.status_message =
+                    format!("'{}' has no playable tracks", clean_title(&list.title));
+                return;
+            }
+            Ok(Ok(tracks)) => tracks,
+            Ok(Err(e)) => {
+                self.status_message =
+                    format!("Failed to open '{}': {}", clean_title(&list.title), e);
+                self.note_network_failure(&e).await;
+                return;
+            }
+            Err(e) => {
+                self.status_message = format!("Task error: {}", e);
+                return;
+            }
+        };
+        self.note_network_success();
+
+        self.status_message = format!(
+            "{} — {} tracks (Enter to queue, Esc to go back)",
+            clean_title(&list.title),
+            tracks.len()
+        );
+        let songs = tracks
+            .into_iter()
+            .map(|t| {
+                SearchResult::Song(VideoInfo {
+                    id: t.video_id,
+                    title: t.title,
+                    duration: t.duration,
+                    uploader: t.uploader,
+                    thumbnail: None,
+                    url: t.url,
+                })
+            })
+            .collect();
+        // Opening a list from inside an opened list keeps the original
+        // results to go back to.
+        let parent = std::mem::replace(&mut self.search.results, songs);
+        if self.search.opened.is_none() {
+            self.search.opened = Some(OpenedList {
+                title: list.title,
+                parent,
+                parent_selected: self.ui.selected_result,
+            });
+        } else if let Some(opened) = self.search.opened.as_mut() {
+            opened.title = list.title;
+        }
+        self.ui.selected_result = 0;
+    }
+
+    /// Esc on an opened album or playlist: back to the search results.
+    pub(super) fn close_search_list(&mut self) {
+        if let Some(opened) = self.search.opened.take() {
+            self.search.results = opened.parent;
+            self.ui.selected_result = opened.parent_selected;
+            self.status_message = "Back to search results".to_string();
+        }
     }
 
     pub(super) async fn load_playlist_from_url(&mut self, url: &str) {
@@ -89,6 +275,10 @@ impl MusicPlayerApp {
             self.status_message = "Invalid URL: must be a YouTube or YouTube Music URL".to_string();
             return;
         }
+        if let Some(msg) = self.offline_refusal("load playlists") {
+            self.status_message = msg;
+            return;
+        }
 
         self.status_message = "Loading playlist... (this may take a moment)".to_string();
 
@@ -102,8 +292,9 @@ impl MusicPlayerApp {
             .map(|account| self.browser_auth.get_cookie_arg(&account));
 
         let playlist_url = url.to_string();
+        let ytdlp = self.ytdlp.clone();
         let fetch_result = tokio::task::spawn_blocking(move || {
-            crate::services::playlist::fetch_playlist_tracks(&playlist_url, cookie_config)
+            crate::services::playlist::fetch_playlist_tracks(&*ytdlp, &playlist_url, cookie_config)
         })
         .await;
 
@@ -117,6 +308,7 @@ impl MusicPlayerApp {
                 let track_count = tracks.len();
 
                 self.playlist.loaded_name = format!("Loaded Playlist ({} tracks)", track_count);
+                self.playlist.loaded_url = url.to_string();
 
                 let (added_count, skipped_dur, skipped_dup) = self.add_filtered_tracks(&tracks);
 
@@ -128,8 +320,10 @@ impl MusicPlayerApp {
 
                 if skipped_dur > 0 {
                     self.status_message = format!(
-                        "Added {} tracks to queue ({} filtered — press 'Shift+F' to allow all)",
-                        added_count, skipped_dur
+                        "Added {} tracks to queue ({} filtered by '{}' — Shift+F to switch profile)",
+                        added_count,
+                        skipped_dur,
+                        self.filters.active().name
                     );
                 } else if skipped_dup > 0 {
                     self.status_message = format!(
@@ -142,6 +336,7 @@ impl MusicPlayerApp {
             }
             Ok(Err(e)) => {
                 self.status_message = format!("Failed to fetch playlist: {}", e);
+                self.note_network_failure(&e).await;
             }
             Err(e) => {
                 self.status_message = format!("Task error: {}", e);
@@ -156,6 +351,10 @@ impl MusicPlayerApp {
             .get(self.ui.selected_mix_item)
             .cloned()
         {
+            if let Some(msg) = self.offline_refusal("fetch mixes") {
+                self.status_message = msg;
+                return;
+            }
             self.status_message = format!(
                 "⏳ Fetching tracks from '{}'... (this may take a moment)",
                 mix.title
@@ -171,8 +370,13 @@ impl MusicPlayerApp {
                 .map(|account| self.browser_auth.get_cookie_arg(&account));
 
             let playlist_url = mix.url.clone();
+            let ytdlp = self.ytdlp.clone();
             let fetch_result = tokio::task::spawn_blocking(move || {
-                crate::services::playlist::fetch_playlist_tracks(&playlist_url, cookie_config)
+                crate::services::playlist::fetch_playlist_tracks(
+                    &*ytdlp,
+                    &playlist_url,
+                    cookie_config,
+                )
             })
             .await;
 
@@ -190,8 +394,11 @@ impl MusicPlayerApp {
 
                     if skipped_dur > 0 {
                         self.status_message = format!(
-                            "Added {} from '{}' ({} filtered — press 'Shift+F' to allow all)",
-                            added_count, mix.title, skipped_dur
+                            "Added {} from '{}' ({} filtered by '{}' — Shift+F to switch profile)",
+                            added_count,
+                            mix.title,
+                            skipped_dur,
+                            self.filters.active().name
                         );
                     } else if skipped_dup > 0 {
                         self.status_message = format!(
@@ -211,6 +418,7 @@ impl MusicPlayerApp {
                 }
                 Ok(Err(e)) => {
                     self.status_message = format!("Failed to fetch tracks: {}", e);
+                    self.note_network_failure(&e).await;
                 }
                 Err(e) => {
                     self.status_message = format!("Task error: {}", e);
@@ -225,14 +433,19 @@ impl MusicPlayerApp {
     }
 
     pub(super) async fn fetch_my_mix(&mut self) {
+        if let Some(msg) = self.offline_refusal("fetch My Mix") {
+            self.status_message = msg;
+            return;
+        }
         // Fetch My Mix playlists using yt-dlp
         let cookie_config = self
             .browser_auth
             .load_selected_account()
             .map(|account| self.browser_auth.get_cookie_arg(&account));
 
+        let ytdlp = self.ytdlp.clone();
         let fetch_result = tokio::task::spawn_blocking(move || {
-            crate::services::playlist::fetch_my_mix(cookie_config)
+            crate::services::playlist::fetch_my_mix(&*ytdlp, cookie_config)
         })
         .await;
 
@@ -250,6 +463,7 @@ impl MusicPlayerApp {
             }
             Ok(Err(e)) => {
                 self.status_message = format!("Failed to fetch My Mix: {}", e);
+                self.note_network_failure(&e).await;
                 // Keep existing playlists if any
             }
             Err(e) => {
@@ -259,14 +473,19 @@ impl MusicPlayerApp {
     }
 
     pub(super) fn add_selected_to_queue(&mut self) {
-        if let Some(video) = self.search.results.get(self.ui.selected_result) {
-            // In music-only mode, filter out tracks > 7 minutes
-            if self.ui.music_only_mode && video.duration > MAX_TRACK_DURATION_SECS {
-                let clean_title = clean_title(&video.title);
-                let mins = video.duration / 60;
+        if let Some(video) = self
+            .search
+            .results
+            .get(self.ui.selected_result)
+            .and_then(SearchResult::video)
+        {
+            let profile = self.filters.active();
+            if let Err(reason) = profile.check(&video.title, &video.uploader, video.duration) {
                 self.status_message = format!(
-                    "'{}' is too long ({}min) — press 'f' to allow all content",
-                    clean_title, mins
+                    "'{}' filtered by '{}': {} — Shift+F to switch profile",
+                    clean_title(&video.title),
+                    profile.name,
+                    reason
                 );
                 return;
             }
@@ -282,7 +501,7 @@ impl MusicPlayerApp {
             let was_empty = self.queue.is_empty();
 
             // Start background download through centralized rate-limited system
-            self.spawn_download_with_limit(&track);
+            self.request_download(&track, DownloadPriority::Background);
 
             self.queue.add(track);
 
@@ -306,6 +525,46 @@ impl MusicPlayerApp {
         }
     }
 
+    // -----------------------------------------------------------------------
+    // Listening statistics
+    // -----------------------------------------------------------------------
+
+    /// Re-query the library for the Stats view's current window. The play
+    /// in progress is checkpointed first so it shows up in the totals.
+    pub(super) fn refresh_stats(&mut self) {
+        use crate::services::library::now_unix;
+
+        if let Some(play) = self.active_play.as_mut() {
+            play.tick(
+                std::time::Instant::now(),
+                self.player.get_state() == crate::player::audio::PlayerState::Playing,
+            );
+        }
+
+        let Some(library) = self.persistence.library() else {
+            self.stats.data = None;
+            self.stats.error = Some("Library database unavailable".to_string());
+            return;
+        };
+        if let Some(play) = &self.active_play {
+            let _ = library.finish_play(play.play_id, play.listened_secs(), false);
+        }
+
+        let now = now_unix();
+        let window = self.stats.window;
+        let since = window.days().map(|d| now - i64::from(d) * 86_400);
+        match library.listening_stats(since, now, window.sparkline_days()) {
+            Ok(data) => {
+                self.stats.data = Some(data);
+                self.stats.error = None;
+            }
+            Err(e) => {
+                self.stats.data = None;
+                self.stats.error = Some(format!("Failed to load stats: {}", e));
+            }
+        }
+    }
+
     // -----------------------------------------------------------------------
     // Feed browser actions
     // -----------------------------------------------------------------------
@@ -320,16 +579,22 @@ impl MusicPlayerApp {
         self.mode = AppMode::FeedBrowser;
         self.feed.selected_section = 0;
         self.feed.selected_item = 0;
+        self.refresh_favorites_section();
 
         // Already have in-memory sections — nothing to do.
-        if !self.feed.sections.is_empty() {
+        if self.feed.has_remote_sections() {
             return;
         }
 
-        // Try the disk cache before spawning yt-dlp.
+        // Try the disk cache before spawning yt-dlp. Offline, any cached
+        // feed beats none.
         if self.try_load_feed_cache() {
             return;
         }
+        if let Some(msg) = self.offline_refusal("fetch the feed") {
+            self.feed.last_error = Some(msg);
+            return;
+        }
 
         // Cache miss — fetch in background.
         if !self.feed.is_loading {
@@ -340,11 +605,16 @@ impl MusicPlayerApp {
     /// Try to load the feed from the on-disk `CacheStore`.
     ///
     /// Returns `true` and populates `feed.sections` if a fresh cache entry
-    /// exists. Returns `false` on miss (expired, missing, corrupt, schema
+    /// exists, or any cache entry while offline. Returns `false` on miss (expired, missing, corrupt, schema
     /// mismatch) without modifying state.
     fn try_load_feed_cache(&mut self) -> bool {
         let store = Self::feed_cache_store(self.persistence.config_dir().to_owned());
-        match store.load() {
+        let cached = if self.is_offline() {
+            store.load_stale()
+        } else {
+            store.load()
+        };
+        match cached {
             Ok(Some(mut sections)) => {
                 // Re-validate URLs loaded from disk — defence-in-depth matching
                 // the pattern used for queue restoration in load_queue_async.
@@ -354,6 +624,7 @@ impl MusicPlayerApp {
                         .retain(|p| crate::config::is_allowed_youtube_url(&p.url));
                 }
                 self.feed.sections = sections;
+                self.refresh_favorites_section();
                 self.feed.last_fetch = Some(std::time::Instant::now());
                 self.feed.last_error = None;
                 true
@@ -374,6 +645,10 @@ impl MusicPlayerApp {
             self.status_message = "Feed refresh already in progress…".to_string();
             return;
         }
+        if let Some(msg) = self.offline_refusal("refresh the feed") {
+            self.status_message = msg;
+            return;
+        }
 
         if self.cookie_config().is_none() {
             self.feed.last_error = Some(
@@ -388,6 +663,7 @@ impl MusicPlayerApp {
             let store = Self::feed_cache_store(self.persistence.config_dir().to_owned());
             store.invalidate();
             self.feed.sections.clear();
+            self.refresh_favorites_section();
         }
 
         self.feed.is_loading = true;
@@ -397,10 +673,11 @@ impl MusicPlayerApp {
         let cookie = self.cookie_config();
         let config_dir = self.persistence.config_dir().to_owned();
         let tx = self.feed_tx.clone();
+        let ytdlp = self.ytdlp.clone();
 
         tokio::spawn(async move {
             let result = tokio::task::spawn_blocking(move || {
-                crate::services::feed::fetch_all_parallel(cookie)
+                crate::services::feed::fetch_all_parallel(&*ytdlp, cookie)
                     .map_err(|e| e.user_message())
             })
             .await
@@ -579,17 +856,46 @@ impl MusicPlayerApp {
         self.feed.selected_track = 0;
         self.status_message = format!("Loading tracks from '{}'…", item.title);
 
-        tokio::task::yield_now().await;
+        let result = if let Some(local) = self.favorites_tracks(&item) {
+            local.map(|tracks| {
+                tracks
+                    .into_iter()
+                    .map(super::ratings::to_feed_track)
+                    .collect()
+            })
+        } else if let Some(tracks) = self
+            .offline_job_tracks(&item.id)
+            .filter(|_| self.is_offline())
+        {
+            // Made available offline: the stored snapshot stands in.
+            Ok(tracks
+                .into_iter()
+                .map(super::ratings::to_feed_track)
+                .collect())
+        } else if item.playlist_type == PlaylistType::Album {
+            self.load_album(&item).await.map(|album| {
+                album
+                    .tracks
+                    .into_iter()
+                    .map(super::ratings::to_feed_track)
+                    .collect()
+            })
+        } else if let Some(msg) = self.offline_refusal(&format!("load '{}'", item.title)) {
+            Err(msg)
+        } else {
+            tokio::task::yield_now().await;
 
-        let cookie = self.cookie_config();
-        let url = item.url.clone();
+            let cookie = self.cookie_config();
+            let url = item.url.clone();
+            let ytdlp = self.ytdlp.clone();
 
-        let result = tokio::task::spawn_blocking(move || {
-            crate::services::feed::fetch_tracks_for_playlist(cookie, &url)
-                .map_err(|e| e.user_message())
-        })
-        .await
-        .unwrap_or_else(|e| Err(format!("Task error: {e}")));
+            tokio::task::spawn_blocking(move || {
+                crate::services::feed::fetch_tracks_for_playlist(&*ytdlp, cookie, &url)
+                    .map_err(|e| e.user_message())
+            })
+            .await
+            .unwrap_or_else(|e| Err(format!("Task error: {e}")))
+        };
 
         self.feed.tracks_loading = false;
 
@@ -607,6 +913,7 @@ impl MusicPlayerApp {
                 );
             }
             Err(e) => {
+                self.note_network_failure(&e).await;
                 self.status_message = e;
             }
         }
@@ -650,6 +957,17 @@ impl MusicPlayerApp {
             return;
         }
 
+        let profile = self.filters.active();
+        if let Err(reason) = profile.check(&track.title, &track.uploader, track.duration) {
+            let msg = format!(
+                "'{}' filtered by '{}': {} — Shift+F to switch profile",
+                track.title, profile.name, reason
+            );
+            self.feed.feed_status = Some(msg.clone());
+            self.status_message = msg;
+            return;
+        }
+
         let queue_track = crate::player::queue::Track::new(
             track.video_id.clone(),
             track.title.clone(),
@@ -659,7 +977,7 @@ impl MusicPlayerApp {
         );
 
         let was_empty = self.queue.is_empty();
-        self.spawn_download_with_limit(&queue_track);
+        self.request_download(&queue_track, DownloadPriority::Background);
         self.queue.add(queue_track);
 
         let msg = format!(
@@ -697,9 +1015,20 @@ impl MusicPlayerApp {
         tokio::task::yield_now().await;
         tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
 
-        let tracks = match self.feed_load_tracks(&item.url, &item.title).await {
+        let loaded = match self.favorites_tracks(&item) {
+            Some(local) => local,
+            None => match self
+                .offline_job_tracks(&item.id)
+                .filter(|_| self.is_offline())
+            {
+                Some(tracks) => Ok(tracks),
+                None => self.feed_load_tracks(&item.url, &item.title).await,
+            },
+        };
+        let tracks = match loaded {
             Ok(t) => t,
             Err(msg) => {
+                self.note_network_failure(&msg).await;
                 self.feed.feed_status = Some(msg.clone());
                 self.status_message = msg;
                 return;
@@ -707,7 +1036,7 @@ impl MusicPlayerApp {
         };
 
         let was_empty = self.queue.is_empty();
-        let (added, skipped_duration, skipped_duplicate) = self.add_filtered_tracks(&tracks);
+        let (added, skipped_filtered, skipped_duplicate) = self.add_filtered_tracks(&tracks);
 
         if added == 0 && skipped_duplicate == tracks.len() {
             let msg = format!("All tracks from '{}' are already in the queue", item.title);
@@ -733,8 +1062,12 @@ impl MusicPlayerApp {
         if skipped_duplicate > 0 {
             parts.push(format!("{} already in queue", skipped_duplicate));
         }
-        if skipped_duration > 0 {
-            parts.push(format!("{} filtered (too long)", skipped_duration));
+        if skipped_filtered > 0 {
+            parts.push(format!(
+                "{} filtered by '{}'",
+                skipped_filtered,
+                self.filters.active().name
+            ));
         }
         parts.push(format!("{} in queue", self.queue.len()));
         if was_empty && added > 0 {
@@ -756,7 +1089,7 @@ impl MusicPlayerApp {
     ///
     /// Returns `Ok(tracks)` on success, or `Err(user_message)` on failure.
     /// Handles cookie-missing, auth-expired, and yt-dlp errors uniformly.
-    async fn feed_load_tracks(
+    pub(super) async fn feed_load_tracks(
         &self,
         url: &str,
         title: &str,
@@ -766,12 +1099,16 @@ impl MusicPlayerApp {
                 "No browser account selected. Press 'q' then 'l' to log in.".to_string(),
             );
         }
+        if let Some(msg) = self.offline_refusal(&format!("load '{}'", title)) {
+            return Err(msg);
+        }
 
         let cookie_config = self.cookie_config();
         let playlist_url = url.to_string();
 
+        let ytdlp = self.ytdlp.clone();
         let result = tokio::task::spawn_blocking(move || {
-            crate::services::playlist::fetch_playlist_tracks(&playlist_url, cookie_config)
+            crate::services::playlist::fetch_playlist_tracks(&*ytdlp, &playlist_url, cookie_config)
         })
         .await
         .unwrap_or_else(|e| Err(format!("Task error: {e}")));
@@ -0,0 +1,188 @@
+//! Albums for MusicPlayerApp.
+//!
+//! Album playlists in the feed are loaded with their metadata, remembered
+//! in the library (so history can group by album), and can be played as a
+//! whole: `p` replaces the queue with the album in track order, and while
+//! it plays each next track is appended to the player before the current
+//! one ends so there is no gap between them.
+
+use crate::config::{format_time, GAPLESS_PRELOAD_SECS};
+use crate::player::audio::PlayerState;
+use crate::player::queue::Track;
+use crate::services::album::{self, Album, AlbumRef};
+use crate::services::download_queue::DownloadPriority;
+use crate::services::persistence::MAX_HISTORY_SIZE;
+use crate::ui::state::{AppMode, FeedFocus, FeedPlaylist, PlayEnd, PlaylistType};
+
+use super::app::MusicPlayerApp;
+
+impl MusicPlayerApp {
+    /// The highlighted feed item's album, once its tracks have been loaded.
+    pub(crate) fn feed_selected_album(&self) -> Option<&Album> {
+        let item = self.feed_selected_item_ref()?;
+        self.feed.albums.get(&item.id)
+    }
+
+    /// The album behind a feed item: loaded this session, or fetched now.
+    pub(super) async fn load_album(&mut self, item: &FeedPlaylist) -> Result<Album, String> {
+        if let Some(album) = self.feed.albums.get(&item.id) {
+            return Ok(album.clone());
+        }
+        if let Some(msg) = self.offline_refusal(&format!("load '{}'", item.title)) {
+            return Err(msg);
+        }
+        tokio::task::yield_now().await;
+
+        let cookie = self.cookie_config();
+        let url = item.url.clone();
+        let ytdlp = self.ytdlp.clone();
+        let album = tokio::task::spawn_blocking(move || album::fetch_album(&*ytdlp, &url, cookie))
+            .await
+            .unwrap_or_else(|e| Err(format!("Task error: {e}")))
+            .map_err(|e| format!("Failed to load '{}': {}", item.title, e))?;
+        self.remember_album(&album);
+        Ok(album)
+    }
+
+    /// Keep `album` for this session and record its tracks in the library.
+    fn remember_album(&mut self, album: &Album) {
+        if let Err(e) = self.persistence.save_album(album) {
+            self.status_message = format!("Album not saved to library: {}", e);
+        }
+        for (i, track) in album.tracks.iter().enumerate() {
+            let album_ref = AlbumRef {
+                album_id: album.id.clone(),
+                title: album.title.clone(),
+                artist: album.artist.clone(),
+                year: album.year,
+                track_no: i + 1,
+            };
+            self.album_of.insert(track.video_id.clone(), album_ref);
+        }
+        self.feed.albums.insert(album.id.clone(), album.clone());
+    }
+
+    /// `p` in the feed browser — replace the queue with the highlighted
+    /// album in track order (from the highlighted track in the track list)
+    /// and play it gaplessly. Albums are played as released, so the filter
+    /// profile doesn't drop interludes or skits.
+    pub(super) async fn feed_play_album(&mut self) {
+        let Some(item) = self.feed_selected_item() else {
+            return;
+        };
+        if item.playlist_type != PlaylistType::Album {
+            let msg = format!("'{}' isn't an album", item.title);
+            self.feed.feed_status = Some(msg.clone());
+            self.status_message = msg;
+            return;
+        }
+
+        let loading = format!("Loading album '{}'…", item.title);
+        self.feed.feed_status = Some(loading.clone());
+        self.status_message = loading;
+        let album = match self.load_album(&item).await {
+            Ok(album) => album,
+            Err(e) => {
+                self.note_network_failure(&e).await;
+                self.feed.feed_status = Some(e.clone());
+                self.status_message = e;
+                return;
+            }
+        };
+
+        let start = match self.feed.focus {
+            FeedFocus::Tracks => self
+                .feed
+                .selected_track
+                .min(album.tracks.len().saturating_sub(1)),
+            _ => 0,
+        };
+        let tracks: Vec<Track> = album.tracks[start..].to_vec();
+
+        if let Some(current) = self.queue.get_current().cloned() {
+            self.queue.add_to_history(current);
+        }
+        self.queue.restore_queue(tracks, None);
+        self.ui.selected_queue_item = 0;
+        self.gapless_album = Some(album.id.clone());
+
+        // Fetch the rest of the album in track order behind the lookahead,
+        // so every transition finds the next file ready.
+        self.trigger_smart_downloads();
+        for track in self.queue.get_queue_list() {
+            self.request_download(&track, DownloadPriority::Background);
+        }
+        self.play_current_or_first().await;
+
+        self.feed.focus = FeedFocus::Playlists;
+        self.mode = AppMode::Normal;
+        self.status_message = format!(
+            "▶ Playing album '{}' — {} tracks, {}",
+            album.title,
+            album.tracks.len() - start,
+            format_time(album.total_duration() as f64)
+        );
+        if let Err(e) = self.save_queue() {
+            self.status_message = format!("{} — queue not saved: {}", self.status_message, e);
+        }
+    }
+
+    /// Called every loop tick during album playback. Shortly before the
+    /// current track ends the next one on the album is appended to the
+    /// player; once the player has moved on to it, the queue catches up.
+    pub(super) fn tick_gapless(&mut self) {
+        let Some(album_id) = self.gapless_album.clone() else {
+            return;
+        };
+
+        if self.player.take_advance() {
+            self.persistence.clear_playback_state();
+            self.finish_active_play(PlayEnd::Finished);
+            if let Some(track) = self.queue.next() {
+                self.queue.limit_history(MAX_HISTORY_SIZE);
+                self.start_timing_play(&track);
+                self.trigger_smart_downloads();
+            }
+            return;
+        }
+
+        if self.player.has_queued_next() || self.player.get_state() != PlayerState::Playing {
+            return;
+        }
+        let duration = self.player.get_duration();
+        if duration <= 0.0 || duration - self.player.get_time_pos() > GAPLESS_PRELOAD_SECS {
+            return;
+        }
+
+        let on_album = |track: &Track| {
+            self.album_of
+                .get(&track.video_id)
+                .is_some_and(|a| a.album_id == album_id)
+        };
+        // Something else is playing now; stop chaining.
+        if !self.queue.get_current().is_some_and(on_album) {
+            self.gapless_album = None;
+            return;
+        }
+        let Some(next) = self
+            .queue
+            .get_queue_slice(0, 1)
+            .first()
+            .map(|t| (*t).clone())
+        else {
+            return;
+        };
+        if !on_album(&next) {
+            return;
+        }
+        let Some(path) = self
+            .downloads
+            .get_cached_file(&next.video_id)
+            .filter(|path| std::path::Path::new(path).exists())
+        else {
+            return;
+        };
+        self.player
+            .queue_next(&path, &next.title, next.duration as f64);
+    }
+}
@@ -10,20 +10,41 @@ use crossterm::{
 use ratatui::{
     backend::CrosstermBackend,
     layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
     Frame, Terminal,
 };
-use std::io;
+use std::io::{self, Write};
+use std::sync::Arc;
 use tokio::sync::mpsc;
 
 use crate::config::{is_allowed_youtube_url, LOOKAHEAD_DOWNLOAD_COUNT, STARTUP_DOWNLOAD_COUNT};
 use crate::player::audio::{AudioPlayer, PlayerState};
 use crate::player::queue::{Queue, Track};
+use crate::services::album::AlbumRef;
+use crate::services::artist::ArtistPage;
+use crate::services::audio_cache::{self, AudioCache, FileHealth};
+use crate::services::diagnostics::Check;
 use crate::services::download::DownloadManager;
+use crate::services::download_queue::DownloadPriority;
+use crate::services::filter::FilterSet;
+use crate::services::library::TrackRating;
+use crate::services::lyrics::Lyrics;
+use crate::services::network::NetworkStatus;
 use crate::services::persistence::PersistenceService;
-use crate::ui::state::{AppMode, FeedSection, FeedState, PlaylistState, QueueState, SearchState, UiState, ViewMode};
+use crate::services::settings::Settings;
+use crate::services::thumbnail::Cover;
+use crate::ui::graphics::ImageProtocol;
+use crate::ui::state::{
+    ActivePlay, AppMode, ArtistState, CacheViewState, CoverState, DiagnosticsState, FeedSection,
+    FeedState, LocalPlaylistsState, LyricsState, OfflineJobsState, PlayEnd, PlaylistPickerState,
+    PlaylistState, PromptState, QueueState, SearchEvent, SearchState, StatsState, TagsState,
+    UiState, ViewMode,
+};
 use crate::youtube::browser_auth::{BrowserAccount, BrowserAuth};
-use crate::youtube::extractor::VideoInfo;
+use crate::youtube::extractor::SearchScope;
+use crate::youtube::ytdlp::{SystemYtDlp, YtDlp};
 
 /// Scroll `text` by `offset` characters, wrapping around with a separator gap.
 /// Used to make the controls bar scroll so all keys are visible over time.
@@ -52,11 +73,38 @@ pub struct MusicPlayerApp {
     pub(crate) available_accounts: Vec<BrowserAccount>,
     pub(super) persistence: PersistenceService,
     pub(crate) downloads: DownloadManager,
+    /// Runs every yt-dlp command (search, playlists, feed, downloads).
+    pub(crate) ytdlp: Arc<dyn YtDlp>,
+    /// Content filter profiles applied when adding tracks to the queue.
+    pub(crate) filters: FilterSet,
+    /// Favorites and star ratings by video id, mirrored from the library.
+    pub(crate) ratings: std::collections::HashMap<String, TrackRating>,
+    /// User settings from `settings.json`.
+    pub(crate) settings: Settings,
+    /// Video ids pinned in the audio cache, mirrored from the library.
+    pub(crate) pinned: std::collections::HashSet<String>,
+    /// The album of every track on a loaded album, mirrored from the library.
+    pub(crate) album_of: std::collections::HashMap<String, AlbumRef>,
+    /// Set by "play album": the album whose tracks are chained gaplessly.
+    pub(super) gapless_album: Option<String>,
+    /// Bytes used by the audio cache, refreshed by `enforce_cache_quota`.
+    pub(crate) cache_usage_bytes: u64,
+    /// Offline mode, manual or detected from network failures.
+    pub(super) network: NetworkStatus,
 
     // UI state (sub-structs)
     pub(crate) ui: UiState,
     pub(crate) search: SearchState,
     pub(crate) playlist: PlaylistState,
+    pub(crate) local_playlists: LocalPlaylistsState,
+    /// The open text prompt, if `mode` is [`AppMode::Prompt`].
+    pub(crate) prompt: Option<PromptState>,
+    /// The open playlist picker, if `mode` is [`AppMode::PlaylistPicker`].
+    pub(crate) playlist_picker: Option<PlaylistPickerState>,
+    pub(crate) tags: TagsState,
+    pub(crate) cache_view: CacheViewState,
+    pub(crate) offline_jobs: OfflineJobsState,
+    pub(crate) diagnostics: DiagnosticsState,
     pub(crate) mode: AppMode,
     pub(crate) current_view: ViewMode,
     previous_view: ViewMode,
@@ -69,14 +117,51 @@ pub struct MusicPlayerApp {
     queue_loaded: bool,
 
     // Async channels
-    search_rx: mpsc::UnboundedReceiver<Vec<VideoInfo>>,
-    pub(super) search_tx: mpsc::UnboundedSender<Vec<VideoInfo>>,
+    search_rx: mpsc::UnboundedReceiver<SearchEvent>,
+    pub(super) search_tx: mpsc::UnboundedSender<SearchEvent>,
 
     // Feed browser state + async channel
     pub(crate) feed: FeedState,
     feed_rx: mpsc::UnboundedReceiver<Result<Vec<FeedSection>, String>>,
     pub(super) feed_tx: mpsc::UnboundedSender<Result<Vec<FeedSection>, String>>,
 
+    // Artist browser state + pages fetched in the background, by the
+    // name they were requested under
+    pub(crate) artist: ArtistState,
+    artist_rx: mpsc::UnboundedReceiver<(String, Result<ArtistPage, String>)>,
+    pub(super) artist_tx: mpsc::UnboundedSender<(String, Result<ArtistPage, String>)>,
+
+    // Cache verify results, one file at a time
+    verify_rx: mpsc::UnboundedReceiver<(String, FileHealth)>,
+    pub(super) verify_tx: mpsc::UnboundedSender<(String, FileHealth)>,
+
+    // Network probe results while offline was auto-detected
+    pub(super) probe_rx: mpsc::UnboundedReceiver<bool>,
+    pub(super) probe_tx: mpsc::UnboundedSender<bool>,
+
+    // Audio export results
+    export_rx: mpsc::UnboundedReceiver<Result<String, String>>,
+    pub(super) export_tx: mpsc::UnboundedSender<Result<String, String>>,
+
+    // Dependency check results
+    pub(super) diag_rx: mpsc::UnboundedReceiver<Vec<Check>>,
+    pub(super) diag_tx: mpsc::UnboundedSender<Vec<Check>>,
+
+    // Lyrics of the playing track, looked up in the background by video id
+    pub(crate) lyrics: LyricsState,
+    lyrics_rx: mpsc::UnboundedReceiver<(String, Result<Lyrics, String>)>,
+    pub(super) lyrics_tx: mpsc::UnboundedSender<(String, Result<Lyrics, String>)>,
+
+    // Cover art by video or playlist id, loaded in the background
+    pub(crate) covers: CoverState,
+    cover_rx: mpsc::UnboundedReceiver<(String, Result<Cover, String>)>,
+    pub(super) cover_tx: mpsc::UnboundedSender<(String, Result<Cover, String>)>,
+
+    // Listening statistics
+    pub(crate) stats: StatsState,
+    /// The play currently being timed; closed out when the track changes.
+    pub(super) active_play: Option<ActivePlay>,
+
     // Playback state
     pub(super) pending_play_track: Option<Track>,
     pub(super) currently_downloading: Option<String>,
@@ -86,6 +171,13 @@ impl MusicPlayerApp {
     pub fn new() -> Result<Self> {
         let (search_tx, search_rx) = mpsc::unbounded_channel();
         let (feed_tx, feed_rx) = mpsc::unbounded_channel();
+        let (artist_tx, artist_rx) = mpsc::unbounded_channel();
+        let (verify_tx, verify_rx) = mpsc::unbounded_channel();
+        let (probe_tx, probe_rx) = mpsc::unbounded_channel();
+        let (diag_tx, diag_rx) = mpsc::unbounded_channel();
+        let (export_tx, export_rx) = mpsc::unbounded_channel();
+        let (lyrics_tx, lyrics_rx) = mpsc::unbounded_channel();
+        let (cover_tx, cover_rx) = mpsc::unbounded_channel();
 
         // Initialize browser auth (fallible — may fail if $HOME is unset or config dir is inaccessible)
         let browser_auth = BrowserAuth::new()
@@ -114,7 +206,18 @@ impl MusicPlayerApp {
         };
 
         // Load persisted history
-        let persistence = PersistenceService::new()?;
+        let mut persistence = PersistenceService::new()?;
+
+        // The library database is additive — if it can't be opened the app
+        // still works from the JSON files, so report and carry on.
+        let status_message = match persistence.open_library() {
+            Ok(Some(import)) if import.plays + import.tracks + import.cached_files > 0 => format!(
+                "{} (imported {} plays, {} tracks, {} cached files into library)",
+                status_message, import.plays, import.tracks, import.cached_files
+            ),
+            Ok(_) => status_message,
+            Err(e) => format!("⚠ Library database unavailable: {}", e),
+        };
         let mut queue = Queue::new();
         if let Ok(history) = persistence.load_history() {
             for track in history {
@@ -123,9 +226,116 @@ impl MusicPlayerApp {
             queue.limit_history(crate::services::persistence::MAX_HISTORY_SIZE);
         }
 
-        // Warm the download cache from previous session
-        let download_cache = persistence.load_download_cache();
+        // A corrupt filters.json falls back to the built-in profiles rather
+        // than blocking startup; the file is left untouched for the user to fix.
+        let filters = persistence.load_filters().unwrap_or_default();
+
+        let ratings = persistence
+            .library()
+            .and_then(|lib| lib.track_ratings().ok())
+            .unwrap_or_default();
+
+        let (local_playlists, status_message) = match persistence.load_local_playlists() {
+            Ok(store) => (store, status_message),
+            Err(e) => (Default::default(), format!("⚠ Playlists not loaded: {}", e)),
+        };
+
+        // Same for settings.json: a corrupt file means default settings.
+        let settings = persistence.load_settings().unwrap_or_default();
+
+        let pinned = persistence
+            .library()
+            .and_then(|lib| lib.pinned_ids().ok())
+            .unwrap_or_default();
+
+        let album_of = persistence
+            .library()
+            .and_then(|lib| lib.album_refs().ok())
+            .unwrap_or_default();
+
+        // Unfinished "make available offline" jobs pick up where they left off.
+        let offline_jobs = persistence
+            .library()
+            .and_then(|lib| lib.offline_jobs().ok())
+            .unwrap_or_default();
+
+        // Warm the download cache from previous session. The library keeps
+        // its own record of cached files, which covers a lost or truncated
+        // download_cache.json.
+        let mut download_cache = persistence.load_download_cache();
+        if let Some(Ok(files)) = persistence.library().map(|lib| lib.cached_files()) {
+            for (video_id, path) in files {
+                if std::path::Path::new(&path).exists() {
+                    download_cache.entry(video_id).or_insert(path);
+                } else if let Some(library) = persistence.library() {
+                    let _ = library.remove_cached_file(&video_id);
+                }
+            }
+        }
+
+        // No usable cache dir (no XDG cache, read-only home) isn't fatal:
+        // downloads go to the temp dir for this session instead.
+        let persistent_cache = crate::config::audio_cache_dir().and_then(|dir| {
+            let cache = AudioCache::new(dir, settings.audio_profile());
+            cache.ensure_dir().map(|_| cache)
+        });
+        let (audio_cache, persistent, status_message) = match persistent_cache {
+            Ok(cache) => (cache, true, status_message),
+            Err(e) => {
+                let dir = std::env::temp_dir()
+                    .join(crate::config::APP_NAME)
+                    .join("audio");
+                let cache = AudioCache::new(dir, settings.audio_profile());
+                let _ = cache.ensure_dir();
+                (
+                    cache,
+                    false,
+                    format!(
+                        "⚠ Audio cache unavailable, downloads won't be kept: {:#}",
+                        e
+                    ),
+                )
+            }
+        };
+        let ytdlp: Arc<dyn YtDlp> = Arc::new(SystemYtDlp);
+
+        // Files downloaded by older versions live in the temp dir; move
+        // them into the permanent audio cache so a reboot doesn't lose them.
+        let before = download_cache.clone();
+        let (download_cache, migrated) = if persistent {
+            audio_cache::migrate_legacy(&audio_cache, download_cache)
+        } else {
+            (download_cache, 0)
+        };
+        if migrated > 0 {
+            if let Some(library) = persistence.library() {
+                let now = crate::services::library::now_unix();
+                for (video_id, path) in &download_cache {
+                    if before.get(video_id) != Some(path) {
+                        let _ = library.record_cached_file(video_id, path, now);
+                    }
+                }
+            }
+            let _ = persistence.save_download_cache(&download_cache);
+        }
+
+        // Files downloaded in another audio format than the one configured
+        // now keep playing (pinned and offline tracks included) and are
+        // replaced as they're played again.
+        let outdated = audio_cache::outdated_formats(&audio_cache, &download_cache);
+        let status_message = if outdated.is_empty() {
+            status_message
+        } else {
+            format!(
+                "{} (audio format is now {} — {} files in the old format are replaced as they play)",
+                status_message,
+                audio_cache.profile(),
+                outdated.len()
+            )
+        };
         let cache_count = download_cache.len();
+        let downloads = DownloadManager::with_cache(download_cache, audio_cache, ytdlp.clone());
+        downloads.set_outdated(outdated);
 
         Ok(MusicPlayerApp {
             player: AudioPlayer::new(),
@@ -133,9 +343,30 @@ impl MusicPlayerApp {
             browser_auth,
             available_accounts: Vec::new(),
             persistence,
+            filters,
+            ratings,
+            settings,
+            pinned,
+            album_of,
+            gapless_album: None,
+            cache_usage_bytes: 0,
+            network: NetworkStatus::default(),
             ui: UiState::default(),
             search: SearchState::default(),
             playlist: PlaylistState::default(),
+            local_playlists: LocalPlaylistsState {
+                store: local_playlists,
+                ..Default::default()
+            },
+            prompt: None,
+            playlist_picker: None,
+            tags: TagsState::default(),
+            cache_view: CacheViewState::default(),
+            offline_jobs: OfflineJobsState {
+                jobs: offline_jobs,
+                ..Default::default()
+            },
+            diagnostics: DiagnosticsState::default(),
             mode: initial_mode,
             current_view: ViewMode::Home,
             previous_view: ViewMode::Home,
@@ -148,12 +379,35 @@ impl MusicPlayerApp {
             status_message_set_at: Some(std::time::Instant::now()),
             status_message_snapshot: String::new(),
             queue_loaded: false,
-            downloads: DownloadManager::with_cache(download_cache),
+            downloads,
+            ytdlp,
             search_rx,
             search_tx,
             feed: FeedState::default(),
             feed_rx,
             feed_tx,
+            artist: ArtistState::default(),
+            artist_rx,
+            artist_tx,
+            verify_rx,
+            verify_tx,
+            probe_rx,
+            probe_tx,
+            export_rx,
+            export_tx,
+            diag_rx,
+            diag_tx,
+            lyrics: LyricsState::default(),
+            lyrics_rx,
+            lyrics_tx,
+            covers: CoverState {
+                protocol: ImageProtocol::detect(|name| std::env::var(name).ok()),
+                ..Default::default()
+            },
+            cover_rx,
+            cover_tx,
+            stats: StatsState::default(),
+            active_play: None,
             pending_play_track: None,
             currently_downloading: None,
         })
@@ -220,7 +474,7 @@ impl MusicPlayerApp {
 
                 if let Some(current_track) = self.queue.get_current() {
                     // Current track exists - download it with HIGHEST priority (plays on Space)
-                    if self.spawn_download_with_limit(current_track) {
+                    if self.request_download(current_track, DownloadPriority::Current) {
                         downloads_started += 1;
                     }
                 }
@@ -232,12 +486,12 @@ impl MusicPlayerApp {
                     if idx == 0 && !has_current {
                         // CRITICAL: If no current track, first queue track is HIGHEST priority
                         // This ensures instant playback when user presses Space for first time
-                        if self.spawn_download_with_limit(track) {
+                        if self.request_download(track, DownloadPriority::Current) {
                             downloads_started += 1;
                         }
                     } else {
                         // Buffer downloads (reduced from 20 to 5 for lightweight startup)
-                        if self.spawn_download_with_limit(track) {
+                        if self.request_download(track, DownloadPriority::Lookahead) {
                             downloads_started += 1;
                         }
                     }
@@ -278,8 +532,14 @@ impl MusicPlayerApp {
         let backend = CrosstermBackend::new(stdout);
         let mut terminal = Terminal::new(backend)?;
 
-        // Clean up old pre-downloaded files on startup
-        DownloadManager::cleanup_old_downloads();
+        // Clean up partial and leftover temp downloads on startup, and
+        // trim the audio cache if the quota was lowered since last run
+        self.downloads.cleanup_old_downloads();
+        self.enforce_cache_quota();
+
+        // Check yt-dlp, ffmpeg, and friends; a failure opens the
+        // Diagnostics screen
+        self.run_diagnostics(true);
 
         // Trigger async queue load on first iteration
         let mut queue_load_triggered = false;
@@ -302,7 +562,12 @@ impl MusicPlayerApp {
             let should_render = now.duration_since(last_render) >= frame_duration;
 
             if should_render {
-                terminal.draw(|f| self.draw_ui(f))?;
+                let completed = terminal.draw(|f| self.draw_ui(f))?;
+                let graphics = self.cover_graphics(completed.buffer);
+                if !graphics.is_empty() {
+                    terminal.backend_mut().write_all(graphics.as_bytes())?;
+                    terminal.backend_mut().flush()?;
+                }
                 last_render = now;
             }
 
@@ -314,7 +579,7 @@ impl MusicPlayerApp {
 
                 // Scroll controls text — advance one character every 4 animation frames
                 // (~600ms per char at 150ms/frame) for comfortable reading speed.
-                if self.ui.animation_frame % 4 == 0 {
+                if self.ui.animation_frame.is_multiple_of(4) {
                     self.ui.title_scroll_offset = self.ui.title_scroll_offset.wrapping_add(1);
                 }
 
@@ -342,12 +607,9 @@ impl MusicPlayerApp {
                 self.status_message_snapshot.clear();
             }
 
-            // Check for search results
-            if let Ok(results) = self.search_rx.try_recv() {
-                self.search.results = results;
-                self.ui.selected_result = 0;
-                self.search.is_searching = false;
-                self.status_message = format!("Found {} results", self.search.results.len());
+            // Search results stream in one at a time
+            while let Ok(event) = self.search_rx.try_recv() {
+                self.handle_search_event(event).await;
             }
 
             // Check for feed fetch results
@@ -355,6 +617,7 @@ impl MusicPlayerApp {
                 self.feed.is_loading = false;
                 match result {
                     Ok(mut sections) => {
+                        self.note_network_success();
                         // Validate URLs from the network path, matching the
                         // pattern used for queue restoration.
                         for section in &mut sections {
@@ -362,31 +625,106 @@ impl MusicPlayerApp {
                                 .items
                                 .retain(|p| is_allowed_youtube_url(&p.url));
                         }
+                        let total: usize = sections.iter().map(|s| s.items.len()).sum();
                         self.feed.sections = sections;
+                        self.refresh_favorites_section();
                         self.feed.last_fetch = Some(std::time::Instant::now());
                         self.feed.last_error = None;
-                        let total: usize = self.feed.sections.iter().map(|s| s.items.len()).sum();
                         self.status_message = format!("Feed loaded — {} playlists", total);
                     }
                     Err(e) => {
+                        self.note_network_failure(&e).await;
                         self.feed.last_error = Some(e);
                     }
                 }
             }
 
+            // Artist pages fetched in the background
+            if let Ok((name, result)) = self.artist_rx.try_recv() {
+                self.handle_artist_page(name, result).await;
+            }
+
+            // Lyrics for the playing track, once the view is open
+            if let Ok((video_id, result)) = self.lyrics_rx.try_recv() {
+                self.handle_lyrics(video_id, result).await;
+            }
+            self.tick_lyrics();
+
+            // Cover art for the player bar and feed detail panes
+            while let Ok((key, result)) = self.cover_rx.try_recv() {
+                self.handle_cover(key, result);
+            }
+            self.tick_covers();
+
+            // Report finished audio exports
+            while let Ok(result) = self.export_rx.try_recv() {
+                self.status_message = match result {
+                    Ok(msg) | Err(msg) => msg,
+                };
+            }
+
+            // Show dependency check results as they arrive
+            self.poll_diagnostics();
+
+            // Detect the network coming back while offline
+            self.poll_network(now);
+
+            // Keep "make available offline" jobs downloading
+            self.pump_offline_jobs(now, false);
+
+            // Apply cache verify results as they arrive
+            while let Ok((video_id, health)) = self.verify_rx.try_recv() {
+                self.apply_verify_result(video_id, health);
+            }
+
+            // Retry transient download failures whose backoff has elapsed
+            let due = self.downloads.due_retries();
+            if !due.is_empty() {
+                let cookie = self.cookie_config();
+                for track in due {
+                    let is_pending = self
+                        .pending_play_track
+                        .as_ref()
+                        .is_some_and(|p| p.video_id == track.video_id);
+                    let priority = if is_pending {
+                        DownloadPriority::Current
+                    } else {
+                        DownloadPriority::Background
+                    };
+                    if self
+                        .downloads
+                        .request_download(&track, priority, cookie.clone())
+                        && is_pending
+                    {
+                        self.currently_downloading = Some(track.title.clone());
+                    }
+                }
+            }
+
+            // Files replaced by a download in the current format go once
+            // the player has moved off them.
+            self.downloads
+                .remove_superseded(&self.player.files_in_use());
+
             // Check for completed downloads
             if let Some((video_id, result)) = self.downloads.poll_completion() {
                 match result {
-                    Ok(temp_file_path) => {
+                    Ok(file_path) => {
+                        self.note_network_success();
+                        if let Some(library) = self.persistence.library() {
+                            let _ = library.record_cached_file(
+                                &video_id,
+                                &file_path,
+                                crate::services::library::now_unix(),
+                            );
+                        }
+
                         // Download succeeded! Play it if it's the pending track
                         if let Some(track) = &self.pending_play_track {
                             if track.video_id == video_id {
+                                let track = track.clone();
                                 tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-                                self.player.play_with_duration(
-                                    &temp_file_path,
-                                    &track.title,
-                                    track.duration as f64,
-                                );
+                                self.start_track_playback(&file_path, &track);
                                 self.status_message.clear();
                                 self.pending_play_track = None;
                                 self.currently_downloading = None;
@@ -395,61 +733,67 @@ impl MusicPlayerApp {
                                 let next = self.queue.get_queue_slice(0, LOOKAHEAD_DOWNLOAD_COUNT);
                                 self.downloads
                                     .ensure_next_tracks_ready(&next, self.cookie_config());
-
-                                // No per-track cleanup — the hourly startup sweep
-                                // (cleanup_old_downloads) handles stale files.
-                                // Keeping files alive enables the persistent download cache.
-                            }
-                        }
-
-                        // RETRY PENDING: Check if there's a pending track waiting
-                        if let Some(pending_track) = &self.pending_play_track {
-                            if !self.downloads.is_cached(&pending_track.video_id)
-                                && self.currently_downloading.is_none()
-                            {
-                                let cookie = self.cookie_config();
-                                if self.downloads.spawn_download(pending_track, cookie) {
-                                    self.currently_downloading = Some(pending_track.title.clone());
-                                }
                             }
                         }
                     }
                     Err(e) => {
-                        // Download failed
+                        self.note_network_failure(&e).await;
+                        // Download failed — keep waiting on the pending track
+                        // if a transient failure will be retried
                         if let Some(track) = &self.pending_play_track {
                             if track.video_id == video_id {
-                                self.status_message = format!("❌ Download failed: {}", e);
-                                self.pending_play_track = None;
                                 self.currently_downloading = None;
-                            }
-                        }
-
-                        // RETRY PENDING
-                        if let Some(pending_track) = &self.pending_play_track {
-                            if !self.downloads.is_cached(&pending_track.video_id)
-                                && self.currently_downloading.is_none()
-                            {
-                                let cookie = self.cookie_config();
-                                if self.downloads.spawn_download(pending_track, cookie) {
-                                    self.currently_downloading = Some(pending_track.title.clone());
+                                match self.downloads.failure_for(&video_id) {
+                                    Some(record) if record.retry_at.is_some() => {
+                                        self.status_message = format!(
+                                            "⚠ Download failed ({}) — {} (attempt {}/{})",
+                                            record.short_reason(),
+                                            record.label(std::time::Instant::now()),
+                                            record.attempts,
+                                            crate::config::MAX_DOWNLOAD_ATTEMPTS
+                                        );
+                                    }
+                                    Some(record) => {
+                                        self.status_message = format!(
+                                            "❌ Download failed: {}",
+                                            record.short_reason()
+                                        );
+                                        self.pending_play_track = None;
+                                    }
+                                    None => {
+                                        self.status_message = format!("❌ Download failed: {}", e);
+                                        self.pending_play_track = None;
+                                    }
                                 }
                             }
                         }
                     }
                 }
 
-                // Incrementally persist the download cache after each completion
+                // Keep the cache under quota, then incrementally persist the
+                // download cache after each completion
+                self.enforce_cache_quota();
+                self.pump_offline_jobs(std::time::Instant::now(), true);
                 let _ = self
                     .persistence
                     .save_download_cache(&self.downloads.get_cache_snapshot());
             }
 
+            // Accumulate listening time for the play being timed
+            if let Some(play) = self.active_play.as_mut() {
+                play.tick(now, self.player.get_state() == PlayerState::Playing);
+            }
+
+            // Chain album tracks without a gap
+            self.tick_gapless();
+
             // Auto-advance to next track when current finishes
             // IMPORTANT: Only auto-advance when state is Playing (not Loading, Stopped, or Paused)
             // This prevents race condition where sink is empty during track loading
             if self.player.is_finished() && self.player.get_state() == PlayerState::Playing {
                 // Track finished naturally — clear saved resume state
                 self.persistence.clear_playback_state();
+                self.finish_active_play(PlayEnd::Finished);
                 if !self.queue.is_empty() {
                     self.status_message = "Track finished, playing next...".to_string();
                     self.play_next().await;
@@ -476,9 +820,12 @@ impl MusicPlayerApp {
             }
         }
 
+        self.finish_active_play(PlayEnd::Interrupted);
+
         // Abort all background download tasks before saving
         self.downloads.abort_all();
         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        self.downloads.remove_superseded(&[]);
 
         // Save playback state (position + volume) for resume-on-restart
         let volume = self.player.get_volume();
@@ -517,6 +864,9 @@ impl MusicPlayerApp {
             eprintln!("Failed to save download cache: {}", e);
         }
 
+        let cleanup = self.cover_cleanup();
+        terminal.backend_mut().write_all(cleanup.as_bytes())?;
+
         disable_raw_mode()?;
         execute!(
             terminal.backend_mut(),
@@ -529,32 +879,86 @@ impl MusicPlayerApp {
     }
 
     fn draw_ui(&self, frame: &mut Frame) {
+        // A prompt pops up over whatever screen it was opened from.
+        match (&self.mode, &self.prompt) {
+            (AppMode::Prompt, Some(prompt)) => {
+                self.draw_screen(frame, &prompt.return_mode);
+                super::views::prompt::draw_prompt(prompt, frame);
+            }
+            (AppMode::PlaylistPicker, _) => match &self.playlist_picker {
+                Some(picker) => {
+                    self.draw_screen(frame, &picker.return_mode);
+                    super::views::local_playlists::draw_picker(self, picker, frame);
+                }
+                None => self.draw_screen(frame, &AppMode::Normal),
+            },
+            (mode, _) => self.draw_screen(frame, mode),
+        }
+    }
+
+    fn draw_screen(&self, frame: &mut Frame, mode: &AppMode) {
         use super::views;
 
         // Show login screen if not authenticated
-        if matches!(self.mode, AppMode::LoginPrompt) {
+        if matches!(mode, AppMode::LoginPrompt) {
             views::login::draw_login_screen(self, frame);
             return;
         }
 
         // Show account picker
-        if matches!(self.mode, AppMode::AccountPicker) {
+        if matches!(mode, AppMode::AccountPicker) {
             views::login::draw_account_picker(self, frame);
             return;
         }
 
         // Show help screen
-        if matches!(self.mode, AppMode::Help) {
+        if matches!(mode, AppMode::Help) {
             views::help::draw_help_screen(self, frame);
             return;
         }
 
+        // Show the dependency diagnostics screen
+        if matches!(mode, AppMode::Diagnostics) {
+            views::diagnostics::draw(self, frame);
+            return;
+        }
+
+        // Show the artist browser
+        if matches!(mode, AppMode::ArtistBrowser) {
+            views::artist::draw(self, frame);
+            return;
+        }
+
         // Show feed browser (full-screen overlay)
-        if matches!(self.mode, AppMode::FeedBrowser) {
+        if matches!(mode, AppMode::FeedBrowser) {
             views::feed::draw(self, frame);
             return;
         }
 
+        // Show local playlists (full-screen overlay)
+        if matches!(mode, AppMode::Playlists) {
+            views::local_playlists::draw(self, frame);
+            return;
+        }
+
+        // Show the tag cloud (full-screen overlay)
+        if matches!(mode, AppMode::Tags) {
+            views::tags::draw(self, frame);
+            return;
+        }
+
+        // Show the audio cache view (full-screen overlay)
+        if matches!(mode, AppMode::Cache) {
+            views::cache_stats::draw_cache_view(self, frame);
+            return;
+        }
+
+        // Show the offline downloads panel (full-screen overlay)
+        if matches!(mode, AppMode::Downloads) {
+            views::downloads::draw(self, frame);
+            return;
+        }
+
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
@@ -570,8 +974,20 @@ impl MusicPlayerApp {
         } else if !self.status_message.is_empty() {
             self.status_message.clone()
         } else {
-            match self.mode {
-                AppMode::Searching => format!("🔍 SEARCH MODE: {}_", self.search.query),
+            match mode {
+                AppMode::Searching => {
+                    let scopes: Vec<String> = SearchScope::ALL
+                        .iter()
+                        .map(|&scope| {
+                            if scope == self.search.scope {
+                                format!("[{}]", scope.label())
+                            } else {
+                                scope.label().to_string()
+                            }
+                        })
+                        .collect();
+                    format!("🔍 {} (Tab) › {}", scopes.join(" "), self.search.query)
+                }
                 AppMode::LoadingPlaylist => format!(
                     "📋 PASTE PLAYLIST URL: {}_  (Press Enter to load, Esc to cancel)",
                     self.playlist.url
@@ -596,13 +1012,48 @@ impl MusicPlayerApp {
                 AppMode::FeedBrowser => {
                     "Feed Browser - [j/k] Navigate  [h/l] Expand/Collapse  [Enter] Play  [a] Add  [r] Refresh  [Esc/f] Close".to_string()
                 }
+                AppMode::Playlists
+                | AppMode::Prompt
+                | AppMode::PlaylistPicker
+                | AppMode::Tags
+                | AppMode::Cache
+                | AppMode::Downloads
+                | AppMode::Diagnostics
+                | AppMode::ArtistBrowser => {
+                    String::new()
+                }
             }
         };
-        let header = Paragraph::new(title).block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("YouTube Music Player"),
-        );
+        let header_title = if self.is_offline() {
+            Line::from(vec![
+                Span::raw("YouTube Music Player "),
+                Span::styled(
+                    "[OFFLINE]",
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                ),
+            ])
+        } else {
+            Line::from("YouTube Music Player")
+        };
+        let mut title = Line::from(title);
+        if matches!(mode, AppMode::Searching)
+            && !self.search.is_searching
+            && self.status_message.is_empty()
+        {
+            // Ghosted completion after the typed query; → accepts it.
+            match self.search.inline_completion() {
+                Some(rest) => {
+                    title.push_span(Span::styled(
+                        rest.to_string(),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                    title.push_span(Span::styled("  →", Style::default().fg(Color::DarkGray)));
+                }
+                None => title.push_span("_"),
+            }
+        }
+        let header =
+            Paragraph::new(title).block(Block::default().borders(Borders::ALL).title(header_title));
         frame.render_widget(header, chunks[0]);
 
         // Main area layout depends on queue expansion, my mix expansion, history expansion, or view mode
@@ -618,14 +1069,22 @@ impl MusicPlayerApp {
         } else if self.ui.playlist_loading_expanded {
             // Playlist loading expanded: Show URL input interface
             views::playlist::draw_playlist_loading_expanded(self, frame, chunks[1]);
-        } else if self.current_view == ViewMode::Search || matches!(self.mode, AppMode::Searching) {
+        } else if self.current_view == ViewMode::Stats && !matches!(mode, AppMode::Searching) {
+            views::stats::draw_stats(self, frame, chunks[1]);
+        } else if self.current_view == ViewMode::Lyrics && !matches!(mode, AppMode::Searching) {
+            views::lyrics::draw_lyrics(self, frame, chunks[1]);
+        } else if self.current_view == ViewMode::Search || matches!(mode, AppMode::Searching) {
             // Search view: Search Results (left) | History (right)
             let main_chunks = Layout::default()
                 .direction(Direction::Horizontal)
                 .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
                 .split(chunks[1]);
 
-            views::search::draw_search_results(self, frame, main_chunks[0]);
+            if matches!(mode, AppMode::Searching) && !self.search.suggestions.is_empty() {
+                views::search::draw_suggestions(self, frame, main_chunks[0]);
+            } else {
+                views::search::draw_search_results(self, frame, main_chunks[0]);
+            }
             views::history::draw_history(self, frame, main_chunks[1]);
         } else {
             // Home view (default): Queue (left) | History (right)
@@ -666,6 +1125,7 @@ impl MusicPlayerApp {
             history_expanded: self.ui.history_expanded,
             search_query_len: self.search.query.len(),
             playlist_url_len: self.playlist.url.len(),
+            prompt_len: self.prompt.as_ref().map_or(0, |p| p.input.chars().count()),
         };
         let cmd = key_to_command(key, &ctx);
 
@@ -675,7 +1135,12 @@ impl MusicPlayerApp {
             AppCommand::Quit => self.should_quit = true,
             AppCommand::ShowHelp => self.mode = AppMode::Help,
             AppCommand::DismissHelp => self.mode = AppMode::Normal,
-            AppCommand::StartSearch => self.mode = AppMode::Searching,
+            AppCommand::StartSearch => {
+                self.mode = AppMode::Searching;
+                // The header doubles as the search input; don't cover it.
+                self.status_message.clear();
+                self.refresh_search_suggestions();
+            }
             AppCommand::StartLogin => self.start_login().await,
             AppCommand::StartLoadPlaylist => {
                 self.mode = AppMode::LoadingPlaylist;
@@ -709,8 +1174,10 @@ impl MusicPlayerApp {
                     self.next_history_item();
                 } else if self.current_view == ViewMode::Home {
                     self.next_mix_item();
-                } else {
+                } else if self.current_view == ViewMode::Search {
                     self.next_search_result();
+                } else if self.current_view == ViewMode::Lyrics {
+                    self.scroll_lyrics(1);
                 }
             }
             AppCommand::NavigateUp => {
@@ -722,22 +1189,51 @@ impl MusicPlayerApp {
                     self.prev_history_item();
                 } else if self.current_view == ViewMode::Home {
                     self.prev_mix_item();
-                } else {
+                } else if self.current_view == ViewMode::Search {
                     self.prev_search_result();
+                } else if self.current_view == ViewMode::Lyrics {
+                    self.scroll_lyrics(-1);
                 }
             }
-            AppCommand::Select => {
-                if self.current_view == ViewMode::Home {
-                    self.add_selected_mix_to_queue().await;
-                } else {
-                    self.add_selected_to_queue();
-                }
-            }
+            AppCommand::Select => match self.current_view {
+                ViewMode::Home => self.add_selected_mix_to_queue().await,
+                ViewMode::Search => self.select_search_result().await,
+                ViewMode::Stats | ViewMode::Lyrics => {}
+            },
             AppCommand::GoHome => {
                 self.previous_view = self.current_view;
                 self.current_view = ViewMode::Home;
                 self.status_message = "Returned to Home (My Mix)".to_string();
             }
+            AppCommand::ToggleStatsView => {
+                if self.current_view == ViewMode::Stats {
+                    std::mem::swap(&mut self.current_view, &mut self.previous_view);
+                } else {
+                    self.previous_view = self.current_view;
+                    self.current_view = ViewMode::Stats;
+                    self.refresh_stats();
+                }
+            }
+            AppCommand::ToggleLyricsView => {
+                if self.current_view == ViewMode::Lyrics {
+                    std::mem::swap(&mut self.current_view, &mut self.previous_view);
+                } else {
+                    self.previous_view = self.current_view;
+                    self.current_view = ViewMode::Lyrics;
+                    self.tick_lyrics();
+                }
+            }
+            AppCommand::CycleStatsWindow => {
+                if self.current_view == ViewMode::Stats {
+                    self.stats.window = self.stats.window.next();
+                    self.refresh_stats();
+                }
+            }
+            AppCommand::EscapeBack
+                if self.current_view == ViewMode::Search && self.search.opened.is_some() =>
+            {
+                self.close_search_list();
+            }
             AppCommand::EscapeBack => {
                 std::mem::swap(&mut self.current_view, &mut self.previous_view);
                 self.status_message = "Returned to previous view".to_string();
@@ -774,13 +1270,12 @@ impl MusicPlayerApp {
                     self.refresh_my_mix().await;
                 }
             }
-            AppCommand::ToggleMusicOnlyMode => {
-                self.ui.music_only_mode = !self.ui.music_only_mode;
-                self.status_message = if self.ui.music_only_mode {
-                    "Music mode ON (tracks >7min filtered)".to_string()
-                } else {
-                    "All content mode (no duration filter)".to_string()
-                };
+            AppCommand::CycleFilterProfile => {
+                let name = self.filters.cycle();
+                self.status_message = format!("Filter profile: {}", name);
+                if let Err(e) = self.persistence.save_filters(&self.filters) {
+                    self.status_message = format!("Filter profile: {} (not saved: {})", name, e);
+                }
             }
             AppCommand::Delete => {
                 if self.ui.history_expanded {
@@ -806,21 +1301,40 @@ impl MusicPlayerApp {
             AppCommand::SwitchAccount => self.switch_account().await,
 
             // Search input
-            AppCommand::SearchChar(c) => self.search.query.push(c),
+            AppCommand::SearchChar(c) => {
+                self.search.query.push(c);
+                self.refresh_search_suggestions();
+            }
+            AppCommand::CycleSearchScope { back } => {
+                self.search.scope = self.search.scope.cycle(back);
+            }
             AppCommand::SearchBackspace => {
                 self.search.query.pop();
+                self.refresh_search_suggestions();
+            }
+            AppCommand::SearchSuggestionNext => self.move_search_suggestion(true),
+            AppCommand::SearchSuggestionPrev => self.move_search_suggestion(false),
+            AppCommand::SearchAcceptCompletion => self.accept_search_completion(),
+            AppCommand::SearchForget => self.forget_selected_search(),
+            AppCommand::LoadMoreResults => {
+                if self.current_view == ViewMode::Search {
+                    self.load_more_results();
+                }
             }
             AppCommand::SearchSubmit => {
-                let query = self.search.query.clone();
+                let query = self.search_query_to_submit();
+                self.remember_search(&query);
                 self.perform_search(&query).await;
                 self.mode = AppMode::Normal;
                 self.previous_view = self.current_view;
                 self.current_view = ViewMode::Search;
                 self.search.query.clear();
+                self.search.suggestions.clear();
             }
             AppCommand::SearchCancel => {
                 self.mode = AppMode::Normal;
                 self.search.query.clear();
+                self.search.suggestions.clear();
                 std::mem::swap(&mut self.current_view, &mut self.previous_view);
             }
 
@@ -865,6 +1379,7 @@ impl MusicPlayerApp {
                     self.feed_expand_playlist().await;
                 }
             }
+            AppCommand::FeedPlayAlbum => self.feed_play_album().await,
             AppCommand::FeedAddToPlaylist => {
                 use crate::ui::state::FeedFocus;
                 if self.feed.focus == FeedFocus::Tracks {
@@ -873,6 +1388,115 @@ impl MusicPlayerApp {
                     self.feed_add_to_playlist().await;
                 }
             }
+
+            // Local playlists
+            AppCommand::OpenPlaylists => self.open_playlists(),
+            AppCommand::ClosePlaylists => self.mode = AppMode::Normal,
+            AppCommand::OpenPlaylistPicker => self.open_playlist_picker(),
+            AppCommand::PlaylistPickerNext => self.move_playlist_picker(true),
+            AppCommand::PlaylistPickerPrev => self.move_playlist_picker(false),
+            AppCommand::PlaylistPickerAdd => self.playlist_picker_add(),
+            AppCommand::PlaylistPickerRemove => self.playlist_picker_remove(),
+            AppCommand::PlaylistPickerCancel => self.close_playlist_picker(),
+            AppCommand::ToggleFavorite => self.toggle_favorite(),
+            AppCommand::RateTrack(stars) => self.rate_track(stars),
+            AppCommand::PlaylistsNavigateDown => self.local_playlists.navigate_down(),
+            AppCommand::PlaylistsNavigateUp => self.local_playlists.navigate_up(),
+            AppCommand::PlaylistsFocusTracks => self.playlists_focus_tracks(),
+            AppCommand::PlaylistsFocusLists => {
+                self.local_playlists.focus = crate::ui::state::PlaylistsFocus::Lists;
+            }
+            AppCommand::PlaylistsPlay => self.playlists_play().await,
+            AppCommand::PlaylistsEnqueue => self.playlists_enqueue(),
+            AppCommand::NewPlaylist => {
+                self.open_prompt(crate::ui::state::PromptPurpose::CreatePlaylist, "");
+            }
+            AppCommand::RenamePlaylist => self.start_rename_playlist(),
+            AppCommand::PlaylistsDelete => self.playlists_delete(),
+            AppCommand::MoveTrackUp => self.playlists_move_track(false),
+            AppCommand::MoveTrackDown => self.playlists_move_track(true),
+            AppCommand::ExportTracks => {
+                if self.mode == AppMode::Playlists {
+                    let selected = self.local_playlists.selected;
+                    self.start_export(crate::ui::state::ExportSource::Playlist(selected));
+                } else {
+                    self.start_export_from_main();
+                }
+            }
+            AppCommand::ExportAudio => match self.mode {
+                AppMode::Playlists => {
+                    let selected = self.local_playlists.selected;
+                    self.start_export_audio(crate::ui::state::ExportSource::Playlist(selected));
+                }
+                AppMode::Cache => {
+                    let selected = self.cache_view.selected;
+                    self.start_export_audio(crate::ui::state::ExportSource::Cached(selected));
+                }
+                _ => self.start_export_audio_from_main(),
+            },
+            AppCommand::ImportPlaylist => {
+                self.open_prompt(crate::ui::state::PromptPurpose::ImportPlaylist, "");
+            }
+
+            // Tags
+            AppCommand::EditTags => self.start_edit_tags(),
+            AppCommand::TogglePin => self.toggle_pin(),
+            AppCommand::RetryFailedDownloads => self.retry_failed_downloads(),
+            AppCommand::CancelDownload => self.cancel_download(),
+            AppCommand::ToggleOfflineMode => self.toggle_offline_mode().await,
+            AppCommand::MakeFeedPlaylistOffline => self.make_feed_playlist_offline().await,
+            AppCommand::MakeLoadedPlaylistOffline => self.make_loaded_playlist_offline(),
+            AppCommand::OpenDownloads => self.open_downloads_panel(),
+            AppCommand::CloseDownloads => self.mode = AppMode::Normal,
+            AppCommand::DownloadsNext => self.offline_jobs.select_next(),
+            AppCommand::DownloadsPrev => self.offline_jobs.select_prev(),
+            AppCommand::DownloadsDelete => self.delete_selected_offline_job(),
+            AppCommand::OpenDiagnostics => self.open_diagnostics(),
+            AppCommand::RerunDiagnostics => self.run_diagnostics(false),
+            AppCommand::CloseDiagnostics => self.close_diagnostics(),
+
+            // Artist browser
+            AppCommand::OpenArtist => self.open_artist(),
+            AppCommand::CloseArtist => self.close_artist(),
+            AppCommand::RefreshArtist => self.refresh_artist(),
+            AppCommand::ArtistDown => self.artist.move_selection(true),
+            AppCommand::ArtistUp => self.artist.move_selection(false),
+            AppCommand::ArtistRight => self.artist_right().await,
+            AppCommand::ArtistLeft => self.artist_left(),
+            AppCommand::ArtistPlay => self.artist_play().await,
+            AppCommand::ArtistAdd => self.artist_add().await,
+            AppCommand::OpenCache => self.open_cache_view(),
+            AppCommand::CloseCache => self.mode = AppMode::Normal,
+            AppCommand::CacheNext => self.cache_view.select_next(),
+            AppCommand::CachePrev => self.cache_view.select_prev(),
+            AppCommand::CacheVerify => self.start_cache_verify(),
+            AppCommand::CacheDelete => self.cache_delete_selected(),
+            AppCommand::CacheRedownload => self.cache_redownload_selected(),
+            AppCommand::OpenTags => self.open_tags_view(),
+            AppCommand::CloseTags => self.mode = AppMode::Normal,
+            AppCommand::TagsNext => self.tags.select_next(),
+            AppCommand::TagsPrev => self.tags.select_prev(),
+            AppCommand::TagsToggleMark => self.tags.toggle_mark(),
+            AppCommand::TagsToggleMatchAll => self.tags.match_all = !self.tags.match_all,
+            AppCommand::TagsQueue => self.queue_selected_tags(),
+            AppCommand::TagsQueryPrompt => {
+                let initial = self.tags.query().map(|q| q.to_string()).unwrap_or_default();
+                self.open_prompt(crate::ui::state::PromptPurpose::QueueByTags, &initial);
+            }
+
+            // Text prompt
+            AppCommand::PromptChar(c) => {
+                if let Some(prompt) = self.prompt.as_mut() {
+                    prompt.input.push(c);
+                }
+            }
+            AppCommand::PromptBackspace => {
+                if let Some(prompt) = self.prompt.as_mut() {
+                    prompt.input.pop();
+                }
+            }
+            AppCommand::PromptSubmit => self.submit_prompt(),
+            AppCommand::PromptCancel => self.cancel_prompt(),
         }
     }
 
@@ -0,0 +1,309 @@
+//! Artist/channel browser for MusicPlayerApp.
+//!
+//! `e` on a track resolves its uploader's channel and opens a column view of
+//! the channel's uploads, albums, singles and playlists. Pages are fetched in
+//! the background (`artist_tx`) and cached on disk per channel.
+
+use crate::config::clean_title;
+use crate::player::queue::Track;
+use crate::services::artist::{self, display_name, ArtistPage, Channel, Release};
+use crate::ui::state::{AppMode, ArtistFocus, ArtistSection, ViewMode};
+use crate::youtube::extractor::SearchResult;
+
+use super::app::MusicPlayerApp;
+
+impl MusicPlayerApp {
+    /// `e` — open the page of the artist under the cursor: a search result
+    /// artist, or the uploader of the highlighted or playing track.
+    pub(super) fn open_artist(&mut self) {
+        let picked = match self.search.results.get(self.ui.selected_result) {
+            Some(SearchResult::Artist(info))
+                if self.mode == AppMode::Normal && self.current_view == ViewMode::Search =>
+            {
+                Channel::new(&info.id, &info.name, &info.url)
+            }
+            _ => None,
+        };
+        let (name, track) = match &picked {
+            Some(channel) => (channel.name.clone(), None),
+            None => match self.track_under_cursor() {
+                Some(track) => (display_name(&track.uploader), Some(track)),
+                None => {
+                    self.status_message = "No track selected".to_string();
+                    return;
+                }
+            },
+        };
+        if let Some(loading) = &self.artist.loading {
+            self.status_message = format!("Still loading {}…", loading);
+            return;
+        }
+
+        self.artist.return_mode = std::mem::replace(&mut self.mode, AppMode::ArtistBrowser);
+        self.artist.status = None;
+        let channel = picked.or_else(|| self.artist.channels.get(&name).cloned());
+        if let (Some(channel), Some(page)) = (&channel, &self.artist.page) {
+            if page.channel.id == channel.id {
+                return;
+            }
+        }
+        if let Some(channel) = &channel {
+            let store = artist::artist_cache_store(self.persistence.config_dir(), &channel.id);
+            let cached = if self.is_offline() {
+                store.load_stale()
+            } else {
+                store.load()
+            };
+            if let Ok(Some(page)) = cached {
+                self.artist.show(page);
+                return;
+            }
+        }
+        self.fetch_artist(name, channel, track);
+    }
+
+    /// `r` in the artist browser — list the channel again, bypassing the cache.
+    pub(super) fn refresh_artist(&mut self) {
+        let Some(channel) = self.artist.page.as_ref().map(|p| p.channel.clone()) else {
+            return;
+        };
+        if self.artist.loading.is_some() {
+            return;
+        }
+        artist::artist_cache_store(self.persistence.config_dir(), &channel.id).invalidate();
+        self.fetch_artist(channel.name.clone(), Some(channel), None);
+    }
+
+    /// Resolve the channel (unless known) and list it in the background;
+    /// the result arrives on `artist_tx`.
+    fn fetch_artist(&mut self, name: String, channel: Option<Channel>, track: Option<Track>) {
+        if let Some(msg) = self.offline_refusal(&format!("open {}'s page", name)) {
+            self.artist.error = Some(msg);
+            return;
+        }
+        let Some(lookup) = channel.clone().map(Ok).or_else(|| track.map(Err)) else {
+            return;
+        };
+        self.artist.loading = Some(name.clone());
+        self.artist.error = None;
+
+        let ytdlp = self.ytdlp.clone();
+        let cookie = self.cookie_config();
+        let config_dir = self.persistence.config_dir().to_owned();
+        let tx = self.artist_tx.clone();
+        tokio::spawn(async move {
+            let result = tokio::task::spawn_blocking(move || {
+                let channel = match lookup {
+                    Ok(channel) => channel,
+                    Err(track) => artist::resolve_channel(&*ytdlp, &track, cookie.clone())?,
+                };
+                let page = artist::fetch_artist_page(&*ytdlp, &channel, cookie)?;
+                // A failed tab is worth retrying on the next visit.
+                if page.tab_errors().is_empty() {
+                    let _ = artist::artist_cache_store(&config_dir, &channel.id).save(&page);
+                }
+                Ok(page)
+            })
+            .await
+            .unwrap_or_else(|e| Err(format!("Task error: {e}")));
+            let _ = tx.send((name, result));
+        });
+    }
+
+    /// A page fetched by `fetch_artist` arrived.
+    pub(super) async fn handle_artist_page(
+        &mut self,
+        name: String,
+        result: Result<ArtistPage, String>,
+    ) {
+        self.artist.loading = None;
+        match result {
+            Ok(page) => {
+                self.note_network_success();
+                self.artist.channels.insert(name, page.channel.clone());
+                let empty = page.is_empty().then(|| {
+                    let mut msg = format!("{} has nothing to play", page.channel.name);
+                    for failed in page.tab_errors() {
+                        msg.push_str(&format!(" · {failed}"));
+                    }
+                    msg
+                });
+                self.artist.show(page);
+                self.artist.error = empty;
+            }
+            Err(e) => {
+                self.note_network_failure(&e).await;
+                self.artist.error = Some(e);
+            }
+        }
+    }
+
+    /// Back to the screen the page was opened from.
+    pub(super) fn close_artist(&mut self) {
+        self.mode = std::mem::take(&mut self.artist.return_mode);
+        self.artist.status = None;
+    }
+
+    /// `l` / `→` — focus the next column; on a release, open its tracks.
+    pub(super) async fn artist_right(&mut self) {
+        match self.artist.focus {
+            ArtistFocus::Sections if self.artist.section().is_some() => {
+                self.artist.focus = ArtistFocus::Items;
+                self.artist.selected_item = 0;
+            }
+            ArtistFocus::Items => self.open_artist_release().await,
+            _ => {}
+        }
+    }
+
+    /// `h` / `←` — back to the previous column.
+    pub(super) fn artist_left(&mut self) {
+        self.artist.focus = match self.artist.focus {
+            ArtistFocus::Tracks => ArtistFocus::Items,
+            _ => ArtistFocus::Sections,
+        };
+    }
+
+    async fn open_artist_release(&mut self) {
+        let Some(release) = self.artist.selected_release().cloned() else {
+            return;
+        };
+        match self.load_release_tracks(release).await {
+            Ok(tracks) => {
+                self.artist.release_tracks = tracks;
+                self.artist.selected_track = 0;
+                self.artist.focus = ArtistFocus::Tracks;
+                self.artist.status = None;
+            }
+            Err(e) => self.artist.status = Some(e),
+        }
+    }
+
+    /// The tracks of an album, single or playlist on the page.
+    async fn load_release_tracks(&mut self, release: Release) -> Result<Vec<Track>, String> {
+        let title = clean_title(&release.title).to_string();
+        if let Some(msg) = self.offline_refusal(&format!("open '{}'", title)) {
+            return Err(msg);
+        }
+        self.artist.tracks_loading = true;
+        let ytdlp = self.ytdlp.clone();
+        let cookie = self.cookie_config();
+        let result = tokio::task::spawn_blocking(move || {
+            artist::fetch_release_tracks(&*ytdlp, &release, cookie)
+        })
+        .await
+        .unwrap_or_else(|e| Err(format!("Task error: {e}")));
+        self.artist.tracks_loading = false;
+
+        match result {
+            Ok(tracks) if tracks.is_empty() => Err(format!("No tracks found in '{}'", title)),
+            Ok(tracks) => {
+                self.note_network_success();
+                Ok(tracks)
+            }
+            Err(e) => {
+                self.note_network_failure(&e).await;
+                Err(format!("Failed to open '{}': {}", title, e))
+            }
+        }
+    }
+
+    /// Enter — play the focused track list from the highlighted track,
+    /// replacing the queue; on a release, open it.
+    pub(super) async fn artist_play(&mut self) {
+        match self.artist.focus {
+            ArtistFocus::Sections => self.artist_right().await,
+            ArtistFocus::Items if self.artist.section() != Some(ArtistSection::Uploads) => {
+                self.open_artist_release().await;
+            }
+            _ => {
+                let start = match self.artist.focus {
+                    ArtistFocus::Tracks => self.artist.selected_track,
+                    _ => self.artist.selected_item,
+                };
+                let tracks: Vec<Track> = self
+                    .artist
+                    .track_list()
+                    .iter()
+                    .skip(start)
+                    .cloned()
+                    .collect();
+                let Some(first) = tracks.first().map(|t| clean_title(&t.title).to_string()) else {
+                    return;
+                };
+                if let Some(current) = self.queue.get_current().cloned() {
+                    self.queue.add_to_history(current);
+                }
+                self.queue.restore_queue(tracks, None);
+                self.ui.selected_queue_item = 0;
+                self.trigger_smart_downloads();
+                self.play_current_or_first().await;
+
+                self.mode = AppMode::Normal;
+                self.status_message =
+                    format!("▶ Playing '{}' — {} queued", first, self.queue.len());
+                if let Err(e) = self.save_queue() {
+                    self.status_message =
+                        format!("{} — queue not saved: {}", self.status_message, e);
+                }
+            }
+        }
+    }
+
+    /// `a` — add to the queue: the highlighted track, every track of the
+    /// highlighted release, or with the section list focused, all uploads.
+    pub(super) async fn artist_add(&mut self) {
+        let (tracks, what) = match (self.artist.focus, self.artist.section()) {
+            (ArtistFocus::Tracks, _) | (ArtistFocus::Items, Some(ArtistSection::Uploads)) => {
+                let Some(track) = self.artist.selected_track().cloned() else {
+                    return;
+                };
+                let title = clean_title(&track.title).to_string();
+                (vec![track], format!("'{}'", title))
+            }
+            (ArtistFocus::Items, Some(_)) => {
+                let Some(release) = self.artist.selected_release().cloned() else {
+                    return;
+                };
+                let title = format!("'{}'", clean_title(&release.title));
+                match self.load_release_tracks(release).await {
+                    Ok(tracks) => (tracks, title),
+                    Err(e) => {
+                        self.artist.status = Some(e);
+                        return;
+                    }
+                }
+            }
+            (ArtistFocus::Sections, Some(ArtistSection::Uploads)) => {
+                let Some(page) = &self.artist.page else {
+                    return;
+                };
+                (
+                    page.uploads.clone(),
+                    format!("{}'s uploads", page.channel.name),
+                )
+            }
+            _ => return,
+        };
+
+        let (added, skipped_filtered, skipped_duplicate) = self.add_filtered_tracks(&tracks);
+        self.trigger_smart_downloads();
+        let mut parts = vec![format!("✓ Added {} from {}", added, what)];
+        if skipped_duplicate > 0 {
+            parts.push(format!("{} already in queue", skipped_duplicate));
+        }
+        if skipped_filtered > 0 {
+            parts.push(format!(
+                "{} filtered by '{}'",
+                skipped_filtered,
+                self.filters.active().name
+            ));
+        }
+        parts.push(format!("{} in queue", self.queue.len()));
+        let mut msg = parts.join(" — ");
+        if let Err(e) = self.save_queue() {
+            msg = format!("{} — queue not saved: {}", msg, e);
+        }
+        self.artist.status = Some(msg);
+    }
+}
@@ -0,0 +1,347 @@
+//! Audio cache housekeeping for MusicPlayerApp.
+//!
+//! Keeps the offline cache under the `cache_quota_mb` setting by evicting
+//! least-recently-played files, handles pinning tracks so they are never
+//! evicted, runs the Cache view's verify pass, which purges broken files
+//! and re-downloads the ones still needed, and retries or cancels
+//! downloads.
+
+use std::collections::HashSet;
+
+use crate::config::clean_title;
+use crate::player::queue::Track;
+use crate::services::audio_cache::{plan_eviction, verify_file, FileHealth};
+use crate::services::download_queue::DownloadPriority;
+use crate::ui::state::{AppMode, CacheRow, VerifyOutcome};
+
+use super::app::MusicPlayerApp;
+
+impl MusicPlayerApp {
+    /// Evict least-recently-played files until the cache fits its quota.
+    /// Pinned tracks, the current track and the track waiting to play are
+    /// kept. Also refreshes `cache_usage_bytes`.
+    pub(super) fn enforce_cache_quota(&mut self) {
+        let Some(library) = self.persistence.library() else {
+            return;
+        };
+        let Ok(entries) = library.cache_entries() else {
+            return;
+        };
+        // The library may still list files this session dropped.
+        let entries: Vec<_> = entries
+            .into_iter()
+            .filter(|e| self.downloads.is_cached(&e.video_id))
+            .collect();
+
+        let evict = match self.settings.cache_quota_bytes() {
+            Some(quota) => {
+                let mut keep: HashSet<String> = self.pinned.clone();
+                keep.extend(self.queue.get_current().map(|t| t.video_id.clone()));
+                keep.extend(self.pending_play_track.as_ref().map(|t| t.video_id.clone()));
+                keep.extend(self.offline_jobs.track_ids().cloned());
+                plan_eviction(&entries, quota, &keep)
+            }
+            None => Vec::new(),
+        };
+
+        for video_id in &evict {
+            self.purge_cached_file(video_id);
+        }
+
+        self.cache_usage_bytes = entries
+            .iter()
+            .filter(|e| !evict.contains(&e.video_id))
+            .map(|e| e.size_bytes)
+            .sum();
+    }
+
+    /// Delete a cached file and forget it in the download cache and library.
+    fn purge_cached_file(&self, video_id: &str) {
+        if let Some(path) = self.downloads.get_cached_file(video_id) {
+            let path = std::path::Path::new(&path);
+            // Never delete anything outside the cache directory.
+            if self.downloads.audio_cache().contains(path) {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+        self.downloads.remove_from_cache(video_id);
+        if let Some(library) = self.persistence.library() {
+            let _ = library.remove_cached_file(video_id);
+        }
+    }
+
+    /// `b` — pin or unpin the track under the cursor. Pinning a track that
+    /// isn't cached yet starts its download.
+    pub(super) fn toggle_pin(&mut self) {
+        let Some(track) = self.track_under_cursor() else {
+            self.report_pin("No track selected".to_string());
+            return;
+        };
+        let Some(library) = self.persistence.library() else {
+            self.report_pin(
+                "⚠ Pinning needs the library database, which is unavailable".to_string(),
+            );
+            return;
+        };
+        let pin = !self.pinned.contains(&track.video_id);
+        if let Err(e) = library.set_pinned(&track, pin) {
+            self.report_pin(format!("⚠ Pin not saved: {}", e));
+            return;
+        }
+
+        let title = clean_title(&track.title).to_string();
+        let msg = if pin {
+            self.pinned.insert(track.video_id.clone());
+            if self.downloads.is_cached(&track.video_id) {
+                format!("✓ 📌 Pinned '{}' — it stays in the cache", title)
+            } else {
+                self.request_download(&track, DownloadPriority::Background);
+                format!("✓ 📌 Pinned '{}' — downloading for offline play", title)
+            }
+        } else {
+            self.pinned.remove(&track.video_id);
+            format!("Unpinned '{}'", title)
+        };
+        self.report_pin(msg);
+    }
+
+    /// `r` — forget every failed download and retry now. The track
+    /// waiting to play goes first.
+    pub(super) fn retry_failed_downloads(&mut self) {
+        let failed = self.downloads.take_failed();
+        if failed.is_empty() {
+            self.status_message = "No failed downloads".to_string();
+            return;
+        }
+        let pending_id = self.pending_play_track.as_ref().map(|t| t.video_id.clone());
+        for track in &failed {
+            let priority = if pending_id.as_deref() == Some(track.video_id.as_str()) {
+                self.currently_downloading = Some(track.title.clone());
+                DownloadPriority::Current
+            } else {
+                DownloadPriority::Background
+            };
+            self.request_download(track, priority);
+        }
+        self.status_message = format!("⬇ Retrying {} failed downloads", failed.len());
+    }
+
+    /// `u` — cancel the download of the track under the cursor, or else of
+    /// the track waiting to play. Cancelling the latter stops waiting.
+    pub(super) fn cancel_download(&mut self) {
+        let in_flight = |t: &Track| {
+            self.downloads.is_downloading(&t.video_id) || self.downloads.is_queued(&t.video_id)
+        };
+        let Some(track) = self
+            .track_under_cursor()
+            .filter(in_flight)
+            .or_else(|| self.pending_play_track.clone().filter(in_flight))
+        else {
+            self.status_message = "No download to cancel".to_string();
+            return;
+        };
+        self.downloads.cancel_download(&track.video_id);
+        if self
+            .pending_play_track
+            .as_ref()
+            .is_some_and(|p| p.video_id == track.video_id)
+        {
+            self.pending_play_track = None;
+            self.currently_downloading = None;
+        }
+        self.status_message = format!("✗ Cancelled download of '{}'", clean_title(&track.title));
+    }
+
+    fn report_pin(&mut self, msg: String) {
+        if self.mode == AppMode::FeedBrowser {
+            self.feed.feed_status = Some(msg.clone());
+        }
+        self.status_message = msg;
+    }
+
+    // -----------------------------------------------------------------------
+    // Cache view
+    // -----------------------------------------------------------------------
+
+    /// `c` — open the full-screen cache view.
+    pub(super) fn open_cache_view(&mut self) {
+        self.refresh_cache_rows();
+        self.mode = AppMode::Cache;
+    }
+
+    /// Rebuild the Cache view's rows from the library and download cache.
+    pub(super) fn refresh_cache_rows(&mut self) {
+        self.enforce_cache_quota();
+        let Some(library) = self.persistence.library() else {
+            self.cache_view.error = Some("Library database unavailable".to_string());
+            return;
+        };
+        let entries = match library.cache_entries() {
+            Ok(entries) => entries,
+            Err(e) => {
+                self.cache_view.error = Some(format!("Failed to load cache: {}", e));
+                return;
+            }
+        };
+        let rows = entries
+            .into_iter()
+            .filter_map(|entry| {
+                let path = self.downloads.get_cached_file(&entry.video_id)?;
+                let track = library
+                    .get_track(&entry.video_id)
+                    .ok()
+                    .flatten()
+                    .unwrap_or_else(|| {
+                        Track::new(
+                            entry.video_id.clone(),
+                            entry.video_id.clone(),
+                            0,
+                            String::new(),
+                            format!("https://www.youtube.com/watch?v={}", entry.video_id),
+                        )
+                    });
+                Some(CacheRow {
+                    track,
+                    path,
+                    size_bytes: entry.size_bytes,
+                    last_used: entry.last_used,
+                })
+            })
+            .collect();
+        self.cache_view.set_rows(rows);
+        self.cache_view.error = None;
+    }
+
+    /// `v` in the cache view — check every cached file in the background.
+    /// Results arrive through `verify_rx` one file at a time.
+    pub(super) fn start_cache_verify(&mut self) {
+        if self.cache_view.is_verifying() {
+            self.status_message = "Verify already running".to_string();
+            return;
+        }
+        self.refresh_cache_rows();
+        let jobs: Vec<(String, String, Option<u64>)> = self
+            .cache_view
+            .rows
+            .iter()
+            .map(|row| {
+                let duration = (row.track.duration > 0).then_some(row.track.duration);
+                (row.track.video_id.clone(), row.path.clone(), duration)
+            })
+            .collect();
+        if jobs.is_empty() {
+            self.status_message = "Cache is empty — nothing to verify".to_string();
+            return;
+        }
+
+        self.cache_view.outcomes.clear();
+        self.cache_view.progress = Some((0, jobs.len()));
+        let tx = self.verify_tx.clone();
+        tokio::task::spawn_blocking(move || {
+            for (video_id, path, duration) in jobs {
+                let health = verify_file(std::path::Path::new(&path), duration);
+                // The app is gone (quitting) — stop early.
+                if tx.send((video_id, health)).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Record one verify result. Broken files are purged; the ones still
+    /// wanted (pinned, playing, pending, or queued) are downloaded again.
+    pub(super) fn apply_verify_result(&mut self, video_id: String, health: FileHealth) {
+        let mut redownloading = false;
+        if !health.is_ok() {
+            self.purge_cached_file(&video_id);
+            let track = self
+                .cache_view
+                .rows
+                .iter()
+                .find(|row| row.track.video_id == video_id)
+                .map(|row| row.track.clone());
+            if let Some(track) = track.filter(|t| self.still_wanted(&t.video_id)) {
+                redownloading = self.request_download(&track, DownloadPriority::Background);
+            }
+        }
+        self.cache_view.outcomes.insert(
+            video_id,
+            VerifyOutcome {
+                health,
+                redownloading,
+            },
+        );
+
+        if let Some((checked, total)) = self.cache_view.progress.as_mut() {
+            *checked += 1;
+            if *checked >= *total {
+                self.cache_view.progress = None;
+                let (ok, broken, redownloading) = self.cache_view.verify_counts();
+                self.status_message = if broken == 0 {
+                    format!("✓ Verified {} cached files — all ok", ok)
+                } else {
+                    format!(
+                        "Verified {} cached files — {} ok, {} broken purged, {} re-downloading",
+                        ok + broken,
+                        ok,
+                        broken,
+                        redownloading
+                    )
+                };
+                let _ = self
+                    .persistence
+                    .save_download_cache(&self.downloads.get_cache_snapshot());
+            }
+        }
+    }
+
+    /// Whether a purged track should be downloaded again right away.
+    fn still_wanted(&self, video_id: &str) -> bool {
+        self.pinned.contains(video_id)
+            || self
+                .queue
+                .get_current()
+                .is_some_and(|t| t.video_id == video_id)
+            || self
+                .pending_play_track
+                .as_ref()
+                .is_some_and(|t| t.video_id == video_id)
+            || self
+                .queue
+                .get_queue_slice(0, self.queue.len())
+                .iter()
+                .any(|t| t.video_id == video_id)
+    }
+
+    /// `d` in the cache view — delete the selected file from the cache.
+    pub(super) fn cache_delete_selected(&mut self) {
+        let Some(row) = self.cache_view.selected_row().cloned() else {
+            return;
+        };
+        if self.pinned.contains(&row.track.video_id) {
+            self.status_message = "Track is pinned — press b to unpin it first".to_string();
+            return;
+        }
+        self.purge_cached_file(&row.track.video_id);
+        let _ = self
+            .persistence
+            .save_download_cache(&self.downloads.get_cache_snapshot());
+        self.refresh_cache_rows();
+        self.status_message = format!("Removed '{}' from the cache", clean_title(&row.track.title));
+    }
+
+    /// `r` in the cache view — purge the selected file and download it again.
+    pub(super) fn cache_redownload_selected(&mut self) {
+        let Some(row) = self.cache_view.selected_row().cloned() else {
+            return;
+        };
+        self.purge_cached_file(&row.track.video_id);
+        let title = clean_title(&row.track.title).to_string();
+        self.status_message = if self.request_download(&row.track, DownloadPriority::Background) {
+            format!("⬇ Re-downloading '{}'", title)
+        } else {
+            format!("Removed '{}' — it can't be downloaded again yet", title)
+        };
+        self.refresh_cache_rows();
+    }
+}
@@ -0,0 +1,176 @@
+//! Cover art for MusicPlayerApp.
+//!
+//! The covers of the playing track and of what's highlighted in the feed
+//! browser are loaded in the background (`cover_tx`) — from the disk cache
+//! or through yt-dlp. Half-block covers are ordinary cells; kitty and
+//! sixel covers are written to the terminal after each frame.
+
+use std::time::{Duration, Instant};
+
+use ratatui::buffer::{Buffer, CellDiffOption};
+
+use crate::config::{thumbnail_cache_dir, COVER_FETCHES_IN_FLIGHT, COVER_RETRY_SECS};
+use crate::services::thumbnail::{self, Cover};
+use crate::ui::graphics::{kitty_delete, kitty_transmit, sixel, CoverPlacement, ImageProtocol};
+use crate::ui::state::{AppMode, FeedFocus};
+
+use super::app::MusicPlayerApp;
+
+/// Covers kept in memory before the ones not on screen are dropped.
+const COVERS_IN_MEMORY: usize = 32;
+
+impl MusicPlayerApp {
+    /// The covers to show now, as (video or playlist id, page URL).
+    fn wanted_covers(&self) -> Vec<(String, String)> {
+        let mut wanted = Vec::new();
+        if let Some(track) = self.queue.get_current() {
+            wanted.push((track.video_id.clone(), track.url.clone()));
+        }
+        if self.mode == AppMode::FeedBrowser {
+            if self.feed.focus == FeedFocus::Tracks {
+                if let Some(track) = self.feed.expanded_tracks.get(self.feed.selected_track) {
+                    wanted.push((track.video_id.clone(), track.url.clone()));
+                }
+            } else if let Some(item) = self.feed_selected_item_ref() {
+                wanted.push((item.id.clone(), item.url.clone()));
+            }
+        }
+        wanted
+    }
+
+    /// Called every loop tick: load the covers on screen that aren't yet.
+    pub(super) fn tick_covers(&mut self) {
+        let retry = Duration::from_secs(COVER_RETRY_SECS);
+        self.covers.failed.retain(|_, at| at.elapsed() < retry);
+        for (key, url) in self.wanted_covers() {
+            if self.covers.loading.len() >= COVER_FETCHES_IN_FLIGHT {
+                return;
+            }
+            if self.covers.images.contains_key(&key)
+                || self.covers.loading.contains(&key)
+                || self.covers.failed.contains_key(&key)
+            {
+                continue;
+            }
+            self.covers.loading.insert(key.clone());
+
+            let ytdlp = self.ytdlp.clone();
+            let cookie = self.cookie_config();
+            let offline = self.is_offline();
+            let tx = self.cover_tx.clone();
+            tokio::spawn(async move {
+                let id = key.clone();
+                let result = tokio::task::spawn_blocking(move || {
+                    let dir = thumbnail_cache_dir().map_err(|e| e.to_string())?;
+                    thumbnail::load_cover(&*ytdlp, &id, &url, cookie, &dir, offline)
+                })
+                .await
+                .unwrap_or_else(|e| Err(format!("Task error: {e}")));
+                let _ = tx.send((key, result));
+            });
+        }
+    }
+
+    /// A cover loaded by `tick_covers` arrived. Covers are decoration, so
+    /// one that can't be had is just left out.
+    pub(super) fn handle_cover(&mut self, key: String, result: Result<Cover, String>) {
+        self.covers.loading.remove(&key);
+        match result {
+            Ok(cover) => {
+                if self.covers.images.len() >= COVERS_IN_MEMORY {
+                    let wanted: Vec<String> =
+                        self.wanted_covers().into_iter().map(|(k, _)| k).collect();
+                    self.covers.images.retain(|k, _| wanted.contains(k));
+                }
+                self.covers.images.insert(key, cover);
+            }
+            Err(_) => {
+                self.covers.failed.insert(key, Instant::now());
+            }
+        }
+    }
+
+    /// Terminal output to follow a frame: kitty images the terminal hasn't
+    /// been sent, and sixel covers that aren't on screen as drawn — new,
+    /// moved, or uncovered again after something was drawn over them.
+    pub(super) fn cover_graphics(&mut self, buffer: &Buffer) -> String {
+        let placements = self.covers.placements.take();
+        let mut out = String::new();
+        match self.covers.protocol {
+            ImageProtocol::HalfBlocks => {}
+            ImageProtocol::Kitty => {
+                for placement in placements {
+                    let id = placement.image_id();
+                    if let Some(cover) = self.covers.images.get(&placement.key) {
+                        if self.covers.transmitted.insert(id) {
+                            out.push_str(&kitty_transmit(
+                                cover,
+                                id,
+                                placement.area.width,
+                                placement.area.height,
+                            ));
+                        }
+                    }
+                }
+            }
+            ImageProtocol::Sixel => {
+                let intact: Vec<CoverPlacement> = placements
+                    .into_iter()
+                    .filter(|p| {
+                        p.area.positions().all(|pos| {
+                            buffer
+                                .cell(pos)
+                                .is_some_and(|c| c.diff_option == CellDiffOption::Skip)
+                        })
+                    })
+                    .collect();
+                let (cell_w, cell_h) = cell_pixels();
+                for placement in &intact {
+                    if self.covers.shown.contains(placement) {
+                        continue;
+                    }
+                    let Some(cover) = self.covers.images.get(&placement.key) else {
+                        continue;
+                    };
+                    let area = placement.area;
+                    // Blank the cells first: the image may come out a
+                    // little smaller than them.
+                    for row in 0..area.height {
+                        out.push_str(&format!(
+                            "\x1b[{};{}H{}",
+                            area.y + row + 1,
+                            area.x + 1,
+                            " ".repeat(area.width as usize)
+                        ));
+                    }
+                    let (w, h) = (area.width as u32 * cell_w, area.height as u32 * cell_h);
+                    out.push_str(&format!("\x1b[{};{}H", area.y + 1, area.x + 1));
+                    out.push_str(&sixel(&cover.resample(w, h), w as usize, h as usize));
+                }
+                self.covers.shown = intact;
+            }
+        }
+        out
+    }
+
+    /// Terminal output on quit: free the images kitty was sent.
+    pub(super) fn cover_cleanup(&self) -> String {
+        self.covers
+            .transmitted
+            .iter()
+            .map(|&id| kitty_delete(id))
+            .collect()
+    }
+}
+
+/// Size of a terminal cell in pixels, assuming 10×20 if the terminal
+/// doesn't say.
+fn cell_pixels() -> (u32, u32) {
+    match crossterm::terminal::window_size() {
+        Ok(size) if size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0 => (
+            u32::from(size.width / size.columns).max(1),
+            u32::from(size.height / size.rows).max(1),
+        ),
+        _ => (10, 20),
+    }
+}
@@ -0,0 +1,78 @@
+//! Dependency diagnostics for MusicPlayerApp.
+//!
+//! The checks run once at startup — opening the screen if one fails — and
+//! again with `!` or `r` on the Diagnostics screen. They spawn processes,
+//! so they run on a blocking task and report back over `diag_tx`.
+
+use std::time::Instant;
+
+use crate::services::diagnostics::{self, CheckStatus, CookieAccount, DiagnosticsInput};
+use crate::services::library::now_unix;
+use crate::ui::state::AppMode;
+
+use super::app::MusicPlayerApp;
+
+impl MusicPlayerApp {
+    /// Start a check run unless one is in flight. With `open_on_failure`
+    /// the screen opens by itself if anything fails.
+    pub(super) fn run_diagnostics(&mut self, open_on_failure: bool) {
+        if self.diagnostics.running {
+            return;
+        }
+        self.diagnostics.running = true;
+        self.diagnostics.open_on_failure = open_on_failure;
+
+        let account = self
+            .browser_auth
+            .load_selected_account()
+            .map(|account| CookieAccount {
+                store: self.browser_auth.cookie_store(&account),
+                display_name: account.display_name,
+            });
+        let input = DiagnosticsInput {
+            config_dir: self.persistence.config_dir().to_path_buf(),
+            cache_dir: self.downloads.audio_cache().dir().to_path_buf(),
+            audio_output: self.player.has_output(),
+            account,
+            now: now_unix(),
+        };
+        let ytdlp = self.ytdlp.clone();
+        let tx = self.diag_tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let _ = tx.send(diagnostics::run_checks(ytdlp.as_ref(), &input));
+        });
+    }
+
+    /// `!`: open the Diagnostics screen with fresh results.
+    pub(super) fn open_diagnostics(&mut self) {
+        self.mode = AppMode::Diagnostics;
+        self.run_diagnostics(false);
+    }
+
+    pub(super) fn close_diagnostics(&mut self) {
+        self.mode = if self.browser_auth.is_authenticated() {
+            AppMode::Normal
+        } else {
+            AppMode::LoginPrompt
+        };
+    }
+
+    /// Take finished check results.
+    pub(super) fn poll_diagnostics(&mut self) {
+        while let Ok(checks) = self.diag_rx.try_recv() {
+            let failed = diagnostics::worst(&checks) == CheckStatus::Fail;
+            self.diagnostics.checks = checks;
+            self.diagnostics.running = false;
+            self.diagnostics.checked_at = Some(Instant::now());
+
+            // Only take over the screen from the startup modes, not from
+            // something the user is in the middle of.
+            if failed
+                && std::mem::take(&mut self.diagnostics.open_on_failure)
+                && matches!(self.mode, AppMode::Normal | AppMode::LoginPrompt)
+            {
+                self.mode = AppMode::Diagnostics;
+            }
+        }
+    }
+}
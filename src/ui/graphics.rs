@@ -0,0 +1,349 @@
+//! Pictures in the terminal, for cover art.
+//!
+//! Unicode half blocks work everywhere with true colour: each cell shows
+//! two pixels, the top one as the `▀` foreground and the bottom one as the
+//! background. Kitty (and Ghostty) get the real image through the kitty
+//! graphics protocol with Unicode placeholders — the image is sent once
+//! and the cells hold placeholder characters, so it moves and disappears
+//! with the text like any other cell. Sixel terminals get an image written
+//! over cells the frame leaves untouched.
+
+use ratatui::layout::Rect;
+
+use crate::services::thumbnail::Cover;
+
+/// How pictures are drawn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum ImageProtocol {
+    #[default]
+    HalfBlocks,
+    Kitty,
+    Sixel,
+}
+
+impl ImageProtocol {
+    /// Guess the terminal's best protocol from its environment (`var`
+    /// looks up an environment variable). Inside tmux or screen images
+    /// wouldn't get through, so half blocks are used there.
+    pub(crate) fn detect(var: impl Fn(&str) -> Option<String>) -> Self {
+        if var("TMUX").is_some() || var("STY").is_some() {
+            return ImageProtocol::HalfBlocks;
+        }
+        let term = var("TERM").unwrap_or_default();
+        let program = var("TERM_PROGRAM").unwrap_or_default();
+        if var("KITTY_WINDOW_ID").is_some()
+            || term.contains("kitty")
+            || term.contains("ghostty")
+            || program == "ghostty"
+        {
+            ImageProtocol::Kitty
+        } else if ["foot", "mlterm", "contour", "sixel"]
+            .iter()
+            .any(|t| term.contains(t))
+            || ["WezTerm", "iTerm.app", "contour"].contains(&program.as_str())
+        {
+            ImageProtocol::Sixel
+        } else {
+            ImageProtocol::HalfBlocks
+        }
+    }
+}
+
+/// Where a frame drew a cover with kitty or sixel, to be sent to the
+/// terminal once the frame is out.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CoverPlacement {
+    /// The cover's video or playlist id.
+    pub key: String,
+    pub area: Rect,
+}
+
+impl CoverPlacement {
+    /// The kitty image id for this cover at this size: 24 bits, never 0.
+    pub(crate) fn image_id(&self) -> u32 {
+        let mut hash: u32 = 2_166_136_261;
+        let size = [self.area.width as u8, self.area.height as u8];
+        for b in self.key.bytes().chain(size) {
+            hash = (hash ^ u32::from(b)).wrapping_mul(16_777_619);
+        }
+        (hash & 0xFF_FFFF).max(1)
+    }
+}
+
+/// The largest picture of a square cover that fits `area`, centred. Cells
+/// are about twice as tall as wide, so it's twice as many columns as rows.
+pub(crate) fn fit_square(area: Rect) -> Rect {
+    let rows = area.height.min(area.width / 2);
+    let cols = rows * 2;
+    Rect {
+        x: area.x + (area.width - cols) / 2,
+        y: area.y,
+        width: cols,
+        height: rows,
+    }
+}
+
+/// Half-block cells for `pixels` (`cols` wide, `2 × rows` tall): per row,
+/// per cell, the (top, bottom) pixel colours.
+pub(crate) fn half_blocks(
+    pixels: &[[u8; 3]],
+    cols: usize,
+    rows: usize,
+) -> Vec<Vec<([u8; 3], [u8; 3])>> {
+    (0..rows)
+        .map(|row| {
+            (0..cols)
+                .map(|col| {
+                    (
+                        pixels[2 * row * cols + col],
+                        pixels[(2 * row + 1) * cols + col],
+                    )
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Kitty's row and column marks for placeholder cells, in order.
+const DIACRITICS: [char; 30] = [
+    '\u{0305}', '\u{030D}', '\u{030E}', '\u{0310}', '\u{0312}', '\u{033D}', '\u{033E}', '\u{033F}',
+    '\u{0346}', '\u{034A}', '\u{034B}', '\u{034C}', '\u{0350}', '\u{0351}', '\u{0352}', '\u{0357}',
+    '\u{035B}', '\u{0363}', '\u{0364}', '\u{0365}', '\u{0366}', '\u{0367}', '\u{0368}', '\u{0369}',
+    '\u{036A}', '\u{036B}', '\u{036C}', '\u{036D}', '\u{036E}', '\u{036F}',
+];
+
+/// The most rows or columns a kitty cover can span.
+pub(crate) const KITTY_MAX_CELLS: u16 = DIACRITICS.len() as u16;
+
+/// The placeholder for cell (`row`, `col`) of a kitty image. The cell's
+/// foreground colour must be the image id.
+pub(crate) fn kitty_placeholder(row: u16, col: u16) -> String {
+    let mut cell = String::from('\u{10EEEE}');
+    cell.push(DIACRITICS[row as usize]);
+    cell.push(DIACRITICS[col as usize]);
+    cell
+}
+
+/// Send `cover` to kitty as image `id` with a virtual placement of
+/// `cols × rows` cells for placeholders to show.
+pub(crate) fn kitty_transmit(cover: &Cover, id: u32, cols: u16, rows: u16) -> String {
+    let data = base64(&cover.rgb);
+    let chunks: Vec<&str> = data
+        .as_bytes()
+        .chunks(4096)
+        .map(|c| std::str::from_utf8(c).unwrap_or_default())
+        .collect();
+    let mut out = String::with_capacity(data.len() + chunks.len() * 16 + 64);
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        if i == 0 {
+            out.push_str(&format!(
+                "\x1b_Ga=T,U=1,q=2,f=24,s={},v={},i={},c={},r={},m={};{}\x1b\\",
+                cover.width, cover.height, id, cols, rows, more, chunk
+            ));
+        } else {
+            out.push_str(&format!("\x1b_Gm={};{}\x1b\\", more, chunk));
+        }
+    }
+    out
+}
+
+/// Delete kitty image `id` and free its data.
+pub(crate) fn kitty_delete(id: u32) -> String {
+    format!("\x1b_Ga=d,d=I,i={},q=2\x1b\\", id)
+}
+
+/// Encode `pixels` (`width × height`) as a sixel image in a 6×6×6 colour
+/// cube.
+pub(crate) fn sixel(pixels: &[[u8; 3]], width: usize, height: usize) -> String {
+    let level = |v: u8| (u16::from(v) * 5 + 127) / 255;
+    let colors: Vec<usize> = pixels
+        .iter()
+        .map(|[r, g, b]| (level(*r) * 36 + level(*g) * 6 + level(*b)) as usize)
+        .collect();
+    let mut used = [false; 216];
+    for &c in &colors {
+        used[c] = true;
+    }
+
+    let mut out = format!("\x1bPq\"1;1;{};{}", width, height);
+    for c in (0..216).filter(|&c| used[c]) {
+        let pct = |l: usize| l * 100 / 5;
+        out.push_str(&format!(
+            "#{};2;{};{};{}",
+            c,
+            pct(c / 36),
+            pct(c / 6 % 6),
+            pct(c % 6)
+        ));
+    }
+    for band in (0..height).step_by(6) {
+        let rows = band..(band + 6).min(height);
+        let mut in_band = [false; 216];
+        for y in rows.clone() {
+            for &c in &colors[y * width..(y + 1) * width] {
+                in_band[c] = true;
+            }
+        }
+        for c in (0..216).filter(|&c| in_band[c]) {
+            out.push_str(&format!("#{}", c));
+            let sixels = (0..width).map(|x| {
+                let bits = rows
+                    .clone()
+                    .filter(|y| colors[y * width + x] == c)
+                    .fold(0u8, |bits, y| bits | 1 << (y - band));
+                (63 + bits) as char
+            });
+            push_runs(&mut out, sixels);
+            out.push('$');
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+/// Append sixel characters, writing runs of four or more as `!<n><char>`.
+fn push_runs(out: &mut String, chars: impl Iterator<Item = char>) {
+    let mut run: Option<(char, usize)> = None;
+    let flush = |out: &mut String, (ch, n): (char, usize)| {
+        if n >= 4 {
+            out.push_str(&format!("!{}{}", n, ch));
+        } else {
+            out.extend(std::iter::repeat_n(ch, n));
+        }
+    };
+    for ch in chars {
+        run = match run {
+            Some((prev, n)) if prev == ch => Some((prev, n + 1)),
+            Some(done) => {
+                flush(out, done);
+                Some((ch, 1))
+            }
+            None => Some((ch, 1)),
+        };
+    }
+    if let Some(done) = run {
+        flush(out, done);
+    }
+}
+
+/// Standard base64 with padding.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | u32::from(b) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn protocol_follows_the_terminal_and_falls_back_to_half_blocks() {
+        assert_eq!(
+            ImageProtocol::detect(env(&[("TERM", "xterm-kitty")])),
+            ImageProtocol::Kitty
+        );
+        assert_eq!(
+            ImageProtocol::detect(env(&[("TERM_PROGRAM", "ghostty")])),
+            ImageProtocol::Kitty
+        );
+        assert_eq!(
+            ImageProtocol::detect(env(&[("TERM", "foot")])),
+            ImageProtocol::Sixel
+        );
+        assert_eq!(
+            ImageProtocol::detect(env(&[("TERM_PROGRAM", "WezTerm")])),
+            ImageProtocol::Sixel
+        );
+        assert_eq!(
+            ImageProtocol::detect(env(&[("TERM", "xterm-256color")])),
+            ImageProtocol::HalfBlocks
+        );
+        let tmux = env(&[
+            ("TERM", "xterm-kitty"),
+            ("TMUX", "/tmp/tmux-1000/default,1,0"),
+        ]);
+        assert_eq!(ImageProtocol::detect(tmux), ImageProtocol::HalfBlocks);
+    }
+
+    #[test]
+    fn covers_fit_square_and_split_into_half_blocks() {
+        let area = Rect::new(10, 5, 30, 8);
+        assert_eq!(fit_square(area), Rect::new(17, 5, 16, 8));
+        assert_eq!(fit_square(Rect::new(0, 0, 6, 8)), Rect::new(0, 0, 6, 3));
+
+        let (red, blue) = ([255, 0, 0], [0, 0, 255]);
+        let pixels = [red, blue, blue, red];
+        assert_eq!(
+            half_blocks(&pixels, 2, 1),
+            vec![vec![(red, blue), (blue, red)]]
+        );
+    }
+
+    #[test]
+    fn kitty_images_are_sent_in_chunks_and_shown_by_placeholders() {
+        assert_eq!(base64(b"Man"), "TWFu");
+        assert_eq!(base64(b"Ma"), "TWE=");
+        assert_eq!(base64(b"M"), "TQ==");
+
+        let cover = Cover {
+            width: 64,
+            height: 64,
+            rgb: vec![7; 64 * 64 * 3],
+        };
+        let out = kitty_transmit(&cover, 42, 8, 4);
+        assert!(out.starts_with("\x1b_Ga=T,U=1,q=2,f=24,s=64,v=64,i=42,c=8,r=4,m=1;"));
+        // 12 KiB of pixels is 16 KiB of base64: four chunks, the last m=0.
+        assert_eq!(out.matches("\x1b_G").count(), 4);
+        assert!(out.contains("\x1b_Gm=0;"));
+        assert_eq!(kitty_placeholder(0, 2), "\u{10EEEE}\u{0305}\u{030E}");
+
+        let a = CoverPlacement {
+            key: "a".to_string(),
+            area: Rect::new(0, 0, 8, 4),
+        };
+        let b = CoverPlacement {
+            area: Rect::new(0, 0, 16, 8),
+            ..a.clone()
+        };
+        assert_ne!(a.image_id(), b.image_id());
+        assert!(a.image_id() > 0 && a.image_id() <= 0xFF_FFFF);
+    }
+
+    #[test]
+    fn sixel_images_use_used_colours_and_compress_runs() {
+        let red = [255, 0, 0];
+        let out = sixel(&[red; 8 * 7], 8, 7);
+        assert!(out.starts_with("\x1bPq\"1;1;8;7#180;2;100;0;0"));
+        // Two bands: six full rows, then one.
+        assert!(out.ends_with("#180!8~$-#180!8@$-\x1b\\"));
+
+        let mut short = String::new();
+        push_runs(&mut short, "aab".chars());
+        assert_eq!(short, "aab");
+    }
+}
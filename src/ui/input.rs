@@ -32,12 +32,17 @@ pub(crate) enum AppCommand {
     Select,
     GoHome,
     EscapeBack,
+    ToggleStatsView,
+    /// Cycle the Stats view window (7 days / 30 days / all time).
+    CycleStatsWindow,
+    /// Show or hide the playing track's lyrics.
+    ToggleLyricsView,
 
     // Queue / History / Mix toggles
     ToggleQueueExpand,
     ToggleHistoryExpand,
     ToggleMixExpand,
-    ToggleMusicOnlyMode,
+    CycleFilterProfile,
     RefreshMix,
     Delete,
     ClearHistory,
@@ -55,6 +60,19 @@ pub(crate) enum AppCommand {
     SearchBackspace,
     SearchSubmit,
     SearchCancel,
+    /// Switch what the search looks for (songs, videos, albums, …).
+    CycleSearchScope {
+        back: bool,
+    },
+    /// Fetch the next page of search results.
+    LoadMoreResults,
+    /// Move the highlight through the search suggestions.
+    SearchSuggestionNext,
+    SearchSuggestionPrev,
+    /// Take the inline completion into the query.
+    SearchAcceptCompletion,
+    /// Remove the highlighted past search from the history.
+    SearchForget,
 
     // Playlist URL input
     PlaylistChar(char),
@@ -72,6 +90,109 @@ pub(crate) enum AppCommand {
     FeedPrevSection,
     FeedPlayNow,
     FeedAddToPlaylist,
+    /// Replace the queue with the highlighted album and play it gaplessly.
+    FeedPlayAlbum,
+
+    // Local playlists
+    OpenPlaylists,
+    ClosePlaylists,
+    /// Pick a local playlist for the selected search result / queue /
+    /// history / feed track.
+    OpenPlaylistPicker,
+    PlaylistPickerNext,
+    PlaylistPickerPrev,
+    /// Add the track to the highlighted playlist.
+    PlaylistPickerAdd,
+    /// Remove the track from the highlighted playlist.
+    PlaylistPickerRemove,
+    PlaylistPickerCancel,
+    PlaylistsNavigateDown,
+    PlaylistsNavigateUp,
+    PlaylistsFocusTracks,
+    PlaylistsFocusLists,
+    PlaylistsPlay,
+    PlaylistsEnqueue,
+    NewPlaylist,
+    RenamePlaylist,
+    PlaylistsDelete,
+    MoveTrackUp,
+    MoveTrackDown,
+    /// Export the queue (or history, when expanded) or the selected playlist.
+    ExportTracks,
+    /// Copy cached audio files out under readable names.
+    ExportAudio,
+    ImportPlaylist,
+
+    // Favorites and ratings (track under the cursor)
+    ToggleFavorite,
+    /// Rate 1–5 stars; 0 clears the rating.
+    RateTrack(u8),
+
+    /// Pin/unpin the track under the cursor in the offline audio cache.
+    TogglePin,
+    /// Forget failed downloads and try them again now.
+    RetryFailedDownloads,
+    /// Cancel the download of the track under the cursor or waiting to play.
+    CancelDownload,
+    /// Switch offline mode on or off.
+    ToggleOfflineMode,
+
+    // Offline playlist jobs
+    /// Download every track of the selected feed playlist.
+    MakeFeedPlaylistOffline,
+    /// Download every track of the playlist loaded with `l`.
+    MakeLoadedPlaylistOffline,
+    OpenDownloads,
+    CloseDownloads,
+    DownloadsNext,
+    DownloadsPrev,
+    DownloadsDelete,
+
+    // Diagnostics screen
+    OpenDiagnostics,
+    RerunDiagnostics,
+    CloseDiagnostics,
+
+    // Artist browser
+    /// Open the channel of the artist or track under the cursor.
+    OpenArtist,
+    CloseArtist,
+    RefreshArtist,
+    ArtistDown,
+    ArtistUp,
+    ArtistRight,
+    ArtistLeft,
+    ArtistPlay,
+    ArtistAdd,
+
+    // Cache view
+    OpenCache,
+    CloseCache,
+    CacheNext,
+    CachePrev,
+    /// Verify every cached file, purging and re-downloading broken ones.
+    CacheVerify,
+    CacheDelete,
+    CacheRedownload,
+
+    // Tags
+    /// Edit the tags of the track under the cursor.
+    EditTags,
+    OpenTags,
+    CloseTags,
+    TagsNext,
+    TagsPrev,
+    TagsToggleMark,
+    /// Switch between queueing tracks with *any* and *all* marked tags.
+    TagsToggleMatchAll,
+    TagsQueue,
+    TagsQueryPrompt,
+
+    // Text prompt popup
+    PromptChar(char),
+    PromptBackspace,
+    PromptSubmit,
+    PromptCancel,
 }
 
 /// Snapshot of relevant UI state for key mapping decisions.
@@ -80,6 +201,7 @@ pub(crate) struct InputContext<'a> {
     pub history_expanded: bool,
     pub search_query_len: usize,
     pub playlist_url_len: usize,
+    pub prompt_len: usize,
 }
 
 /// Pure function: maps a key event + current context to a command.
@@ -91,6 +213,7 @@ pub(crate) fn key_to_command(key: KeyEvent, ctx: &InputContext<'_>) -> Option<Ap
         AppMode::LoginPrompt => match key.code {
             KeyCode::Char('q') | KeyCode::Char('Q') => Some(AppCommand::Quit),
             KeyCode::Char('l') | KeyCode::Char('L') => Some(AppCommand::StartLogin),
+            KeyCode::Char('!') => Some(AppCommand::OpenDiagnostics),
             _ => None,
         },
         AppMode::AccountPicker => match key.code {
@@ -109,6 +232,12 @@ pub(crate) fn key_to_command(key: KeyEvent, ctx: &InputContext<'_>) -> Option<Ap
             KeyCode::Backspace => Some(AppCommand::SearchBackspace),
             KeyCode::Enter => Some(AppCommand::SearchSubmit),
             KeyCode::Esc => Some(AppCommand::SearchCancel),
+            KeyCode::Tab => Some(AppCommand::CycleSearchScope { back: false }),
+            KeyCode::BackTab => Some(AppCommand::CycleSearchScope { back: true }),
+            KeyCode::Down => Some(AppCommand::SearchSuggestionNext),
+            KeyCode::Up => Some(AppCommand::SearchSuggestionPrev),
+            KeyCode::Right | KeyCode::End => Some(AppCommand::SearchAcceptCompletion),
+            KeyCode::Delete => Some(AppCommand::SearchForget),
             _ => None,
         },
         AppMode::LoadingPlaylist => match key.code {
@@ -138,17 +267,133 @@ pub(crate) fn key_to_command(key: KeyEvent, ctx: &InputContext<'_>) -> Option<Ap
             KeyCode::Char('l') | KeyCode::Right => Some(AppCommand::FeedNextSection),
             KeyCode::Char('h') | KeyCode::Left => Some(AppCommand::FeedPrevSection),
             KeyCode::Enter => Some(AppCommand::FeedPlayNow),
-            KeyCode::Char('a') | KeyCode::Char('A') => Some(AppCommand::FeedAddToPlaylist),
+            KeyCode::Char('a') if has_shift => Some(AppCommand::OpenPlaylistPicker),
+            KeyCode::Char('A') => Some(AppCommand::OpenPlaylistPicker),
+            KeyCode::Char('a') => Some(AppCommand::FeedAddToPlaylist),
+            KeyCode::Char('p') => Some(AppCommand::FeedPlayAlbum),
+            KeyCode::Char('*') => Some(AppCommand::ToggleFavorite),
+            KeyCode::Char(c @ '0'..='5') => Some(AppCommand::RateTrack(c as u8 - b'0')),
+            KeyCode::Char('g') => Some(AppCommand::EditTags),
+            KeyCode::Char('b') => Some(AppCommand::TogglePin),
+            KeyCode::Char('o') => Some(AppCommand::MakeFeedPlaylistOffline),
+            KeyCode::Char('e') => Some(AppCommand::OpenArtist),
+            _ => None,
+        },
+        AppMode::ArtistBrowser => match key.code {
+            KeyCode::Char('q') => Some(AppCommand::Quit),
+            KeyCode::Char('?') => Some(AppCommand::ShowHelp),
+            KeyCode::Char('e') | KeyCode::Esc => Some(AppCommand::CloseArtist),
+            KeyCode::Char('r') | KeyCode::Char('R') => Some(AppCommand::RefreshArtist),
+            KeyCode::Char('j') | KeyCode::Down => Some(AppCommand::ArtistDown),
+            KeyCode::Char('k') | KeyCode::Up => Some(AppCommand::ArtistUp),
+            KeyCode::Char('l') | KeyCode::Right => Some(AppCommand::ArtistRight),
+            KeyCode::Char('h') | KeyCode::Left => Some(AppCommand::ArtistLeft),
+            KeyCode::Enter => Some(AppCommand::ArtistPlay),
+            KeyCode::Char('a') => Some(AppCommand::ArtistAdd),
+            _ => None,
+        },
+        AppMode::Downloads => match key.code {
+            KeyCode::Char('q') => Some(AppCommand::Quit),
+            KeyCode::Char('?') => Some(AppCommand::ShowHelp),
+            KeyCode::Char('v') | KeyCode::Esc => Some(AppCommand::CloseDownloads),
+            KeyCode::Char('j') | KeyCode::Down => Some(AppCommand::DownloadsNext),
+            KeyCode::Char('k') | KeyCode::Up => Some(AppCommand::DownloadsPrev),
+            KeyCode::Char('d') => Some(AppCommand::DownloadsDelete),
+            _ => None,
+        },
+        AppMode::Diagnostics => match key.code {
+            KeyCode::Char('q') => Some(AppCommand::Quit),
+            KeyCode::Char('?') => Some(AppCommand::ShowHelp),
+            KeyCode::Char('!') | KeyCode::Esc => Some(AppCommand::CloseDiagnostics),
+            KeyCode::Char('r') => Some(AppCommand::RerunDiagnostics),
+            _ => None,
+        },
+        AppMode::Tags => match key.code {
+            KeyCode::Char('q') => Some(AppCommand::Quit),
+            KeyCode::Char('?') => Some(AppCommand::ShowHelp),
+            KeyCode::Char('G') | KeyCode::Esc => Some(AppCommand::CloseTags),
+            KeyCode::Char('j') | KeyCode::Char('l') | KeyCode::Down | KeyCode::Right => {
+                Some(AppCommand::TagsNext)
+            }
+            KeyCode::Char('k') | KeyCode::Char('h') | KeyCode::Up | KeyCode::Left => {
+                Some(AppCommand::TagsPrev)
+            }
+            KeyCode::Char(' ') => Some(AppCommand::TagsToggleMark),
+            KeyCode::Char('m') => Some(AppCommand::TagsToggleMatchAll),
+            KeyCode::Enter => Some(AppCommand::TagsQueue),
+            KeyCode::Char('/') => Some(AppCommand::TagsQueryPrompt),
+            _ => None,
+        },
+        AppMode::Cache => match key.code {
+            KeyCode::Char('q') => Some(AppCommand::Quit),
+            KeyCode::Char('?') => Some(AppCommand::ShowHelp),
+            KeyCode::Char('c') | KeyCode::Esc => Some(AppCommand::CloseCache),
+            KeyCode::Char('j') | KeyCode::Down => Some(AppCommand::CacheNext),
+            KeyCode::Char('k') | KeyCode::Up => Some(AppCommand::CachePrev),
+            KeyCode::Char('v') => Some(AppCommand::CacheVerify),
+            KeyCode::Char('d') => Some(AppCommand::CacheDelete),
+            KeyCode::Char('r') => Some(AppCommand::CacheRedownload),
+            KeyCode::Char('b') => Some(AppCommand::TogglePin),
+            KeyCode::Char('x') => Some(AppCommand::ExportAudio),
+            _ => None,
+        },
+        AppMode::Playlists => match key.code {
+            KeyCode::Char('q') => Some(AppCommand::Quit),
+            KeyCode::Char('?') => Some(AppCommand::ShowHelp),
+            KeyCode::Char('P') | KeyCode::Esc => Some(AppCommand::ClosePlaylists),
+            // Shift+j/k reorder tracks; plain j/k navigate.
+            KeyCode::Char('j') if has_shift => Some(AppCommand::MoveTrackDown),
+            KeyCode::Char('k') if has_shift => Some(AppCommand::MoveTrackUp),
+            KeyCode::Char('J') => Some(AppCommand::MoveTrackDown),
+            KeyCode::Char('K') => Some(AppCommand::MoveTrackUp),
+            KeyCode::Char('j') | KeyCode::Down => Some(AppCommand::PlaylistsNavigateDown),
+            KeyCode::Char('k') | KeyCode::Up => Some(AppCommand::PlaylistsNavigateUp),
+            KeyCode::Char('l') | KeyCode::Right => Some(AppCommand::PlaylistsFocusTracks),
+            KeyCode::Char('h') | KeyCode::Left => Some(AppCommand::PlaylistsFocusLists),
+            KeyCode::Enter => Some(AppCommand::PlaylistsPlay),
+            KeyCode::Char('a') => Some(AppCommand::PlaylistsEnqueue),
+            KeyCode::Char('n') => Some(AppCommand::NewPlaylist),
+            KeyCode::Char('r') => Some(AppCommand::RenamePlaylist),
+            KeyCode::Char('d') => Some(AppCommand::PlaylistsDelete),
+            KeyCode::Char('x') if has_shift => Some(AppCommand::ExportAudio),
+            KeyCode::Char('X') => Some(AppCommand::ExportAudio),
+            KeyCode::Char('x') => Some(AppCommand::ExportTracks),
+            KeyCode::Char('i') => Some(AppCommand::ImportPlaylist),
+            _ => None,
+        },
+        AppMode::PlaylistPicker => match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => Some(AppCommand::PlaylistPickerCancel),
+            KeyCode::Char('j') | KeyCode::Down => Some(AppCommand::PlaylistPickerNext),
+            KeyCode::Char('k') | KeyCode::Up => Some(AppCommand::PlaylistPickerPrev),
+            KeyCode::Enter => Some(AppCommand::PlaylistPickerAdd),
+            KeyCode::Char('d') => Some(AppCommand::PlaylistPickerRemove),
+            _ => None,
+        },
+        AppMode::Prompt => match key.code {
+            KeyCode::Char(c) if ctx.prompt_len < crate::config::MAX_PROMPT_INPUT_LEN => {
+                Some(AppCommand::PromptChar(c))
+            }
+            KeyCode::Backspace => Some(AppCommand::PromptBackspace),
+            KeyCode::Enter => Some(AppCommand::PromptSubmit),
+            KeyCode::Esc => Some(AppCommand::PromptCancel),
             _ => None,
         },
         AppMode::Normal => match key.code {
             KeyCode::Char('q') => Some(AppCommand::Quit),
             KeyCode::Char('?') => Some(AppCommand::ShowHelp),
             KeyCode::Char('/') => Some(AppCommand::StartSearch),
+            KeyCode::Char('l') if has_shift => Some(AppCommand::MakeLoadedPlaylistOffline),
+            KeyCode::Char('L') => Some(AppCommand::MakeLoadedPlaylistOffline),
             KeyCode::Char('l') => Some(AppCommand::StartLoadPlaylist),
             KeyCode::Char(' ') => Some(AppCommand::TogglePause),
+            KeyCode::Char('n') if has_shift => Some(AppCommand::ToggleOfflineMode),
+            KeyCode::Char('N') => Some(AppCommand::ToggleOfflineMode),
             KeyCode::Char('n') => Some(AppCommand::NextTrack),
+            KeyCode::Char('p') if has_shift => Some(AppCommand::OpenPlaylists),
+            KeyCode::Char('P') => Some(AppCommand::OpenPlaylists),
             KeyCode::Char('p') => Some(AppCommand::PreviousTrack),
+            KeyCode::Char('a') if has_shift => Some(AppCommand::OpenPlaylistPicker),
+            KeyCode::Char('A') => Some(AppCommand::OpenPlaylistPicker),
             KeyCode::Char('t') | KeyCode::Char('T') => Some(AppCommand::ToggleQueueExpand),
             KeyCode::Char('h') if has_shift => Some(AppCommand::ToggleHistoryExpand),
             KeyCode::Char('H') => Some(AppCommand::ToggleHistoryExpand),
@@ -156,14 +401,33 @@ pub(crate) fn key_to_command(key: KeyEvent, ctx: &InputContext<'_>) -> Option<Ap
             KeyCode::Char('m') if has_shift => Some(AppCommand::RefreshMix),
             KeyCode::Char('M') => Some(AppCommand::RefreshMix),
             KeyCode::Char('m') => Some(AppCommand::ToggleMixExpand),
-            KeyCode::Char('f') if has_shift => Some(AppCommand::ToggleMusicOnlyMode),
-            KeyCode::Char('F') => Some(AppCommand::ToggleMusicOnlyMode),
+            KeyCode::Char('f') if has_shift => Some(AppCommand::CycleFilterProfile),
+            KeyCode::Char('F') => Some(AppCommand::CycleFilterProfile),
             KeyCode::Char('f') => Some(AppCommand::OpenFeedBrowser),
+            KeyCode::Char('s') => Some(AppCommand::ToggleStatsView),
+            KeyCode::Char('w') => Some(AppCommand::CycleStatsWindow),
+            KeyCode::Char('y') => Some(AppCommand::ToggleLyricsView),
+            KeyCode::Char('x') if has_shift => Some(AppCommand::ExportAudio),
+            KeyCode::Char('X') => Some(AppCommand::ExportAudio),
+            KeyCode::Char('x') => Some(AppCommand::ExportTracks),
+            KeyCode::Char('g') if has_shift => Some(AppCommand::OpenTags),
+            KeyCode::Char('G') => Some(AppCommand::OpenTags),
+            KeyCode::Char('g') => Some(AppCommand::EditTags),
+            KeyCode::Char('*') => Some(AppCommand::ToggleFavorite),
+            KeyCode::Char(c @ '0'..='5') => Some(AppCommand::RateTrack(c as u8 - b'0')),
+            KeyCode::Char('b') => Some(AppCommand::TogglePin),
             KeyCode::Char('o') | KeyCode::Char('O') => Some(AppCommand::SwitchAccount),
             KeyCode::Char('d') | KeyCode::Char('D') => Some(AppCommand::Delete),
             KeyCode::Char('c') | KeyCode::Char('C') if has_shift && ctx.history_expanded => {
                 Some(AppCommand::ClearHistory)
             }
+            KeyCode::Char('c') => Some(AppCommand::OpenCache),
+            KeyCode::Char('r') => Some(AppCommand::RetryFailedDownloads),
+            KeyCode::Char('u') => Some(AppCommand::CancelDownload),
+            KeyCode::Char('v') => Some(AppCommand::OpenDownloads),
+            KeyCode::Char('!') => Some(AppCommand::OpenDiagnostics),
+            KeyCode::Char('+') => Some(AppCommand::LoadMoreResults),
+            KeyCode::Char('e') => Some(AppCommand::OpenArtist),
             KeyCode::Esc => Some(AppCommand::EscapeBack),
             KeyCode::Up => Some(AppCommand::VolumeUp {
                 big_step: has_shift,
@@ -210,6 +474,7 @@ mod tests {
             history_expanded: he,
             search_query_len: 0,
             playlist_url_len: 0,
+            prompt_len: 0,
         }
     }
 
@@ -374,7 +639,8 @@ mod tests {
                     mode: &AppMode::Searching,
                     history_expanded: false,
                     search_query_len: 5,
-                    playlist_url_len: 0
+                    playlist_url_len: 0,
+                    prompt_len: 0
                 }
             ),
             Some(AppCommand::SearchChar('a'))
@@ -390,13 +656,26 @@ mod tests {
                     mode: &AppMode::Searching,
                     history_expanded: false,
                     search_query_len: 5,
-                    playlist_url_len: 0
+                    playlist_url_len: 0,
+                    prompt_len: 0
                 }
             ),
             Some(AppCommand::SearchSubmit)
         );
     }
 
+    #[test]
+    fn searching_tab_cycles_scope() {
+        assert_eq!(
+            cmd(key(KeyCode::Tab), &AppMode::Searching, false),
+            Some(AppCommand::CycleSearchScope { back: false })
+        );
+        assert_eq!(
+            cmd(shift_key(KeyCode::BackTab), &AppMode::Searching, false),
+            Some(AppCommand::CycleSearchScope { back: true })
+        );
+    }
+
     #[test]
     fn searching_esc_cancels() {
         assert_eq!(
@@ -406,7 +685,8 @@ mod tests {
                     mode: &AppMode::Searching,
                     history_expanded: false,
                     search_query_len: 5,
-                    playlist_url_len: 0
+                    playlist_url_len: 0,
+                    prompt_len: 0
                 }
             ),
             Some(AppCommand::SearchCancel)
@@ -470,7 +750,8 @@ mod tests {
                     mode: &AppMode::LoadingPlaylist,
                     history_expanded: false,
                     search_query_len: 0,
-                    playlist_url_len: 10
+                    playlist_url_len: 10,
+                    prompt_len: 0
                 }
             ),
             Some(AppCommand::PlaylistChar('x'))
@@ -486,7 +767,8 @@ mod tests {
                     mode: &AppMode::LoadingPlaylist,
                     history_expanded: false,
                     search_query_len: 0,
-                    playlist_url_len: 0
+                    playlist_url_len: 0,
+                    prompt_len: 0
                 }
             ),
             Some(AppCommand::PlaylistCancel)
@@ -509,10 +791,30 @@ mod tests {
     }
 
     #[test]
-    fn normal_shift_f_toggles_music_only() {
+    fn normal_shift_f_cycles_filter_profile() {
         assert_eq!(
             cmd(shift_key(KeyCode::Char('F')), &AppMode::Normal, false),
-            Some(AppCommand::ToggleMusicOnlyMode)
+            Some(AppCommand::CycleFilterProfile)
+        );
+    }
+
+    #[test]
+    fn normal_s_toggles_stats_and_w_cycles_window() {
+        assert_eq!(
+            cmd(key(KeyCode::Char('s')), &AppMode::Normal, false),
+            Some(AppCommand::ToggleStatsView)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('w')), &AppMode::Normal, false),
+            Some(AppCommand::CycleStatsWindow)
+        );
+    }
+
+    #[test]
+    fn normal_y_toggles_lyrics() {
+        assert_eq!(
+            cmd(key(KeyCode::Char('y')), &AppMode::Normal, false),
+            Some(AppCommand::ToggleLyricsView)
         );
     }
 
@@ -588,6 +890,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn feed_p_plays_album() {
+        assert_eq!(
+            cmd(key(KeyCode::Char('p')), &AppMode::FeedBrowser, false),
+            Some(AppCommand::FeedPlayAlbum)
+        );
+    }
+
     #[test]
     fn feed_q_quits() {
         assert_eq!(
@@ -595,4 +905,432 @@ mod tests {
             Some(AppCommand::Quit)
         );
     }
+
+    // -- Local playlists / prompt tests --
+
+    #[test]
+    fn normal_shift_p_opens_playlists_and_shift_a_picks_playlist() {
+        assert_eq!(
+            cmd(shift_key(KeyCode::Char('P')), &AppMode::Normal, false),
+            Some(AppCommand::OpenPlaylists)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('p')), &AppMode::Normal, false),
+            Some(AppCommand::PreviousTrack)
+        );
+        assert_eq!(
+            cmd(shift_key(KeyCode::Char('A')), &AppMode::Normal, false),
+            Some(AppCommand::OpenPlaylistPicker)
+        );
+    }
+
+    #[test]
+    fn feed_shift_a_picks_local_playlist() {
+        assert_eq!(
+            cmd(shift_key(KeyCode::Char('A')), &AppMode::FeedBrowser, false),
+            Some(AppCommand::OpenPlaylistPicker)
+        );
+    }
+
+    #[test]
+    fn playlist_picker_adds_and_removes_separately() {
+        let mode = AppMode::PlaylistPicker;
+        assert_eq!(
+            cmd(key(KeyCode::Enter), &mode, false),
+            Some(AppCommand::PlaylistPickerAdd)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('d')), &mode, false),
+            Some(AppCommand::PlaylistPickerRemove)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('j')), &mode, false),
+            Some(AppCommand::PlaylistPickerNext)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Up), &mode, false),
+            Some(AppCommand::PlaylistPickerPrev)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Esc), &mode, false),
+            Some(AppCommand::PlaylistPickerCancel)
+        );
+        // Shift+A doesn't act on the track from inside the picker.
+        assert_eq!(cmd(shift_key(KeyCode::Char('A')), &mode, false), None);
+    }
+
+    #[test]
+    fn star_and_digits_rate_in_normal_and_feed() {
+        for mode in [AppMode::Normal, AppMode::FeedBrowser] {
+            assert_eq!(
+                cmd(shift_key(KeyCode::Char('*')), &mode, false),
+                Some(AppCommand::ToggleFavorite)
+            );
+            assert_eq!(
+                cmd(key(KeyCode::Char('4')), &mode, false),
+                Some(AppCommand::RateTrack(4))
+            );
+            assert_eq!(
+                cmd(key(KeyCode::Char('0')), &mode, false),
+                Some(AppCommand::RateTrack(0))
+            );
+            assert_eq!(cmd(key(KeyCode::Char('6')), &mode, false), None);
+        }
+    }
+
+    #[test]
+    fn g_edits_tags_and_shift_g_opens_cloud() {
+        assert_eq!(
+            cmd(key(KeyCode::Char('g')), &AppMode::Normal, false),
+            Some(AppCommand::EditTags)
+        );
+        assert_eq!(
+            cmd(shift_key(KeyCode::Char('G')), &AppMode::Normal, false),
+            Some(AppCommand::OpenTags)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('g')), &AppMode::FeedBrowser, false),
+            Some(AppCommand::EditTags)
+        );
+    }
+
+    #[test]
+    fn b_pins_in_normal_and_feed() {
+        for mode in [AppMode::Normal, AppMode::FeedBrowser] {
+            assert_eq!(
+                cmd(key(KeyCode::Char('b')), &mode, false),
+                Some(AppCommand::TogglePin)
+            );
+        }
+    }
+
+    #[test]
+    fn c_opens_cache_view_and_shift_c_still_clears_history() {
+        assert_eq!(
+            cmd(key(KeyCode::Char('c')), &AppMode::Normal, true),
+            Some(AppCommand::OpenCache)
+        );
+        assert_eq!(
+            cmd(shift_key(KeyCode::Char('C')), &AppMode::Normal, true),
+            Some(AppCommand::ClearHistory)
+        );
+        let mode = AppMode::Cache;
+        assert_eq!(
+            cmd(key(KeyCode::Char('v')), &mode, false),
+            Some(AppCommand::CacheVerify)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Esc), &mode, false),
+            Some(AppCommand::CloseCache)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('b')), &mode, false),
+            Some(AppCommand::TogglePin)
+        );
+    }
+
+    #[test]
+    fn r_retries_failed_downloads() {
+        assert_eq!(
+            cmd(key(KeyCode::Char('r')), &AppMode::Normal, false),
+            Some(AppCommand::RetryFailedDownloads)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('u')), &AppMode::Normal, false),
+            Some(AppCommand::CancelDownload)
+        );
+    }
+
+    #[test]
+    fn shift_n_toggles_offline_mode() {
+        let mode = AppMode::Normal;
+        assert_eq!(
+            cmd(shift_key(KeyCode::Char('n')), &mode, false),
+            Some(AppCommand::ToggleOfflineMode)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('N')), &mode, false),
+            Some(AppCommand::ToggleOfflineMode)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('n')), &mode, false),
+            Some(AppCommand::NextTrack)
+        );
+    }
+
+    #[test]
+    fn offline_playlist_job_keys() {
+        let mode = AppMode::Normal;
+        assert_eq!(
+            cmd(shift_key(KeyCode::Char('l')), &mode, false),
+            Some(AppCommand::MakeLoadedPlaylistOffline)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('L')), &mode, false),
+            Some(AppCommand::MakeLoadedPlaylistOffline)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('l')), &mode, false),
+            Some(AppCommand::StartLoadPlaylist)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('v')), &mode, false),
+            Some(AppCommand::OpenDownloads)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('o')), &AppMode::FeedBrowser, false),
+            Some(AppCommand::MakeFeedPlaylistOffline)
+        );
+
+        let mode = AppMode::Downloads;
+        assert_eq!(
+            cmd(key(KeyCode::Char('j')), &mode, false),
+            Some(AppCommand::DownloadsNext)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Up), &mode, false),
+            Some(AppCommand::DownloadsPrev)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('d')), &mode, false),
+            Some(AppCommand::DownloadsDelete)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Esc), &mode, false),
+            Some(AppCommand::CloseDownloads)
+        );
+    }
+
+    #[test]
+    fn diagnostics_keys() {
+        assert_eq!(
+            cmd(key(KeyCode::Char('!')), &AppMode::Normal, false),
+            Some(AppCommand::OpenDiagnostics)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('!')), &AppMode::LoginPrompt, false),
+            Some(AppCommand::OpenDiagnostics)
+        );
+
+        let mode = AppMode::Diagnostics;
+        assert_eq!(
+            cmd(key(KeyCode::Char('r')), &mode, false),
+            Some(AppCommand::RerunDiagnostics)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Esc), &mode, false),
+            Some(AppCommand::CloseDiagnostics)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('!')), &mode, false),
+            Some(AppCommand::CloseDiagnostics)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('q')), &mode, false),
+            Some(AppCommand::Quit)
+        );
+    }
+
+    #[test]
+    fn searching_arrows_browse_suggestions() {
+        let mode = AppMode::Searching;
+        assert_eq!(
+            cmd(key(KeyCode::Down), &mode, false),
+            Some(AppCommand::SearchSuggestionNext)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Up), &mode, false),
+            Some(AppCommand::SearchSuggestionPrev)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Right), &mode, false),
+            Some(AppCommand::SearchAcceptCompletion)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Delete), &mode, false),
+            Some(AppCommand::SearchForget)
+        );
+        // Letters are still typed, not treated as navigation.
+        assert_eq!(
+            cmd(key(KeyCode::Char('j')), &mode, false),
+            Some(AppCommand::SearchChar('j'))
+        );
+    }
+
+    #[test]
+    fn artist_browser_keys() {
+        assert_eq!(
+            cmd(key(KeyCode::Char('e')), &AppMode::Normal, false),
+            Some(AppCommand::OpenArtist)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('e')), &AppMode::FeedBrowser, false),
+            Some(AppCommand::OpenArtist)
+        );
+
+        let mode = AppMode::ArtistBrowser;
+        assert_eq!(
+            cmd(key(KeyCode::Char('e')), &mode, false),
+            Some(AppCommand::CloseArtist)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Esc), &mode, false),
+            Some(AppCommand::CloseArtist)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('j')), &mode, false),
+            Some(AppCommand::ArtistDown)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Left), &mode, false),
+            Some(AppCommand::ArtistLeft)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('l')), &mode, false),
+            Some(AppCommand::ArtistRight)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Enter), &mode, false),
+            Some(AppCommand::ArtistPlay)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('a')), &mode, false),
+            Some(AppCommand::ArtistAdd)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('r')), &mode, false),
+            Some(AppCommand::RefreshArtist)
+        );
+    }
+
+    #[test]
+    fn plus_loads_more_results() {
+        assert_eq!(
+            cmd(key(KeyCode::Char('+')), &AppMode::Normal, false),
+            Some(AppCommand::LoadMoreResults)
+        );
+        assert_eq!(
+            cmd(shift_key(KeyCode::Char('+')), &AppMode::Normal, false),
+            Some(AppCommand::LoadMoreResults)
+        );
+        // Typed into the query while searching.
+        assert_eq!(
+            cmd(key(KeyCode::Char('+')), &AppMode::Searching, false),
+            Some(AppCommand::SearchChar('+'))
+        );
+    }
+
+    #[test]
+    fn tags_view_keys() {
+        let mode = AppMode::Tags;
+        assert_eq!(
+            cmd(key(KeyCode::Char('l')), &mode, false),
+            Some(AppCommand::TagsNext)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Up), &mode, false),
+            Some(AppCommand::TagsPrev)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char(' ')), &mode, false),
+            Some(AppCommand::TagsToggleMark)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('m')), &mode, false),
+            Some(AppCommand::TagsToggleMatchAll)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Enter), &mode, false),
+            Some(AppCommand::TagsQueue)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('/')), &mode, false),
+            Some(AppCommand::TagsQueryPrompt)
+        );
+        assert_eq!(
+            cmd(shift_key(KeyCode::Char('G')), &mode, false),
+            Some(AppCommand::CloseTags)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Esc), &mode, false),
+            Some(AppCommand::CloseTags)
+        );
+    }
+
+    #[test]
+    fn playlists_keys() {
+        let mode = AppMode::Playlists;
+        assert_eq!(
+            cmd(key(KeyCode::Char('j')), &mode, false),
+            Some(AppCommand::PlaylistsNavigateDown)
+        );
+        assert_eq!(
+            cmd(shift_key(KeyCode::Char('J')), &mode, false),
+            Some(AppCommand::MoveTrackDown)
+        );
+        assert_eq!(
+            cmd(shift_key(KeyCode::Char('K')), &mode, false),
+            Some(AppCommand::MoveTrackUp)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Enter), &mode, false),
+            Some(AppCommand::PlaylistsPlay)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('a')), &mode, false),
+            Some(AppCommand::PlaylistsEnqueue)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Esc), &mode, false),
+            Some(AppCommand::ClosePlaylists)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('x')), &mode, false),
+            Some(AppCommand::ExportTracks)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('i')), &mode, false),
+            Some(AppCommand::ImportPlaylist)
+        );
+    }
+
+    #[test]
+    fn normal_x_exports() {
+        assert_eq!(
+            cmd(key(KeyCode::Char('x')), &AppMode::Normal, false),
+            Some(AppCommand::ExportTracks)
+        );
+        assert_eq!(
+            cmd(shift_key(KeyCode::Char('x')), &AppMode::Normal, false),
+            Some(AppCommand::ExportAudio)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('X')), &AppMode::Playlists, false),
+            Some(AppCommand::ExportAudio)
+        );
+        assert_eq!(
+            cmd(key(KeyCode::Char('x')), &AppMode::Cache, false),
+            Some(AppCommand::ExportAudio)
+        );
+    }
+
+    #[test]
+    fn prompt_chars_stop_at_max_len() {
+        let full = InputContext {
+            mode: &AppMode::Prompt,
+            history_expanded: false,
+            search_query_len: 0,
+            playlist_url_len: 0,
+            prompt_len: crate::config::MAX_PROMPT_INPUT_LEN,
+        };
+        assert_eq!(
+            cmd(key(KeyCode::Char('x')), &AppMode::Prompt, false),
+            Some(AppCommand::PromptChar('x'))
+        );
+        assert_eq!(key_to_command(key(KeyCode::Char('x')), &full), None);
+        assert_eq!(
+            key_to_command(key(KeyCode::Enter), &full),
+            Some(AppCommand::PromptSubmit)
+        );
+    }
 }
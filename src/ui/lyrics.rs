@@ -0,0 +1,74 @@
+//! Lyrics view for MusicPlayerApp.
+//!
+//! `y` shows the playing track's lyrics. While the view is open each new
+//! track's lyrics are looked up in the background (`lyrics_tx`) — a local
+//! `.lrc` file, the cache, or the video's captions. Synced lyrics follow
+//! the player's position; unsynced ones scroll with j/k.
+
+use crate::config::lyrics_cache_dir;
+use crate::services::lyrics::{self, Lyrics};
+use crate::ui::state::{LyricsState, ViewMode};
+
+use super::app::MusicPlayerApp;
+
+impl MusicPlayerApp {
+    /// Called every loop tick (and when the view opens): look up the
+    /// playing track's lyrics if the view is open and they aren't loaded.
+    pub(super) fn tick_lyrics(&mut self) {
+        if self.current_view != ViewMode::Lyrics || self.lyrics.loading {
+            return;
+        }
+        let Some(video_id) = self.queue.get_current().map(|t| t.video_id.clone()) else {
+            return;
+        };
+        if self.lyrics.video_id.as_deref() == Some(video_id.as_str()) {
+            return;
+        }
+        self.lyrics = LyricsState {
+            video_id: Some(video_id.clone()),
+            loading: true,
+            ..LyricsState::default()
+        };
+
+        let ytdlp = self.ytdlp.clone();
+        let cookie = self.cookie_config();
+        let audio_cache = self.downloads.audio_cache().clone();
+        let offline = self.is_offline();
+        let tx = self.lyrics_tx.clone();
+        tokio::spawn(async move {
+            let id = video_id.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                let dir = lyrics_cache_dir().map_err(|e| e.to_string())?;
+                lyrics::load_lyrics(&*ytdlp, &id, &audio_cache, &dir, cookie, offline)
+            })
+            .await
+            .unwrap_or_else(|e| Err(format!("Task error: {e}")));
+            let _ = tx.send((video_id, result));
+        });
+    }
+
+    /// Lyrics looked up by `tick_lyrics` arrived.
+    pub(super) async fn handle_lyrics(&mut self, video_id: String, result: Result<Lyrics, String>) {
+        if self.lyrics.video_id.as_deref() != Some(video_id.as_str()) {
+            return;
+        }
+        self.lyrics.loading = false;
+        match result {
+            Ok(lyrics) => self.lyrics.lyrics = Some(lyrics),
+            Err(e) => {
+                self.note_network_failure(&e).await;
+                self.lyrics.error = Some(e);
+            }
+        }
+    }
+
+    /// j/k in the lyrics view — scroll unsynced lyrics by `delta` lines.
+    /// Synced lyrics follow playback instead.
+    pub(super) fn scroll_lyrics(&mut self, delta: isize) {
+        let Some(lyrics) = self.lyrics.lyrics.as_ref().filter(|l| !l.synced) else {
+            return;
+        };
+        let last = lyrics.lines.len().saturating_sub(1);
+        self.lyrics.scroll = self.lyrics.scroll.saturating_add_signed(delta).min(last);
+    }
+}
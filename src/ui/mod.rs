@@ -1,9 +1,22 @@
 // UI module - TUI interface using ratatui
 
 mod actions;
+mod album;
 pub mod app;
+mod artist;
+mod cache;
+mod cover;
+mod diagnostics;
+pub(crate) mod graphics;
 pub(crate) mod input;
+mod lyrics;
 mod navigation;
+mod offline;
+mod offline_jobs;
 mod playback;
+mod playlists;
+mod ratings;
+mod search_history;
 pub(crate) mod state;
+mod tags;
 pub(crate) mod views;
@@ -56,6 +56,10 @@ impl MusicPlayerApp {
             if let Some(removed_track) = self.queue.remove_at(self.ui.selected_queue_item) {
                 let clean_title = clean_title(&removed_track.title);
                 self.status_message = format!("Removed '{}' from queue", clean_title);
+                // No need to download it any more unless it's pinned
+                if !self.pinned.contains(&removed_track.video_id) {
+                    self.downloads.cancel_download(&removed_track.video_id);
+                }
 
                 // Adjust selection if needed
                 let queue_len = self.queue.len();
@@ -0,0 +1,189 @@
+//! Offline mode for MusicPlayerApp.
+//!
+//! Switched on with Shift+N or detected from repeated network failures.
+//! While offline no yt-dlp process is spawned: downloads wait in their
+//! queue, the queue skips tracks that aren't cached, network actions are
+//! refused up front, and searches run against cached tracks and history.
+
+use crate::config::clean_title;
+use crate::player::queue::Track;
+use crate::services::network::probe_youtube;
+use crate::youtube::extractor::{SearchResult, VideoInfo};
+
+use super::app::MusicPlayerApp;
+
+/// Most results an offline search returns.
+const OFFLINE_SEARCH_LIMIT: usize = 50;
+
+impl MusicPlayerApp {
+    pub(crate) fn is_offline(&self) -> bool {
+        self.network.is_offline()
+    }
+
+    /// Shift+N: switch offline mode on or off by hand.
+    pub(super) async fn toggle_offline_mode(&mut self) {
+        if self.network.toggle_manual() {
+            self.enter_offline("Offline mode — playing from the cache only (Shift+N to go online)")
+                .await;
+        } else {
+            self.leave_offline("Back online");
+        }
+    }
+
+    /// Count a failed yt-dlp run; repeated network errors switch the app
+    /// offline.
+    pub(super) async fn note_network_failure(&mut self, error: &str) {
+        if self.network.record_failure(error) {
+            self.enter_offline(
+                "⚠ YouTube unreachable — switched to offline mode (Shift+N to retry)",
+            )
+            .await;
+        }
+    }
+
+    pub(super) fn note_network_success(&mut self) {
+        self.network.record_success();
+    }
+
+    /// `Some(message)` refusing `action` (e.g. "load playlists") while
+    /// offline, so callers don't spawn yt-dlp only to watch it fail.
+    pub(super) fn offline_refusal(&self, action: &str) -> Option<String> {
+        self.is_offline()
+            .then(|| format!("Offline — can't {} (Shift+N to go online)", action))
+    }
+
+    async fn enter_offline(&mut self, message: &str) {
+        self.downloads.set_offline(true);
+        self.search.is_searching = false;
+        self.feed.is_loading = false;
+        self.status_message = message.to_string();
+
+        // A track waiting on its download would wait forever; move on to
+        // the next cached one instead.
+        if let Some(track) = self.pending_play_track.take() {
+            self.play_next().await;
+            // A cached track started playing and cleared the status.
+            if self.status_message.is_empty() {
+                self.status_message = format!(
+                    "{} — skipped '{}' (not cached)",
+                    message,
+                    clean_title(&track.title)
+                );
+            }
+        }
+    }
+
+    fn leave_offline(&mut self, message: &str) {
+        self.downloads.set_offline(false);
+        // Covers that failed offline may load now.
+        self.covers.failed.clear();
+        self.trigger_smart_downloads();
+        self.status_message = message.to_string();
+    }
+
+    /// While offline was detected automatically, probe for the network now
+    /// and then, and come back online once YouTube answers.
+    pub(super) fn poll_network(&mut self, now: std::time::Instant) {
+        while let Ok(reachable) = self.probe_rx.try_recv() {
+            if self.network.record_probe(reachable) {
+                self.leave_offline("✓ YouTube reachable again — back online");
+            }
+        }
+        if self.network.probe_due(now) {
+            self.network.mark_probe_started(now);
+            let tx = self.probe_tx.clone();
+            tokio::task::spawn_blocking(move || {
+                let _ = tx.send(probe_youtube());
+            });
+        }
+    }
+
+    /// Search cached tracks — the library's and those in the queue and
+    /// history — for every word of `query`.
+    pub(super) fn search_offline(&mut self, query: &str) {
+        let mut tracks: Vec<Track> = self
+            .persistence
+            .library()
+            .and_then(|lib| lib.search_cached_tracks(query, OFFLINE_SEARCH_LIMIT).ok())
+            .unwrap_or_default();
+
+        // Tracks downloaded but never played may not be in the library yet.
+        let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        let in_memory = self
+            .queue
+            .get_history()
+            .iter()
+            .rev()
+            .chain(self.queue.get_current())
+            .cloned()
+            .chain(self.queue.get_queue_list());
+        for track in in_memory {
+            if tracks.len() >= OFFLINE_SEARCH_LIMIT {
+                break;
+            }
+            if self.downloads.is_cached(&track.video_id)
+                && matches_words(&track, &words)
+                && !tracks.iter().any(|t| t.video_id == track.video_id)
+            {
+                tracks.push(track);
+            }
+        }
+
+        self.search.results = tracks
+            .into_iter()
+            .map(|t| {
+                SearchResult::Song(VideoInfo {
+                    id: t.video_id,
+                    title: t.title,
+                    duration: t.duration,
+                    uploader: t.uploader,
+                    thumbnail: None,
+                    url: t.url,
+                })
+            })
+            .collect();
+        self.search.offline = true;
+        self.search.opened = None;
+        self.search.is_searching = false;
+        self.ui.selected_result = 0;
+        self.status_message = format!(
+            "Offline — {} cached tracks match '{}'",
+            self.search.results.len(),
+            query.trim()
+        );
+    }
+}
+
+/// Whether `track`'s title or uploader contains every one of `words`
+/// (already lowercased).
+fn matches_words(track: &Track, words: &[String]) -> bool {
+    if words.is_empty() {
+        return false;
+    }
+    let haystack = format!("{} {}", track.title, track.uploader).to_lowercase();
+    words.iter().all(|w| haystack.contains(w.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_every_word_in_title_or_uploader() {
+        let track = Track::new(
+            "a".to_string(),
+            "Around the World".to_string(),
+            0,
+            "Daft Punk".to_string(),
+            String::new(),
+        );
+        let words = |q: &str| {
+            q.split_whitespace()
+                .map(str::to_lowercase)
+                .collect::<Vec<_>>()
+        };
+        assert!(matches_words(&track, &words("daft WORLD")));
+        assert!(!matches_words(&track, &words("daft moon")));
+        assert!(!matches_words(&track, &words("")));
+    }
+}
@@ -0,0 +1,197 @@
+//! "Make available offline" playlist jobs for MusicPlayerApp.
+//!
+//! `o` in the feed browser or Shift+L for the loaded playlist stores a job
+//! in the library; the job pump then keeps `OFFLINE_JOB_CONCURRENCY` of its
+//! tracks downloading at `Bulk` priority until all are cached. Job tracks
+//! are exempt from cache eviction. Progress shows in the Downloads panel
+//! (`v`).
+
+use std::time::{Duration, Instant};
+
+use crate::config::OFFLINE_JOB_CONCURRENCY;
+use crate::player::queue::Track;
+use crate::services::download_queue::DownloadPriority;
+use crate::services::library::now_unix;
+use crate::services::offline_jobs::{next_requests, OfflineJob, TrackStatus};
+use crate::ui::state::AppMode;
+
+use super::app::MusicPlayerApp;
+
+/// How often the job pump runs between download completions.
+const PUMP_INTERVAL: Duration = Duration::from_secs(1);
+
+impl MusicPlayerApp {
+    pub(super) fn open_downloads_panel(&mut self) {
+        self.mode = AppMode::Downloads;
+        self.pump_offline_jobs(Instant::now(), true);
+    }
+
+    /// `o` in the feed browser: make the selected playlist available offline.
+    pub(super) async fn make_feed_playlist_offline(&mut self) {
+        let Some(item) = self.feed_selected_item() else {
+            return;
+        };
+        let loaded = match self.favorites_tracks(&item) {
+            Some(local) => local,
+            None => {
+                self.status_message = format!("Fetching '{}'…", item.title);
+                self.feed_load_tracks(&item.url, &item.title).await
+            }
+        };
+        let msg = match loaded {
+            Ok(tracks) => self.start_offline_job(item.id.clone(), item.title.clone(), tracks),
+            Err(msg) => {
+                self.note_network_failure(&msg).await;
+                msg
+            }
+        };
+        self.feed.feed_status = Some(msg.clone());
+        self.status_message = msg;
+    }
+
+    /// Shift+L: make the playlist loaded with `l` available offline.
+    pub(super) fn make_loaded_playlist_offline(&mut self) {
+        if self.playlist.loaded_tracks.is_empty() {
+            self.status_message = "Load a playlist with [l] first".to_string();
+            return;
+        }
+        let id = self.playlist.loaded_url.clone();
+        let title = self.playlist.loaded_name.clone();
+        let tracks = self.playlist.loaded_tracks.clone();
+        self.status_message = self.start_offline_job(id, title, tracks);
+    }
+
+    /// Store a job for `tracks` (replacing one for the same playlist) and
+    /// start downloading. Returns the status message.
+    fn start_offline_job(&mut self, id: String, title: String, tracks: Vec<Track>) -> String {
+        let created_at = self
+            .offline_jobs
+            .find(&id)
+            .map_or_else(now_unix, |job| job.created_at);
+        let job = OfflineJob::new(id, title, tracks, created_at);
+        let count = job.tracks.len();
+        let title = job.title.clone();
+
+        let saved = self.persistence.save_offline_job(&job);
+        match self.offline_jobs.jobs.iter_mut().find(|j| j.id == job.id) {
+            Some(existing) => *existing = job,
+            None => self.offline_jobs.jobs.push(job),
+        }
+        self.pump_offline_jobs(Instant::now(), true);
+
+        match saved {
+            Ok(()) => format!(
+                "⬇ Making '{}' available offline — {} tracks ([v] for progress)",
+                title, count
+            ),
+            Err(e) => format!(
+                "⬇ Making '{}' available offline — won't resume after restart: {}",
+                title, e
+            ),
+        }
+    }
+
+    /// `d` in the Downloads panel: forget the selected job. Files already
+    /// downloaded stay cached.
+    pub(super) fn delete_selected_offline_job(&mut self) {
+        let state = &mut self.offline_jobs;
+        if state.selected >= state.jobs.len() {
+            return;
+        }
+        let job = state.jobs.remove(state.selected);
+        state.progress.remove(&job.id);
+        state.selected = state.selected.min(state.jobs.len().saturating_sub(1));
+        if let Some(library) = self.persistence.library() {
+            let _ = library.delete_offline_job(&job.id);
+        }
+        self.status_message = format!(
+            "Removed '{}' from offline downloads — cached files are kept",
+            job.title
+        );
+    }
+
+    /// Tracks of a stored job, for opening the playlist while offline.
+    pub(super) fn offline_job_tracks(&self, id: &str) -> Option<Vec<Track>> {
+        self.offline_jobs.find(id).map(|job| job.tracks.clone())
+    }
+
+    fn job_track_status(&self, track: &Track) -> TrackStatus {
+        let video_id = &track.video_id;
+        if self.downloads.is_cached(video_id) {
+            TrackStatus::Cached
+        } else if self.downloads.is_downloading(video_id) || self.downloads.is_queued(video_id) {
+            TrackStatus::Downloading
+        } else {
+            match self.downloads.failure_for(video_id) {
+                Some(record) if record.retry_at.is_none() => TrackStatus::Failed,
+                Some(_) => TrackStatus::Downloading,
+                None => TrackStatus::Pending,
+            }
+        }
+    }
+
+    /// Refresh job progress, record completions, and request more job
+    /// downloads into free slots. Runs at most once a second unless
+    /// `force`d (after a download finishes or a job changes).
+    pub(super) fn pump_offline_jobs(&mut self, now: Instant, force: bool) {
+        if self.offline_jobs.jobs.is_empty() {
+            return;
+        }
+        if !force
+            && self
+                .offline_jobs
+                .last_pump
+                .is_some_and(|last| now.duration_since(last) < PUMP_INTERVAL)
+        {
+            return;
+        }
+        self.offline_jobs.last_pump = Some(now);
+
+        let sizes: std::collections::HashMap<String, u64> = self
+            .persistence
+            .library()
+            .and_then(|lib| lib.cache_entries().ok())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|e| (e.video_id, e.size_bytes))
+            .collect();
+
+        let mut changed = Vec::new();
+        let mut progress = std::collections::HashMap::new();
+        for job in &self.offline_jobs.jobs {
+            let p = job.progress(
+                |t| self.job_track_status(t),
+                |id| sizes.get(id).copied().unwrap_or(0),
+            );
+            if p.is_complete() != job.completed_at.is_some() {
+                changed.push((job.id.clone(), p.is_complete().then(now_unix)));
+            }
+            progress.insert(job.id.clone(), p);
+        }
+        self.offline_jobs.progress = progress;
+
+        for (id, completed_at) in changed {
+            if let Some(library) = self.persistence.library() {
+                let _ = library.set_offline_job_completed(&id, completed_at);
+            }
+            if let Some(job) = self.offline_jobs.jobs.iter_mut().find(|j| j.id == id) {
+                job.completed_at = completed_at;
+                if completed_at.is_some() {
+                    self.status_message = format!("✓ '{}' is available offline", job.title);
+                }
+            }
+        }
+
+        let requests: Vec<Track> = next_requests(
+            &self.offline_jobs.jobs,
+            |t| self.job_track_status(t),
+            OFFLINE_JOB_CONCURRENCY,
+        )
+        .into_iter()
+        .cloned()
+        .collect();
+        for track in &requests {
+            self.request_download(track, DownloadPriority::Bulk);
+        }
+    }
+}
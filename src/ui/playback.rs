@@ -5,7 +5,10 @@
 use crate::config::{format_time, is_allowed_youtube_url, LOOKAHEAD_DOWNLOAD_COUNT};
 use crate::player::audio::PlayerState;
 use crate::player::queue::Track;
+use crate::services::download_queue::DownloadPriority;
+use crate::services::library::now_unix;
 use crate::services::persistence::MAX_HISTORY_SIZE;
+use crate::ui::state::{ActivePlay, PlayEnd};
 
 use super::app::MusicPlayerApp;
 
@@ -15,9 +18,18 @@ impl MusicPlayerApp {
         self.pending_play_track = None;
         self.currently_downloading = None;
 
-        if let Some(track) = self.queue.next() {
+        // Offline, jump over tracks that aren't cached; they stay queued
+        let next = if self.is_offline() {
+            self.queue
+                .next_where(|t| self.downloads.is_cached(&t.video_id))
+        } else {
+            self.queue.next()
+        };
+        if let Some(track) = next {
             self.queue.limit_history(MAX_HISTORY_SIZE);
             self.play_track_from_cache_or_download(&track);
+        } else if self.is_offline() && !self.queue.is_empty() {
+            self.status_message = "Offline — no cached tracks left in the queue".to_string();
         } else {
             self.status_message = "Queue is empty!".to_string();
         }
@@ -47,33 +59,90 @@ impl MusicPlayerApp {
 
         if let Some(local_file) = cached_file {
             if std::path::Path::new(&local_file).exists() {
-                self.player
-                    .play_with_duration(&local_file, &track.title, track.duration as f64);
+                self.start_track_playback(&local_file, track);
                 self.status_message.clear();
+                // Replace a file in an older audio format while it plays.
+                if self.downloads.is_outdated(&track.video_id) {
+                    self.request_download(track, DownloadPriority::Background);
+                }
                 let next = self.queue.get_queue_slice(0, LOOKAHEAD_DOWNLOAD_COUNT);
                 self.downloads
                     .ensure_next_tracks_ready(&next, self.cookie_config());
             } else {
                 self.downloads.remove_from_cache(&track.video_id);
+                if let Some(library) = self.persistence.library() {
+                    let _ = library.remove_cached_file(&track.video_id);
+                }
+                if self.refuse_offline(track) {
+                    return;
+                }
                 self.pending_play_track = Some(track.clone());
-                let cookie = self.cookie_config();
-                if self.downloads.spawn_download(track, cookie) {
+                if self.request_download(track, DownloadPriority::Current) {
                     self.currently_downloading = Some(track.title.clone());
                 }
             }
-        } else if is_allowed_youtube_url(&track.url) {
+        } else if !is_allowed_youtube_url(&track.url) {
+            self.status_message = "Cannot play non-YouTube URL".to_string();
+        } else if !self.refuse_offline(track) {
             self.pending_play_track = Some(track.clone());
-            let cookie = self.cookie_config();
-            if self.downloads.spawn_download(track, cookie) {
+            if self.request_download(track, DownloadPriority::Current) {
                 self.currently_downloading = Some(track.title.clone());
             }
-        } else {
-            self.status_message = "Cannot play non-YouTube URL".to_string();
         }
     }
 
-    pub(super) fn spawn_download_with_limit(&self, track: &Track) -> bool {
-        self.downloads.spawn_download(track, self.cookie_config())
+    /// In offline mode, report that `track` can't be downloaded and return
+    /// true instead of leaving it pending.
+    fn refuse_offline(&mut self, track: &Track) -> bool {
+        if !self.is_offline() {
+            return false;
+        }
+        self.status_message = format!(
+            "⊘ '{}' isn't cached — unavailable offline (Shift+N to go online)",
+            crate::config::clean_title(&track.title)
+        );
+        true
+    }
+
+    /// Start playing `track` from a local file and log the play to the library.
+    /// Any play still being timed is closed out as replaced.
+    pub(super) fn start_track_playback(&mut self, file_path: &str, track: &Track) {
+        self.finish_active_play(PlayEnd::Replaced);
+        self.player
+            .play_with_duration(file_path, &track.title, track.duration as f64);
+        self.start_timing_play(track);
+    }
+
+    /// Log a play of `track`, which has just started, and time it.
+    pub(super) fn start_timing_play(&mut self, track: &Track) {
+        if let Some(library) = self.persistence.library() {
+            if let Ok(play_id) = library.record_play(track, now_unix()) {
+                self.active_play = Some(ActivePlay::new(
+                    play_id,
+                    track.duration,
+                    std::time::Instant::now(),
+                ));
+            }
+        }
+    }
+
+    /// Store the listened time and skip flag for the play being timed, if any.
+    pub(super) fn finish_active_play(&mut self, end: PlayEnd) {
+        let Some(mut play) = self.active_play.take() else {
+            return;
+        };
+        play.tick(
+            std::time::Instant::now(),
+            self.player.get_state() == PlayerState::Playing,
+        );
+        if let Some(library) = self.persistence.library() {
+            let _ = library.finish_play(play.play_id, play.listened_secs(), play.is_skip(end));
+        }
+    }
+
+    pub(super) fn request_download(&self, track: &Track, priority: DownloadPriority) -> bool {
+        self.downloads
+            .request_download(track, priority, self.cookie_config())
     }
 
     pub(super) fn trigger_smart_downloads(&self) {
@@ -0,0 +1,578 @@
+//! Local playlist actions for MusicPlayerApp.
+//!
+//! Handles the Playlists view (create/rename/delete, reorder, play, enqueue),
+//! adding tracks to a playlist from the other views, M3U8/XSPF/JSON export
+//! and import, copying cached audio out to a folder, and the text prompt
+//! popup those actions share.
+
+use crate::config::clean_title;
+use crate::player::queue::Track;
+use crate::services::audio_export::{default_export_dir, export_audio};
+use crate::services::local_playlists::LocalPlaylists;
+use crate::services::playlist_io::{
+    default_export_path, expand_home, export_to_file, import_from_file, SkippedEntry,
+};
+use crate::services::tags::TagQuery;
+use crate::ui::state::{
+    AppMode, ExportSource, FeedFocus, PlaylistPickerState, PlaylistsFocus, PromptPurpose,
+    PromptState, ViewMode,
+};
+
+use super::app::MusicPlayerApp;
+
+impl MusicPlayerApp {
+    // -----------------------------------------------------------------------
+    // Persistence
+    // -----------------------------------------------------------------------
+
+    /// Write the playlists to disk, reporting failures in the status bar.
+    fn save_local_playlists(&mut self) {
+        if let Err(e) = self
+            .persistence
+            .save_local_playlists(&self.local_playlists.store)
+        {
+            self.status_message = format!("{} (playlists not saved: {})", self.status_message, e);
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Playlists view
+    // -----------------------------------------------------------------------
+
+    pub(super) fn open_playlists(&mut self) {
+        self.local_playlists.clamp_selection();
+        self.mode = AppMode::Playlists;
+        if self.local_playlists.store.is_empty() {
+            self.status_message = "No playlists yet — press 'n' to create one".to_string();
+        }
+    }
+
+    pub(super) fn playlists_focus_tracks(&mut self) {
+        if self
+            .local_playlists
+            .selected_playlist()
+            .is_some_and(|p| !p.tracks.is_empty())
+        {
+            self.local_playlists.focus = PlaylistsFocus::Tracks;
+        }
+    }
+
+    /// `d` — delete the highlighted track, or the whole playlist when the
+    /// playlist list is focused.
+    pub(super) fn playlists_delete(&mut self) {
+        let state = &mut self.local_playlists;
+        let index = state.selected;
+        self.status_message = match state.focus {
+            PlaylistsFocus::Lists => match state.store.delete(index) {
+                Some(playlist) => format!("Deleted playlist '{}'", playlist.name),
+                None => return,
+            },
+            PlaylistsFocus::Tracks => match state.store.remove_track(index, state.selected_track) {
+                Some(track) => format!("Removed '{}' from playlist", clean_title(&track.title)),
+                None => return,
+            },
+        };
+        self.local_playlists.clamp_selection();
+        self.save_local_playlists();
+    }
+
+    pub(super) fn playlists_move_track(&mut self, down: bool) {
+        let state = &mut self.local_playlists;
+        if state.focus != PlaylistsFocus::Tracks {
+            return;
+        }
+        if let Some(position) = state
+            .store
+            .move_track(state.selected, state.selected_track, down)
+        {
+            state.selected_track = position;
+            self.save_local_playlists();
+        }
+    }
+
+    /// Enter — replace the queue with the playlist (starting from the
+    /// highlighted track when the track list is focused) and play it.
+    pub(super) async fn playlists_play(&mut self) {
+        let Some(playlist) = self.local_playlists.selected_playlist() else {
+            return;
+        };
+        let start = match self.local_playlists.focus {
+            PlaylistsFocus::Lists => 0,
+            PlaylistsFocus::Tracks => self.local_playlists.selected_track,
+        };
+        let tracks: Vec<Track> = playlist.tracks.iter().skip(start).cloned().collect();
+        let name = playlist.name.clone();
+        if tracks.is_empty() {
+            self.status_message = format!("Playlist '{}' is empty", name);
+            return;
+        }
+
+        // The playing track goes to history so 'p' can get back to it.
+        if let Some(current) = self.queue.get_current().cloned() {
+            self.queue.add_to_history(current);
+        }
+        let count = tracks.len();
+        self.queue.restore_queue(tracks, None);
+        self.ui.selected_queue_item = 0;
+        self.trigger_smart_downloads();
+        self.play_current_or_first().await;
+
+        self.mode = AppMode::Normal;
+        self.status_message = format!("▶ Playing '{}' ({} tracks)", name, count);
+        if let Err(e) = self.save_queue() {
+            self.status_message = format!("{} — queue not saved: {}", self.status_message, e);
+        }
+    }
+
+    /// `a` — append the playlist to the queue. Playlists are hand-picked, so
+    /// only duplicates are skipped, not the active filter profile.
+    pub(super) fn playlists_enqueue(&mut self) {
+        let Some(playlist) = self.local_playlists.selected_playlist() else {
+            return;
+        };
+        let name = playlist.name.clone();
+        let tracks = playlist.tracks.clone();
+
+        let mut queued = self.queued_video_ids();
+        let mut added = 0;
+        for track in tracks {
+            if queued.insert(track.video_id.clone()) {
+                self.queue.add(track);
+                added += 1;
+            }
+        }
+        self.trigger_smart_downloads();
+
+        self.status_message = format!(
+            "Added {} tracks from '{}' — {} in queue",
+            added,
+            name,
+            self.queue.len()
+        );
+        if let Err(e) = self.save_queue() {
+            self.status_message = format!("{} — queue not saved: {}", self.status_message, e);
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Adding tracks from other views
+    // -----------------------------------------------------------------------
+
+    /// The track the user is pointing at: the feed track list, the expanded
+    /// queue or history, a search result, or else the track now playing.
+    pub(super) fn track_under_cursor(&self) -> Option<Track> {
+        if self.mode == AppMode::Cache {
+            return self.cache_view.selected_row().map(|row| row.track.clone());
+        }
+        if self.mode == AppMode::ArtistBrowser {
+            return self.artist.selected_track().cloned();
+        }
+        if self.mode == AppMode::FeedBrowser {
+            if self.feed.focus != FeedFocus::Tracks {
+                return None;
+            }
+            return self
+                .feed
+                .expanded_tracks
+                .get(self.feed.selected_track)
+                .map(|t| {
+                    Track::new(
+                        t.video_id.clone(),
+                        t.title.clone(),
+                        t.duration,
+                        t.uploader.clone(),
+                        t.url.clone(),
+                    )
+                });
+        }
+        if self.ui.queue_expanded {
+            return self
+                .queue
+                .get_queue_slice(self.ui.selected_queue_item, 1)
+                .first()
+                .map(|t| (*t).clone());
+        }
+        if self.ui.history_expanded {
+            // History is displayed most recent first.
+            return self
+                .queue
+                .get_history()
+                .iter()
+                .rev()
+                .nth(self.ui.selected_history_item)
+                .cloned();
+        }
+        if self.current_view == ViewMode::Search {
+            return self
+                .search
+                .results
+                .get(self.ui.selected_result)
+                .and_then(|r| r.video())
+                .map(|v| {
+                    Track::new(
+                        v.id.clone(),
+                        v.title.clone(),
+                        v.duration,
+                        v.uploader.clone(),
+                        v.url.clone(),
+                    )
+                });
+        }
+        self.queue.get_current().cloned()
+    }
+
+    /// Shift+A — pick the local playlist to add the track under the cursor
+    /// to (Enter) or remove it from (`d`). Starts on the playlist last used.
+    pub(super) fn open_playlist_picker(&mut self) {
+        let Some(track) = self.track_under_cursor() else {
+            self.status_message = "No track selected".to_string();
+            return;
+        };
+        if self.local_playlists.store.is_empty() {
+            self.status_message =
+                "No playlists yet — press Shift+P, then 'n' to create one".to_string();
+            return;
+        }
+        let return_mode = std::mem::replace(&mut self.mode, AppMode::PlaylistPicker);
+        self.playlist_picker = Some(PlaylistPickerState {
+            track,
+            selected: self
+                .local_playlists
+                .selected
+                .min(self.local_playlists.store.len() - 1),
+            return_mode,
+        });
+    }
+
+    /// j/k in the playlist picker.
+    pub(super) fn move_playlist_picker(&mut self, down: bool) {
+        let len = self.local_playlists.store.len();
+        if let Some(picker) = self.playlist_picker.as_mut() {
+            picker.selected = if down {
+                (picker.selected + 1).min(len.saturating_sub(1))
+            } else {
+                picker.selected.saturating_sub(1)
+            };
+        }
+    }
+
+    pub(super) fn close_playlist_picker(&mut self) {
+        self.mode = self
+            .playlist_picker
+            .take()
+            .map_or(AppMode::Normal, |picker| picker.return_mode);
+    }
+
+    /// Enter in the playlist picker — add the track to the highlighted
+    /// playlist. A track already in it stays where it is.
+    pub(super) fn playlist_picker_add(&mut self) {
+        self.edit_picked_playlist(|store, index, track, name| {
+            let title = clean_title(&track.title).to_string();
+            match store.add_track(index, track) {
+                Ok(true) => (true, format!("✓ Added '{}' to '{}'", title, name)),
+                Ok(false) => (false, format!("'{}' is already in '{}'", title, name)),
+                Err(e) => (false, format!("Can't add to '{}': {}", name, e)),
+            }
+        });
+    }
+
+    /// `d` in the playlist picker — remove the track from the highlighted
+    /// playlist.
+    pub(super) fn playlist_picker_remove(&mut self) {
+        self.edit_picked_playlist(|store, index, track, name| {
+            let title = clean_title(&track.title).to_string();
+            match store.remove_video(index, &track.video_id) {
+                Some(_) => (true, format!("Removed '{}' from '{}'", title, name)),
+                None => (false, format!("'{}' isn't in '{}'", title, name)),
+            }
+        });
+    }
+
+    /// Close the picker and apply `edit` to the playlist it had highlighted,
+    /// which becomes the one the next Shift+A starts on. `edit` returns
+    /// whether it changed the playlist, and the message to show.
+    fn edit_picked_playlist(
+        &mut self,
+        edit: impl FnOnce(&mut LocalPlaylists, usize, Track, &str) -> (bool, String),
+    ) {
+        let Some(picker) = self.playlist_picker.take() else {
+            self.mode = AppMode::Normal;
+            return;
+        };
+        self.mode = picker.return_mode;
+        let index = picker.selected;
+        let Some(name) = self
+            .local_playlists
+            .store
+            .get(index)
+            .map(|p| p.name.clone())
+        else {
+            return;
+        };
+        if self.local_playlists.selected != index {
+            self.local_playlists.selected = index;
+            self.local_playlists.selected_track = 0;
+        }
+
+        let (changed, msg) = edit(&mut self.local_playlists.store, index, picker.track, &name);
+        if self.mode == AppMode::FeedBrowser {
+            self.feed.feed_status = Some(msg.clone());
+        }
+        self.status_message = msg;
+        if changed {
+            self.local_playlists.clamp_selection();
+            self.save_local_playlists();
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Text prompt
+    // -----------------------------------------------------------------------
+
+    pub(super) fn open_prompt(&mut self, purpose: PromptPurpose, initial: &str) {
+        let return_mode = std::mem::replace(&mut self.mode, AppMode::Prompt);
+        self.prompt = Some(PromptState {
+            purpose,
+            input: initial.to_string(),
+            return_mode,
+        });
+    }
+
+    pub(super) fn start_rename_playlist(&mut self) {
+        if let Some(name) = self
+            .local_playlists
+            .selected_playlist()
+            .map(|p| p.name.clone())
+        {
+            self.open_prompt(PromptPurpose::RenamePlaylist, &name);
+        }
+    }
+
+    /// Close the prompt without acting on it.
+    pub(super) fn cancel_prompt(&mut self) {
+        self.tags.editing = None;
+        if let Some(prompt) = self.prompt.take() {
+            self.mode = prompt.return_mode;
+        } else {
+            self.mode = AppMode::Normal;
+        }
+    }
+
+    /// Act on the entered text. On an error the prompt stays open so the
+    /// user can correct the input.
+    pub(super) fn submit_prompt(&mut self) {
+        let Some(prompt) = self.prompt.as_ref() else {
+            self.mode = AppMode::Normal;
+            return;
+        };
+        let purpose = prompt.purpose;
+        let input = prompt.input.clone();
+        let state = &mut self.local_playlists;
+        let result = match purpose {
+            PromptPurpose::CreatePlaylist => state
+                .store
+                .create(&input)
+                .map(|index| {
+                    state.selected = index;
+                    state.selected_track = 0;
+                    state.focus = PlaylistsFocus::Lists;
+                    format!("Created playlist '{}'", input.trim())
+                })
+                .map_err(|e| format!("Can't save playlist: {}", e)),
+            PromptPurpose::RenamePlaylist => state
+                .store
+                .rename(state.selected, &input)
+                .map(|()| format!("Renamed playlist to '{}'", input.trim()))
+                .map_err(|e| format!("Can't save playlist: {}", e)),
+            PromptPurpose::ExportTracks(source) => self.export_tracks(source, &input),
+            PromptPurpose::ExportAudio(source) => self.export_audio_files(source, &input),
+            PromptPurpose::ImportPlaylist => self.import_playlist_file(&input),
+            PromptPurpose::EditTags => self.save_edited_tags(&input),
+            PromptPurpose::QueueByTags => TagQuery::parse(&input)
+                .map_err(|e| format!("Invalid tag query: {}", e))
+                .and_then(|query| self.queue_by_tags(&query)),
+        };
+
+        match result {
+            Ok(msg) => {
+                self.status_message = msg;
+                self.cancel_prompt();
+                if matches!(
+                    purpose,
+                    PromptPurpose::CreatePlaylist
+                        | PromptPurpose::RenamePlaylist
+                        | PromptPurpose::ImportPlaylist
+                ) {
+                    self.save_local_playlists();
+                }
+            }
+            Err(e) => self.status_message = e,
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Export / import
+    // -----------------------------------------------------------------------
+
+    /// Name and tracks for an export source.
+    fn export_source_tracks(&self, source: ExportSource) -> Option<(String, Vec<Track>)> {
+        match source {
+            ExportSource::Queue => {
+                let tracks: Vec<Track> = self
+                    .queue
+                    .get_current()
+                    .cloned()
+                    .into_iter()
+                    .chain(self.queue.get_queue_list())
+                    .collect();
+                Some(("Queue".to_string(), tracks))
+            }
+            ExportSource::History => {
+                Some(("History".to_string(), self.queue.get_history().to_vec()))
+            }
+            ExportSource::Playlist(index) => self
+                .local_playlists
+                .store
+                .get(index)
+                .map(|p| (p.name.clone(), p.tracks.clone())),
+            ExportSource::Cached(index) => self
+                .cache_view
+                .rows
+                .get(index)
+                .map(|row| (row.track.title.clone(), vec![row.track.clone()])),
+        }
+    }
+
+    /// `x` — ask where to export `source`, suggesting `<Music>/<name>.m3u8`.
+    pub(super) fn start_export(&mut self, source: ExportSource) {
+        let Some((name, tracks)) = self.export_source_tracks(source) else {
+            return;
+        };
+        if tracks.is_empty() {
+            self.status_message = format!("Nothing to export — {} is empty", name);
+            return;
+        }
+        let path = default_export_path(&name);
+        self.open_prompt(PromptPurpose::ExportTracks(source), &path.to_string_lossy());
+    }
+
+    /// `x` in Normal mode exports the history when it's expanded, else the queue.
+    pub(super) fn start_export_from_main(&mut self) {
+        let source = if self.ui.history_expanded {
+            ExportSource::History
+        } else {
+            ExportSource::Queue
+        };
+        self.start_export(source);
+    }
+
+    /// Shift+X (`x` in the Cache view) — ask which folder to copy the
+    /// cached audio of `source` to, suggesting `<Music>/<name>`.
+    pub(super) fn start_export_audio(&mut self, source: ExportSource) {
+        let Some((name, tracks)) = self.export_source_tracks(source) else {
+            return;
+        };
+        if tracks.is_empty() {
+            self.status_message = format!("Nothing to copy — {} is empty", name);
+            return;
+        }
+        let dir = match source {
+            ExportSource::Cached(_) => default_export_dir("Crusty"),
+            _ => default_export_dir(&name),
+        };
+        self.open_prompt(PromptPurpose::ExportAudio(source), &dir.to_string_lossy());
+    }
+
+    /// Shift+X in Normal mode copies the history when it's expanded, else
+    /// the queue.
+    pub(super) fn start_export_audio_from_main(&mut self) {
+        let source = if self.ui.history_expanded {
+            ExportSource::History
+        } else {
+            ExportSource::Queue
+        };
+        self.start_export_audio(source);
+    }
+
+    /// Copy in the background; the result arrives on `export_rx`.
+    fn export_audio_files(&self, source: ExportSource, input: &str) -> Result<String, String> {
+        let (_, tracks) = self
+            .export_source_tracks(source)
+            .ok_or_else(|| "Nothing to copy".to_string())?;
+        let dir = expand_home(input.trim());
+        let cached = self.downloads.get_cache_snapshot();
+        let count = tracks.len();
+        let tx = self.export_tx.clone();
+        let message = format!("Copying {} tracks to {}…", count, dir.display());
+        tokio::task::spawn_blocking(move || {
+            let result = export_audio(&dir, &tracks, &cached)
+                .map(|report| format!("✓ Copied audio to {} — {}", dir.display(), report.summary()))
+                .map_err(|e| format!("Copy failed: {:#}", e));
+            let _ = tx.send(result);
+        });
+        Ok(message)
+    }
+
+    fn export_tracks(&self, source: ExportSource, input: &str) -> Result<String, String> {
+        let (name, tracks) = self
+            .export_source_tracks(source)
+            .ok_or_else(|| "Nothing to export".to_string())?;
+        let path = expand_home(input.trim());
+        let cached = self.downloads.get_cache_snapshot();
+        let format = export_to_file(&path, &name, &tracks, Some(&cached))
+            .map_err(|e| format!("Export failed: {:#}", e))?;
+        Ok(format!(
+            "✓ Exported {} tracks to {} ({})",
+            tracks.len(),
+            path.display(),
+            format.label()
+        ))
+    }
+
+    /// Parse the file at `input` and store it as a new local playlist.
+    fn import_playlist_file(&mut self, input: &str) -> Result<String, String> {
+        let path = expand_home(input.trim());
+        let report = import_from_file(&path).map_err(|e| format!("Import failed: {:#}", e))?;
+        if report.tracks.is_empty() {
+            return Err(format!(
+                "Import failed: no valid tracks ({})",
+                skipped_summary(&report.skipped)
+            ));
+        }
+
+        let base = report.name.clone().unwrap_or_else(|| {
+            path.file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default()
+        });
+        let state = &mut self.local_playlists;
+        let name = state.store.unique_name(&base);
+        let index = state
+            .store
+            .create(&name)
+            .map_err(|e| format!("Import failed: {}", e))?;
+        let count = report.tracks.len();
+        for track in report.tracks {
+            let _ = state.store.add_track(index, track);
+        }
+        state.selected = index;
+        state.selected_track = 0;
+        state.focus = PlaylistsFocus::Lists;
+
+        let mut msg = format!("✓ Imported {} tracks into '{}'", count, name);
+        if !report.skipped.is_empty() {
+            msg.push_str(&format!(" — {}", skipped_summary(&report.skipped)));
+        }
+        Ok(msg)
+    }
+}
+
+/// "3 skipped: #4 not a YouTube URL, #7 duplicate track, …"
+fn skipped_summary(skipped: &[SkippedEntry]) -> String {
+    const SHOWN: usize = 3;
+    let mut parts: Vec<String> = skipped.iter().take(SHOWN).map(|s| s.to_string()).collect();
+    if skipped.len() > SHOWN {
+        parts.push("…".to_string());
+    }
+    format!("{} skipped: {}", skipped.len(), parts.join(", "))
+}
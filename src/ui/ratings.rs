@@ -0,0 +1,203 @@
+//! Favorites and star ratings for MusicPlayerApp.
+//!
+//! Ratings are stored in the library database and mirrored in
+//! `MusicPlayerApp::ratings` so the player bar can show them every frame.
+//! The feed browser gets a local "Favorites" section whose entries select
+//! rated tracks for building a queue.
+
+use crate::config::clean_title;
+use crate::player::queue::Track;
+use crate::services::library::{RatedFilter, TrackRating, MAX_STARS};
+use crate::ui::state::{AppMode, FeedPlaylist, FeedSection, FeedTrack, PlaylistType};
+
+use super::app::MusicPlayerApp;
+
+/// Entries of the Favorites feed section: `(id, title, filter)`.
+const FAVORITES_ENTRIES: [(&str, &str, RatedFilter); 4] = [
+    ("local:favorites", "♥ Favorites", RatedFilter::Favorites),
+    ("local:stars:5", "★★★★★ Rated 5", RatedFilter::MinStars(5)),
+    (
+        "local:stars:4",
+        "★★★★☆ Rated 4 and up",
+        RatedFilter::MinStars(4),
+    ),
+    (
+        "local:stars:3",
+        "★★★☆☆ Rated 3 and up",
+        RatedFilter::MinStars(3),
+    ),
+];
+
+/// The filter behind a Favorites section entry, by its id.
+fn rated_filter_for(id: &str) -> Option<RatedFilter> {
+    FAVORITES_ENTRIES
+        .iter()
+        .find(|(entry_id, _, _)| *entry_id == id)
+        .map(|(_, _, filter)| *filter)
+}
+
+impl MusicPlayerApp {
+    /// The rating of `video_id`; default (unrated) if unknown.
+    pub(crate) fn rating_for(&self, video_id: &str) -> TrackRating {
+        self.ratings.get(video_id).copied().unwrap_or_default()
+    }
+
+    /// `*` — toggle the favorite flag on the track under the cursor.
+    pub(super) fn toggle_favorite(&mut self) {
+        let Some(track) = self.track_under_cursor() else {
+            self.report_rating("No track selected".to_string());
+            return;
+        };
+        let mut rating = self.rating_for(&track.video_id);
+        rating.favorite = !rating.favorite;
+        let title = clean_title(&track.title).to_string();
+        let msg = if rating.favorite {
+            format!("✓ ♥ '{}' added to favorites", title)
+        } else {
+            format!("Removed '{}' from favorites", title)
+        };
+        self.store_rating(&track, rating, msg);
+    }
+
+    /// `1`–`5` rate the track under the cursor; `0` clears its rating.
+    pub(super) fn rate_track(&mut self, stars: u8) {
+        let Some(track) = self.track_under_cursor() else {
+            self.report_rating("No track selected".to_string());
+            return;
+        };
+        let mut rating = self.rating_for(&track.video_id);
+        rating.stars = (1..=MAX_STARS).contains(&stars).then_some(stars);
+        let title = clean_title(&track.title).to_string();
+        let msg = if rating.stars.is_some() {
+            format!("✓ Rated '{}' {}", title, rating.stars_label())
+        } else {
+            format!("Cleared rating for '{}'", title)
+        };
+        self.store_rating(&track, rating, msg);
+    }
+
+    /// Persist `rating` and update the in-memory copy and Favorites section.
+    fn store_rating(&mut self, track: &Track, rating: TrackRating, msg: String) {
+        let Some(library) = self.persistence.library() else {
+            self.report_rating(
+                "⚠ Ratings need the library database, which is unavailable".to_string(),
+            );
+            return;
+        };
+        if let Err(e) = library.set_track_rating(track, rating) {
+            self.report_rating(format!("⚠ Rating not saved: {}", e));
+            return;
+        }
+        if rating.is_empty() {
+            self.ratings.remove(&track.video_id);
+        } else {
+            self.ratings.insert(track.video_id.clone(), rating);
+        }
+        self.refresh_favorites_section();
+        self.report_rating(msg);
+    }
+
+    fn report_rating(&mut self, msg: String) {
+        if self.mode == AppMode::FeedBrowser {
+            self.feed.feed_status = Some(msg.clone());
+        }
+        self.status_message = msg;
+    }
+
+    // -----------------------------------------------------------------------
+    // Favorites feed section
+    // -----------------------------------------------------------------------
+
+    /// Add (or rebuild) the local Favorites section at the end of the feed's
+    /// section list, keeping the selection on the same section.
+    pub(super) fn refresh_favorites_section(&mut self) {
+        let items = FAVORITES_ENTRIES
+            .iter()
+            .map(|(id, title, filter)| FeedPlaylist {
+                id: id.to_string(),
+                title: title.to_string(),
+                url: String::new(),
+                playlist_type: PlaylistType::Favorites,
+                track_count_estimate: self.ratings.values().filter(|r| filter.matches(r)).count(),
+                thumbnail_url: None,
+                description: Some("Rated locally — independent of YouTube likes".to_string()),
+            })
+            .collect();
+        let section = FeedSection {
+            title: "Favorites".to_string(),
+            kind: PlaylistType::Favorites,
+            items,
+        };
+
+        let sections = &mut self.feed.sections;
+        match sections
+            .iter()
+            .position(|s| s.kind == PlaylistType::Favorites)
+        {
+            Some(index) => sections[index] = section,
+            None => sections.push(section),
+        }
+    }
+
+    /// Tracks behind a Favorites section entry, or `None` if `item` isn't one.
+    pub(super) fn favorites_tracks(
+        &self,
+        item: &FeedPlaylist,
+    ) -> Option<Result<Vec<Track>, String>> {
+        if item.playlist_type != PlaylistType::Favorites {
+            return None;
+        }
+        let filter = rated_filter_for(&item.id)?;
+        let Some(library) = self.persistence.library() else {
+            return Some(Err("Library database unavailable".to_string()));
+        };
+        Some(match library.rated_tracks(filter) {
+            Ok(tracks) if tracks.is_empty() => Err(format!(
+                "No tracks in '{}' yet — press * or 1–5 on a track",
+                item.title
+            )),
+            Ok(tracks) => Ok(tracks),
+            Err(e) => Err(format!("Failed to load '{}': {}", item.title, e)),
+        })
+    }
+}
+
+/// Convert a queue track for display in the feed's track pane.
+pub(super) fn to_feed_track(track: Track) -> FeedTrack {
+    FeedTrack {
+        video_id: track.video_id,
+        title: track.title,
+        uploader: track.uploader,
+        duration: track.duration,
+        url: track.url,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn favorites_entries_resolve_to_filters() {
+        assert_eq!(
+            rated_filter_for("local:favorites"),
+            Some(RatedFilter::Favorites)
+        );
+        assert_eq!(
+            rated_filter_for("local:stars:4"),
+            Some(RatedFilter::MinStars(4))
+        );
+        assert_eq!(rated_filter_for("PL123"), None);
+    }
+
+    #[test]
+    fn rated_filter_matches() {
+        let rating = TrackRating {
+            favorite: false,
+            stars: Some(4),
+        };
+        assert!(RatedFilter::MinStars(3).matches(&rating));
+        assert!(!RatedFilter::MinStars(5).matches(&rating));
+        assert!(!RatedFilter::Favorites.matches(&rating));
+    }
+}
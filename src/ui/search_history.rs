@@ -0,0 +1,95 @@
+//! Search history and completion for MusicPlayerApp.
+//!
+//! Submitted queries are stored in the library database. While typing in
+//! the search input the panel under it lists completions — past queries,
+//! titles of played tracks, known uploaders — or, with nothing typed yet,
+//! the recent searches.
+
+use crate::services::library::{now_unix, SuggestionSource};
+
+use super::app::MusicPlayerApp;
+
+/// Completions listed while typing.
+const SUGGESTIONS_SHOWN: usize = 8;
+
+/// Recent searches listed while the input is empty.
+const RECENT_SEARCHES_SHOWN: usize = 20;
+
+impl MusicPlayerApp {
+    /// Recompute the suggestions for the current query and clear the
+    /// highlight. Called on entering the input and after every edit.
+    pub(super) fn refresh_search_suggestions(&mut self) {
+        let query = self.search.query.as_str();
+        self.search.suggestions = self
+            .persistence
+            .library()
+            .and_then(|lib| {
+                if query.trim().is_empty() {
+                    lib.recent_searches(RECENT_SEARCHES_SHOWN).ok()
+                } else {
+                    lib.search_suggestions(query, SUGGESTIONS_SHOWN).ok()
+                }
+            })
+            .unwrap_or_default();
+        self.search.suggestion_selected = None;
+    }
+
+    /// `↓` / `↑` in the search input: move the highlight through the
+    /// suggestions; moving up past the first returns to the typed query.
+    pub(super) fn move_search_suggestion(&mut self, down: bool) {
+        let count = self.search.suggestions.len();
+        self.search.suggestion_selected = match (self.search.suggestion_selected, down) {
+            _ if count == 0 => None,
+            (None, true) => Some(0),
+            (Some(i), true) => Some((i + 1).min(count - 1)),
+            (Some(0), false) | (None, false) => None,
+            (Some(i), false) => Some(i - 1),
+        };
+    }
+
+    /// `→` in the search input: take the inline completion into the query.
+    pub(super) fn accept_search_completion(&mut self) {
+        if let Some(rest) = self.search.inline_completion().map(str::to_string) {
+            self.search.query.push_str(&rest);
+            self.refresh_search_suggestions();
+        }
+    }
+
+    /// The query Enter submits: the highlighted suggestion, if any.
+    pub(super) fn search_query_to_submit(&self) -> String {
+        self.search
+            .suggestion_selected
+            .and_then(|i| self.search.suggestions.get(i))
+            .map_or_else(|| self.search.query.clone(), |s| s.text.clone())
+    }
+
+    /// Store a submitted query in the search history.
+    pub(super) fn remember_search(&self, query: &str) {
+        if let Some(library) = self.persistence.library() {
+            let _ = library.record_search(query, now_unix());
+        }
+    }
+
+    /// `Delete` on a highlighted past search: remove it from the history.
+    pub(super) fn forget_selected_search(&mut self) {
+        let Some(suggestion) = self
+            .search
+            .suggestion_selected
+            .and_then(|i| self.search.suggestions.get(i))
+        else {
+            return;
+        };
+        if !matches!(suggestion.source, SuggestionSource::Recent { .. }) {
+            return;
+        }
+        if let Some(library) = self.persistence.library() {
+            let _ = library.forget_search(&suggestion.text);
+        }
+        let selected = self.search.suggestion_selected;
+        self.refresh_search_suggestions();
+        // Keep the highlight in place so several entries can go in a row.
+        self.search.suggestion_selected = selected
+            .map(|i| i.min(self.search.suggestions.len().saturating_sub(1)))
+            .filter(|_| !self.search.suggestions.is_empty());
+    }
+}
@@ -1,7 +1,21 @@
 //! UI state types extracted from the MusicPlayerApp god object.
 
+use std::time::{Duration, Instant};
+
+use crate::config::SKIP_THRESHOLD_SECS;
 use crate::player::queue::Track;
-use crate::youtube::extractor::VideoInfo;
+use crate::services::album::Album;
+use crate::services::artist::{ArtistPage, Channel, Release};
+use crate::services::audio_cache::FileHealth;
+use crate::services::diagnostics::Check;
+use crate::services::library::{ListeningStats, SearchSuggestion};
+use crate::services::local_playlists::{LocalPlaylist, LocalPlaylists};
+use crate::services::lyrics::Lyrics;
+use crate::services::offline_jobs::{JobProgress, OfflineJob};
+use crate::services::thumbnail::Cover;
+use crate::ui::graphics::{CoverPlacement, ImageProtocol};
+use crate::youtube::extractor::{SearchResult, SearchScope};
+use crate::youtube::ytdlp::CancelHandle;
 
 /// Application interaction mode — determines which input handler is active.
 #[derive(Debug, Default, PartialEq)]
@@ -15,6 +29,22 @@ pub(crate) enum AppMode {
     LoadingPlaylist,
     /// The YouTube Music feed browser is open.
     FeedBrowser,
+    /// The local Playlists view is open.
+    Playlists,
+    /// A single-line text prompt popup is open (see [`PromptState`]).
+    Prompt,
+    /// The pick-a-playlist popup is open (see [`PlaylistPickerState`]).
+    PlaylistPicker,
+    /// The tag cloud view is open.
+    Tags,
+    /// The full-screen audio cache view is open.
+    Cache,
+    /// The Downloads panel with "make available offline" jobs is open.
+    Downloads,
+    /// The dependency diagnostics screen is open.
+    Diagnostics,
+    /// The artist/channel browser is open.
+    ArtistBrowser,
 }
 
 /// Which top-level view is currently displayed.
@@ -23,6 +53,10 @@ pub(crate) enum ViewMode {
     #[default]
     Home,
     Search,
+    /// Listening statistics (top tracks, uploaders, skip rate).
+    Stats,
+    /// Lyrics of the playing track.
+    Lyrics,
 }
 
 /// A YouTube "My Mix" auto-generated playlist.
@@ -42,14 +76,19 @@ pub(crate) struct MixPlaylist {
 pub(crate) enum PlaylistType {
     /// Auto-generated "My Mix" playlists (`RDCLAK*`, `RDAMPL*`).
     Mix,
-    /// Algorithmically recommended playlists (`OLAK5uy_*`).
+    /// Algorithmically recommended playlists.
     Recommended,
+    /// YouTube Music album playlists (`OLAK5uy_*`).
+    Album,
     /// "Listen Again" re-recommendation entries.
     ListenAgain,
     /// User-saved or imported playlists (`PL*`, `VL*`).
     LibrarySaved,
     /// The "Liked Music" auto-playlist (`LM`).
     LibraryLiked,
+    /// Local favorites / rated tracks from the library database — never
+    /// fetched from YouTube or written to the feed cache.
+    Favorites,
     /// Unrecognised playlist type.
     Unknown,
 }
@@ -59,9 +98,11 @@ impl std::fmt::Display for PlaylistType {
         let label = match self {
             PlaylistType::Mix => "Mix",
             PlaylistType::Recommended => "Recommended",
+            PlaylistType::Album => "Album",
             PlaylistType::ListenAgain => "Listen Again",
             PlaylistType::LibrarySaved => "Library",
             PlaylistType::LibraryLiked => "Liked",
+            PlaylistType::Favorites => "Favorites",
             PlaylistType::Unknown => "Playlist",
         };
         write!(f, "{label}")
@@ -100,7 +141,8 @@ impl FeedSection {
     /// stale cache files rather than failing to deserialize.
     /// Bumped to 2: feed/playlists replaces channel/playlists — section titles
     /// and structure changed (added "Saved Mixes", renamed "My Playlists").
-    pub(crate) const CACHE_SCHEMA_VERSION: u32 = 2;
+    /// Bumped to 3: `OLAK5uy_*` playlists are classified as `Album`.
+    pub(crate) const CACHE_SCHEMA_VERSION: u32 = 3;
 }
 
 /// A single track inside an expanded feed playlist.
@@ -150,12 +192,24 @@ pub(crate) struct FeedState {
     pub feed_status: Option<String>,
     /// Tracks expanded from the currently selected playlist.
     pub expanded_tracks: Vec<FeedTrack>,
+    /// Albums whose track lists were loaded this session, by playlist id.
+    pub albums: std::collections::HashMap<String, Album>,
     /// Which track is highlighted in the expanded track list.
     pub selected_track: usize,
     /// Which pane has keyboard focus.
     pub focus: FeedFocus,
 }
 
+impl FeedState {
+    /// `true` if any sections came from YouTube (as opposed to only the
+    /// locally injected Favorites section).
+    pub(crate) fn has_remote_sections(&self) -> bool {
+        self.sections
+            .iter()
+            .any(|s| s.kind != PlaylistType::Favorites)
+    }
+}
+
 /// Serializable snapshot of the queue for persistence.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub(crate) struct QueueState {
@@ -178,7 +232,6 @@ pub(crate) struct UiState {
     pub animation_frame: u8,
     pub title_scroll_offset: usize,
     pub last_animation_update: std::time::Instant,
-    pub music_only_mode: bool,
 }
 
 impl Default for UiState {
@@ -196,7 +249,6 @@ impl Default for UiState {
             animation_frame: 0,
             title_scroll_offset: 0,
             last_animation_update: std::time::Instant::now(),
-            music_only_mode: true,
         }
     }
 }
@@ -204,9 +256,83 @@ impl Default for UiState {
 /// Search-related state.
 #[derive(Debug, Default)]
 pub(crate) struct SearchState {
-    pub results: Vec<VideoInfo>,
+    pub results: Vec<SearchResult>,
     pub query: String,
+    /// What the next search looks for; Tab cycles it in the search input.
+    pub scope: SearchScope,
     pub is_searching: bool,
+    /// The results came from the local cache, not YouTube.
+    pub offline: bool,
+    /// The album or playlist whose tracks replaced the results.
+    pub opened: Option<OpenedList>,
+    /// The query and scope the current results are for; new pages use
+    /// these, not whatever has been typed since.
+    pub last_query: String,
+    pub last_scope: SearchScope,
+    /// Pages fetched so far for `last_query`.
+    pub pages: usize,
+    /// The last page came back full, so there may be more.
+    pub has_more: bool,
+    /// Kills the yt-dlp run of the search in flight.
+    pub cancel: Option<CancelHandle>,
+    /// Bumped for every new search; events of older ones are dropped.
+    pub generation: u64,
+    /// Completions for the query being typed, or the recent searches
+    /// while it's empty.
+    pub suggestions: Vec<SearchSuggestion>,
+    /// The highlighted suggestion; Enter searches it instead of the query.
+    pub suggestion_selected: Option<usize>,
+}
+
+impl SearchState {
+    /// The rest of the suggestion the typed query is a prefix of — the
+    /// highlighted one, else the first that fits. `→` accepts it.
+    pub(crate) fn inline_completion(&self) -> Option<&str> {
+        let highlighted = self
+            .suggestion_selected
+            .and_then(|i| self.suggestions.get(i));
+        highlighted
+            .into_iter()
+            .chain(&self.suggestions)
+            .find_map(|s| completion_suffix(&s.text, &self.query))
+    }
+}
+
+/// What `text` adds after `typed`, if it starts with `typed` ignoring case.
+fn completion_suffix<'a>(text: &'a str, typed: &str) -> Option<&'a str> {
+    let mut rest = text;
+    for c in typed.chars() {
+        let mut chars = rest.chars();
+        if !chars.next()?.to_lowercase().eq(c.to_lowercase()) {
+            return None;
+        }
+        rest = chars.as_str();
+    }
+    (!rest.is_empty()).then_some(rest)
+}
+
+/// What a background search reports, tagged with `SearchState::generation`.
+#[derive(Debug)]
+pub(crate) enum SearchEvent {
+    /// One result, as soon as yt-dlp printed it.
+    Result {
+        generation: u64,
+        result: SearchResult,
+    },
+    /// The page is finished: how many entries yt-dlp produced, or why it failed.
+    Done {
+        generation: u64,
+        outcome: Result<usize, String>,
+    },
+}
+
+/// An album or playlist opened from the search results; Esc goes back.
+#[derive(Debug)]
+pub(crate) struct OpenedList {
+    pub title: String,
+    /// The search results it was opened from, and the selection there.
+    pub parent: Vec<SearchResult>,
+    pub parent_selected: usize,
 }
 
 /// Playlist-related state (My Mix + loaded playlists).
@@ -215,9 +341,496 @@ pub(crate) struct PlaylistState {
     pub my_mix_playlists: Vec<MixPlaylist>,
     pub loaded_tracks: Vec<Track>,
     pub loaded_name: String,
+    /// URL the loaded playlist came from.
+    pub loaded_url: String,
     pub url: String,
 }
 
+// ---------------------------------------------------------------------------
+// Local playlists + text prompt
+// ---------------------------------------------------------------------------
+
+/// Which pane is focused in the Playlists view.
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub(crate) enum PlaylistsFocus {
+    /// Left column — the list of playlists.
+    #[default]
+    Lists,
+    /// Right column — the tracks of the selected playlist.
+    Tracks,
+}
+
+/// Runtime state for the local Playlists view.
+#[derive(Debug, Default)]
+pub(crate) struct LocalPlaylistsState {
+    pub store: LocalPlaylists,
+    /// Highlighted playlist. Also where Shift+A's picker starts.
+    pub selected: usize,
+    /// Highlighted track within the selected playlist.
+    pub selected_track: usize,
+    pub focus: PlaylistsFocus,
+}
+
+impl LocalPlaylistsState {
+    pub(crate) fn selected_playlist(&self) -> Option<&LocalPlaylist> {
+        self.store.get(self.selected)
+    }
+
+    pub(crate) fn navigate_down(&mut self) {
+        match self.focus {
+            PlaylistsFocus::Lists => {
+                if self.selected + 1 < self.store.len() {
+                    self.selected += 1;
+                    self.selected_track = 0;
+                }
+            }
+            PlaylistsFocus::Tracks => {
+                let len = self.selected_playlist().map_or(0, |p| p.tracks.len());
+                if self.selected_track + 1 < len {
+                    self.selected_track += 1;
+                }
+            }
+        }
+    }
+
+    pub(crate) fn navigate_up(&mut self) {
+        match self.focus {
+            PlaylistsFocus::Lists => {
+                if self.selected > 0 {
+                    self.selected -= 1;
+                    self.selected_track = 0;
+                }
+            }
+            PlaylistsFocus::Tracks => self.selected_track = self.selected_track.saturating_sub(1),
+        }
+    }
+
+    /// Pull both selections back in range after a deletion.
+    pub(crate) fn clamp_selection(&mut self) {
+        self.selected = self.selected.min(self.store.len().saturating_sub(1));
+        let tracks = self.selected_playlist().map_or(0, |p| p.tracks.len());
+        self.selected_track = self.selected_track.min(tracks.saturating_sub(1));
+        if tracks == 0 {
+            self.focus = PlaylistsFocus::Lists;
+        }
+    }
+}
+
+/// The popup Shift+A opens over the current screen: pick the local
+/// playlist to add the track under the cursor to, or to remove it from.
+#[derive(Debug)]
+pub(crate) struct PlaylistPickerState {
+    pub track: Track,
+    /// Highlighted playlist.
+    pub selected: usize,
+    /// Mode to go back to when the popup closes.
+    pub return_mode: AppMode,
+}
+
+/// Which track list an export writes out.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ExportSource {
+    /// The current track followed by the queue.
+    Queue,
+    History,
+    /// A local playlist, by index.
+    Playlist(usize),
+    /// A file in the Cache view, by row index.
+    Cached(usize),
+}
+
+/// What the text entered in a prompt will be used for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum PromptPurpose {
+    CreatePlaylist,
+    RenamePlaylist,
+    /// Export to the entered path; the extension picks the format.
+    ExportTracks(ExportSource),
+    /// Copy the cached audio files to the entered folder.
+    ExportAudio(ExportSource),
+    /// Import the file at the entered path as a new local playlist.
+    ImportPlaylist,
+    /// Comma-separated tags for [`TagsState::editing`].
+    EditTags,
+    /// Queue every track matching a tag query (`a & b` or `a | b`).
+    QueueByTags,
+}
+
+impl PromptPurpose {
+    pub(crate) fn title(self) -> &'static str {
+        match self {
+            PromptPurpose::CreatePlaylist => "New playlist name",
+            PromptPurpose::RenamePlaylist => "Rename playlist",
+            PromptPurpose::ExportTracks(ExportSource::Queue) => {
+                "Export queue to (.m3u8 / .xspf / .json)"
+            }
+            PromptPurpose::ExportTracks(ExportSource::History) => {
+                "Export history to (.m3u8 / .xspf / .json)"
+            }
+            PromptPurpose::ExportTracks(ExportSource::Playlist(_)) => {
+                "Export playlist to (.m3u8 / .xspf / .json)"
+            }
+            PromptPurpose::ExportTracks(ExportSource::Cached(_)) => {
+                "Export track to (.m3u8 / .xspf / .json)"
+            }
+            PromptPurpose::ExportAudio(ExportSource::Queue) => {
+                "Copy the queue's audio files to folder"
+            }
+            PromptPurpose::ExportAudio(ExportSource::History) => {
+                "Copy the history's audio files to folder"
+            }
+            PromptPurpose::ExportAudio(ExportSource::Playlist(_)) => {
+                "Copy the playlist's audio files to folder"
+            }
+            PromptPurpose::ExportAudio(ExportSource::Cached(_)) => "Copy the audio file to folder",
+            PromptPurpose::ImportPlaylist => "Import playlist file (M3U8 / XSPF / JSON)",
+            PromptPurpose::EditTags => "Tags (comma-separated)",
+            PromptPurpose::QueueByTags => "Queue tracks tagged (a & b = all, a | b = any)",
+        }
+    }
+}
+
+/// A single-line text prompt shown as a popup while in [`AppMode::Prompt`].
+#[derive(Debug)]
+pub(crate) struct PromptState {
+    pub purpose: PromptPurpose,
+    pub input: String,
+    /// Mode to go back to on submit or cancel.
+    pub return_mode: AppMode,
+}
+
+// ---------------------------------------------------------------------------
+// Tags
+// ---------------------------------------------------------------------------
+
+/// Runtime state for the tag cloud view and the tag editor.
+#[derive(Debug, Default)]
+pub(crate) struct TagsState {
+    /// Every tag with its track count, most used first.
+    pub tags: Vec<(String, u64)>,
+    /// Index of the highlighted tag.
+    pub selected: usize,
+    /// Tags marked with Space, in marking order.
+    pub marked: Vec<String>,
+    /// Queue tracks carrying *all* marked tags rather than *any*.
+    pub match_all: bool,
+    /// The track whose tags are being edited in the prompt.
+    pub editing: Option<Track>,
+    pub error: Option<String>,
+}
+
+impl TagsState {
+    pub(crate) fn selected_tag(&self) -> Option<&str> {
+        self.tags.get(self.selected).map(|(tag, _)| tag.as_str())
+    }
+
+    pub(crate) fn select_next(&mut self) {
+        if self.selected + 1 < self.tags.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub(crate) fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Mark or unmark the highlighted tag.
+    pub(crate) fn toggle_mark(&mut self) {
+        let Some(tag) = self.selected_tag().map(str::to_string) else {
+            return;
+        };
+        if let Some(pos) = self.marked.iter().position(|t| *t == tag) {
+            self.marked.remove(pos);
+        } else {
+            self.marked.push(tag);
+        }
+    }
+
+    /// Replace the tag list, keeping the highlight in range and dropping
+    /// marks for tags that no longer exist.
+    pub(crate) fn set_tags(&mut self, tags: Vec<(String, u64)>) {
+        self.marked.retain(|m| tags.iter().any(|(t, _)| t == m));
+        self.tags = tags;
+        self.selected = self.selected.min(self.tags.len().saturating_sub(1));
+    }
+
+    /// The marked tags combined per `match_all`, or the highlighted tag if
+    /// nothing is marked.
+    pub(crate) fn query(&self) -> Option<crate::services::tags::TagQuery> {
+        use crate::services::tags::TagQuery;
+        let tags = if self.marked.is_empty() {
+            vec![self.selected_tag()?.to_string()]
+        } else {
+            self.marked.clone()
+        };
+        Some(if self.match_all {
+            TagQuery::All(tags)
+        } else {
+            TagQuery::Any(tags)
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Audio cache view
+// ---------------------------------------------------------------------------
+
+/// One cached file in the Cache view.
+#[derive(Debug, Clone)]
+pub(crate) struct CacheRow {
+    pub track: Track,
+    pub path: String,
+    pub size_bytes: u64,
+    /// Unix seconds of the last play, or of caching if never played.
+    pub last_used: i64,
+}
+
+/// What a verify pass found for one file and what was done about it.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct VerifyOutcome {
+    pub health: FileHealth,
+    /// A broken file was purged and its track queued for re-download.
+    pub redownloading: bool,
+}
+
+/// Runtime state for the Cache view and its verify pass.
+#[derive(Debug, Default)]
+pub(crate) struct CacheViewState {
+    /// Cached files, most recently used first.
+    pub rows: Vec<CacheRow>,
+    pub selected: usize,
+    /// Results of the last verify pass by video id.
+    pub outcomes: std::collections::HashMap<String, VerifyOutcome>,
+    /// `(checked, total)` while a verify pass runs.
+    pub progress: Option<(usize, usize)>,
+    pub error: Option<String>,
+}
+
+impl CacheViewState {
+    pub(crate) fn selected_row(&self) -> Option<&CacheRow> {
+        self.rows.get(self.selected)
+    }
+
+    pub(crate) fn select_next(&mut self) {
+        if self.selected + 1 < self.rows.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub(crate) fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub(crate) fn set_rows(&mut self, mut rows: Vec<CacheRow>) {
+        rows.sort_by_key(|row| std::cmp::Reverse(row.last_used));
+        self.rows = rows;
+        self.selected = self.selected.min(self.rows.len().saturating_sub(1));
+    }
+
+    pub(crate) fn is_verifying(&self) -> bool {
+        self.progress.is_some()
+    }
+
+    /// `(ok, broken, redownloading)` counts from the last verify pass.
+    pub(crate) fn verify_counts(&self) -> (usize, usize, usize) {
+        let ok = self.outcomes.values().filter(|o| o.health.is_ok()).count();
+        let redownloading = self.outcomes.values().filter(|o| o.redownloading).count();
+        (ok, self.outcomes.len() - ok, redownloading)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Offline jobs
+// ---------------------------------------------------------------------------
+
+/// "Make available offline" jobs and the Downloads panel cursor.
+#[derive(Debug, Default)]
+pub(crate) struct OfflineJobsState {
+    /// Jobs oldest first, mirrored from the library.
+    pub jobs: Vec<OfflineJob>,
+    /// Progress by job id, refreshed by the job pump.
+    pub progress: std::collections::HashMap<String, JobProgress>,
+    pub selected: usize,
+    /// When the job pump last ran.
+    pub last_pump: Option<Instant>,
+}
+
+impl OfflineJobsState {
+    pub(crate) fn selected_job(&self) -> Option<&OfflineJob> {
+        self.jobs.get(self.selected)
+    }
+
+    pub(crate) fn select_next(&mut self) {
+        if self.selected + 1 < self.jobs.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub(crate) fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub(crate) fn find(&self, id: &str) -> Option<&OfflineJob> {
+        self.jobs.iter().find(|job| job.id == id)
+    }
+
+    /// Whether the playlist `id` has every track cached.
+    pub(crate) fn is_complete(&self, id: &str) -> bool {
+        self.progress.get(id).is_some_and(|p| p.is_complete())
+    }
+
+    /// Video ids of every job's tracks, kept out of cache eviction.
+    pub(crate) fn track_ids(&self) -> impl Iterator<Item = &String> {
+        self.jobs
+            .iter()
+            .flat_map(|job| job.tracks.iter().map(|t| &t.video_id))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Listening statistics
+// ---------------------------------------------------------------------------
+
+/// Time window shown in the Stats view.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) enum StatsWindow {
+    #[default]
+    Week,
+    Month,
+    AllTime,
+}
+
+impl StatsWindow {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            StatsWindow::Week => "7 days",
+            StatsWindow::Month => "30 days",
+            StatsWindow::AllTime => "All time",
+        }
+    }
+
+    /// Window length in days; `None` for all time.
+    pub(crate) fn days(self) -> Option<u32> {
+        match self {
+            StatsWindow::Week => Some(7),
+            StatsWindow::Month => Some(30),
+            StatsWindow::AllTime => None,
+        }
+    }
+
+    /// Days of history shown in the sparkline.
+    pub(crate) fn sparkline_days(self) -> usize {
+        self.days().map_or(90, |d| d as usize)
+    }
+
+    pub(crate) fn next(self) -> Self {
+        match self {
+            StatsWindow::Week => StatsWindow::Month,
+            StatsWindow::Month => StatsWindow::AllTime,
+            StatsWindow::AllTime => StatsWindow::Week,
+        }
+    }
+}
+
+/// State for the Stats view. `data` is refreshed when the view opens or the
+/// window changes, not on every frame.
+#[derive(Debug, Default)]
+pub(crate) struct StatsState {
+    pub window: StatsWindow,
+    pub data: Option<ListeningStats>,
+    pub error: Option<String>,
+}
+
+/// State for the Lyrics view. Lyrics are looked up in the background while
+/// the view is open, once per track.
+#[derive(Debug, Default)]
+pub(crate) struct LyricsState {
+    /// The track `lyrics` (or the lookup in flight) belong to.
+    pub video_id: Option<String>,
+    pub lyrics: Option<Lyrics>,
+    pub loading: bool,
+    pub error: Option<String>,
+    /// First line shown when scrolling unsynced lyrics by hand.
+    pub scroll: usize,
+}
+
+/// Cover art for the player bar and feed detail panes, by video or
+/// playlist id. Covers are fetched in the background, a few at a time.
+#[derive(Debug, Default)]
+pub(crate) struct CoverState {
+    pub protocol: ImageProtocol,
+    pub images: std::collections::HashMap<String, Cover>,
+    pub loading: std::collections::HashSet<String>,
+    /// Covers that couldn't be had, and when; not asked for again until
+    /// `COVER_RETRY_SECS` have passed or the app comes back online.
+    pub failed: std::collections::HashMap<String, std::time::Instant>,
+    /// Kitty and sixel covers drawn by the current frame, written to the
+    /// terminal after it (the draw only has `&self`).
+    pub placements: std::cell::RefCell<Vec<CoverPlacement>>,
+    /// Sixel covers on screen as of the last frame.
+    pub shown: Vec<CoverPlacement>,
+    /// Kitty image ids sent to the terminal.
+    pub transmitted: std::collections::HashSet<u32>,
+}
+
+/// How a timed play ended.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum PlayEnd {
+    /// Played through to the end.
+    Finished,
+    /// Another track was started (next/previous/selection).
+    Replaced,
+    /// The app quit mid-track — not counted as a skip.
+    Interrupted,
+}
+
+/// The play currently being timed for listening statistics. Only time spent
+/// in the Playing state counts, so pauses and seeks don't inflate it.
+#[derive(Debug)]
+pub(crate) struct ActivePlay {
+    pub play_id: i64,
+    pub duration: u64,
+    listened: Duration,
+    last_tick: Instant,
+}
+
+impl ActivePlay {
+    pub(crate) fn new(play_id: i64, duration: u64, now: Instant) -> Self {
+        Self {
+            play_id,
+            duration,
+            listened: Duration::ZERO,
+            last_tick: now,
+        }
+    }
+
+    /// Accumulate the time since the last tick if audio was playing.
+    pub(crate) fn tick(&mut self, now: Instant, playing: bool) {
+        if playing {
+            self.listened += now.saturating_duration_since(self.last_tick);
+        }
+        self.last_tick = now;
+    }
+
+    pub(crate) fn listened_secs(&self) -> u64 {
+        self.listened.as_secs()
+    }
+
+    /// A play is a skip if it was cut short before half the track (or
+    /// `SKIP_THRESHOLD_SECS`, whichever is shorter) was heard.
+    pub(crate) fn is_skip(&self, end: PlayEnd) -> bool {
+        if end != PlayEnd::Replaced {
+            return false;
+        }
+        let threshold = if self.duration > 0 {
+            (self.duration / 2).min(SKIP_THRESHOLD_SECS)
+        } else {
+            SKIP_THRESHOLD_SECS
+        };
+        self.listened_secs() < threshold
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -247,6 +860,45 @@ mod tests {
         assert_eq!(state.animation_frame, 0);
     }
 
+    #[test]
+    fn test_stats_window_cycles() {
+        let w = StatsWindow::default();
+        assert_eq!(w, StatsWindow::Week);
+        assert_eq!(w.next().next().next(), StatsWindow::Week);
+        assert_eq!(StatsWindow::AllTime.days(), None);
+    }
+
+    #[test]
+    fn test_active_play_counts_only_playing_time() {
+        let start = Instant::now();
+        let mut play = ActivePlay::new(1, 200, start);
+        play.tick(start + Duration::from_secs(30), true);
+        play.tick(start + Duration::from_secs(90), false); // paused
+        play.tick(start + Duration::from_secs(100), true);
+        assert_eq!(play.listened_secs(), 40);
+    }
+
+    #[test]
+    fn test_active_play_skip_rules() {
+        let start = Instant::now();
+        let mut play = ActivePlay::new(1, 200, start);
+        play.tick(start + Duration::from_secs(60), true);
+        assert!(play.is_skip(PlayEnd::Replaced));
+        assert!(!play.is_skip(PlayEnd::Finished));
+        assert!(!play.is_skip(PlayEnd::Interrupted));
+
+        play.tick(start + Duration::from_secs(100), true);
+        assert!(!play.is_skip(PlayEnd::Replaced)); // reached half
+    }
+
+    #[test]
+    fn test_active_play_long_track_threshold_capped() {
+        let start = Instant::now();
+        let mut play = ActivePlay::new(1, 3600, start);
+        play.tick(start + Duration::from_secs(SKIP_THRESHOLD_SECS), true);
+        assert!(!play.is_skip(PlayEnd::Replaced));
+    }
+
     #[test]
     fn test_search_state_default() {
         let state = SearchState::default();
@@ -255,6 +907,33 @@ mod tests {
         assert!(!state.is_searching);
     }
 
+    #[test]
+    fn inline_completion_prefers_the_highlighted_suggestion() {
+        use crate::services::library::SuggestionSource;
+
+        let suggestion = |text: &str| SearchSuggestion {
+            text: text.to_string(),
+            source: SuggestionSource::Track,
+        };
+        let mut state = SearchState {
+            query: "DA".to_string(),
+            suggestions: vec![
+                suggestion("Aphex Twin"),
+                suggestion("daft punk"),
+                suggestion("Dave"),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(state.inline_completion(), Some("ft punk"));
+        state.suggestion_selected = Some(2);
+        assert_eq!(state.inline_completion(), Some("ve"));
+        // A highlighted suggestion that doesn't fit falls back to one that does.
+        state.suggestion_selected = Some(0);
+        assert_eq!(state.inline_completion(), Some("ft punk"));
+        state.query = "dave".to_string();
+        assert_eq!(state.inline_completion(), None);
+    }
+
     #[test]
     fn test_playlist_state_default() {
         let state = PlaylistState::default();
@@ -263,4 +942,315 @@ mod tests {
         assert!(state.loaded_name.is_empty());
         assert!(state.url.is_empty());
     }
+
+    fn playlists_state(track_counts: &[usize]) -> LocalPlaylistsState {
+        let mut state = LocalPlaylistsState::default();
+        for (i, &count) in track_counts.iter().enumerate() {
+            state.store.create(&format!("P{i}")).unwrap();
+            for t in 0..count {
+                let id = format!("{i}-{t}");
+                let track = Track::new(id.clone(), id, 60, String::new(), String::new());
+                state.store.add_track(i, track).unwrap();
+            }
+        }
+        state
+    }
+
+    #[test]
+    fn test_local_playlists_navigation_clamps() {
+        let mut state = playlists_state(&[2, 0]);
+        state.navigate_down();
+        state.navigate_down();
+        assert_eq!(state.selected, 1);
+        state.navigate_up();
+        state.focus = PlaylistsFocus::Tracks;
+        state.navigate_down();
+        state.navigate_down();
+        assert_eq!(state.selected_track, 1);
+        state.navigate_up();
+        state.navigate_up();
+        assert_eq!(state.selected_track, 0);
+    }
+
+    #[test]
+    fn test_local_playlists_clamp_after_delete() {
+        let mut state = playlists_state(&[3, 1]);
+        state.selected = 1;
+        state.selected_track = 0;
+        state.focus = PlaylistsFocus::Tracks;
+        state.store.delete(1);
+        state.clamp_selection();
+        assert_eq!(state.selected, 0);
+        assert_eq!(state.focus, PlaylistsFocus::Tracks);
+
+        state.store.delete(0);
+        state.clamp_selection();
+        assert_eq!(state.selected, 0);
+        assert_eq!(state.selected_track, 0);
+        assert_eq!(state.focus, PlaylistsFocus::Lists);
+    }
+
+    fn tags_state(tags: &[(&str, u64)]) -> TagsState {
+        let mut state = TagsState::default();
+        state.set_tags(tags.iter().map(|(t, n)| (t.to_string(), *n)).collect());
+        state
+    }
+
+    #[test]
+    fn test_tags_query_uses_marks_or_selection() {
+        use crate::services::tags::TagQuery;
+        let mut state = tags_state(&[("coding", 3), ("gym", 2), ("chill", 1)]);
+        state.select_next();
+        assert_eq!(state.query(), Some(TagQuery::Any(vec!["gym".to_string()])));
+
+        state.toggle_mark();
+        state.select_next();
+        state.toggle_mark();
+        state.match_all = true;
+        assert_eq!(
+            state.query(),
+            Some(TagQuery::All(vec!["gym".to_string(), "chill".to_string()]))
+        );
+
+        state.toggle_mark();
+        assert_eq!(state.marked, ["gym"]);
+    }
+
+    #[test]
+    fn test_tags_set_tags_clamps_and_drops_stale_marks() {
+        let mut state = tags_state(&[("a", 1), ("b", 1), ("c", 1)]);
+        state.select_next();
+        state.select_next();
+        state.toggle_mark();
+        state.set_tags(vec![("a".to_string(), 1)]);
+        assert_eq!(state.selected, 0);
+        assert!(state.marked.is_empty());
+        assert!(tags_state(&[]).query().is_none());
+    }
+
+    fn cache_row(id: &str, last_used: i64) -> CacheRow {
+        CacheRow {
+            track: Track::new(
+                id.to_string(),
+                id.to_string(),
+                60,
+                String::new(),
+                String::new(),
+            ),
+            path: format!("/cache/{id}.mp3"),
+            size_bytes: 100,
+            last_used,
+        }
+    }
+
+    #[test]
+    fn test_cache_rows_sort_recent_first_and_count_outcomes() {
+        let mut state = CacheViewState {
+            selected: 5,
+            ..Default::default()
+        };
+        state.set_rows(vec![
+            cache_row("old", 1),
+            cache_row("new", 3),
+            cache_row("mid", 2),
+        ]);
+        let ids: Vec<&str> = state
+            .rows
+            .iter()
+            .map(|r| r.track.video_id.as_str())
+            .collect();
+        assert_eq!(ids, ["new", "mid", "old"]);
+        assert_eq!(state.selected, 2);
+
+        state.outcomes.insert(
+            "new".to_string(),
+            VerifyOutcome {
+                health: FileHealth::Ok,
+                redownloading: false,
+            },
+        );
+        state.outcomes.insert(
+            "old".to_string(),
+            VerifyOutcome {
+                health: FileHealth::Undecodable,
+                redownloading: true,
+            },
+        );
+        assert_eq!(state.verify_counts(), (1, 1, 1));
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Diagnostics
+// ---------------------------------------------------------------------------
+
+/// Results of the last dependency check run.
+#[derive(Debug, Default)]
+pub(crate) struct DiagnosticsState {
+    pub checks: Vec<Check>,
+    /// A run is in flight.
+    pub running: bool,
+    /// Open the screen when the run in flight finds a failure (startup).
+    pub open_on_failure: bool,
+    /// When the shown results were gathered.
+    pub checked_at: Option<Instant>,
+}
+
+// ---------------------------------------------------------------------------
+// Artist browser
+// ---------------------------------------------------------------------------
+
+/// The columns of an artist page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ArtistSection {
+    Uploads,
+    Albums,
+    Singles,
+    Playlists,
+}
+
+impl ArtistSection {
+    pub(crate) const ALL: [ArtistSection; 4] = [
+        ArtistSection::Uploads,
+        ArtistSection::Albums,
+        ArtistSection::Singles,
+        ArtistSection::Playlists,
+    ];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            ArtistSection::Uploads => "Uploads",
+            ArtistSection::Albums => "Albums",
+            ArtistSection::Singles => "Singles",
+            ArtistSection::Playlists => "Playlists",
+        }
+    }
+}
+
+/// Which column of the artist browser has focus.
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub(crate) enum ArtistFocus {
+    #[default]
+    Sections,
+    /// The uploads, or the releases of the selected section.
+    Items,
+    /// The tracks of the opened release.
+    Tracks,
+}
+
+/// Runtime state for the artist browser.
+#[derive(Debug, Default)]
+pub(crate) struct ArtistState {
+    pub page: Option<ArtistPage>,
+    /// Mode to go back to when the page closes: the feed browser or Normal.
+    pub return_mode: AppMode,
+    /// The artist whose page is being fetched.
+    pub loading: Option<String>,
+    pub error: Option<String>,
+    /// Feedback for the last action, shown in the status bar.
+    pub status: Option<String>,
+    pub focus: ArtistFocus,
+    pub selected_section: usize,
+    pub selected_item: usize,
+    pub selected_track: usize,
+    /// The tracks of the release opened with `l`.
+    pub release_tracks: Vec<Track>,
+    pub tracks_loading: bool,
+    /// Channels resolved this session, by uploader display name.
+    pub channels: std::collections::HashMap<String, Channel>,
+}
+
+impl ArtistState {
+    /// Sections of the page that have anything in them.
+    pub(crate) fn sections(&self) -> Vec<ArtistSection> {
+        ArtistSection::ALL
+            .into_iter()
+            .filter(|&section| self.item_count(section) > 0)
+            .collect()
+    }
+
+    pub(crate) fn section(&self) -> Option<ArtistSection> {
+        self.sections().get(self.selected_section).copied()
+    }
+
+    pub(crate) fn item_count(&self, section: ArtistSection) -> usize {
+        match section {
+            ArtistSection::Uploads => self.page.as_ref().map_or(0, |p| p.uploads.len()),
+            section => self.releases(section).len(),
+        }
+    }
+
+    pub(crate) fn releases(&self, section: ArtistSection) -> &[Release] {
+        let Some(page) = &self.page else {
+            return &[];
+        };
+        match section {
+            ArtistSection::Uploads => &[],
+            ArtistSection::Albums => &page.albums,
+            ArtistSection::Singles => &page.singles,
+            ArtistSection::Playlists => &page.playlists,
+        }
+    }
+
+    /// The release highlighted in the items column.
+    pub(crate) fn selected_release(&self) -> Option<&Release> {
+        self.releases(self.section()?).get(self.selected_item)
+    }
+
+    /// The tracks the focused column shows: the uploads, or the opened
+    /// release's tracks.
+    pub(crate) fn track_list(&self) -> &[Track] {
+        match (self.focus, self.section(), &self.page) {
+            (ArtistFocus::Tracks, _, _) => &self.release_tracks,
+            (_, Some(ArtistSection::Uploads), Some(page)) => &page.uploads,
+            _ => &[],
+        }
+    }
+
+    /// The highlighted track, if the focused column lists tracks.
+    pub(crate) fn selected_track(&self) -> Option<&Track> {
+        match self.focus {
+            ArtistFocus::Tracks => self.release_tracks.get(self.selected_track),
+            ArtistFocus::Items => self.track_list().get(self.selected_item),
+            ArtistFocus::Sections => None,
+        }
+    }
+
+    /// `j` / `k` in the focused column.
+    pub(crate) fn move_selection(&mut self, down: bool) {
+        let step = |i: usize, len: usize| {
+            if down {
+                (i + 1).min(len.saturating_sub(1))
+            } else {
+                i.saturating_sub(1)
+            }
+        };
+        match self.focus {
+            ArtistFocus::Sections => {
+                let next = step(self.selected_section, self.sections().len());
+                if next != self.selected_section {
+                    self.selected_section = next;
+                    self.selected_item = 0;
+                }
+            }
+            ArtistFocus::Items => {
+                let len = self.section().map_or(0, |s| self.item_count(s));
+                self.selected_item = step(self.selected_item, len);
+            }
+            ArtistFocus::Tracks => {
+                self.selected_track = step(self.selected_track, self.release_tracks.len());
+            }
+        }
+    }
+
+    /// Show a freshly loaded page from the top.
+    pub(crate) fn show(&mut self, page: ArtistPage) {
+        self.page = Some(page);
+        self.focus = ArtistFocus::Sections;
+        self.selected_section = 0;
+        self.selected_item = 0;
+        self.selected_track = 0;
+        self.release_tracks.clear();
+        self.error = None;
+    }
 }
@@ -0,0 +1,130 @@
+//! Tag actions for MusicPlayerApp.
+//!
+//! Handles the tag editor (a prompt prefilled with the track's current
+//! tags), the tag cloud view, and queueing every track that matches a tag
+//! query.
+
+use crate::config::clean_title;
+use crate::services::tags::{parse_tag_list, TagQuery};
+use crate::ui::state::{AppMode, PromptPurpose};
+
+use super::app::MusicPlayerApp;
+
+impl MusicPlayerApp {
+    /// Reload tag counts from the library for the tag cloud.
+    pub(super) fn refresh_tags(&mut self) {
+        let result = match self.persistence.library() {
+            Some(library) => library
+                .tag_counts()
+                .map_err(|e| format!("Failed to load tags: {}", e)),
+            None => Err("Library database unavailable".to_string()),
+        };
+        match result {
+            Ok(tags) => {
+                self.tags.set_tags(tags);
+                self.tags.error = None;
+            }
+            Err(e) => self.tags.error = Some(e),
+        }
+    }
+
+    /// Shift+G — open the tag cloud.
+    pub(super) fn open_tags_view(&mut self) {
+        self.refresh_tags();
+        self.mode = AppMode::Tags;
+    }
+
+    /// `g` — edit the tags of the track under the cursor.
+    pub(super) fn start_edit_tags(&mut self) {
+        let Some(track) = self.track_under_cursor() else {
+            self.status_message = "No track selected".to_string();
+            return;
+        };
+        let Some(library) = self.persistence.library() else {
+            self.status_message =
+                "⚠ Tags need the library database, which is unavailable".to_string();
+            return;
+        };
+        let current = library.track_tags(&track.video_id).unwrap_or_default();
+        self.tags.editing = Some(track);
+        self.open_prompt(PromptPurpose::EditTags, &current.join(", "));
+    }
+
+    /// Store the tags entered in the editor prompt.
+    pub(super) fn save_edited_tags(&mut self, input: &str) -> Result<String, String> {
+        let tags = parse_tag_list(input).map_err(|e| format!("Can't save tags: {}", e))?;
+        let track = self
+            .tags
+            .editing
+            .clone()
+            .ok_or_else(|| "No track selected".to_string())?;
+        self.persistence
+            .save_track_tags(&track, &tags)
+            .map_err(|e| format!("Can't save tags: {}", e))?;
+        self.refresh_tags();
+
+        let title = clean_title(&track.title);
+        Ok(if tags.is_empty() {
+            format!("Cleared tags on '{}'", title)
+        } else {
+            format!("✓ Tagged '{}': {}", title, tags.join(", "))
+        })
+    }
+
+    /// Enter in the tag cloud — queue tracks for the marked (or highlighted) tags.
+    pub(super) fn queue_selected_tags(&mut self) {
+        let Some(query) = self.tags.query() else {
+            self.status_message = "No tags yet — press g on a track to add some".to_string();
+            return;
+        };
+        self.status_message = match self.queue_by_tags(&query) {
+            Ok(msg) | Err(msg) => msg,
+        };
+    }
+
+    /// Add every track matching `query` to the queue, through the active
+    /// filter profile.
+    pub(super) fn queue_by_tags(&mut self, query: &TagQuery) -> Result<String, String> {
+        let tracks = match self.persistence.library() {
+            Some(library) => library
+                .tracks_with_tags(query)
+                .map_err(|e| format!("Failed to load tagged tracks: {}", e))?,
+            None => return Err("Library database unavailable".to_string()),
+        };
+        if tracks.is_empty() {
+            return Err(format!("No tracks tagged {}", query));
+        }
+
+        let was_empty = self.queue.is_empty();
+        let (added, skipped_filtered, skipped_duplicate) = self.add_filtered_tracks(&tracks);
+        if added == 0 && skipped_duplicate == tracks.len() {
+            return Err(format!(
+                "All tracks tagged {} are already in the queue",
+                query
+            ));
+        }
+        if added > 0 {
+            self.trigger_smart_downloads();
+        }
+
+        let mut parts = vec![format!("✓ Added {} tracks tagged {}", added, query)];
+        if skipped_duplicate > 0 {
+            parts.push(format!("{} already in queue", skipped_duplicate));
+        }
+        if skipped_filtered > 0 {
+            parts.push(format!(
+                "{} filtered by '{}'",
+                skipped_filtered,
+                self.filters.active().name
+            ));
+        }
+        if was_empty && added > 0 {
+            parts.push("press Space to play".to_string());
+        }
+        let mut msg = parts.join(" — ");
+        if let Err(e) = self.save_queue() {
+            msg = format!("{} (queue not saved: {})", msg, e);
+        }
+        Ok(msg)
+    }
+}
@@ -0,0 +1,347 @@
+//! Artist browser (`e`): a channel's uploads, albums, singles and playlists.
+//!
+//! ```text
+//! ┌─ The Weeknd ────────────────────────────────────────────────────────────┐
+//! │ ✓ 50 uploads · 12 albums · 20 singles                                   │
+//! ├──────────────┬───────────────────────────────┬──────────────────────────┤
+//! │  Sections    │  Albums                       │  After Hours             │
+//! │  Uploads (50)│  > After Hours        14 trk  │  > Alone Again     4:10  │
+//! │ >Albums (12) │    Dawn FM            16 trk  │    Too Late        3:59  │
+//! │  Singles (20)│    Starboy             — trk  │    Hardest To Love 3:31  │
+//! ├──────────────┴───────────────────────────────┴──────────────────────────┤
+//! │ [j/k] Navigate  [l] Open  [Enter] Play  [a] Add  [r] Refresh  [Esc/e] Close │
+//! └─────────────────────────────────────────────────────────────────────────┘
+//! ```
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::config::{clean_title, format_time};
+use crate::player::queue::Track;
+use crate::ui::state::{ArtistFocus, ArtistSection};
+
+use super::super::app::MusicPlayerApp;
+
+const SPINNER: [&str; 4] = ["⠋", "⠙", "⠹", "⠸"];
+
+pub(crate) fn draw(app: &MusicPlayerApp, frame: &mut Frame) {
+    let area = frame.area();
+    let state = &app.artist;
+
+    let title = match (&state.loading, &state.page) {
+        (Some(name), _) => format!(" {} ", name),
+        (None, Some(page)) => format!(" {} ", page.channel.name),
+        (None, None) => " Artist ".to_string(),
+    };
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .style(Style::default().fg(Color::Cyan));
+    let inner = outer.inner(area);
+    frame.render_widget(outer, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // status bar
+            Constraint::Min(5),    // columns
+            Constraint::Length(1), // hint bar
+        ])
+        .split(inner);
+
+    draw_status_bar(app, frame, rows[0]);
+    let cols = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(20), // sections
+            Constraint::Percentage(40), // uploads or releases
+            Constraint::Percentage(40), // release tracks or detail
+        ])
+        .split(rows[1]);
+    draw_sections(app, frame, cols[0]);
+    draw_items(app, frame, cols[1]);
+    if state.focus == ArtistFocus::Tracks {
+        let title = state
+            .selected_release()
+            .map_or("Tracks", |r| r.title.as_str());
+        let rows = track_rows(&state.release_tracks);
+        draw_list(
+            frame,
+            cols[2],
+            title,
+            rows,
+            Some(state.selected_track),
+            true,
+        );
+    } else {
+        draw_detail(app, frame, cols[2]);
+    }
+    draw_hint_bar(app, frame, rows[2]);
+}
+
+fn draw_status_bar(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let state = &app.artist;
+    let spinner = SPINNER[(app.ui.animation_frame as usize) % SPINNER.len()];
+    let (text, color) = if let Some(name) = &state.loading {
+        (
+            format!("{spinner} Looking up {} on YouTube…", name),
+            Color::Yellow,
+        )
+    } else if state.tracks_loading {
+        (format!("{spinner} Loading tracks…"), Color::Yellow)
+    } else if let Some(msg) = &state.status {
+        let color = if msg.starts_with('✓') {
+            Color::Green
+        } else {
+            Color::Red
+        };
+        (msg.clone(), color)
+    } else if let Some(err) = &state.error {
+        (format!("⚠  {err}"), Color::Red)
+    } else if let Some(page) = &state.page {
+        let counts: Vec<String> = state
+            .sections()
+            .into_iter()
+            .map(|s| format!("{} {}", state.item_count(s), s.label().to_lowercase()))
+            .collect();
+        let failed = page.tab_errors();
+        if failed.is_empty() {
+            (format!("✓ {}", counts.join(" · ")), Color::DarkGray)
+        } else {
+            (
+                format!("⚠  {} · {}", counts.join(" · "), failed.join(" · ")),
+                Color::Yellow,
+            )
+        }
+    } else {
+        (String::new(), Color::DarkGray)
+    };
+    frame.render_widget(Paragraph::new(text).style(Style::default().fg(color)), area);
+}
+
+fn draw_sections(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let state = &app.artist;
+    let rows = state
+        .sections()
+        .into_iter()
+        .map(|s| {
+            (
+                format!("{} ({})", s.label(), state.item_count(s)),
+                String::new(),
+            )
+        })
+        .collect();
+    let focused = state.focus == ArtistFocus::Sections;
+    draw_list(
+        frame,
+        area,
+        "Sections",
+        rows,
+        Some(state.selected_section),
+        focused,
+    );
+}
+
+fn draw_items(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let state = &app.artist;
+    let Some(section) = state.section() else {
+        draw_list(frame, area, "", Vec::new(), None, false);
+        return;
+    };
+    let rows = match section {
+        ArtistSection::Uploads => track_rows(state.page.as_ref().map_or(&[], |p| &p.uploads)),
+        section => state
+            .releases(section)
+            .iter()
+            .map(|release| {
+                let count = release
+                    .track_count
+                    .map_or_else(|| "  — trk".to_string(), |n| format!("{:>3} trk", n));
+                (release.title.clone(), count)
+            })
+            .collect(),
+    };
+    let focused = state.focus == ArtistFocus::Items;
+    let selected = (state.focus != ArtistFocus::Sections).then_some(state.selected_item);
+    draw_list(frame, area, section.label(), rows, selected, focused);
+}
+
+fn draw_detail(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let state = &app.artist;
+    let dim = Style::default().fg(Color::DarkGray);
+    let lines = if let Some(track) = state.selected_track() {
+        vec![
+            Line::from(Span::styled(
+                clean_title(&track.title).to_string(),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from(Span::styled(format_time(track.duration as f64), dim)),
+            Line::from(""),
+            Line::from(Span::styled(
+                "[Enter] Play from here  [a] Add to queue",
+                dim,
+            )),
+        ]
+    } else if let Some(release) = state
+        .selected_release()
+        .filter(|_| state.focus == ArtistFocus::Items)
+    {
+        let count = release
+            .track_count
+            .map_or_else(String::new, |n| format!("{} tracks", n));
+        vec![
+            Line::from(Span::styled(
+                release.title.clone(),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from(Span::styled(count, dim)),
+            Line::from(""),
+            Line::from(Span::styled("[l/Enter] Open  [a] Add all to queue", dim)),
+        ]
+    } else if let Some(page) = &state.page {
+        vec![
+            Line::from(Span::styled(
+                page.channel.name.clone(),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from(Span::styled(page.channel.url.clone(), dim)),
+        ]
+    } else {
+        Vec::new()
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(
+        Paragraph::new(lines).block(block).wrap(Wrap { trim: true }),
+        area,
+    );
+}
+
+/// `(title, duration)` rows for a track list.
+fn track_rows(tracks: &[Track]) -> Vec<(String, String)> {
+    tracks
+        .iter()
+        .map(|t| {
+            (
+                clean_title(&t.title).to_string(),
+                format_time(t.duration as f64),
+            )
+        })
+        .collect()
+}
+
+/// A bordered list of `(label, right-hand detail)` rows that scrolls to
+/// keep `selected` visible. The border is bright while `focused`.
+fn draw_list(
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    rows: Vec<(String, String)>,
+    selected: Option<usize>,
+    focused: bool,
+) {
+    let visible = area.height.saturating_sub(2) as usize;
+    let sel = selected.unwrap_or(0);
+    let start = if rows.len() <= visible {
+        0
+    } else {
+        sel.saturating_sub(visible / 2).min(rows.len() - visible)
+    };
+    let width = area.width.saturating_sub(4) as usize;
+
+    let items: Vec<ListItem> = if rows.is_empty() {
+        vec![ListItem::new("  (empty)").style(Style::default().fg(Color::DarkGray))]
+    } else {
+        rows.into_iter()
+            .enumerate()
+            .skip(start)
+            .take(visible)
+            .map(|(i, (label, detail))| {
+                let room = width.saturating_sub(detail.chars().count() + 3);
+                let label = if label.chars().count() > room {
+                    let cut: String = label.chars().take(room.saturating_sub(1)).collect();
+                    format!("{cut}…")
+                } else {
+                    label
+                };
+                let pad = width.saturating_sub(label.chars().count() + detail.chars().count() + 2);
+                let style = match selected {
+                    Some(s) if s == i && focused => Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                    Some(s) if s == i => Style::default().fg(Color::Cyan),
+                    _ => Style::default().fg(Color::White),
+                };
+                let marker = if selected == Some(i) { "> " } else { "  " };
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{marker}{label}"), style),
+                    Span::raw(" ".repeat(pad)),
+                    Span::styled(detail, Style::default().fg(Color::DarkGray)),
+                ]))
+            })
+            .collect()
+    };
+
+    let border = if focused {
+        Color::Cyan
+    } else {
+        Color::DarkGray
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {title} "))
+        .border_style(Style::default().fg(border));
+    frame.render_widget(List::new(items).block(block), area);
+}
+
+fn draw_hint_bar(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let key = |k: &'static str, color| Span::styled(k, Style::default().fg(color));
+    let mut hints = vec![key("[j/k]", Color::Yellow), Span::raw(" Navigate  ")];
+    match app.artist.focus {
+        ArtistFocus::Sections => {
+            hints.extend([key("[l]", Color::Cyan), Span::raw(" Focus items  ")]);
+            if app.artist.section() == Some(ArtistSection::Uploads) {
+                hints.extend([key("[a]", Color::Green), Span::raw(" Add all uploads  ")]);
+            }
+        }
+        ArtistFocus::Items => hints.extend([
+            key("[h]", Color::Cyan),
+            Span::raw(" Sections  "),
+            key("[Enter]", Color::Green),
+            Span::raw(if app.artist.section() == Some(ArtistSection::Uploads) {
+                " Play  "
+            } else {
+                " Open  "
+            }),
+            key("[a]", Color::Green),
+            Span::raw(" Add  "),
+        ]),
+        ArtistFocus::Tracks => hints.extend([
+            key("[h]", Color::Cyan),
+            Span::raw(" Back  "),
+            key("[Enter]", Color::Green),
+            Span::raw(" Play  "),
+            key("[a]", Color::Green),
+            Span::raw(" Add  "),
+        ]),
+    }
+    hints.extend([
+        key("[r]", Color::Cyan),
+        Span::raw(" Refresh  "),
+        key("[Esc/e]", Color::Red),
+        Span::raw(" Close"),
+    ]);
+    frame.render_widget(Paragraph::new(Line::from(hints)), area);
+}
@@ -1,26 +1,65 @@
+//! Cache widgets: the compact download/cache counter in the bottom bar, and
+//! the full-screen Cache view (`c`) with per-file details and verification.
+//!
+//! ```text
+//! ┌─ Cache ─────────────────────────────────────────────────────────────────┐
+//! │ status bar                                                              │
+//! │ ~/.cache/youtube-music-player/audio · 212 files · 820 MB / 2.0 GB · 3 📌 │
+//! │ ██████████████░░░░░░░░░░ Verifying 120/212                              │
+//! ├─────────────────────────────────────────────────────────────────────────┤
+//! │ > 📌 Song A                          4.1 MB   2h ago    ✓ ok            │
+//! │      Song B                          6.0 MB   3d ago    ✗ truncated     │
+//! ├─────────────────────────────────────────────────────────────────────────┤
+//! │ /home/me/.cache/youtube-music-player/audio/abc123.mp3                   │
+//! ├─────────────────────────────────────────────────────────────────────────┤
+//! │ [j/k] Move  [v] Verify all  [r] Re-download  [d] Delete  [b] Pin  [x] … │
+//! └─────────────────────────────────────────────────────────────────────────┘
+//! ```
+
 use ratatui::{
-    layout::{Alignment, Rect},
-    style::{Color, Style},
-    widgets::{Block, Borders, Paragraph},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Gauge, List, ListItem, Paragraph},
     Frame,
 };
 
+use crate::config::clean_title;
+use crate::services::audio_cache::format_bytes;
+use crate::services::library::now_unix;
+
 use super::super::app::MusicPlayerApp;
 use super::player_bar::get_download_animation;
 
 pub(crate) fn draw_cache_stats(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
     let active_count = app.downloads.active_count();
+    let queued_count = app.downloads.queued_count();
     let cached_count = app.downloads.cached_count();
+    let usage = match app.settings.cache_quota_bytes() {
+        Some(quota) => format!(
+            "{}/{}",
+            format_bytes(app.cache_usage_bytes),
+            format_bytes(quota)
+        ),
+        None => format_bytes(app.cache_usage_bytes),
+    };
 
     let cache_info = if active_count > 0 {
+        let queued = if queued_count > 0 {
+            format!(" +{}", queued_count)
+        } else {
+            String::new()
+        };
         format!(
-            "{}\n⬇ {}\n💾 {}",
+            "{}\n⬇ {}{}\n💾 {}\n{}",
             get_download_animation(app.ui.animation_frame),
             active_count,
-            cached_count
+            queued,
+            cached_count,
+            usage
         )
     } else {
-        format!("💾\n{}\ncached", cached_count)
+        format!("💾\n{} cached\n{}", cached_count, usage)
     };
 
     let cache_widget = Paragraph::new(cache_info)
@@ -30,3 +69,273 @@ pub(crate) fn draw_cache_stats(app: &MusicPlayerApp, frame: &mut Frame, area: Re
 
     frame.render_widget(cache_widget, area);
 }
+
+pub(crate) fn draw_cache_view(app: &MusicPlayerApp, frame: &mut Frame) {
+    let area = frame.area();
+
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .title(" Cache ")
+        .style(Style::default().fg(Color::Cyan));
+    let inner = outer.inner(area);
+    frame.render_widget(outer, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // status bar
+            Constraint::Length(1), // summary
+            Constraint::Length(1), // verify progress / result
+            Constraint::Min(5),    // file list
+            Constraint::Length(4), // selected file details
+            Constraint::Length(1), // hint bar
+        ])
+        .split(inner);
+
+    draw_status_bar(app, frame, rows[0]);
+    draw_summary(app, frame, rows[1]);
+    draw_verify_line(app, frame, rows[2]);
+    draw_files(app, frame, rows[3]);
+    draw_details(app, frame, rows[4]);
+    draw_hint_bar(frame, rows[5]);
+}
+
+fn draw_status_bar(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let (text, style) = if let Some(err) = &app.cache_view.error {
+        (format!("⚠ {}", err), Style::default().fg(Color::Red))
+    } else if !app.status_message.is_empty() {
+        let style = if app.status_message.starts_with('✓') {
+            Style::default().fg(Color::Green)
+        } else {
+            Style::default().fg(Color::Yellow)
+        };
+        (app.status_message.clone(), style)
+    } else {
+        (
+            "Press v to check every cached file for truncation or corruption".to_string(),
+            Style::default().fg(Color::DarkGray),
+        )
+    };
+    frame.render_widget(Paragraph::new(text).style(style), area);
+}
+
+fn draw_summary(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let state = &app.cache_view;
+    let used: u64 = state.rows.iter().map(|r| r.size_bytes).sum();
+    let quota = match app.settings.cache_quota_bytes() {
+        Some(quota) => format!("{} / {}", format_bytes(used), format_bytes(quota)),
+        None => format!("{} (no limit)", format_bytes(used)),
+    };
+    let pinned = state
+        .rows
+        .iter()
+        .filter(|r| app.pinned.contains(&r.track.video_id))
+        .count();
+    let line = Line::from(vec![
+        Span::styled(
+            app.downloads.audio_cache().dir().display().to_string(),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::raw(format!(" · {} files · ", state.rows.len())),
+        Span::styled(quota, Style::default().fg(Color::Yellow)),
+        Span::raw(format!(" · {} 📌", pinned)),
+    ]);
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+fn draw_verify_line(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let state = &app.cache_view;
+    if let Some((checked, total)) = state.progress {
+        let ratio = checked as f64 / total.max(1) as f64;
+        let gauge = Gauge::default()
+            .gauge_style(Style::default().fg(Color::Green).bg(Color::DarkGray))
+            .ratio(ratio.clamp(0.0, 1.0))
+            .label(format!("Verifying {}/{}", checked, total));
+        frame.render_widget(gauge, area);
+        return;
+    }
+    let line = if state.outcomes.is_empty() {
+        Line::from(Span::styled(
+            "Not verified this session",
+            Style::default().fg(Color::DarkGray),
+        ))
+    } else {
+        let (ok, broken, redownloading) = state.verify_counts();
+        Line::from(vec![
+            Span::styled(format!("✓ {} ok", ok), Style::default().fg(Color::Green)),
+            Span::raw("  "),
+            Span::styled(
+                format!("✗ {} broken", broken),
+                Style::default().fg(if broken > 0 {
+                    Color::Red
+                } else {
+                    Color::DarkGray
+                }),
+            ),
+            Span::raw("  "),
+            Span::styled(
+                format!("⬇ {} re-downloading", redownloading),
+                Style::default().fg(Color::Cyan),
+            ),
+        ])
+    };
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+/// `just now`, `5m ago`, `3h ago`, `12d ago`.
+pub(super) fn format_age(secs: i64) -> String {
+    match secs.max(0) {
+        s if s < 60 => "just now".to_string(),
+        s if s < 3600 => format!("{}m ago", s / 60),
+        s if s < 86_400 => format!("{}h ago", s / 3600),
+        s => format!("{}d ago", s / 86_400),
+    }
+}
+
+fn draw_files(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let state = &app.cache_view;
+    let now = now_unix();
+    let items: Vec<ListItem> = if state.rows.is_empty() {
+        vec![ListItem::new("  (nothing cached yet)").style(Style::default().fg(Color::DarkGray))]
+    } else {
+        let visible = area.height.saturating_sub(2) as usize;
+        let start = if state.rows.len() <= visible {
+            0
+        } else {
+            state
+                .selected
+                .saturating_sub(visible / 2)
+                .min(state.rows.len() - visible)
+        };
+        state
+            .rows
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(visible)
+            .map(|(i, row)| {
+                let marker = if i == state.selected { ">" } else { " " };
+                let pin = if app.pinned.contains(&row.track.video_id) {
+                    "📌"
+                } else {
+                    "  "
+                };
+                let style = if i == state.selected {
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                let health = match state.outcomes.get(&row.track.video_id) {
+                    Some(outcome) if outcome.health.is_ok() => {
+                        Span::styled("✓ ok", Style::default().fg(Color::Green))
+                    }
+                    Some(outcome) => Span::styled(
+                        format!(
+                            "✗ {}{}",
+                            outcome.health,
+                            if outcome.redownloading {
+                                " — re-downloading"
+                            } else {
+                                " — purged"
+                            }
+                        ),
+                        Style::default().fg(Color::Red),
+                    ),
+                    None => Span::raw(""),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{marker} {pin} {}", clean_title(&row.track.title)),
+                        style,
+                    ),
+                    Span::styled(
+                        format!(
+                            "  {}  {}  ",
+                            format_bytes(row.size_bytes),
+                            format_age(now - row.last_used)
+                        ),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    health,
+                ]))
+            })
+            .collect()
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Files — most recently used first ")
+        .border_style(Style::default().fg(Color::Yellow));
+    frame.render_widget(List::new(items).block(block), area);
+}
+
+fn draw_details(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+    let Some(row) = app.cache_view.selected_row() else {
+        frame.render_widget(block, area);
+        return;
+    };
+    let uploader = if row.track.uploader.is_empty() {
+        String::new()
+    } else {
+        format!("{} · ", row.track.uploader)
+    };
+    let lines = vec![
+        Line::from(Span::styled(
+            row.path.clone(),
+            Style::default().fg(Color::White),
+        )),
+        Line::from(Span::styled(
+            format!(
+                "{}{} · {}{}",
+                uploader,
+                row.track.video_id,
+                format_bytes(row.size_bytes),
+                if app.pinned.contains(&row.track.video_id) {
+                    " · pinned — never evicted"
+                } else {
+                    ""
+                }
+            ),
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn draw_hint_bar(frame: &mut Frame, area: Rect) {
+    let hints = vec![
+        Span::styled("[j/k]", Style::default().fg(Color::Yellow)),
+        Span::raw(" Move  "),
+        Span::styled("[v]", Style::default().fg(Color::Green)),
+        Span::raw(" Verify all  "),
+        Span::styled("[r]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Re-download  "),
+        Span::styled("[d]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Delete  "),
+        Span::styled("[b]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Pin  "),
+        Span::styled("[x]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Copy to folder  "),
+        Span::styled("[Esc]", Style::default().fg(Color::Red)),
+        Span::raw(" Close"),
+    ];
+    frame.render_widget(Paragraph::new(Line::from(hints)), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_age_buckets() {
+        assert_eq!(format_age(-5), "just now");
+        assert_eq!(format_age(300), "5m ago");
+        assert_eq!(format_age(3 * 3600 + 10), "3h ago");
+        assert_eq!(format_age(12 * 86_400), "12d ago");
+    }
+}
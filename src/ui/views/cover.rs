@@ -0,0 +1,120 @@
+//! Cover art in the player bar and feed detail panes, drawn with the
+//! terminal's best picture protocol (see [`crate::ui::graphics`]).
+
+use ratatui::{
+    buffer::CellDiffOption,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::ui::graphics::{
+    fit_square, half_blocks, kitty_placeholder, CoverPlacement, ImageProtocol, KITTY_MAX_CELLS,
+};
+
+use super::super::app::MusicPlayerApp;
+
+/// Tallest cover in a detail pane, in rows.
+const DETAIL_COVER_ROWS: u16 = 10;
+
+/// Draw `key`'s cover as large as fits `area`. Draws nothing until the
+/// cover is loaded.
+pub(crate) fn draw_cover(app: &MusicPlayerApp, frame: &mut Frame, area: Rect, key: &str) {
+    let Some(cover) = app.covers.images.get(key) else {
+        return;
+    };
+    let protocol = app.covers.protocol;
+    let area = match protocol {
+        ImageProtocol::Kitty => Rect {
+            width: area.width.min(KITTY_MAX_CELLS),
+            height: area.height.min(KITTY_MAX_CELLS),
+            ..area
+        },
+        _ => area,
+    };
+    let rect = fit_square(area);
+    if rect.height == 0 {
+        return;
+    }
+
+    match protocol {
+        ImageProtocol::HalfBlocks => {
+            let pixels = cover.resample(u32::from(rect.width), u32::from(rect.height) * 2);
+            let rgb = |[r, g, b]: [u8; 3]| Color::Rgb(r, g, b);
+            let lines: Vec<Line> = half_blocks(&pixels, rect.width as usize, rect.height as usize)
+                .into_iter()
+                .map(|row| {
+                    Line::from(
+                        row.into_iter()
+                            .map(|(top, bottom)| {
+                                Span::styled("▀", Style::default().fg(rgb(top)).bg(rgb(bottom)))
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .collect();
+            frame.render_widget(Paragraph::new(lines), rect);
+        }
+        ImageProtocol::Kitty => {
+            let placement = CoverPlacement {
+                key: key.to_string(),
+                area: rect,
+            };
+            let id = placement.image_id();
+            let color = Color::Rgb((id >> 16) as u8, (id >> 8) as u8, id as u8);
+            let buf = frame.buffer_mut();
+            for row in 0..rect.height {
+                for col in 0..rect.width {
+                    buf[(rect.x + col, rect.y + row)]
+                        .set_symbol(&kitty_placeholder(row, col))
+                        .set_style(Style::default().fg(color));
+                }
+            }
+            app.covers.placements.borrow_mut().push(placement);
+        }
+        ImageProtocol::Sixel => {
+            // The frame leaves these cells alone; the image is written
+            // over them once it's out.
+            let buf = frame.buffer_mut();
+            for pos in rect.positions() {
+                buf[pos]
+                    .set_symbol(" ")
+                    .set_diff_option(CellDiffOption::Skip);
+            }
+            app.covers.placements.borrow_mut().push(CoverPlacement {
+                key: key.to_string(),
+                area: rect,
+            });
+        }
+    }
+}
+
+/// Draw `key`'s cover at the top of a detail pane and return the area left
+/// below it — all of `area` while the cover isn't loaded.
+pub(crate) fn draw_cover_above(
+    app: &MusicPlayerApp,
+    frame: &mut Frame,
+    area: Rect,
+    key: &str,
+) -> Rect {
+    let rows = (area.height / 2).min(area.width / 2).min(DETAIL_COVER_ROWS);
+    if !app.covers.images.contains_key(key) || rows < 3 {
+        return area;
+    }
+    draw_cover(
+        app,
+        frame,
+        Rect {
+            height: rows,
+            ..area
+        },
+        key,
+    );
+    Rect {
+        y: area.y + rows + 1,
+        height: area.height - rows - 1,
+        ..area
+    }
+}
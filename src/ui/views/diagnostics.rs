@@ -0,0 +1,152 @@
+//! Diagnostics screen (`!`): what crusty depends on and how to fix it.
+//!
+//! ```text
+//! ┌─ Diagnostics ───────────────────────────────────────────────────────────┐
+//! │ ✗ 1 problem · ⚠ 1 warning — checked 4s ago                              │
+//! ├─ Checks ────────────────────────────────────────────────────────────────┤
+//! │ ✓ yt-dlp            2026.09.30 at /usr/bin/yt-dlp                       │
+//! │ ✗ ffmpeg            Not found — yt-dlp needs it to extract audio (-x)…  │
+//! │                     → Install ffmpeg with your package manager (…)      │
+//! │ ⚠ Browser cookies   No browser account selected — …                     │
+//! │                     → Press o to pick the browser you're logged into…   │
+//! ├─────────────────────────────────────────────────────────────────────────┤
+//! │ [r] Re-run  [Esc] Close                                                 │
+//! └─────────────────────────────────────────────────────────────────────────┘
+//! ```
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::services::diagnostics::CheckStatus;
+
+use super::super::app::MusicPlayerApp;
+
+/// Width of the check name column.
+const NAME_WIDTH: usize = 18;
+
+pub(crate) fn draw(app: &MusicPlayerApp, frame: &mut Frame) {
+    let area = frame.area();
+
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .title(" Diagnostics ")
+        .style(Style::default().fg(Color::Cyan));
+    let inner = outer.inner(area);
+    frame.render_widget(outer, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // summary
+            Constraint::Min(5),    // checks
+            Constraint::Length(1), // hint bar
+        ])
+        .split(inner);
+
+    draw_summary(app, frame, rows[0]);
+    draw_checks(app, frame, rows[1]);
+    draw_hint_bar(frame, rows[2]);
+}
+
+fn status_icon(status: CheckStatus) -> (&'static str, Color) {
+    match status {
+        CheckStatus::Ok => ("✓", Color::Green),
+        CheckStatus::Warn => ("⚠", Color::Yellow),
+        CheckStatus::Fail => ("✗", Color::Red),
+    }
+}
+
+fn draw_summary(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let state = &app.diagnostics;
+    let count = |status| state.checks.iter().filter(|c| c.status == status).count();
+    let (failed, warned) = (count(CheckStatus::Fail), count(CheckStatus::Warn));
+
+    let mut spans = if state.checks.is_empty() {
+        vec![Span::styled(
+            "Checking…",
+            Style::default().fg(Color::DarkGray),
+        )]
+    } else if failed == 0 && warned == 0 {
+        vec![Span::styled(
+            "✓ Everything crusty needs is in place",
+            Style::default().fg(Color::Green),
+        )]
+    } else {
+        let plural =
+            |n: usize, word: &str| format!("{} {}{}", n, word, if n == 1 { "" } else { "s" });
+        vec![
+            Span::styled(
+                format!("✗ {}", plural(failed, "problem")),
+                Style::default().fg(Color::Red),
+            ),
+            Span::raw(" · "),
+            Span::styled(
+                format!("⚠ {}", plural(warned, "warning")),
+                Style::default().fg(Color::Yellow),
+            ),
+        ]
+    };
+    if state.running {
+        if !state.checks.is_empty() {
+            spans.push(Span::styled(
+                " — re-checking…",
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+    } else if let Some(at) = state.checked_at {
+        spans.push(Span::styled(
+            format!(" — checked {}s ago", at.elapsed().as_secs()),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+fn draw_checks(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let mut lines = Vec::new();
+    for check in &app.diagnostics.checks {
+        let (icon, color) = status_icon(check.status);
+        lines.push(Line::from(vec![
+            Span::styled(format!("{} ", icon), Style::default().fg(color)),
+            Span::styled(
+                format!("{:<width$}", check.name, width = NAME_WIDTH),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(check.detail.clone(), Style::default().fg(Color::Gray)),
+        ]));
+        if let Some(fix) = &check.fix {
+            lines.push(Line::from(vec![
+                Span::raw(" ".repeat(NAME_WIDTH + 2)),
+                Span::styled(format!("→ {}", fix), Style::default().fg(color)),
+            ]));
+        }
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Checks ")
+        .border_style(Style::default().fg(Color::Yellow));
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
+
+fn draw_hint_bar(frame: &mut Frame, area: Rect) {
+    let hints = vec![
+        Span::styled("[r]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Re-run  "),
+        Span::styled("[Esc]", Style::default().fg(Color::Red)),
+        Span::raw(" Close"),
+    ];
+    frame.render_widget(Paragraph::new(Line::from(hints)), area);
+}
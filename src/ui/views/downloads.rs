@@ -0,0 +1,261 @@
+//! Downloads panel (`v`): "make available offline" playlist jobs and their
+//! progress.
+//!
+//! ```text
+//! ┌─ Downloads ─────────────────────────────────────────────────────────────┐
+//! │ status bar                                                              │
+//! │ ⬇ 2 downloading · 5 queued · 3 playlists · 1 fully offline              │
+//! ├─ Playlists ─────────────────────────────────────────────────────────────┤
+//! │ > ⤓ Road trip       ██████████  42/42  180 MB  ✓ offline                │
+//! │     Late night      ███░░░░░░░  12/40   51 MB  2 failed                 │
+//! ├─────────────────────────────────────────────────────────────────────────┤
+//! │ ⬇ Song C  ████░░░░ 48%                                                  │
+//! │ ✗ Song D  Private video                                                 │
+//! ├─────────────────────────────────────────────────────────────────────────┤
+//! │ [j/k] Move  [d] Remove  [Esc] Close                                     │
+//! └─────────────────────────────────────────────────────────────────────────┘
+//! ```
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+
+use crate::config::clean_title;
+use crate::services::audio_cache::format_bytes;
+
+use super::super::app::MusicPlayerApp;
+
+/// Width of the per-job progress bar.
+const BAR_WIDTH: usize = 10;
+
+pub(crate) fn draw(app: &MusicPlayerApp, frame: &mut Frame) {
+    let area = frame.area();
+
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .title(" Downloads ")
+        .style(Style::default().fg(Color::Cyan));
+    let inner = outer.inner(area);
+    frame.render_widget(outer, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // status bar
+            Constraint::Length(1), // summary
+            Constraint::Min(5),    // job list
+            Constraint::Length(8), // selected job details
+            Constraint::Length(1), // hint bar
+        ])
+        .split(inner);
+
+    draw_status_bar(app, frame, rows[0]);
+    draw_summary(app, frame, rows[1]);
+    draw_jobs(app, frame, rows[2]);
+    draw_details(app, frame, rows[3]);
+    draw_hint_bar(frame, rows[4]);
+}
+
+fn draw_status_bar(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let (text, style) = if !app.status_message.is_empty() {
+        let style = if app.status_message.starts_with('✓') {
+            Style::default().fg(Color::Green)
+        } else {
+            Style::default().fg(Color::Yellow)
+        };
+        (app.status_message.clone(), style)
+    } else {
+        (
+            "Press o on a feed playlist or Shift+L on a loaded one to make it available offline"
+                .to_string(),
+            Style::default().fg(Color::DarkGray),
+        )
+    };
+    frame.render_widget(Paragraph::new(text).style(style), area);
+}
+
+fn draw_summary(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let state = &app.offline_jobs;
+    let complete = state
+        .jobs
+        .iter()
+        .filter(|job| state.is_complete(&job.id))
+        .count();
+    let mut spans = vec![
+        Span::styled(
+            format!("⬇ {} downloading", app.downloads.active_count()),
+            Style::default().fg(Color::Cyan),
+        ),
+        Span::raw(format!(
+            " · {} queued · {} playlists · ",
+            app.downloads.queued_count(),
+            state.jobs.len()
+        )),
+        Span::styled(
+            format!("{} fully offline", complete),
+            Style::default().fg(Color::Green),
+        ),
+    ];
+    if app.is_offline() {
+        spans.push(Span::styled(
+            " · offline — paused",
+            Style::default().fg(Color::Red),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// `██████░░░░` for `ratio` of `width` cells.
+fn bar(ratio: f64, width: usize) -> String {
+    let filled = (ratio.clamp(0.0, 1.0) * width as f64).round() as usize;
+    format!("{}{}", "█".repeat(filled), "░".repeat(width - filled))
+}
+
+fn draw_jobs(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let state = &app.offline_jobs;
+    let items: Vec<ListItem> = if state.jobs.is_empty() {
+        vec![ListItem::new("  (no playlists made available offline)")
+            .style(Style::default().fg(Color::DarkGray))]
+    } else {
+        let visible = area.height.saturating_sub(2) as usize;
+        let start = if state.jobs.len() <= visible {
+            0
+        } else {
+            state
+                .selected
+                .saturating_sub(visible / 2)
+                .min(state.jobs.len() - visible)
+        };
+        state
+            .jobs
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(visible)
+            .map(|(i, job)| {
+                let progress = state.progress.get(&job.id).copied().unwrap_or_default();
+                let marker = if i == state.selected { ">" } else { " " };
+                let done = if progress.is_complete() { "⤓" } else { " " };
+                let style = if i == state.selected {
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                let outcome = if progress.is_complete() {
+                    Span::styled("✓ offline", Style::default().fg(Color::Green))
+                } else if progress.failed > 0 {
+                    Span::styled(
+                        format!("{} failed", progress.failed),
+                        Style::default().fg(Color::Red),
+                    )
+                } else {
+                    Span::raw("")
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{marker} {done} {}  ", job.title), style),
+                    Span::styled(
+                        bar(progress.ratio(), BAR_WIDTH),
+                        Style::default().fg(Color::Green),
+                    ),
+                    Span::styled(
+                        format!(
+                            "  {}/{}  {}  ",
+                            progress.cached,
+                            progress.total,
+                            format_bytes(progress.bytes)
+                        ),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    outcome,
+                ]))
+            })
+            .collect()
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Playlists — oldest first ")
+        .border_style(Style::default().fg(Color::Yellow));
+    frame.render_widget(List::new(items).block(block), area);
+}
+
+/// The selected job's downloading and failed tracks.
+fn draw_details(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+    let Some(job) = app.offline_jobs.selected_job() else {
+        frame.render_widget(block, area);
+        return;
+    };
+
+    let mut lines = Vec::new();
+    for track in &job.tracks {
+        let id = &track.video_id;
+        if let Some(progress) = app.downloads.progress_for(id) {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("⬇ {}  ", clean_title(&track.title)),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(
+                    format!("{} {:.0}%", progress.bar(8), progress.percent),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        } else if let Some(record) = app
+            .downloads
+            .failure_for(id)
+            .filter(|r| r.retry_at.is_none())
+        {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("✗ {}  ", clean_title(&track.title)),
+                    Style::default().fg(Color::Red),
+                ),
+                Span::styled(record.short_reason(), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+    }
+    let progress = app
+        .offline_jobs
+        .progress
+        .get(&job.id)
+        .copied()
+        .unwrap_or_default();
+    if progress.is_complete() {
+        lines.push(Line::from(Span::styled(
+            "Every track is cached — playable offline",
+            Style::default().fg(Color::Green),
+        )));
+    } else if progress.is_settled() {
+        lines.push(Line::from(Span::styled(
+            "Done except for failed tracks — [r] in the main view retries them",
+            Style::default().fg(Color::DarkGray),
+        )));
+    } else if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "Waiting for a free download slot",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn draw_hint_bar(frame: &mut Frame, area: Rect) {
+    let hints = vec![
+        Span::styled("[j/k]", Style::default().fg(Color::Yellow)),
+        Span::raw(" Move  "),
+        Span::styled("[d]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Remove (keeps files)  "),
+        Span::styled("[Esc]", Style::default().fg(Color::Red)),
+        Span::raw(" Close"),
+    ];
+    frame.render_widget(Paragraph::new(Line::from(hints)), area);
+}
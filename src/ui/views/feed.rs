@@ -25,6 +25,9 @@
 //! │ [j/k] Navigate  [h/l] Back  [Enter] Play  [a] Add track  [Esc/f] Close  │
 //! └─────────────────────────────────────────────────────────────────────────┘
 //! ```
+//!
+//! Albums (`OLAK5uy_*`) get their own detail pane — artist, year, length
+//! and the numbered track list once loaded — and numbered tracks.
 
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -35,7 +38,7 @@ use ratatui::{
 };
 
 use crate::config::format_time;
-use crate::ui::state::{FeedFocus, PlaylistType};
+use crate::ui::state::{FeedFocus, FeedPlaylist, PlaylistType};
 
 use super::super::app::MusicPlayerApp;
 
@@ -106,7 +109,12 @@ fn draw_status_bar(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
             format!("Track {sel} / {count}"),
             Style::default().fg(Color::Cyan),
         )
-    } else if app.feed.sections.is_empty() {
+    } else if app.is_offline() {
+        (
+            "⊘ Offline — showing the cached feed; ⊘ playlists need the network".to_string(),
+            Style::default().fg(Color::Yellow),
+        )
+    } else if !app.feed.has_remote_sections() {
         (
             "No feed loaded — press [r] to fetch".to_string(),
             Style::default().fg(Color::DarkGray),
@@ -209,62 +217,85 @@ fn draw_playlists(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
         .map(|s| s.title.as_str())
         .unwrap_or("Feed");
 
-    let items: Vec<ListItem> = match app.feed.sections.get(app.feed.selected_section) {
-        None => vec![
-            ListItem::new("  Press [r] to load feed")
-                .style(Style::default().fg(Color::DarkGray)),
-        ],
-        Some(section) if section.items.is_empty() => {
-            vec![ListItem::new("  (no items)").style(Style::default().fg(Color::DarkGray))]
-        }
-        Some(section) => {
-            let visible_h = area.height.saturating_sub(2) as usize;
-            let total = section.items.len();
-            let sel = app.feed.selected_item;
-            let start = if total <= visible_h {
-                0
-            } else {
-                let half = visible_h / 2;
-                sel.saturating_sub(half).min(total - visible_h)
-            };
-            let end = (start + visible_h).min(total);
-
-            section.items[start..end]
-                .iter()
-                .enumerate()
-                .map(|(i, playlist)| {
-                    let actual_idx = start + i;
-                    let imported = app.feed.imported_ids.contains(&playlist.id);
-                    let check = if imported { "✓ " } else { "  " };
-
-                    let count_str = if playlist.track_count_estimate > 0 {
-                        format!("  {:>3} trk", playlist.track_count_estimate)
-                    } else {
-                        "    — trk".to_string()
-                    };
-
-                    let max_chars = (area.width as usize).saturating_sub(16);
-                    let char_count = playlist.title.chars().count();
-                    let title = if char_count > max_chars {
-                        let t: String = playlist.title.chars().take(max_chars.saturating_sub(1)).collect();
-                        format!("{t}…")
-                    } else {
-                        playlist.title.clone()
-                    };
-
-                    let label = format!("{check}{title}{count_str}");
-                    let style = if actual_idx == sel {
-                        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-                    } else if imported {
-                        Style::default().fg(Color::Green)
-                    } else {
-                        Style::default().fg(Color::White)
-                    };
-                    ListItem::new(label).style(style)
-                })
-                .collect()
-        }
-    };
+    let items: Vec<ListItem> =
+        match app.feed.sections.get(app.feed.selected_section) {
+            None => vec![ListItem::new("  Press [r] to load feed")
+                .style(Style::default().fg(Color::DarkGray))],
+            Some(section) if section.items.is_empty() => {
+                vec![ListItem::new("  (no items)").style(Style::default().fg(Color::DarkGray))]
+            }
+            Some(section) => {
+                let visible_h = area.height.saturating_sub(2) as usize;
+                let total = section.items.len();
+                let sel = app.feed.selected_item;
+                let start = if total <= visible_h {
+                    0
+                } else {
+                    let half = visible_h / 2;
+                    sel.saturating_sub(half).min(total - visible_h)
+                };
+                let end = (start + visible_h).min(total);
+
+                section.items[start..end]
+                    .iter()
+                    .enumerate()
+                    .map(|(i, playlist)| {
+                        let actual_idx = start + i;
+                        let imported = app.feed.imported_ids.contains(&playlist.id);
+                        let downloaded = app.offline_jobs.is_complete(&playlist.id);
+                        // Only local favorites and downloaded playlists can be
+                        // opened offline.
+                        let unavailable = app.is_offline()
+                            && playlist.playlist_type != PlaylistType::Favorites
+                            && app.offline_jobs.find(&playlist.id).is_none();
+                        let check = if imported {
+                            "✓ "
+                        } else if downloaded {
+                            "⤓ "
+                        } else if unavailable {
+                            "⊘ "
+                        } else {
+                            "  "
+                        };
+
+                        let count_str = if playlist.track_count_estimate > 0 {
+                            format!("  {:>3} trk", playlist.track_count_estimate)
+                        } else {
+                            "    — trk".to_string()
+                        };
+
+                        let max_chars = (area.width as usize).saturating_sub(16);
+                        let char_count = playlist.title.chars().count();
+                        let title = if char_count > max_chars {
+                            let t: String = playlist
+                                .title
+                                .chars()
+                                .take(max_chars.saturating_sub(1))
+                                .collect();
+                            format!("{t}…")
+                        } else {
+                            playlist.title.clone()
+                        };
+
+                        let label = format!("{check}{title}{count_str}");
+                        let style = if actual_idx == sel {
+                            Style::default()
+                                .fg(Color::Yellow)
+                                .add_modifier(Modifier::BOLD)
+                        } else if imported {
+                            Style::default().fg(Color::Green)
+                        } else if downloaded {
+                            Style::default().fg(Color::Cyan)
+                        } else if unavailable {
+                            Style::default().fg(Color::DarkGray)
+                        } else {
+                            Style::default().fg(Color::White)
+                        };
+                        ListItem::new(label).style(style)
+                    })
+                    .collect()
+            }
+        };
 
     let border_style = if app.feed.focus == FeedFocus::Playlists {
         Style::default().fg(Color::Cyan)
@@ -291,6 +322,7 @@ fn draw_tracks(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
 
     let tracks = &app.feed.expanded_tracks;
     let sel = app.feed.selected_track;
+    let album = app.feed_selected_album();
 
     let items: Vec<ListItem> = if tracks.is_empty() {
         vec![ListItem::new("  (no tracks)").style(Style::default().fg(Color::DarkGray))]
@@ -312,7 +344,13 @@ fn draw_tracks(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
                 let actual_idx = start + i;
                 let dur = format_time(track.duration as f64);
 
-                let max_chars = (area.width as usize).saturating_sub(10);
+                // Album tracks are numbered in release order.
+                let number = match album {
+                    Some(_) => format!("{:>2}. ", actual_idx + 1),
+                    None => String::new(),
+                };
+
+                let max_chars = (area.width as usize).saturating_sub(10 + number.len());
                 let char_count = track.title.chars().count();
                 let title = if char_count > max_chars {
                     let t: String = track.title.chars().take(max_chars.saturating_sub(1)).collect();
@@ -321,9 +359,13 @@ fn draw_tracks(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
                     track.title.clone()
                 };
 
-                let label = format!("  {title}  {dur}");
+                let unavailable = app.is_offline() && !app.downloads.is_cached(&track.video_id);
+                let mark = if unavailable { "⊘" } else { " " };
+                let label = format!("{mark} {number}{title}  {dur}");
                 let style = if actual_idx == sel {
                     Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                } else if unavailable {
+                    Style::default().fg(Color::DarkGray)
                 } else {
                     Style::default().fg(Color::White)
                 };
@@ -332,9 +374,18 @@ fn draw_tracks(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
             .collect()
     };
 
+    let title = match album {
+        Some(album) => format!(
+            " {} — {} tracks, {} ",
+            playlist_title,
+            tracks.len(),
+            format_time(album.total_duration() as f64)
+        ),
+        None => format!(" {} — {} tracks ", playlist_title, tracks.len()),
+    };
     let block = Block::default()
         .borders(Borders::ALL)
-        .title(format!(" {} — {} tracks ", playlist_title, tracks.len()))
+        .title(title)
         .border_style(Style::default().fg(Color::Yellow)); // yellow = track focus active
     frame.render_widget(List::new(items).block(block), area);
 }
@@ -358,15 +409,23 @@ fn draw_playlist_detail(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
         frame.render_widget(empty, inner);
         return;
     };
+    let inner = super::cover::draw_cover_above(app, frame, inner, &playlist.id);
+
+    if playlist.playlist_type == PlaylistType::Album {
+        draw_album_detail(app, frame, inner, playlist);
+        return;
+    }
 
     let imported = app.feed.imported_ids.contains(&playlist.id);
 
     let type_color = match playlist.playlist_type {
         PlaylistType::Mix => Color::Magenta,
         PlaylistType::Recommended => Color::Cyan,
+        PlaylistType::Album => Color::LightMagenta,
         PlaylistType::ListenAgain => Color::Blue,
         PlaylistType::LibrarySaved => Color::Green,
         PlaylistType::LibraryLiked => Color::Red,
+        PlaylistType::Favorites => Color::Yellow,
         PlaylistType::Unknown => Color::DarkGray,
     };
 
@@ -429,6 +488,104 @@ fn draw_playlist_detail(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
     );
 }
 
+/// Album metadata and the numbered track list, once the album is loaded.
+fn draw_album_detail(app: &MusicPlayerApp, frame: &mut Frame, area: Rect, playlist: &FeedPlaylist) {
+    let dim = Style::default().fg(Color::DarkGray);
+    let white = Style::default().fg(Color::White);
+    let album = app.feed.albums.get(&playlist.id);
+
+    let mut lines: Vec<Line> = vec![
+        Line::from(Span::styled(
+            album
+                .map_or(playlist.title.as_str(), |a| a.title.as_str())
+                .to_string(),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+
+    let hints = [
+        "[Enter] Expand tracks",
+        "[p]     Play album",
+        "[a]     Add all to queue",
+    ];
+
+    match album {
+        Some(album) => {
+            lines.push(Line::from(vec![
+                Span::styled("Artist: ", dim),
+                Span::styled(album.artist.clone(), white),
+            ]));
+            if let Some(year) = album.year {
+                lines.push(Line::from(vec![
+                    Span::styled("Year:   ", dim),
+                    Span::styled(year.to_string(), white),
+                ]));
+            }
+            lines.push(Line::from(vec![
+                Span::styled("Tracks: ", dim),
+                Span::styled(
+                    format!(
+                        "{} · {}",
+                        album.tracks.len(),
+                        format_time(album.total_duration() as f64)
+                    ),
+                    white,
+                ),
+            ]));
+            lines.push(Line::from(""));
+
+            // Leave room for the hints below the track list.
+            let room = (area.height as usize).saturating_sub(lines.len() + hints.len() + 1);
+            let width = area.width as usize;
+            for (i, track) in album.tracks.iter().enumerate().take(room) {
+                let dur = format_time(track.duration as f64);
+                let number = format!("{:>2}. ", i + 1);
+                let max_chars = width.saturating_sub(number.len() + dur.len() + 1);
+                let title: String = track.title.chars().take(max_chars).collect();
+                let pad = width.saturating_sub(number.len() + title.chars().count() + dur.len());
+                lines.push(Line::from(vec![
+                    Span::styled(number, dim),
+                    Span::styled(title, white),
+                    Span::raw(" ".repeat(pad)),
+                    Span::styled(dur, dim),
+                ]));
+            }
+            if album.tracks.len() > room {
+                lines.push(Line::from(Span::styled(
+                    format!("    … {} more", album.tracks.len() - room),
+                    dim,
+                )));
+            }
+        }
+        None => {
+            lines.push(Line::from(vec![
+                Span::styled("Type:   ", dim),
+                Span::styled(
+                    "Album",
+                    Style::default()
+                        .fg(Color::LightMagenta)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]));
+            if let Some(ref desc) = playlist.description {
+                lines.push(Line::from(Span::styled(desc.clone(), dim)));
+            }
+            lines.push(Line::from(Span::styled("Track list not loaded yet", dim)));
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.extend(
+        hints
+            .iter()
+            .map(|hint| Line::from(Span::styled(*hint, dim))),
+    );
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
 // ---------------------------------------------------------------------------
 // Right column — track detail
 // ---------------------------------------------------------------------------
@@ -451,8 +608,9 @@ fn draw_track_detail(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
         return;
     };
 
+    let inner = super::cover::draw_cover_above(app, frame, inner, &track.video_id);
     let dur = format_time(track.duration as f64);
-    let lines = vec![
+    let mut lines = vec![
         Line::from(Span::styled(
             track.title.clone(),
             Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
@@ -466,6 +624,24 @@ fn draw_track_detail(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
             Span::styled("Duration: ", Style::default().fg(Color::DarkGray)),
             Span::styled(dur, Style::default().fg(Color::White)),
         ]),
+    ];
+    if let Some(album) = app.album_of.get(&track.video_id) {
+        lines.push(Line::from(vec![
+            Span::styled("Album:    ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format!("{} (track {})", album.title, album.track_no),
+                Style::default().fg(Color::White),
+            ),
+        ]));
+    }
+    if app.feed_selected_album().is_some() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "[p]     Play album from here",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    lines.extend([
         Line::from(""),
         Line::from(Span::styled(
             "[Enter] Play now",
@@ -479,7 +655,7 @@ fn draw_track_detail(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
             "[h/l]   Back to playlists",
             Style::default().fg(Color::DarkGray),
         )),
-    ];
+    ]);
 
     frame.render_widget(
         Paragraph::new(lines).wrap(Wrap { trim: true }),
@@ -492,7 +668,10 @@ fn draw_track_detail(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
 // ---------------------------------------------------------------------------
 
 fn draw_hint_bar(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
-    let hints = match app.feed.focus {
+    let on_album = app
+        .feed_selected_item_ref()
+        .is_some_and(|p| p.playlist_type == PlaylistType::Album);
+    let mut hints = match app.feed.focus {
         FeedFocus::Sections => vec![
             Span::styled("[j/k]", Style::default().fg(Color::Yellow)),
             Span::raw(" Sections  "),
@@ -512,6 +691,8 @@ fn draw_hint_bar(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
             Span::raw(" Expand  "),
             Span::styled("[a]", Style::default().fg(Color::Green)),
             Span::raw(" Add all  "),
+            Span::styled("[o]", Style::default().fg(Color::Cyan)),
+            Span::raw(" Offline  "),
             Span::styled("[r]", Style::default().fg(Color::Cyan)),
             Span::raw(" Refresh  "),
             Span::styled("[Esc/f]", Style::default().fg(Color::Red)),
@@ -524,12 +705,24 @@ fn draw_hint_bar(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
             Span::raw(" Play  "),
             Span::styled("[a]", Style::default().fg(Color::Green)),
             Span::raw(" Add  "),
+            Span::styled("[A]", Style::default().fg(Color::Green)),
+            Span::raw(" Save to playlist  "),
             Span::styled("[h]", Style::default().fg(Color::Cyan)),
             Span::raw(" Back  "),
             Span::styled("[Esc/f]", Style::default().fg(Color::Red)),
             Span::raw(" Close"),
         ],
     };
+    let add_at = hints.iter().position(|span| span.content == "[a]");
+    if let Some(at) = add_at.filter(|_| on_album) {
+        hints.splice(
+            at..at,
+            [
+                Span::styled("[p]", Style::default().fg(Color::Green)),
+                Span::raw(" Play album  "),
+            ],
+        );
+    }
 
     frame.render_widget(Paragraph::new(Line::from(hints)), area);
 }
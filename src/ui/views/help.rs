@@ -36,16 +36,22 @@ pub(crate) fn draw_help_screen(_app: &MusicPlayerApp, frame: &mut Frame) {
         bind("→ / ←",   "Seek forward / backward 10 s"),
         blank(),
         section("NAVIGATION"),
-        bind("j / k",   "Navigate lists down / up"),
-        bind("/",       "Search for music"),
-        bind("l",       "Load playlist from URL"),
-        bind("h",       "Go to Home view"),
-        bind("Esc",     "Return to previous view"),
+        bind("j / k", "Navigate lists down / up"),
+        bind("/", "Search for music"),
+        bind("Tab", "Search scope: songs/videos/albums/…"),
+        bind("+", "Load more search results"),
+        bind("↑/↓ →", "While typing: pick a suggestion / complete"),
+        bind("l", "Load playlist from URL"),
+        bind("e", "Open the artist page of the selected track"),
+        bind("h", "Go to Home view"),
+        bind("Esc", "Return to previous view"),
         blank(),
         section("QUEUE"),
-        bind("Enter",   "Add selected item to queue"),
-        bind("t",       "Toggle queue expand"),
-        bind("d",       "Delete selected item (queue expanded)"),
+        bind("Enter", "Add selected item to queue"),
+        bind("t", "Toggle queue expand"),
+        bind("d", "Delete selected item (queue expanded)"),
+        bind("x", "Export queue (history if expanded)"),
+        bind("Shift+X", "Copy its cached audio to a folder"),
         blank(),
         section("MY MIX"),
         bind("m",       "Toggle My Mix expand"),
@@ -54,6 +60,22 @@ pub(crate) fn draw_help_screen(_app: &MusicPlayerApp, frame: &mut Frame) {
         section("HISTORY"),
         bind("Shift+H", "Toggle history expand"),
         bind("Shift+C", "Clear history (when expanded)"),
+        blank(),
+        section("STATS"),
+        bind("s", "Toggle listening stats view"),
+        bind("w", "Cycle window (7d / 30d / all)"),
+        blank(),
+        section("LYRICS"),
+        bind("y", "Toggle lyrics of the playing track"),
+        bind("j/k", "Scroll lyrics that aren't synced"),
+        blank(),
+        section("RATINGS"),
+        bind("*", "Toggle favorite (selected / playing track)"),
+        bind("1-5 / 0", "Rate 1–5 stars / clear rating"),
+        blank(),
+        section("TAGS"),
+        bind("g", "Edit tags of selected / playing track"),
+        bind("Shift+G", "Tag cloud: Space mark, m any/all, Enter queue"),
     ];
 
     // -----------------------------------------------------------------------
@@ -63,22 +85,52 @@ pub(crate) fn draw_help_screen(_app: &MusicPlayerApp, frame: &mut Frame) {
         section("FEED BROWSER"),
         bind("f",       "Open YouTube Music Feed Browser"),
         bind("Esc / f", "Close Feed Browser"),
-        bind("j / k",   "Navigate items down / up"),
-        bind("h / l",   "Switch sections left / right"),
-        bind("r",       "Force-refresh feed (bypasses cache)"),
-        bind("Enter",   "Play selected playlist now"),
-        bind("a",       "Add selected playlist to queue"),
+        bind("j / k", "Navigate items down / up"),
+        bind("h / l", "Switch sections left / right"),
+        bind("r", "Force-refresh feed (bypasses cache)"),
+        bind("Enter", "Play selected playlist now"),
+        bind("a", "Add selected playlist to queue"),
+        bind("p", "Play album in order, without gaps"),
+        bind("o", "Make selected playlist available offline"),
+        bind("Shift+A", "Pick a local playlist to add track to"),
+        blank(),
+        section("ARTIST PAGE"),
+        bind("e / Esc", "Close the artist page"),
+        bind("h / l", "Switch columns left / right"),
+        bind("Enter", "Play from track / open release"),
+        bind("a", "Add track or release to queue"),
+        bind("r", "Refresh the artist page"),
+        blank(),
+        section("PLAYLISTS"),
+        bind("Shift+P", "Open local playlists"),
+        bind("Shift+A", "Pick playlist: Enter adds, d removes"),
+        bind("n / r / d", "New / rename / delete"),
+        bind("J / K", "Move track down / up"),
+        bind("Enter / a", "Play / enqueue playlist"),
+        bind("x / i", "Export / import (M3U8, XSPF, JSON)"),
+        bind("Shift+X", "Copy playlist audio to a folder"),
+        blank(),
+        section("CACHE"),
+        bind("c", "Cache view: v verify, r re-download, d delete"),
+        bind("x", "Cache view: copy file to a folder"),
+        bind("b", "Pin / unpin in offline cache"),
+        bind("r", "Retry failed downloads now"),
+        bind("u", "Cancel download of selected track"),
+        bind("Shift+N", "Toggle offline mode (cached tracks only)"),
+        bind("Shift+L", "Make loaded playlist available offline"),
+        bind("v", "Downloads panel (offline playlists)"),
         blank(),
         section("FILTER"),
-        bind("Shift+F", "Toggle music-only mode (>7 min filtered)"),
+        bind("Shift+F", "Cycle filter profile (Music/Studio/All)"),
         blank(),
         section("ACCOUNT"),
         bind("l",       "Select account (login screen)"),
         bind("o",       "Switch account / log out (any time)"),
         blank(),
         section("OTHER"),
-        bind("?",       "Show this help screen"),
-        bind("q",       "Quit"),
+        bind("?", "Show this help screen"),
+        bind("!", "Diagnostics: yt-dlp, ffmpeg, audio, cookies"),
+        bind("q", "Quit"),
         blank(),
         blank(),
         footer(),
@@ -5,23 +5,68 @@ use ratatui::{
     Frame,
 };
 
+use crate::player::queue::Track;
+use crate::services::album::{album_runs, AlbumRef};
+
 use super::super::app::MusicPlayerApp;
 
+/// A line of the history list, most recent first. Consecutive tracks from
+/// the same album sit under a heading for it.
+enum Row<'a> {
+    Album(&'a AlbumRef, usize),
+    /// Position in the most-recent-first history, the track, and whether
+    /// it's under an album heading.
+    Track(usize, &'a Track, bool),
+}
+
+fn history_rows(app: &MusicPlayerApp) -> Vec<Row<'_>> {
+    let tracks: Vec<&Track> = app.queue.get_history().iter().rev().collect();
+    let runs = album_runs(tracks.iter().map(|t| t.video_id.as_str()), &app.album_of);
+    let mut rows = Vec::with_capacity(tracks.len());
+    let mut i = 0;
+    for (album, len) in runs {
+        if let Some(album) = album {
+            rows.push(Row::Album(album, len));
+        }
+        for track in &tracks[i..i + len] {
+            rows.push(Row::Track(i, track, album.is_some()));
+            i += 1;
+        }
+    }
+    rows
+}
+
+fn album_heading(album: &AlbumRef, len: usize) -> ListItem<'static> {
+    ListItem::new(format!("💿 {} · {} tracks", album.heading(), len))
+        .style(Style::default().fg(Color::LightMagenta))
+}
+
+/// "⊘ " for a track that can't be played because it's offline and not cached.
+fn unavailable_mark(app: &MusicPlayerApp, track: &Track) -> &'static str {
+    if app.is_offline() && !app.downloads.is_cached(&track.video_id) {
+        "⊘ "
+    } else {
+        ""
+    }
+}
+
 pub(crate) fn draw_history(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
     let queue_history = app.queue.get_history();
     let total_history = queue_history.len();
 
     // Calculate how many items fit in the visible area (fill the whole box like queue!)
     let visible_height = area.height.saturating_sub(2) as usize; // Subtract borders
-    let max_items = visible_height.min(total_history);
 
-    let history_items: Vec<ListItem> = queue_history
-        .iter()
-        .rev() // Show most recent first
-        .take(max_items) // Fill the box!
-        .map(|track| {
-            let content = track.title.clone();
-            ListItem::new(content).style(Style::default().fg(Color::DarkGray))
+    let history_items: Vec<ListItem> = history_rows(app)
+        .into_iter() // Most recent first
+        .take(visible_height) // Fill the box!
+        .map(|row| match row {
+            Row::Album(album, len) => album_heading(album, len),
+            Row::Track(_, track, grouped) => {
+                let indent = if grouped { "  " } else { "" };
+                let content = format!("{}{}{}", indent, unavailable_mark(app, track), track.title);
+                ListItem::new(content).style(Style::default().fg(Color::DarkGray))
+            }
         })
         .collect();
 
@@ -34,38 +79,58 @@ pub(crate) fn draw_history(app: &MusicPlayerApp, frame: &mut Frame, area: Rect)
 }
 
 pub(crate) fn draw_history_expanded(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
-    let queue_history = app.queue.get_history();
-    let total_history = queue_history.len();
+    let total_history = app.queue.get_history().len();
+    let rows = history_rows(app);
+    let total_rows = rows.len();
 
-    // Calculate visible window
+    // Calculate visible window, in rows (album headings take one too)
     let visible_height = area.height.saturating_sub(2) as usize;
     let half_window = visible_height / 2;
+    let selected_row = rows
+        .iter()
+        .position(|row| matches!(row, Row::Track(i, ..) if *i == app.ui.selected_history_item))
+        .unwrap_or(0);
 
-    let (start_idx, end_idx) = if total_history <= visible_height {
-        (0, total_history)
+    let (start_row, end_row) = if total_rows <= visible_height {
+        (0, total_rows)
     } else {
-        let start = app.ui.selected_history_item.saturating_sub(half_window);
-        let end = (start + visible_height).min(total_history);
-        if end == total_history && total_history > visible_height {
-            (total_history - visible_height, total_history)
+        let start = selected_row.saturating_sub(half_window);
+        let end = (start + visible_height).min(total_rows);
+        if end == total_rows && total_rows > visible_height {
+            (total_rows - visible_height, total_rows)
         } else {
             (start, end)
         }
     };
+    let shown = &rows[start_row..end_row];
+    let track_index = |row: &Row| match row {
+        Row::Track(i, ..) => Some(*i),
+        Row::Album(..) => None,
+    };
+    let start_idx = shown.iter().find_map(track_index).unwrap_or(0);
+    let end_idx = shown
+        .iter()
+        .rev()
+        .find_map(track_index)
+        .map_or(0, |i| i + 1);
 
     // Only render visible window
-    let history_items: Vec<ListItem> = queue_history
+    let history_items: Vec<ListItem> = shown
         .iter()
-        .rev()
-        .enumerate()
-        .skip(start_idx)
-        .take(end_idx - start_idx)
-        .map(|(i, track)| {
-            let content = format!("{}. {}", i + 1, &track.title);
+        .map(|row| {
+            let (i, track, grouped) = match *row {
+                Row::Album(album, len) => return album_heading(album, len),
+                Row::Track(i, track, grouped) => (i, track, grouped),
+            };
+            let mark = unavailable_mark(app, track);
+            let indent = if grouped { "  " } else { "" };
+            let content = format!("{}{}. {}{}", indent, i + 1, mark, &track.title);
             let style = if i == app.ui.selected_history_item {
                 Style::default()
                     .fg(Color::Cyan)
                     .add_modifier(Modifier::BOLD)
+            } else if !mark.is_empty() {
+                Style::default().fg(Color::DarkGray)
             } else {
                 Style::default().fg(Color::White)
             };
@@ -0,0 +1,307 @@
+//! Local playlists view — the user's own playlists and their tracks.
+//!
+//! ```text
+//! ┌─ Playlists ─────────────────────────────────────────────────────────────┐
+//! │ status bar                                                              │
+//! ├──────────────────────────┬──────────────────────────────────────────────┤
+//! │  Playlists               │  Road trip — 12 tracks · 48:10               │
+//! │  > Road trip   12        │  1. Song A                            2:56   │
+//! │    Focus        4        │  2. Song B                            3:12   │
+//! ├──────────────────────────┴──────────────────────────────────────────────┤
+//! │ [j/k] Navigate  [l] Tracks  [Enter] Play  [a] Enqueue  [n] New  [r] Rename│
+//! └─────────────────────────────────────────────────────────────────────────┘
+//! ```
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    Frame,
+};
+
+use crate::config::{clean_title, format_time};
+use crate::ui::state::{PlaylistPickerState, PlaylistsFocus};
+
+use super::super::app::MusicPlayerApp;
+
+pub(crate) fn draw(app: &MusicPlayerApp, frame: &mut Frame) {
+    let area = frame.area();
+
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .title(" Playlists ")
+        .style(Style::default().fg(Color::Cyan));
+    let inner = outer.inner(area);
+    frame.render_widget(outer, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // status bar
+            Constraint::Min(5),    // body
+            Constraint::Length(1), // hint bar
+        ])
+        .split(inner);
+
+    draw_status_bar(app, frame, rows[0]);
+
+    let cols = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .split(rows[1]);
+    draw_playlists(app, frame, cols[0]);
+    draw_tracks(app, frame, cols[1]);
+
+    draw_hint_bar(app, frame, rows[2]);
+}
+
+fn draw_status_bar(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let (text, style) = if !app.status_message.is_empty() {
+        let style = if app.status_message.starts_with('✓') {
+            Style::default().fg(Color::Green)
+        } else if app.status_message.contains("not saved") || app.status_message.starts_with('⚠')
+        {
+            Style::default().fg(Color::Red)
+        } else {
+            Style::default().fg(Color::Yellow)
+        };
+        (app.status_message.clone(), style)
+    } else {
+        let state = &app.local_playlists;
+        (
+            format!(
+                "{} playlists — Shift+A in search, queue, history, or feed picks one to add a track to",
+                state.store.len()
+            ),
+            Style::default().fg(Color::DarkGray),
+        )
+    };
+    frame.render_widget(Paragraph::new(text).style(style), area);
+}
+
+/// First index to render so that `selected` stays roughly centred.
+fn scroll_start(selected: usize, total: usize, visible: usize) -> usize {
+    if total <= visible {
+        0
+    } else {
+        selected.saturating_sub(visible / 2).min(total - visible)
+    }
+}
+
+fn focus_border(focused: bool) -> Style {
+    if focused {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    }
+}
+
+fn draw_playlists(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let state = &app.local_playlists;
+    let playlists = &state.store.playlists;
+
+    let items: Vec<ListItem> = if playlists.is_empty() {
+        vec![ListItem::new("  (none — press n)").style(Style::default().fg(Color::DarkGray))]
+    } else {
+        let visible = area.height.saturating_sub(2) as usize;
+        let start = scroll_start(state.selected, playlists.len(), visible);
+        playlists
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(visible)
+            .map(|(i, playlist)| {
+                let marker = if i == state.selected { ">" } else { " " };
+                let style = if i == state.selected {
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{marker} {}", playlist.name), style),
+                    Span::styled(
+                        format!("  {}", playlist.tracks.len()),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]))
+            })
+            .collect()
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Playlists ")
+        .border_style(focus_border(state.focus == PlaylistsFocus::Lists));
+    frame.render_widget(List::new(items).block(block), area);
+}
+
+fn draw_tracks(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let state = &app.local_playlists;
+    let Some(playlist) = state.selected_playlist() else {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Tracks ")
+            .border_style(focus_border(false));
+        frame.render_widget(block, area);
+        return;
+    };
+
+    let tracks = &playlist.tracks;
+    let items: Vec<ListItem> = if tracks.is_empty() {
+        vec![ListItem::new("  (empty — Shift+A on a track to add it)")
+            .style(Style::default().fg(Color::DarkGray))]
+    } else {
+        let visible = area.height.saturating_sub(2) as usize;
+        let start = scroll_start(state.selected_track, tracks.len(), visible);
+        let highlight = state.focus == PlaylistsFocus::Tracks;
+        tracks
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(visible)
+            .map(|(i, track)| {
+                let style = if highlight && i == state.selected_track {
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{:>3}. {}", i + 1, track.title), style),
+                    Span::styled(
+                        format!(
+                            "  {}  {}",
+                            track.uploader,
+                            format_time(track.duration as f64)
+                        ),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]))
+            })
+            .collect()
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            " {} — {} tracks · {} ",
+            playlist.name,
+            tracks.len(),
+            format_time(playlist.total_duration() as f64)
+        ))
+        .border_style(focus_border(state.focus == PlaylistsFocus::Tracks));
+    frame.render_widget(List::new(items).block(block), area);
+}
+
+/// Shift+A's popup, centred over the screen it was opened from: the
+/// playlists, ✓ on those that already have the track.
+pub(crate) fn draw_picker(app: &MusicPlayerApp, picker: &PlaylistPickerState, frame: &mut Frame) {
+    let playlists = &app.local_playlists.store.playlists;
+    let screen = frame.area();
+    let width = screen.width.saturating_sub(4).min(60);
+    let height = (playlists.len() as u16 + 3).clamp(5, 16).min(screen.height);
+    let area = Rect {
+        x: screen.x + (screen.width.saturating_sub(width)) / 2,
+        y: screen.y + (screen.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            " Playlist for '{}' ",
+            clean_title(&picker.track.title)
+        ))
+        .border_style(Style::default().fg(Color::Yellow));
+    let inner = block.inner(area);
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+    let visible = rows[0].height as usize;
+    let start = scroll_start(picker.selected, playlists.len(), visible);
+    let items: Vec<ListItem> = playlists
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(visible)
+        .map(|(i, playlist)| {
+            let marker = if i == picker.selected { ">" } else { " " };
+            let style = if i == picker.selected {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let has = if playlist.contains(&picker.track.video_id) {
+                "✓"
+            } else {
+                " "
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{marker} {has} {}", playlist.name), style),
+                Span::styled(
+                    format!("  {}", playlist.tracks.len()),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        })
+        .collect();
+    frame.render_widget(List::new(items), rows[0]);
+    frame.render_widget(
+        Paragraph::new("Enter: add  d: remove  Esc: cancel")
+            .style(Style::default().fg(Color::DarkGray)),
+        rows[1],
+    );
+}
+
+fn draw_hint_bar(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let hints = match app.local_playlists.focus {
+        PlaylistsFocus::Lists => vec![
+            Span::styled("[j/k]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Navigate  "),
+            Span::styled("[l]", Style::default().fg(Color::Cyan)),
+            Span::raw(" Tracks  "),
+            Span::styled("[Enter]", Style::default().fg(Color::Green)),
+            Span::raw(" Play  "),
+            Span::styled("[a]", Style::default().fg(Color::Green)),
+            Span::raw(" Enqueue  "),
+            Span::styled("[n]", Style::default().fg(Color::Cyan)),
+            Span::raw(" New  "),
+            Span::styled("[r]", Style::default().fg(Color::Cyan)),
+            Span::raw(" Rename  "),
+            Span::styled("[d]", Style::default().fg(Color::Red)),
+            Span::raw(" Delete  "),
+            Span::styled("[x/i]", Style::default().fg(Color::Cyan)),
+            Span::raw(" Export/Import  "),
+            Span::styled("[X]", Style::default().fg(Color::Cyan)),
+            Span::raw(" Copy audio  "),
+            Span::styled("[Esc]", Style::default().fg(Color::Red)),
+            Span::raw(" Close"),
+        ],
+        PlaylistsFocus::Tracks => vec![
+            Span::styled("[j/k]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Tracks  "),
+            Span::styled("[J/K]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Move  "),
+            Span::styled("[Enter]", Style::default().fg(Color::Green)),
+            Span::raw(" Play from here  "),
+            Span::styled("[d]", Style::default().fg(Color::Red)),
+            Span::raw(" Remove  "),
+            Span::styled("[h]", Style::default().fg(Color::Cyan)),
+            Span::raw(" Back  "),
+            Span::styled("[Esc]", Style::default().fg(Color::Red)),
+            Span::raw(" Close"),
+        ],
+    };
+
+    frame.render_widget(Paragraph::new(Line::from(hints)), area);
+}
@@ -13,7 +13,7 @@ pub(crate) fn draw_login_screen(app: &MusicPlayerApp, frame: &mut Frame) {
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Percentage(35),
-            Constraint::Length(12),
+            Constraint::Length(15),
             Constraint::Percentage(35),
         ])
         .split(frame.area());
@@ -34,6 +34,7 @@ pub(crate) fn draw_login_screen(app: &MusicPlayerApp, frame: &mut Frame) {
          Chrome, Chromium, Firefox, or Zen Browser first.\n\
          \n\
          [l]  Select browser account\n\
+         [!]  Check dependencies\n\
          [q]  Quit\n\
          \n\
          {status}"
@@ -0,0 +1,99 @@
+//! Lyrics view (`y`) — the playing track's lyrics.
+//!
+//! ```text
+//! ┌─ Lyrics — Blinding Lights ──────────────────────────────────────────────┐
+//! │                    I said, ooh, I'm blinded by the lights               │
+//! │               No, I can't sleep until I feel your touch                 │
+//! │                    I said, ooh, I'm drowning in the night               │
+//! └─ YouTube captions · synced ───────────────────────── y: close ──────────┘
+//! ```
+//!
+//! Synced lyrics keep the line being sung highlighted in the middle;
+//! unsynced lyrics start at the top and scroll with j/k.
+
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::config::clean_title;
+
+use super::super::app::MusicPlayerApp;
+
+const SPINNER: [&str; 4] = ["⠋", "⠙", "⠹", "⠸"];
+
+pub(crate) fn draw_lyrics(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let title = match app.queue.get_current() {
+        Some(track) => format!(" Lyrics — {} ", clean_title(&track.title)),
+        None => " Lyrics ".to_string(),
+    };
+    let state = &app.lyrics;
+    let footer = match &state.lyrics {
+        Some(lyrics) if !lyrics.is_empty() && lyrics.synced => {
+            format!(" {} · synced ", lyrics.source)
+        }
+        Some(lyrics) if !lyrics.is_empty() => format!(" {} · j/k: scroll ", lyrics.source),
+        _ => String::new(),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .title_bottom(Line::from(footer).left_aligned())
+        .title_bottom(Line::from(" y: close ").right_aligned())
+        .border_style(Style::default().fg(Color::Cyan));
+    let visible = area.height.saturating_sub(2) as usize;
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let message = |text: String, style: Style| {
+        Paragraph::new(vec![Line::from(""), Line::styled(text, style)]).alignment(Alignment::Center)
+    };
+    let body = if app.queue.get_current().is_none() {
+        message("Nothing playing".to_string(), dim)
+    } else if state.loading {
+        let spinner = SPINNER[(app.ui.animation_frame as usize) % SPINNER.len()];
+        message(
+            format!("{spinner} Looking for lyrics…"),
+            Style::default().fg(Color::Yellow),
+        )
+    } else if let Some(err) = &state.error {
+        message(format!("⚠  {err}"), Style::default().fg(Color::Red))
+    } else {
+        match &state.lyrics {
+            Some(lyrics) if !lyrics.is_empty() => {
+                let current = lyrics.line_at(app.player.get_time_pos());
+                let start = match current {
+                    Some(line) => line.saturating_sub(visible / 2),
+                    None if lyrics.synced => 0,
+                    None => state.scroll,
+                };
+                let lines: Vec<Line> = lyrics
+                    .lines
+                    .iter()
+                    .enumerate()
+                    .skip(start)
+                    .take(visible)
+                    .map(|(i, line)| {
+                        let style = match current {
+                            Some(c) if c == i => Style::default()
+                                .fg(Color::Yellow)
+                                .add_modifier(Modifier::BOLD),
+                            Some(c) if i < c => dim,
+                            _ => Style::default().fg(Color::White),
+                        };
+                        Line::styled(line.text.clone(), style)
+                    })
+                    .collect();
+                Paragraph::new(lines).alignment(Alignment::Center)
+            }
+            Some(_) => message(
+                "No lyrics or captions found for this track".to_string(),
+                dim,
+            ),
+            None => message(String::new(), dim),
+        }
+    };
+    frame.render_widget(body.block(block), area);
+}
@@ -1,9 +1,18 @@
+pub(crate) mod artist;
 pub(crate) mod cache_stats;
+pub(crate) mod cover;
+pub(crate) mod diagnostics;
+pub(crate) mod downloads;
 pub(crate) mod feed;
 pub(crate) mod help;
 pub(crate) mod history;
+pub(crate) mod local_playlists;
 pub(crate) mod login;
+pub(crate) mod lyrics;
 pub(crate) mod player_bar;
 pub(crate) mod playlist;
+pub(crate) mod prompt;
 pub(crate) mod queue;
 pub(crate) mod search;
+pub(crate) mod stats;
+pub(crate) mod tags;
@@ -1,12 +1,14 @@
 use ratatui::{
     layout::Rect,
     style::{Color, Style},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Padding, Paragraph},
     Frame,
 };
 
 use crate::config::{clean_title, format_time};
 use crate::player::audio::PlayerState;
+use crate::services::audio_cache::format_bytes;
+use crate::services::download::DownloadProgress;
 
 use super::super::app::MusicPlayerApp;
 
@@ -23,6 +25,25 @@ pub(crate) fn get_download_animation(animation_frame: u8) -> &'static str {
     }
 }
 
+/// Player line shown while the track waiting to play downloads:
+/// `⬇ [██████░░░░░░] 45% · 1.2 MB/s · ETA 0:12`.
+fn download_progress_line(progress: Option<DownloadProgress>) -> String {
+    match progress {
+        None => "⬇ Starting download…".to_string(),
+        Some(p) if p.is_converting() => "⬇ Download complete — converting audio…".to_string(),
+        Some(p) => {
+            let mut line = format!("⬇ [{}] {:>3.0}%", p.bar(24), p.percent);
+            if let Some(speed) = p.speed {
+                line.push_str(&format!(" · {}/s", format_bytes(speed as u64)));
+            }
+            if let Some(eta) = p.eta_secs {
+                line.push_str(&format!(" · ETA {}", format_time(eta as f64)));
+            }
+            line
+        }
+    }
+}
+
 pub(crate) fn draw_player_compact(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
     // Single Player box with 3 lines of content inside
     let current_track = app.queue.get_current();
@@ -79,8 +100,19 @@ pub(crate) fn draw_player_compact(app: &MusicPlayerApp, frame: &mut Frame, area:
         0.0
     };
 
-    // Bouncy bars + timer progress bar
-    let progress_bar = if app.player.get_state() == PlayerState::Playing {
+    // Bouncy bars + timer progress bar, or the download of the track
+    // waiting to play
+    let progress_bar = if let Some(pending) = &app.pending_play_track {
+        match app.downloads.failure_for(&pending.video_id) {
+            Some(record) if !app.downloads.is_downloading(&pending.video_id) => {
+                format!(
+                    "⬇ Download failed — {}",
+                    record.label(std::time::Instant::now())
+                )
+            }
+            _ => download_progress_line(app.downloads.progress_for(&pending.video_id)),
+        }
+    } else if app.player.get_state() == PlayerState::Playing {
         // Bouncing bars animation
         let anim_frame = (app.ui.animation_frame / 4) % 8;
         let bars = match anim_frame {
@@ -124,14 +156,22 @@ pub(crate) fn draw_player_compact(app: &MusicPlayerApp, frame: &mut Frame, area:
     };
 
     let volume = app.player.get_volume();
-    let mode_str = if app.ui.music_only_mode {
-        "Music"
-    } else {
-        "All"
+    let filter = app.filters.active();
+    let mode_str = filter.name.as_str();
+    // Local favorite / rating of the current track, if any.
+    let rating = current_track
+        .map(|t| app.rating_for(&t.video_id))
+        .unwrap_or_default();
+    let rating_str = match (rating.favorite, rating.stars_label()) {
+        (false, stars) if stars.is_empty() => String::new(),
+        (true, stars) if stars.is_empty() => " | ♥".to_string(),
+        (true, stars) => format!(" | ♥ {}", stars),
+        (false, stars) => format!(" | {}", stars),
     };
     let status_line = format!(
-        "{} | Vol: {}% | Queue: {} tracks | [{}]",
+        "{}{} | Vol: {}% | Queue: {} tracks | [{}]",
         state_str,
+        rating_str,
         volume,
         app.queue.len(),
         mode_str
@@ -140,9 +180,31 @@ pub(crate) fn draw_player_compact(app: &MusicPlayerApp, frame: &mut Frame, area:
     // Combine all 3 lines inside single Player box
     let player_content = format!("{}\n{}\n{}", now_playing, progress_bar, status_line);
 
+    // The playing track's cover, if loaded, sits left of the text.
+    let block = Block::default().borders(Borders::ALL).title("Player");
+    let inner = block.inner(area);
+    let cover = current_track
+        .filter(|t| app.covers.images.contains_key(&t.video_id))
+        .map(|t| {
+            (
+                t.video_id.as_str(),
+                Rect {
+                    width: (inner.height * 2).min(inner.width / 4),
+                    ..inner
+                },
+            )
+        });
+    let block = match cover {
+        Some((_, cover_area)) => block.padding(Padding::left(cover_area.width + 1)),
+        None => block,
+    };
+
     let player_widget = Paragraph::new(player_content)
-        .block(Block::default().borders(Borders::ALL).title("Player"))
+        .block(block)
         .style(Style::default().fg(Color::Cyan));
 
     frame.render_widget(player_widget, area);
+    if let Some((key, cover_area)) = cover {
+        super::cover::draw_cover(app, frame, cover_area, key);
+    }
 }
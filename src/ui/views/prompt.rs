@@ -0,0 +1,52 @@
+//! Text prompt popup — a single-line input centred over the current screen.
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::ui::state::PromptState;
+
+pub(crate) fn draw_prompt(prompt: &PromptState, frame: &mut Frame) {
+    let screen = frame.area();
+    let width = screen.width.saturating_sub(4).min(60);
+    let height = 4.min(screen.height);
+    let area = Rect {
+        x: screen.x + (screen.width.saturating_sub(width)) / 2,
+        y: screen.y + (screen.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    // Keep the tail of long input visible.
+    let room = width.saturating_sub(4) as usize;
+    let chars = prompt.input.chars().count();
+    let visible: String = prompt
+        .input
+        .chars()
+        .skip(chars.saturating_sub(room))
+        .collect();
+
+    let widget = Paragraph::new(vec![
+        Line::from(vec![
+            Span::styled(visible, Style::default().fg(Color::White)),
+            Span::styled("_", Style::default().fg(Color::Yellow)),
+        ]),
+        Line::from(Span::styled(
+            "Enter: save  Esc: cancel",
+            Style::default().fg(Color::DarkGray),
+        )),
+    ])
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ", prompt.purpose.title()))
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(widget, area);
+}
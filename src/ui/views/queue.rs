@@ -1,12 +1,51 @@
+use std::time::Instant;
+
 use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
+    text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph},
     Frame,
 };
 
+use crate::player::queue::Track;
+use crate::services::download::FailureKind;
+
 use super::super::app::MusicPlayerApp;
 
+/// `content` followed by the track's download failure, if it has one.
+fn queue_line(app: &MusicPlayerApp, track: &Track, content: String, now: Instant) -> Line<'static> {
+    let mut spans = vec![Span::raw(content)];
+    if app.is_offline() && !app.downloads.is_cached(&track.video_id) {
+        spans.push(Span::styled(
+            "  ⊘ not cached",
+            Style::default().fg(Color::DarkGray),
+        ));
+    } else if app.downloads.is_downloading(&track.video_id) {
+        let label = match app.downloads.progress_for(&track.video_id) {
+            Some(p) if p.is_converting() => "  ⬇ converting…".to_string(),
+            Some(p) => format!("  ⬇ {} {:.0}%", p.bar(8), p.percent),
+            None => "  ⬇ starting…".to_string(),
+        };
+        spans.push(Span::styled(label, Style::default().fg(Color::Cyan)));
+    } else if app.downloads.is_queued(&track.video_id) {
+        spans.push(Span::styled(
+            "  ⬇ queued",
+            Style::default().fg(Color::DarkGray),
+        ));
+    } else if let Some(record) = app.downloads.failure_for(&track.video_id) {
+        let color = match (record.kind, record.retry_at) {
+            (FailureKind::Transient, Some(_)) => Color::Yellow,
+            _ => Color::Red,
+        };
+        spans.push(Span::styled(
+            format!("  {}", record.label(now)),
+            Style::default().fg(color),
+        ));
+    }
+    Line::from(spans)
+}
+
 pub(crate) fn draw_queue_compact(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
     // Show queue items vertically
     let queue_len = app.queue.len();
@@ -26,13 +65,15 @@ pub(crate) fn draw_queue_compact(app: &MusicPlayerApp, frame: &mut Frame, area:
         let max_items = visible_height.min(queue_len);
 
         let queue_slice = app.queue.get_queue_slice(0, max_items);
+        let now = Instant::now();
 
         let items: Vec<ListItem> = queue_slice
             .iter()
             .enumerate()
             .map(|(i, track)| {
                 let content = format!("{}. {}", i + 1, &track.title);
-                ListItem::new(content).style(Style::default().fg(Color::White))
+                ListItem::new(queue_line(app, track, content, now))
+                    .style(Style::default().fg(Color::White))
             })
             .collect();
 
@@ -71,6 +112,7 @@ pub(crate) fn draw_queue_expanded(app: &MusicPlayerApp, frame: &mut Frame, area:
     // Only get visible slice of tracks - huge performance improvement!
     let visible_count = end_idx - start_idx;
     let queue_slice = app.queue.get_queue_slice(start_idx, visible_count);
+    let now = Instant::now();
 
     let queue_items: Vec<ListItem> = queue_slice
         .iter()
@@ -85,7 +127,7 @@ pub(crate) fn draw_queue_expanded(app: &MusicPlayerApp, frame: &mut Frame, area:
             } else {
                 Style::default()
             };
-            ListItem::new(content).style(style)
+            ListItem::new(queue_line(app, track, content, now)).style(style)
         })
         .collect();
 
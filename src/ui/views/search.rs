@@ -1,11 +1,61 @@
 use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
+    text::{Line, Span},
     widgets::{Block, Borders, List, ListItem},
     Frame,
 };
 
+use crate::services::library::{now_unix, SuggestionSource};
+use crate::youtube::extractor::SearchResult;
+
 use super::super::app::MusicPlayerApp;
+use super::cache_stats::format_age;
+
+/// "12.3M", "45K", or "870" subscribers.
+fn compact_count(n: u64) -> String {
+    match n {
+        1_000_000.. => format!("{:.1}M", n as f64 / 1_000_000.0),
+        1_000.. => format!("{}K", n / 1_000),
+        _ => n.to_string(),
+    }
+}
+
+/// The marker and dimmed detail shown for a result of each type.
+fn describe(result: &SearchResult) -> (&'static str, String) {
+    match result {
+        SearchResult::Song(video) | SearchResult::Video(video) => {
+            let marker = if matches!(result, SearchResult::Song(_)) {
+                "♪"
+            } else {
+                "▶"
+            };
+            (marker, format!("  {}", video.uploader))
+        }
+        SearchResult::Album(list) | SearchResult::Playlist(list) => {
+            let marker = if matches!(result, SearchResult::Album(_)) {
+                "💿"
+            } else {
+                "☰"
+            };
+            let mut detail = String::new();
+            if let Some(author) = &list.author {
+                detail.push_str(&format!("  {}", author));
+            }
+            if let Some(count) = list.track_count {
+                detail.push_str(&format!("  · {} tracks", count));
+            }
+            (marker, detail)
+        }
+        SearchResult::Artist(artist) => (
+            "👤",
+            artist
+                .subscribers
+                .map(|n| format!("  {} subscribers", compact_count(n)))
+                .unwrap_or_default(),
+        ),
+    }
+}
 
 pub(crate) fn draw_search_results(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
     let results: Vec<ListItem> = app
@@ -13,8 +63,7 @@ pub(crate) fn draw_search_results(app: &MusicPlayerApp, frame: &mut Frame, area:
         .results
         .iter()
         .enumerate()
-        .map(|(i, video)| {
-            let content = video.title.clone();
+        .map(|(i, result)| {
             let style = if i == app.ui.selected_result {
                 Style::default()
                     .fg(Color::Yellow)
@@ -22,14 +71,77 @@ pub(crate) fn draw_search_results(app: &MusicPlayerApp, frame: &mut Frame, area:
             } else {
                 Style::default()
             };
-            ListItem::new(content).style(style)
+            let (marker, detail) = describe(result);
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{} ", marker), Style::default().fg(Color::Cyan)),
+                Span::styled(result.title().to_string(), style),
+                Span::styled(detail, Style::default().fg(Color::DarkGray)),
+            ]))
         })
         .collect();
 
-    let results_list = List::new(results).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title("Search Results"),
-    );
+    let title = if app.search.offline {
+        "Search Results (offline — cached tracks only)".to_string()
+    } else if let Some(opened) = &app.search.opened {
+        format!(
+            "{} — [Enter] Queue track  [Esc] Back to results",
+            opened.title
+        )
+    } else if app.search.is_searching {
+        format!(
+            "Search Results — {} (searching…)",
+            app.search.last_scope.label()
+        )
+    } else if app.search.has_more {
+        format!(
+            "Search Results — {}  [+] More",
+            app.search.last_scope.label()
+        )
+    } else {
+        format!("Search Results — {}", app.search.last_scope.label())
+    };
+    let results_list =
+        List::new(results).block(Block::default().borders(Borders::ALL).title(title));
     frame.render_widget(results_list, area);
 }
+
+/// The panel under the search input while typing: completions for the
+/// query, or the recent searches when nothing is typed yet.
+pub(crate) fn draw_suggestions(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let now = now_unix();
+    let items: Vec<ListItem> = app
+        .search
+        .suggestions
+        .iter()
+        .enumerate()
+        .map(|(i, suggestion)| {
+            let style = if app.search.suggestion_selected == Some(i) {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let (marker, detail) = match suggestion.source {
+                SuggestionSource::Recent { searched_at } => {
+                    ("🕘", format!("  {}", format_age(now - searched_at)))
+                }
+                SuggestionSource::Track => ("♪", "  played".to_string()),
+                SuggestionSource::Uploader => ("👤", String::new()),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{} ", marker), Style::default().fg(Color::Cyan)),
+                Span::styled(suggestion.text.clone(), style),
+                Span::styled(detail, Style::default().fg(Color::DarkGray)),
+            ]))
+        })
+        .collect();
+
+    let title = if app.search.query.trim().is_empty() {
+        "Recent Searches — [↑/↓] Pick  [Enter] Search  [Del] Forget"
+    } else {
+        "Suggestions — [→] Complete  [↑/↓] Pick  [Enter] Search"
+    };
+    let list = List::new(items).block(Block::default().borders(Borders::ALL).title(title));
+    frame.render_widget(list, area);
+}
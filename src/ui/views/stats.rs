@@ -0,0 +1,195 @@
+//! Listening statistics view — totals, daily sparkline, top tracks/uploaders.
+//!
+//! ```text
+//! ┌─ Listening Stats ───────────────────────────────────────────────────────┐
+//! │  [7 days]  30 days  All time                   w: change window  s: close│
+//! │  Listened 12h 04m · 183 plays · 14% skipped                              │
+//! └──────────────────────────────────────────────────────────────────────────┘
+//! ┌─ Daily listening ───────────────────────────────────────────────────────┐
+//! │ ▂▃▅▇█▆▃                                                                 │
+//! └──────────────────────────────────────────────────────────────────────────┘
+//! ┌─ Top tracks ──────────────────────┐┌─ Top uploaders ────────────────────┐
+//! │ #  Title          Plays  Time     ││ #  Uploader      Plays  Time       │
+//! └───────────────────────────────────┘└────────────────────────────────────┘
+//! ```
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Sparkline, Table},
+    Frame,
+};
+
+use crate::services::library::ListeningStats;
+use crate::ui::state::StatsWindow;
+
+use super::super::app::MusicPlayerApp;
+
+pub(crate) fn draw_stats(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4), // Window selector + totals
+            Constraint::Length(6), // Sparkline
+            Constraint::Min(5),    // Tables
+        ])
+        .split(area);
+
+    draw_summary(app, frame, chunks[0]);
+
+    let Some(stats) = &app.stats.data else {
+        let msg = app
+            .stats
+            .error
+            .clone()
+            .unwrap_or_else(|| "No listening data yet — play something!".to_string());
+        let empty = Paragraph::new(msg)
+            .style(Style::default().fg(Color::DarkGray))
+            .block(Block::default().borders(Borders::ALL));
+        frame.render_widget(empty, chunks[1].union(chunks[2]));
+        return;
+    };
+
+    draw_sparkline(stats, app.stats.window, frame, chunks[1]);
+
+    let tables = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(chunks[2]);
+    draw_top_tracks(stats, frame, tables[0]);
+    draw_top_uploaders(stats, frame, tables[1]);
+}
+
+fn draw_summary(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let mut tabs: Vec<Span> = Vec::new();
+    for window in [StatsWindow::Week, StatsWindow::Month, StatsWindow::AllTime] {
+        let label = format!(" {} ", window.label());
+        if window == app.stats.window {
+            tabs.push(Span::styled(
+                format!("[{}]", label.trim()),
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        } else {
+            tabs.push(Span::styled(label, Style::default().fg(Color::DarkGray)));
+        }
+        tabs.push(Span::raw("  "));
+    }
+    tabs.push(Span::styled(
+        "w: change window  s: close",
+        Style::default().fg(Color::DarkGray),
+    ));
+
+    let totals = match &app.stats.data {
+        Some(stats) => format!(
+            "Listened {} · {} plays · {:.0}% skipped",
+            format_listened(stats.total_listened_secs),
+            stats.plays,
+            stats.skip_rate() * 100.0
+        ),
+        None => String::new(),
+    };
+
+    let widget = Paragraph::new(vec![
+        Line::from(tabs),
+        Line::from(Span::styled(totals, Style::default().fg(Color::White))),
+    ])
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Listening Stats "),
+    );
+    frame.render_widget(widget, area);
+}
+
+fn draw_sparkline(stats: &ListeningStats, window: StatsWindow, frame: &mut Frame, area: Rect) {
+    // Show the most recent days that fit in the box.
+    let width = area.width.saturating_sub(2) as usize;
+    let start = stats.daily_secs.len().saturating_sub(width);
+    let data = &stats.daily_secs[start..];
+    let peak = data.iter().copied().max().unwrap_or(0);
+
+    let sparkline = Sparkline::default()
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            " Daily listening — last {} days (peak {}) ",
+            data.len().min(window.sparkline_days()),
+            format_listened(peak)
+        )))
+        .data(data)
+        .style(Style::default().fg(Color::Green));
+    frame.render_widget(sparkline, area);
+}
+
+fn draw_top_tracks(stats: &ListeningStats, frame: &mut Frame, area: Rect) {
+    let rows = stats.top_tracks.iter().enumerate().map(|(i, t)| {
+        Row::new(vec![
+            Cell::from(format!("{}", i + 1)),
+            Cell::from(t.title.clone()),
+            Cell::from(t.uploader.clone()).style(Style::default().fg(Color::DarkGray)),
+            Cell::from(t.plays.to_string()),
+            Cell::from(format_listened(t.listened_secs)),
+        ])
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(3),
+            Constraint::Percentage(50),
+            Constraint::Percentage(30),
+            Constraint::Length(6),
+            Constraint::Length(8),
+        ],
+    )
+    .header(header_row(&["#", "Title", "Uploader", "Plays", "Time"]))
+    .block(Block::default().borders(Borders::ALL).title(" Top tracks "));
+    frame.render_widget(table, area);
+}
+
+fn draw_top_uploaders(stats: &ListeningStats, frame: &mut Frame, area: Rect) {
+    let rows = stats.top_uploaders.iter().enumerate().map(|(i, u)| {
+        Row::new(vec![
+            Cell::from(format!("{}", i + 1)),
+            Cell::from(u.uploader.clone()),
+            Cell::from(u.plays.to_string()),
+            Cell::from(format_listened(u.listened_secs)),
+        ])
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(3),
+            Constraint::Min(10),
+            Constraint::Length(6),
+            Constraint::Length(8),
+        ],
+    )
+    .header(header_row(&["#", "Uploader", "Plays", "Time"]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Top uploaders "),
+    );
+    frame.render_widget(table, area);
+}
+
+fn header_row(labels: &[&'static str]) -> Row<'static> {
+    Row::new(labels.iter().map(|l| Cell::from(*l))).style(
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    )
+}
+
+/// Format a listening total as `3h 05m` or `12m`.
+fn format_listened(secs: u64) -> String {
+    let hours = secs / 3600;
+    let mins = (secs % 3600) / 60;
+    if hours > 0 {
+        format!("{hours}h {mins:02}m")
+    } else {
+        format!("{mins}m")
+    }
+}
@@ -0,0 +1,169 @@
+//! Tag cloud view — every tag, weighted by how many tracks carry it.
+//!
+//! ```text
+//! ┌─ Tags ──────────────────────────────────────────────────────────────────┐
+//! │ status bar                                                              │
+//! ├─────────────────────────────────────────────────────────────────────────┤
+//! │  CODING 24   chill 11   [✓ gym 9]   late-night 3   focus 2              │
+//! ├─────────────────────────────────────────────────────────────────────────┤
+//! │ Match: any of gym                                                       │
+//! │ [j/k] Move  [Space] Mark  [m] Any/All  [Enter] Queue  [/] Query  [Esc]  │
+//! └─────────────────────────────────────────────────────────────────────────┘
+//! ```
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use super::super::app::MusicPlayerApp;
+
+pub(crate) fn draw(app: &MusicPlayerApp, frame: &mut Frame) {
+    let area = frame.area();
+
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .title(" Tags ")
+        .style(Style::default().fg(Color::Cyan));
+    let inner = outer.inner(area);
+    frame.render_widget(outer, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // status bar
+            Constraint::Min(5),    // cloud
+            Constraint::Length(1), // match line
+            Constraint::Length(1), // hint bar
+        ])
+        .split(inner);
+
+    draw_status_bar(app, frame, rows[0]);
+    draw_cloud(app, frame, rows[1]);
+    draw_match_line(app, frame, rows[2]);
+    draw_hint_bar(frame, rows[3]);
+}
+
+fn draw_status_bar(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let (text, style) = if let Some(err) = &app.tags.error {
+        (format!("⚠ {}", err), Style::default().fg(Color::Red))
+    } else if !app.status_message.is_empty() {
+        let style = if app.status_message.starts_with('✓') {
+            Style::default().fg(Color::Green)
+        } else {
+            Style::default().fg(Color::Yellow)
+        };
+        (app.status_message.clone(), style)
+    } else {
+        (
+            format!(
+                "{} tags — press g on a track in search, queue, history, or feed to tag it",
+                app.tags.tags.len()
+            ),
+            Style::default().fg(Color::DarkGray),
+        )
+    };
+    frame.render_widget(Paragraph::new(text).style(style), area);
+}
+
+/// Style for a tag used on `count` tracks when the most used has `max`.
+fn weight_style(count: u64, max: u64) -> Style {
+    let weight = count as f64 / max.max(1) as f64;
+    if weight > 0.75 {
+        Style::default()
+            .fg(Color::Magenta)
+            .add_modifier(Modifier::BOLD)
+    } else if weight > 0.5 {
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD)
+    } else if weight > 0.25 {
+        Style::default().fg(Color::White)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    }
+}
+
+fn draw_cloud(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+
+    let state = &app.tags;
+    if state.tags.is_empty() {
+        frame.render_widget(
+            Paragraph::new("  (no tags yet)")
+                .style(Style::default().fg(Color::DarkGray))
+                .block(block),
+            area,
+        );
+        return;
+    }
+
+    let max = state.tags.iter().map(|(_, n)| *n).max().unwrap_or(1);
+    let mut spans = Vec::with_capacity(state.tags.len() * 2);
+    for (i, (tag, count)) in state.tags.iter().enumerate() {
+        let marked = state.marked.contains(tag);
+        let mut style = weight_style(*count, max);
+        if marked {
+            style = style.fg(Color::Green).add_modifier(Modifier::UNDERLINED);
+        }
+        if i == state.selected {
+            style = style.add_modifier(Modifier::REVERSED);
+        }
+        let label = if marked {
+            format!("✓ {} {}", tag, count)
+        } else {
+            format!("{} {}", tag, count)
+        };
+        spans.push(Span::styled(label, style));
+        spans.push(Span::raw("   "));
+    }
+
+    frame.render_widget(
+        Paragraph::new(Line::from(spans))
+            .wrap(Wrap { trim: true })
+            .block(block),
+        area,
+    );
+}
+
+fn draw_match_line(app: &MusicPlayerApp, frame: &mut Frame, area: Rect) {
+    let state = &app.tags;
+    let mode = if state.match_all { "all" } else { "any" };
+    let line = match state.query() {
+        Some(query) => Line::from(vec![
+            Span::styled(
+                format!("Match {} of: ", mode),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(query.to_string(), Style::default().fg(Color::Yellow)),
+        ]),
+        None => Line::from(Span::styled(
+            format!("Match {} of the marked tags", mode),
+            Style::default().fg(Color::DarkGray),
+        )),
+    };
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+fn draw_hint_bar(frame: &mut Frame, area: Rect) {
+    let hints = vec![
+        Span::styled("[j/k]", Style::default().fg(Color::Yellow)),
+        Span::raw(" Move  "),
+        Span::styled("[Space]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Mark  "),
+        Span::styled("[m]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Any/All  "),
+        Span::styled("[Enter]", Style::default().fg(Color::Green)),
+        Span::raw(" Queue tracks  "),
+        Span::styled("[/]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Query  "),
+        Span::styled("[Esc]", Style::default().fg(Color::Red)),
+        Span::raw(" Close"),
+    ];
+    frame.render_widget(Paragraph::new(Line::from(hints)), area);
+}
@@ -36,6 +36,65 @@ fn is_safe_profile_name(name: &str) -> bool {
         })
 }
 
+/// Where `browser` keeps its profile directories on this OS.
+fn profiles_dir(browser: &str) -> Option<PathBuf> {
+    match browser {
+        // Chrome config locations by OS
+        "chrome" => {
+            if cfg!(target_os = "linux") {
+                dirs::config_dir().map(|d| d.join("google-chrome"))
+            } else if cfg!(target_os = "macos") {
+                dirs::home_dir().map(|d| d.join("Library/Application Support/Google/Chrome"))
+            } else if cfg!(target_os = "windows") {
+                dirs::data_local_dir().map(|d| d.join("Google/Chrome/User Data"))
+            } else {
+                None
+            }
+        }
+        "chromium" => {
+            if cfg!(target_os = "linux") {
+                dirs::config_dir().map(|d| d.join("chromium"))
+            } else if cfg!(target_os = "macos") {
+                dirs::home_dir().map(|d| d.join("Library/Application Support/Chromium"))
+            } else if cfg!(target_os = "windows") {
+                dirs::data_local_dir().map(|d| d.join("Chromium/User Data"))
+            } else {
+                None
+            }
+        }
+        // Zen Browser config locations (Firefox fork)
+        "zen" => {
+            if cfg!(target_os = "linux") {
+                // Try ~/.config/zen first (common on Arch), then ~/.zen
+                let config_path = dirs::config_dir().map(|d| d.join("zen"));
+                if config_path.as_ref().is_some_and(|p| p.exists()) {
+                    config_path
+                } else {
+                    dirs::home_dir().map(|d| d.join(".zen"))
+                }
+            } else if cfg!(target_os = "macos") {
+                dirs::home_dir().map(|d| d.join("Library/Application Support/Zen"))
+            } else if cfg!(target_os = "windows") {
+                dirs::data_dir().map(|d| d.join("Zen"))
+            } else {
+                None
+            }
+        }
+        "firefox" => {
+            if cfg!(target_os = "linux") {
+                dirs::home_dir().map(|d| d.join(".mozilla/firefox"))
+            } else if cfg!(target_os = "macos") {
+                dirs::home_dir().map(|d| d.join("Library/Application Support/Firefox/Profiles"))
+            } else if cfg!(target_os = "windows") {
+                dirs::data_dir().map(|d| d.join("Mozilla/Firefox/Profiles"))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
 impl BrowserAuth {
     pub fn new() -> Result<Self, String> {
         let config_dir = dirs::config_dir()
@@ -70,16 +129,7 @@ impl BrowserAuth {
     fn detect_chrome_accounts(&self) -> Vec<BrowserAccount> {
         let mut accounts = Vec::new();
 
-        // Chrome config locations by OS
-        let chrome_base = if cfg!(target_os = "linux") {
-            dirs::config_dir().map(|d| d.join("google-chrome"))
-        } else if cfg!(target_os = "macos") {
-            dirs::home_dir().map(|d| d.join("Library/Application Support/Google/Chrome"))
-        } else if cfg!(target_os = "windows") {
-            dirs::data_local_dir().map(|d| d.join("Google/Chrome/User Data"))
-        } else {
-            None
-        };
+        let chrome_base = profiles_dir("chrome");
 
         if let Some(chrome_dir) = chrome_base {
             if chrome_dir.exists() {
@@ -115,15 +165,7 @@ impl BrowserAuth {
     fn detect_chromium_accounts(&self) -> Vec<BrowserAccount> {
         let mut accounts = Vec::new();
 
-        let chromium_base = if cfg!(target_os = "linux") {
-            dirs::config_dir().map(|d| d.join("chromium"))
-        } else if cfg!(target_os = "macos") {
-            dirs::home_dir().map(|d| d.join("Library/Application Support/Chromium"))
-        } else if cfg!(target_os = "windows") {
-            dirs::data_local_dir().map(|d| d.join("Chromium/User Data"))
-        } else {
-            None
-        };
+        let chromium_base = profiles_dir("chromium");
 
         if let Some(chromium_dir) = chromium_base {
             if chromium_dir.exists() {
@@ -157,22 +199,7 @@ impl BrowserAuth {
     fn detect_zen_accounts(&self) -> Vec<BrowserAccount> {
         let mut accounts = Vec::new();
 
-        // Zen Browser config locations (Firefox fork)
-        let zen_base = if cfg!(target_os = "linux") {
-            // Try ~/.config/zen first (common on Arch), then ~/.zen
-            let config_path = dirs::config_dir().map(|d| d.join("zen"));
-            if config_path.as_ref().is_some_and(|p| p.exists()) {
-                config_path
-            } else {
-                dirs::home_dir().map(|d| d.join(".zen"))
-            }
-        } else if cfg!(target_os = "macos") {
-            dirs::home_dir().map(|d| d.join("Library/Application Support/Zen"))
-        } else if cfg!(target_os = "windows") {
-            dirs::data_dir().map(|d| d.join("Zen"))
-        } else {
-            None
-        };
+        let zen_base = profiles_dir("zen");
 
         if let Some(zen_dir) = zen_base {
             if zen_dir.exists() {
@@ -209,16 +236,7 @@ impl BrowserAuth {
     fn detect_firefox_accounts(&self) -> Vec<BrowserAccount> {
         let mut accounts = Vec::new();
 
-        // Firefox config locations
-        let firefox_base = if cfg!(target_os = "linux") {
-            dirs::home_dir().map(|d| d.join(".mozilla/firefox"))
-        } else if cfg!(target_os = "macos") {
-            dirs::home_dir().map(|d| d.join("Library/Application Support/Firefox/Profiles"))
-        } else if cfg!(target_os = "windows") {
-            dirs::data_dir().map(|d| d.join("Mozilla/Firefox/Profiles"))
-        } else {
-            None
-        };
+        let firefox_base = profiles_dir("firefox");
 
         if let Some(firefox_dir) = firefox_base {
             if firefox_dir.exists() {
@@ -352,6 +370,16 @@ impl BrowserAuth {
         }
     }
 
+    /// The cookie database yt-dlp reads for `account`, if it exists.
+    pub fn cookie_store(&self, account: &BrowserAccount) -> Option<PathBuf> {
+        let profile = profiles_dir(&account.browser)?.join(&account.profile);
+        let candidates = match account.browser.as_str() {
+            "chrome" | "chromium" => vec![profile.join("Network/Cookies"), profile.join("Cookies")],
+            _ => vec![profile.join("cookies.sqlite")],
+        };
+        candidates.into_iter().find(|path| path.is_file())
+    }
+
     /// Remove the saved account selection, effectively logging out.
     /// The next `is_authenticated()` call will return `false`.
     pub fn clear_selected_account(&self) {
@@ -2,7 +2,11 @@
 // Uses rustube or yt-dlp subprocess to extract audio streams and metadata
 
 use serde::{Deserialize, Serialize};
-use std::process::Command;
+use std::sync::Arc;
+
+use crate::config::is_allowed_youtube_url;
+
+use super::ytdlp::{CancelHandle, YtDlp, YtDlpArgs};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VideoInfo {
@@ -14,6 +18,159 @@ pub struct VideoInfo {
     pub url: String,
 }
 
+/// What a search looks for. Videos is a plain YouTube search; the others
+/// use the matching YouTube Music search section, except Artists, which
+/// searches YouTube channels (they carry names and subscriber counts).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SearchScope {
+    Songs,
+    #[default]
+    Videos,
+    Albums,
+    Artists,
+    Playlists,
+}
+
+impl SearchScope {
+    pub const ALL: [SearchScope; 5] = [
+        SearchScope::Songs,
+        SearchScope::Videos,
+        SearchScope::Albums,
+        SearchScope::Artists,
+        SearchScope::Playlists,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SearchScope::Songs => "Songs",
+            SearchScope::Videos => "Videos",
+            SearchScope::Albums => "Albums",
+            SearchScope::Artists => "Artists",
+            SearchScope::Playlists => "Playlists",
+        }
+    }
+
+    /// The next scope, wrapping around; `back` goes the other way.
+    pub fn cycle(self, back: bool) -> Self {
+        let i = Self::ALL.iter().position(|s| *s == self).unwrap_or(0);
+        let n = Self::ALL.len();
+        Self::ALL[if back { (i + n - 1) % n } else { (i + 1) % n }]
+    }
+
+    /// The yt-dlp target searching for `query` (already sanitized).
+    fn target(self, query: &str, max_results: usize) -> String {
+        let music = |section: &str| {
+            format!(
+                "https://music.youtube.com/search?q={}#{}",
+                encode_query(query),
+                section
+            )
+        };
+        match self {
+            SearchScope::Videos => format!("ytsearch{}:{}", max_results, query),
+            SearchScope::Songs => music("songs"),
+            SearchScope::Albums => music("albums"),
+            SearchScope::Playlists => music("community%20playlists"),
+            // `sp=EgIQAg==` is YouTube's "type: channel" search filter.
+            SearchScope::Artists => format!(
+                "https://www.youtube.com/results?search_query={}&sp=EgIQAg%3D%3D",
+                encode_query(query)
+            ),
+        }
+    }
+}
+
+/// An album or playlist search result.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListInfo {
+    pub id: String,
+    pub title: String,
+    /// The album artist or playlist author, when known.
+    pub author: Option<String>,
+    pub track_count: Option<u64>,
+    pub url: String,
+}
+
+/// An artist (channel) search result.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArtistInfo {
+    pub id: String,
+    pub name: String,
+    pub subscribers: Option<u64>,
+    pub url: String,
+}
+
+/// One search result, typed by what selecting it does: songs and videos
+/// are queued, albums and playlists open their track list.
+#[derive(Debug, Clone)]
+pub enum SearchResult {
+    Song(VideoInfo),
+    Video(VideoInfo),
+    Album(ListInfo),
+    Playlist(ListInfo),
+    Artist(ArtistInfo),
+}
+
+impl SearchResult {
+    /// The playable track, for songs and videos.
+    pub fn video(&self) -> Option<&VideoInfo> {
+        match self {
+            SearchResult::Song(video) | SearchResult::Video(video) => Some(video),
+            _ => None,
+        }
+    }
+
+    /// The album or playlist, for results that open a track list.
+    pub fn list(&self) -> Option<&ListInfo> {
+        match self {
+            SearchResult::Album(list) | SearchResult::Playlist(list) => Some(list),
+            _ => None,
+        }
+    }
+
+    /// The video, list or channel id.
+    pub fn id(&self) -> &str {
+        match self {
+            SearchResult::Song(video) | SearchResult::Video(video) => &video.id,
+            SearchResult::Album(list) | SearchResult::Playlist(list) => &list.id,
+            SearchResult::Artist(artist) => &artist.id,
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        match self {
+            SearchResult::Song(video) | SearchResult::Video(video) => &video.title,
+            SearchResult::Album(list) | SearchResult::Playlist(list) => &list.title,
+            SearchResult::Artist(artist) => &artist.name,
+        }
+    }
+}
+
+/// Percent-encode a search query for a URL's query string.
+fn encode_query(query: &str) -> String {
+    let mut encoded = String::with_capacity(query.len());
+    for byte in query.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b' ' => encoded.push('+'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Returns true if a playlist, album, or channel id contains only safe
+/// characters and has a plausible length.
+pub(crate) fn is_valid_list_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= 64
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
 /// Returns true if a YouTube video ID contains only safe characters (alphanumeric, dash, underscore).
 #[must_use]
 pub fn is_valid_video_id(id: &str) -> bool {
@@ -25,80 +182,423 @@ pub fn is_valid_video_id(id: &str) -> bool {
 }
 
 pub struct YouTubeExtractor {
-    // No state needed - all operations are stateless subprocess calls
+    ytdlp: Arc<dyn YtDlp>,
+}
+
+/// Which slice of a search's results to fetch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchPage {
+    /// 1-based index of the first result.
+    pub start: usize,
+    pub size: usize,
+}
+
+impl SearchPage {
+    /// The `index`th (0-based) page of `size` results.
+    pub fn nth(index: usize, size: usize) -> Self {
+        SearchPage {
+            start: index * size + 1,
+            size,
+        }
+    }
+
+    fn end(self) -> usize {
+        self.start + self.size - 1
+    }
 }
 
 impl YouTubeExtractor {
-    pub fn new() -> Self {
-        YouTubeExtractor {}
+    pub fn new(ytdlp: Arc<dyn YtDlp>) -> Self {
+        YouTubeExtractor { ytdlp }
     }
 
-    pub async fn search(&self, query: &str, max_results: usize) -> Result<Vec<VideoInfo>, String> {
+    /// Fetch one page of results, handing each to `on_result` as soon as
+    /// yt-dlp prints it. Returns how many entries yt-dlp produced — fewer
+    /// than the page size means there's nothing more to load. `cancel`
+    /// kills the search, which then ends with `Ok` and whatever arrived.
+    pub async fn search(
+        &self,
+        query: &str,
+        scope: SearchScope,
+        page: SearchPage,
+        cancel: CancelHandle,
+        mut on_result: impl FnMut(SearchResult) + Send + 'static,
+    ) -> Result<usize, String> {
         // Run yt-dlp search in a blocking task to avoid blocking async runtime
         // Sanitize query: strip leading dashes to prevent yt-dlp flag injection
         let sanitized = query.trim().trim_start_matches('-').to_string();
         if sanitized.is_empty() {
-            return Ok(Vec::new());
+            return Ok(0);
         }
-        let results = tokio::task::spawn_blocking(move || {
-            let output = Command::new("yt-dlp")
-                .arg("--dump-json")
-                .arg("--skip-download")
-                .arg("--no-playlist")
-                .arg("--default-search")
-                .arg("ytsearch")
-                .arg("--socket-timeout")
-                .arg("30")
-                .arg("--retries")
-                .arg("2")
-                .arg(format!("ytsearch{}:{}", max_results, sanitized))
-                .output()
-                .map_err(|e| format!("Failed to run yt-dlp: {}", e))?;
-
-            if !output.status.success() {
-                let error = String::from_utf8_lossy(&output.stderr);
-                eprintln!("yt-dlp search error: {}", error);
-                return Err("yt-dlp search failed — check logs for details".to_string());
+        let ytdlp = self.ytdlp.clone();
+        tokio::task::spawn_blocking(move || {
+            search_blocking(&*ytdlp, &sanitized, scope, page, &cancel, &mut on_result)
+        })
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+    }
+}
+
+fn search_blocking(
+    ytdlp: &dyn YtDlp,
+    query: &str,
+    scope: SearchScope,
+    page: SearchPage,
+    cancel: &CancelHandle,
+    on_result: &mut dyn FnMut(SearchResult),
+) -> Result<usize, String> {
+    let target = scope.target(query, page.end());
+    let args = match scope {
+        // Full extraction: slower, but gives durations and uploaders.
+        SearchScope::Videos => YtDlpArgs::new()
+            .flag("--dump-json")
+            .flag("--skip-download")
+            .flag("--no-playlist")
+            .opt("--default-search", "ytsearch"),
+        _ => YtDlpArgs::new()
+            .flag("--flat-playlist")
+            .flag("--dump-json")
+            .opt("--playlist-end", page.end().to_string()),
+    }
+    .opt("--playlist-start", page.start.to_string())
+    .flag(&target);
+
+    let mut entries = 0;
+    let output = ytdlp
+        .stream(&args, cancel, &mut |line| {
+            let Ok(json) = serde_json::from_str::<serde_json::Value>(line) else {
+                return;
+            };
+            entries += 1;
+            if let Some(result) = parse_result(&json, scope) {
+                on_result(result);
             }
+        })
+        .map_err(|e| e.to_string())?;
+
+    if !output.success && !cancel.is_cancelled() {
+        eprintln!("yt-dlp search error: {}", output.stderr);
+        return Err(format!("yt-dlp search failed: {}", output.error_snippet()));
+    }
+    Ok(entries)
+}
+
+/// Parse one `--dump-json` line: a fully extracted video for the Videos
+/// scope, a `--flat-playlist` search section entry for the others.
+fn parse_result(json: &serde_json::Value, scope: SearchScope) -> Option<SearchResult> {
+    let text = |key: &str| {
+        json[key]
+            .as_str()
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+    let id = text("id").unwrap_or_default();
+    let url = text("url").unwrap_or_default();
 
-            let stdout =
-                String::from_utf8(output.stdout).map_err(|e| format!("Invalid UTF-8: {}", e))?;
-            let mut results = Vec::new();
-
-            for line in stdout.lines() {
-                if line.trim().is_empty() {
-                    continue;
-                }
-
-                let json: serde_json::Value =
-                    serde_json::from_str(line).map_err(|e| format!("JSON parse error: {}", e))?;
-
-                let video_id = json["id"].as_str().unwrap_or("").to_string();
-
-                // Skip entries with invalid or empty video IDs
-                if !is_valid_video_id(&video_id) {
-                    continue;
-                }
-
-                // Don't fetch audio URL here - it's slow and URLs expire
-                // We'll fetch it on-demand when user actually plays the track
-                let placeholder_url = format!("https://www.youtube.com/watch?v={}", video_id);
-
-                results.push(VideoInfo {
-                    id: video_id,
-                    title: json["title"].as_str().unwrap_or("Unknown").to_string(),
-                    duration: json["duration"].as_u64().unwrap_or(0),
-                    uploader: json["uploader"].as_str().unwrap_or("Unknown").to_string(),
-                    thumbnail: json["thumbnail"].as_str().map(|s| s.to_string()),
-                    url: placeholder_url,
-                });
+    match scope {
+        SearchScope::Videos => {
+            // Skip entries with invalid or empty video IDs
+            if !is_valid_video_id(&id) {
+                return None;
+            }
+            // Don't fetch audio URL here - it's slow and URLs expire
+            // We'll fetch it on-demand when user actually plays the track
+            Some(SearchResult::Video(VideoInfo {
+                url: format!("https://www.youtube.com/watch?v={}", id),
+                id,
+                title: text("title").unwrap_or_else(|| "Unknown".to_string()),
+                duration: json["duration"].as_u64().unwrap_or(0),
+                uploader: text("uploader").unwrap_or_else(|| "Unknown".to_string()),
+                thumbnail: text("thumbnail"),
+            }))
+        }
+        SearchScope::Songs => {
+            if !is_valid_video_id(&id) {
+                return None;
             }
+            Some(SearchResult::Song(VideoInfo {
+                url: format!("https://www.youtube.com/watch?v={}", id),
+                id,
+                title: text("title").unwrap_or_else(|| "Unknown".to_string()),
+                duration: json["duration"].as_f64().unwrap_or(0.0) as u64,
+                uploader: text("channel")
+                    .or_else(|| text("uploader"))
+                    .unwrap_or_else(|| "Unknown".to_string()),
+                thumbnail: None,
+            }))
+        }
+        SearchScope::Albums | SearchScope::Playlists => {
+            if !is_valid_list_id(&id) || !is_allowed_youtube_url(&url) {
+                return None;
+            }
+            let list = ListInfo {
+                title: text("title").unwrap_or_else(|| id.clone()),
+                author: text("channel").or_else(|| text("uploader")),
+                track_count: json["playlist_count"].as_u64(),
+                id,
+                url,
+            };
+            Some(if scope == SearchScope::Albums {
+                SearchResult::Album(list)
+            } else {
+                SearchResult::Playlist(list)
+            })
+        }
+        SearchScope::Artists => {
+            if !is_valid_list_id(&id) || !is_allowed_youtube_url(&url) {
+                return None;
+            }
+            Some(SearchResult::Artist(ArtistInfo {
+                name: text("channel")
+                    .or_else(|| text("title"))
+                    .unwrap_or_else(|| id.clone()),
+                subscribers: json["channel_follower_count"].as_u64(),
+                id,
+                url,
+            }))
+        }
+    }
+}
 
-            Ok::<Vec<VideoInfo>, String>(results)
-        })
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::youtube::fake_ytdlp::{FakeYtDlp, Reply};
+    use std::sync::Mutex;
+
+    /// Run one search and collect what it streams.
+    async fn collect(
+        extractor: &YouTubeExtractor,
+        query: &str,
+        scope: SearchScope,
+        page: SearchPage,
+    ) -> Result<(Vec<SearchResult>, usize), String> {
+        let results = Arc::new(Mutex::new(Vec::new()));
+        let sink = results.clone();
+        let entries = extractor
+            .search(query, scope, page, CancelHandle::default(), move |r| {
+                sink.lock().unwrap().push(r)
+            })
+            .await?;
+        let results = std::mem::take(&mut *results.lock().unwrap());
+        Ok((results, entries))
+    }
+
+    #[tokio::test]
+    async fn search_replays_fixture_and_skips_bad_ids() {
+        let fake = Arc::new(FakeYtDlp::new().on("ytsearch", Reply::fixture("search.jsonl")));
+        let extractor = YouTubeExtractor::new(fake.clone());
+
+        let (results, entries) = collect(
+            &extractor,
+            "--rick astley",
+            SearchScope::Videos,
+            SearchPage::nth(0, 15),
+        )
+        .await
+        .unwrap();
+        // Every JSON line counts towards the page, even the rejected one.
+        assert_eq!(entries, 4);
+        let results: Vec<_> = results.iter().filter_map(SearchResult::video).collect();
+        let ids: Vec<_> = results.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, ["dQw4w9WgXcQ", "yPYZpwSpKmA", "AC3Ejf7vPEY"]);
+        assert_eq!(results[0].duration, 213);
+        assert_eq!(results[2].thumbnail, None);
+        assert_eq!(
+            results[0].url,
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ"
+        );
+        // Leading dashes never reach yt-dlp as flags.
+        assert_eq!(fake.calls()[0].target(), "ytsearch15:rick astley");
+
+        let (blank, _) = collect(
+            &extractor,
+            "  ",
+            SearchScope::Videos,
+            SearchPage::nth(0, 15),
+        )
+        .await
+        .unwrap();
+        assert!(blank.is_empty());
+        assert_eq!(fake.calls().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn search_reports_the_error_line() {
+        let fake = FakeYtDlp::new().on(
+            "ytsearch",
+            Reply::Fail("WARNING: [youtube] retrying\nERROR: [youtube] Unable to download API page: <urlopen error [Errno -3] Temporary failure in name resolution>\n".to_string()),
+        );
+        let err = collect(
+            &YouTubeExtractor::new(Arc::new(fake)),
+            "x",
+            SearchScope::Videos,
+            SearchPage::nth(0, 5),
+        )
+        .await
+        .unwrap_err();
+        assert!(
+            err.starts_with("yt-dlp search failed: ERROR: [youtube] Unable to download"),
+            "{}",
+            err
+        );
+        assert!(crate::services::network::is_network_error(&err));
+
+        let missing =
+            YouTubeExtractor::new(Arc::new(FakeYtDlp::new().on("ytsearch", Reply::Missing)));
+        assert!(
+            collect(&missing, "x", SearchScope::Videos, SearchPage::nth(0, 5))
+                .await
+                .unwrap_err()
+                .contains("not found")
+        );
+    }
+
+    #[tokio::test]
+    async fn later_pages_start_after_earlier_ones() {
+        let fake = Arc::new(
+            FakeYtDlp::new()
+                .on("ytsearch30:", Reply::fixture("search.jsonl"))
+                .on("#songs", Reply::fixture("music_songs.jsonl")),
+        );
+        let extractor = YouTubeExtractor::new(fake.clone());
+
+        let page = SearchPage::nth(1, 15);
+        assert_eq!(
+            page,
+            SearchPage {
+                start: 16,
+                size: 15
+            }
+        );
+        collect(&extractor, "rick", SearchScope::Videos, page)
+            .await
+            .unwrap();
+        let args = &fake.calls()[0];
+        assert_eq!(args.target(), "ytsearch30:rick");
+        assert_eq!(args.value_of("--playlist-start"), Some("16"));
+
+        collect(
+            &extractor,
+            "rick",
+            SearchScope::Songs,
+            SearchPage::nth(2, 10),
+        )
         .await
-        .map_err(|e| format!("Task join error: {}", e))??;
+        .unwrap();
+        let args = &fake.calls()[1];
+        assert_eq!(args.value_of("--playlist-start"), Some("21"));
+        assert_eq!(args.value_of("--playlist-end"), Some("30"));
+    }
+
+    #[tokio::test]
+    async fn cancelled_search_stops_quietly() {
+        let fake = Arc::new(FakeYtDlp::new().on("ytsearch", Reply::fixture("search.jsonl")));
+        let extractor = YouTubeExtractor::new(fake);
+        let cancel = CancelHandle::default();
+        let results = Arc::new(Mutex::new(Vec::new()));
+        let sink = results.clone();
+        let stop = cancel.clone();
+        let entries = extractor
+            .search(
+                "rick",
+                SearchScope::Videos,
+                SearchPage::nth(0, 15),
+                cancel,
+                move |r| {
+                    // Cancel as soon as the first result shows up.
+                    sink.lock().unwrap().push(r);
+                    stop.cancel();
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(entries, 1);
+        assert_eq!(results.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn scopes_cycle_and_build_targets() {
+        assert_eq!(SearchScope::Videos.cycle(false), SearchScope::Albums);
+        assert_eq!(SearchScope::Songs.cycle(true), SearchScope::Playlists);
+        assert_eq!(SearchScope::Playlists.cycle(false), SearchScope::Songs);
+        assert_eq!(
+            SearchScope::Songs.target("AC/DC & co", 10),
+            "https://music.youtube.com/search?q=AC%2FDC+%26+co#songs"
+        );
+        assert_eq!(
+            SearchScope::Playlists.target("x", 10),
+            "https://music.youtube.com/search?q=x#community%20playlists"
+        );
+        assert!(SearchScope::Artists
+            .target("x", 10)
+            .ends_with("&sp=EgIQAg%3D%3D"));
+    }
 
-        Ok(results)
+    #[tokio::test]
+    async fn scoped_searches_return_typed_results() {
+        let fake = Arc::new(
+            FakeYtDlp::new()
+                .on("#songs", Reply::fixture("music_songs.jsonl"))
+                .on("#albums", Reply::fixture("music_albums.jsonl"))
+                .on("sp=EgIQAg", Reply::fixture("channels.jsonl")),
+        );
+        let extractor = YouTubeExtractor::new(fake.clone());
+
+        let songs = collect(
+            &extractor,
+            "weeknd",
+            SearchScope::Songs,
+            SearchPage::nth(0, 10),
+        )
+        .await
+        .unwrap()
+        .0;
+        assert_eq!(songs.len(), 2);
+        let SearchResult::Song(song) = &songs[0] else {
+            panic!("expected a song: {:?}", songs[0]);
+        };
+        assert_eq!((song.id.as_str(), song.duration), ("4NRXx6U8ABQ", 200));
+        assert_eq!(song.uploader, "The Weeknd");
+        let args = &fake.calls()[0];
+        assert_eq!(args.value_of("--playlist-end"), Some("10"));
+        assert!(args.as_slice().contains(&"--flat-playlist".to_string()));
+
+        let albums = collect(
+            &extractor,
+            "weeknd",
+            SearchScope::Albums,
+            SearchPage::nth(0, 10),
+        )
+        .await
+        .unwrap()
+        .0;
+        let lists: Vec<_> = albums.iter().filter_map(SearchResult::list).collect();
+        // The entry with a non-YouTube URL is dropped.
+        assert_eq!(lists.len(), 2);
+        assert!(matches!(albums[0], SearchResult::Album(_)));
+        assert_eq!(lists[0].title, "After Hours");
+        assert_eq!(lists[0].track_count, Some(14));
+        assert_eq!(lists[1].title, "MPREb_untitled");
+        assert_eq!(
+            lists[1].url,
+            "https://music.youtube.com/browse/MPREb_untitled"
+        );
+
+        let artists = collect(
+            &extractor,
+            "weeknd",
+            SearchScope::Artists,
+            SearchPage::nth(0, 10),
+        )
+        .await
+        .unwrap()
+        .0;
+        let SearchResult::Artist(artist) = &artists[0] else {
+            panic!("expected an artist: {:?}", artists[0]);
+        };
+        assert_eq!(artist.name, "The Weeknd");
+        assert_eq!(artist.subscribers, Some(35_000_000));
+        assert_eq!(artists[0].title(), "The Weeknd");
     }
 }
@@ -0,0 +1,176 @@
+//! A scripted stand-in for yt-dlp in tests.
+//!
+//! Replies are keyed by a substring of the run's target (its last argument:
+//! the URL or search term). Several replies for one key are played in order
+//! and the last one repeats, so "fail, then succeed" is easy to script.
+//! Recorded output lives in `tests/fixtures/yt-dlp/`.
+
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::Mutex;
+
+use super::ytdlp::{CancelHandle, YtDlp, YtDlpArgs, YtDlpError, YtDlpOutput};
+
+/// What the fake yt-dlp does for one run.
+#[derive(Debug, Clone)]
+pub(crate) enum Reply {
+    /// Print `stdout` and exit successfully.
+    Output(String),
+    /// Print `stderr` and exit with an error.
+    Fail(String),
+    /// yt-dlp isn't installed.
+    Missing,
+    /// Print `stdout`, then write a `bytes`-long audio file where the `-o`
+    /// template points.
+    Download { stdout: String, bytes: usize },
+    /// Write `vtt` as an English `.vtt` subtitle file where the `-o`
+    /// template points, and exit successfully.
+    Subtitles(String),
+}
+
+impl Reply {
+    pub(crate) fn fixture(name: &str) -> Self {
+        Reply::Output(fixture(name))
+    }
+}
+
+/// The contents of `tests/fixtures/yt-dlp/<name>`.
+pub(crate) fn fixture(name: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/yt-dlp")
+        .join(name);
+    std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("missing fixture {}: {}", path.display(), e))
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct FakeYtDlp {
+    replies: Mutex<Vec<(String, VecDeque<Reply>)>>,
+    calls: Mutex<Vec<YtDlpArgs>>,
+}
+
+impl FakeYtDlp {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reply with `reply` to runs whose target contains `target`.
+    pub(crate) fn on(self, target: &str, reply: Reply) -> Self {
+        {
+            let mut replies = self.replies.lock().unwrap();
+            match replies.iter_mut().find(|(key, _)| key == target) {
+                Some((_, queue)) => queue.push_back(reply),
+                None => replies.push((target.to_string(), VecDeque::from([reply]))),
+            }
+        }
+        self
+    }
+
+    /// Arguments of every run so far, oldest first.
+    pub(crate) fn calls(&self) -> Vec<YtDlpArgs> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    fn reply_for(&self, args: &YtDlpArgs) -> Result<Reply, YtDlpError> {
+        self.calls.lock().unwrap().push(args.clone());
+        let target = args.target();
+        let mut replies = self.replies.lock().unwrap();
+        let (_, queue) = replies
+            .iter_mut()
+            .find(|(key, _)| target.contains(key.as_str()))
+            .ok_or_else(|| YtDlpError::Spawn(format!("no scripted reply for '{}'", target)))?;
+        Ok(if queue.len() > 1 {
+            queue.pop_front().unwrap()
+        } else {
+            queue[0].clone()
+        })
+    }
+}
+
+/// Write `vtt` as `args`' `-o` template's English subtitle file.
+fn write_subtitles(args: &YtDlpArgs, vtt: &str) -> Result<(), YtDlpError> {
+    let template = args
+        .value_of("-o")
+        .ok_or_else(|| YtDlpError::Spawn("subtitles without -o".to_string()))?;
+    std::fs::write(template.replace("%(ext)s", "en.vtt"), vtt)
+        .map_err(|e| YtDlpError::Spawn(e.to_string()))
+}
+
+/// Write a fake audio file for `args`' `-o` template.
+fn write_download(args: &YtDlpArgs, bytes: usize) -> Result<(), YtDlpError> {
+    let template = args
+        .value_of("-o")
+        .ok_or_else(|| YtDlpError::Spawn("download without -o".to_string()))?;
+    let ext = match args.value_of("--audio-format") {
+        Some("best") | None => "opus",
+        Some(ext) => ext,
+    };
+    std::fs::write(template.replace("%(ext)s", ext), vec![0u8; bytes])
+        .map_err(|e| YtDlpError::Spawn(e.to_string()))
+}
+
+impl YtDlp for FakeYtDlp {
+    fn run(&self, args: &YtDlpArgs) -> Result<YtDlpOutput, YtDlpError> {
+        match self.reply_for(args)? {
+            Reply::Output(stdout) => Ok(YtDlpOutput {
+                success: true,
+                stdout,
+                stderr: String::new(),
+            }),
+            Reply::Fail(stderr) => Ok(YtDlpOutput {
+                success: false,
+                stdout: String::new(),
+                stderr,
+            }),
+            Reply::Missing => Err(YtDlpError::Missing),
+            Reply::Download { stdout, bytes } => {
+                write_download(args, bytes)?;
+                Ok(YtDlpOutput {
+                    success: true,
+                    stdout,
+                    stderr: String::new(),
+                })
+            }
+            Reply::Subtitles(vtt) => {
+                write_subtitles(args, &vtt)?;
+                Ok(YtDlpOutput {
+                    success: true,
+                    stdout: String::new(),
+                    stderr: String::new(),
+                })
+            }
+        }
+    }
+
+    fn stream(
+        &self,
+        args: &YtDlpArgs,
+        cancel: &CancelHandle,
+        on_line: &mut dyn FnMut(&str),
+    ) -> Result<YtDlpOutput, YtDlpError> {
+        let (stdout, stderr, bytes) = match self.reply_for(args)? {
+            Reply::Output(stdout) => (stdout, None, None),
+            Reply::Fail(stderr) => (String::new(), Some(stderr), None),
+            Reply::Missing => return Err(YtDlpError::Missing),
+            Reply::Download { stdout, bytes } => (stdout, None, Some(bytes)),
+            Reply::Subtitles(vtt) => {
+                write_subtitles(args, &vtt)?;
+                (String::new(), None, None)
+            }
+        };
+        for line in stdout.lines() {
+            if cancel.is_cancelled() {
+                return Ok(YtDlpOutput::default());
+            }
+            on_line(line);
+        }
+        if let Some(bytes) = bytes {
+            write_download(args, bytes)?;
+        }
+        Ok(YtDlpOutput {
+            success: stderr.is_none(),
+            stdout: String::new(),
+            stderr: stderr.unwrap_or_default(),
+        })
+    }
+}
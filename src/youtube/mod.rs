@@ -2,3 +2,6 @@
 
 pub mod browser_auth;
 pub mod extractor;
+#[cfg(test)]
+pub mod fake_ytdlp;
+pub mod ytdlp;
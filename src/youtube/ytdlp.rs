@@ -0,0 +1,304 @@
+//! The yt-dlp backend.
+//!
+//! Every yt-dlp run — search, playlists, the feed, downloads — goes through
+//! the [`YtDlp`] trait with arguments built by [`YtDlpArgs`], which adds the
+//! shared timeout, retry, and cookie options. [`SystemYtDlp`] runs the real
+//! binary; tests use `FakeYtDlp`, which replays recorded JSON-lines
+//! fixtures and scripted failures without touching the network.
+
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::services::network::error_snippet;
+
+/// Seconds yt-dlp waits on a stalled connection.
+const SOCKET_TIMEOUT_SECS: u32 = 30;
+/// How many times yt-dlp retries a failed request itself.
+const RETRIES: u32 = 2;
+
+/// Arguments for one yt-dlp run, starting with the shared network options.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct YtDlpArgs(Vec<String>);
+
+impl YtDlpArgs {
+    pub(crate) fn new() -> Self {
+        Self(vec![
+            "--socket-timeout".to_string(),
+            SOCKET_TIMEOUT_SECS.to_string(),
+            "--retries".to_string(),
+            RETRIES.to_string(),
+        ])
+    }
+
+    pub(crate) fn flag(mut self, flag: &str) -> Self {
+        self.0.push(flag.to_string());
+        self
+    }
+
+    pub(crate) fn opt(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.0.push(name.to_string());
+        self.0.push(value.into());
+        self
+    }
+
+    pub(crate) fn args(mut self, args: impl IntoIterator<Item = String>) -> Self {
+        self.0.extend(args);
+        self
+    }
+
+    /// Browser cookies, as returned by `BrowserAuth::get_cookie_arg`.
+    pub(crate) fn cookies(self, cookie_config: Option<&(bool, String)>) -> Self {
+        match cookie_config {
+            Some((true, cookie_arg)) => self.opt("--cookies-from-browser", cookie_arg.as_str()),
+            _ => self,
+        }
+    }
+
+    pub(crate) fn as_slice(&self) -> &[String] {
+        &self.0
+    }
+
+    /// The value following option `name`, if present.
+    #[cfg(test)]
+    pub(crate) fn value_of(&self, name: &str) -> Option<&str> {
+        let i = self.0.iter().position(|a| a == name)?;
+        self.0.get(i + 1).map(String::as_str)
+    }
+
+    /// The last argument — the URL or search term by convention.
+    #[cfg(test)]
+    pub(crate) fn target(&self) -> &str {
+        self.0.last().map_or("", String::as_str)
+    }
+}
+
+/// What a finished yt-dlp run printed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct YtDlpOutput {
+    pub success: bool,
+    /// Empty for [`YtDlp::stream`], whose lines went to the callback.
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl YtDlpOutput {
+    /// The part of stderr worth showing to the user.
+    pub(crate) fn error_snippet(&self) -> String {
+        error_snippet(&self.stderr)
+    }
+}
+
+/// yt-dlp couldn't be run at all.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub(crate) enum YtDlpError {
+    #[error("yt-dlp not found. Please install yt-dlp and ensure it is on your PATH.")]
+    Missing,
+    #[error("Failed to run yt-dlp: {0}")]
+    Spawn(String),
+    #[error("yt-dlp output was not valid UTF-8: {0}")]
+    InvalidUtf8(String),
+}
+
+impl From<std::io::Error> for YtDlpError {
+    fn from(e: std::io::Error) -> Self {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            YtDlpError::Missing
+        } else {
+            YtDlpError::Spawn(e.to_string())
+        }
+    }
+}
+
+/// Runs yt-dlp. Blocking: call from `spawn_blocking` or a worker thread.
+pub(crate) trait YtDlp: Send + Sync {
+    /// Run to completion and capture stdout and stderr.
+    fn run(&self, args: &YtDlpArgs) -> Result<YtDlpOutput, YtDlpError>;
+
+    /// Run, handing each stdout line to `on_line` as it's printed. `cancel`
+    /// kills the process; the caller checks `cancel.is_cancelled()` after.
+    fn stream(
+        &self,
+        args: &YtDlpArgs,
+        cancel: &CancelHandle,
+        on_line: &mut dyn FnMut(&str),
+    ) -> Result<YtDlpOutput, YtDlpError>;
+}
+
+/// The `yt-dlp` binary on `PATH`.
+#[derive(Debug, Default)]
+pub(crate) struct SystemYtDlp;
+
+impl SystemYtDlp {
+    fn command(args: &YtDlpArgs) -> Command {
+        let mut cmd = Command::new("yt-dlp");
+        cmd.args(args.as_slice()).stdin(Stdio::null());
+        cmd
+    }
+}
+
+impl YtDlp for SystemYtDlp {
+    fn run(&self, args: &YtDlpArgs) -> Result<YtDlpOutput, YtDlpError> {
+        let output = Self::command(args).output()?;
+        Ok(YtDlpOutput {
+            success: output.status.success(),
+            stdout: String::from_utf8(output.stdout)
+                .map_err(|e| YtDlpError::InvalidUtf8(e.to_string()))?,
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        })
+    }
+
+    fn stream(
+        &self,
+        args: &YtDlpArgs,
+        cancel: &CancelHandle,
+        on_line: &mut dyn FnMut(&str),
+    ) -> Result<YtDlpOutput, YtDlpError> {
+        let mut cmd = Self::command(args);
+        // yt-dlp starts ffmpeg to extract audio and embed metadata; in a
+        // process group of their own they can all be killed on cancel.
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut cmd, 0);
+        let mut child = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+        cancel.attach(child);
+
+        // Drain stderr on its own thread so a chatty yt-dlp can't block on a
+        // full pipe while we're reading stdout.
+        let stderr_reader = std::thread::spawn(move || {
+            let mut error = String::new();
+            if let Some(mut stderr) = stderr {
+                let _ = stderr.read_to_string(&mut error);
+            }
+            error
+        });
+
+        if let Some(stdout) = stdout {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                on_line(&line);
+            }
+        }
+
+        let status = cancel
+            .wait()
+            .map_err(|e| YtDlpError::Spawn(format!("failed to wait for yt-dlp: {}", e)))?;
+        Ok(YtDlpOutput {
+            success: status.success(),
+            stdout: String::new(),
+            stderr: stderr_reader.join().unwrap_or_default(),
+        })
+    }
+}
+
+/// Cancels a streaming yt-dlp run by killing its process group: yt-dlp
+/// and any ffmpeg it started.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CancelHandle {
+    cancelled: Arc<AtomicBool>,
+    child: Arc<Mutex<Option<Child>>>,
+    /// The group the run's processes are in (yt-dlp's pid), until the
+    /// run has been waited for.
+    group: Arc<Mutex<Option<u32>>>,
+}
+
+impl CancelHandle {
+    pub(crate) fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        if let Some(child) = self
+            .child
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_mut()
+        {
+            let _ = child.kill();
+        }
+        self.kill_group();
+    }
+
+    /// SIGKILL everything left in the run's process group.
+    fn kill_group(&self) {
+        #[cfg(unix)]
+        if let Some(group) = *self.group.lock().unwrap_or_else(|e| e.into_inner()) {
+            if let Ok(pgid) = libc::pid_t::try_from(group) {
+                // SAFETY: kill(2) has no memory-safety preconditions; a
+                // negative pid signals every process in that group.
+                unsafe {
+                    libc::kill(-pgid, libc::SIGKILL);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Hand over the yt-dlp process so `cancel` can kill it. Kills it right
+    /// away if the run was cancelled while it was starting.
+    fn attach(&self, child: Child) {
+        *self.group.lock().unwrap_or_else(|e| e.into_inner()) = Some(child.id());
+        *self.child.lock().unwrap_or_else(|e| e.into_inner()) = Some(child);
+        if self.is_cancelled() {
+            self.cancel();
+        }
+    }
+
+    /// Wait for the attached process to exit. The process stays attached
+    /// while it runs, so `cancel` can still kill it. A cancelled run's
+    /// group is killed once more after yt-dlp is gone, in case an ffmpeg
+    /// outlived it.
+    fn wait(&self) -> std::io::Result<ExitStatus> {
+        loop {
+            {
+                let mut slot = self.child.lock().unwrap_or_else(|e| e.into_inner());
+                let Some(child) = slot.as_mut() else {
+                    return Err(std::io::Error::other("no yt-dlp process"));
+                };
+                if let Some(status) = child.try_wait()? {
+                    slot.take();
+                    drop(slot);
+                    if self.is_cancelled() {
+                        self.kill_group();
+                    }
+                    // Its pid may be reused from here on.
+                    self.group.lock().unwrap_or_else(|e| e.into_inner()).take();
+                    return Ok(status);
+                }
+            }
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn args_start_with_network_options_and_honour_cookie_flag() {
+        let args = YtDlpArgs::new()
+            .flag("--dump-json")
+            .cookies(Some(&(true, "firefox".to_string())))
+            .flag("https://www.youtube.com/playlist?list=PL1");
+        assert_eq!(
+            args.as_slice(),
+            [
+                "--socket-timeout",
+                "30",
+                "--retries",
+                "2",
+                "--dump-json",
+                "--cookies-from-browser",
+                "firefox",
+                "https://www.youtube.com/playlist?list=PL1",
+            ]
+        );
+        assert_eq!(args.value_of("--cookies-from-browser"), Some("firefox"));
+        assert_eq!(args.target(), "https://www.youtube.com/playlist?list=PL1");
+
+        let no_cookies = YtDlpArgs::new().cookies(Some(&(false, "firefox".to_string())));
+        assert_eq!(no_cookies.value_of("--cookies-from-browser"), None);
+    }
+}